@@ -0,0 +1,60 @@
+//! Compares hashing a board through [`CompactBoard`] against hashing each cell with
+//! [`DefaultHasher`], as the fuzzer did before the compact encoding was introduced.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use parking_game::{Board, BoardValue, Car, Orientation, State};
+use parking_game_fuzzer::compact::CompactBoard;
+use std::hash::{DefaultHasher, Hasher};
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::ops::Deref;
+
+/// The hash previously used by `hash_state`.
+fn cell_hash<T: BoardValue>(board: &Board<impl Deref<Target = State<T>>, T>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for cell in board.concrete() {
+        hasher.write_usize(cell.map_or(0, NonZeroUsize::get));
+    }
+    hasher.finish()
+}
+
+/// A 6x6 board in the style of the maps provided with the fuzzer.
+fn six_by_six() -> State<u8> {
+    let mut state = State::empty((6, 6)).unwrap();
+    let mut board = state.board_mut().unwrap();
+    let cars = [
+        ((2, 1), 2, Orientation::LeftRight),
+        ((0, 0), 3, Orientation::UpDown),
+        ((0, 1), 2, Orientation::LeftRight),
+        ((3, 3), 3, Orientation::UpDown),
+        ((1, 4), 2, Orientation::UpDown),
+        ((5, 0), 3, Orientation::LeftRight),
+        ((0, 5), 3, Orientation::UpDown),
+    ];
+    for (position, length, orientation) in cars {
+        board
+            .add_car(position, Car::new(length, orientation).unwrap())
+            .unwrap();
+    }
+    drop(board);
+    state
+}
+
+fn hash_6x6(c: &mut Criterion) {
+    let state = six_by_six();
+    let board = state.board().unwrap();
+    let compact = CompactBoard::from_board(&board);
+
+    let mut group = c.benchmark_group("hash_6x6");
+    group.bench_function("default_hasher", |b| {
+        b.iter(|| cell_hash(black_box(&board)))
+    });
+    group.bench_function("compact", |b| {
+        b.iter(|| CompactBoard::from_board(black_box(&board)).hash())
+    });
+    group.bench_function("compact_encoded", |b| b.iter(|| black_box(&compact).hash()));
+    group.finish();
+}
+
+criterion_group!(benches, hash_6x6);
+criterion_main!(benches);
@@ -0,0 +1,85 @@
+//! Measures the cost of the components the fuzzer runs for every input on the 6x6 maps it is
+//! usually given: parsing the map, executing inputs of increasing length, and hashing the final
+//! state of an execution.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use libafl::NopFuzzer;
+use libafl::events::NopEventManager;
+use libafl::executors::{Executor, HasObservers};
+use libafl::observers::ObserverWithHashField;
+use libafl::state::NopState;
+use libafl_bolts::tuples::tuple_list;
+use parking_game_fuzzer::executor::PGExecutorBuilder;
+use parking_game_fuzzer::input::PGInput;
+use parking_game_fuzzer::observers::FinalStateObserver;
+use parking_game_fuzzer::parse_map;
+use std::hint::black_box;
+
+/// A 6x6 map with room for the objective car to move back and forth.
+const MAP: &str = include_str!("../maps/tokyo1.map");
+
+/// An input of `len` moves which shuffles the objective car of [`MAP`] left and right, so that no
+/// move fails and the whole input is executed.
+fn back_and_forth(len: usize) -> PGInput {
+    ["1L", "1R"]
+        .into_iter()
+        .cycle()
+        .take(len)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .parse()
+        .unwrap()
+}
+
+fn parse_6x6(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_map");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("6x6", |b| b.iter(|| parse_map::<u8>(black_box(MAP))));
+    group.finish();
+}
+
+fn run_target(c: &mut Criterion) {
+    let mut executor = PGExecutorBuilder::with_initial(parse_map::<u8>(MAP))
+        .build(tuple_list!(FinalStateObserver::<u8>::default()));
+    let mut fuzzer = NopFuzzer::new();
+    let mut state = NopState::<PGInput>::new();
+    let mut mgr = NopEventManager::new();
+
+    // throughput is in moves, so that the cost of each move can be compared across lengths
+    let mut group = c.benchmark_group("run_target");
+    for len in [10, 50, 100] {
+        let input = back_and_forth(len);
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &input, |b, input| {
+            b.iter(|| {
+                executor
+                    .run_target(&mut fuzzer, &mut state, &mut mgr, black_box(input))
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn final_state_hash(c: &mut Criterion) {
+    let mut executor = PGExecutorBuilder::with_initial(parse_map::<u8>(MAP))
+        .build(tuple_list!(FinalStateObserver::<u8>::default()));
+    executor
+        .run_target(
+            &mut NopFuzzer::new(),
+            &mut NopState::<PGInput>::new(),
+            &mut NopEventManager::new(),
+            &back_and_forth(1),
+        )
+        .unwrap();
+    let observers = executor.observers();
+    let obs = &observers.0;
+
+    let mut group = c.benchmark_group("final_state_hash");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("6x6", |b| b.iter(|| black_box(obs).hash().unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, parse_6x6, run_target, final_state_hash);
+criterion_main!(benches);
@@ -0,0 +1,2099 @@
+//! Tools for making sense of what the fuzzer found, such as the solutions it discovered, once
+//! fuzzing is done.
+
+use crate::executor::PGExecutor;
+use crate::feedbacks::{NearSolutionMetadata, ViewMetadata};
+use crate::hash::hash_car_positions;
+use crate::input::{PGInput, direction_letter};
+use crate::observers::{
+    Axis, DirectionExt, ExecutionCountMetadata, OrientationExt, TransitionMetadata, blocking_path,
+    hash_state,
+};
+use crate::serialization::serialize_state;
+use crate::types::CarId;
+use libafl::HasMetadata;
+use libafl::corpus::{Corpus, CorpusId, InMemoryCorpus};
+use libafl::executors::ExitKind;
+use libafl::state::{HasCorpus, HasExecutions, HasSolutions, NopState};
+use libafl_bolts::rands::Rand;
+use libafl_bolts::{Error, impl_serdeany};
+use parking_game::{Board, BoardValue, Car, Direction, InvalidStateError, Position, State};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// The solutions to a puzzle, kept ordered from shortest to longest and indexed by the hash of the
+/// state each one finishes in (see [`hash_state`]).
+#[derive(Debug, Clone)]
+pub struct SolutionDatabase<T> {
+    initial: State<T>,
+    solutions: Vec<PGInput>,
+    by_hash: HashMap<u64, usize>,
+}
+
+impl<T> SolutionDatabase<T>
+where
+    T: BoardValue,
+{
+    /// Create an empty database for solutions to the puzzle starting from the provided state.
+    pub fn new(initial: State<T>) -> Self {
+        Self {
+            initial,
+            solutions: Vec::new(),
+            by_hash: HashMap::new(),
+        }
+    }
+
+    /// Create a database holding every input in the provided corpus, e.g., the solutions of a
+    /// fuzzer's state.
+    pub fn from_corpus<C>(initial: State<T>, corpus: &C) -> Result<Self, Error>
+    where
+        C: Corpus<PGInput>,
+    {
+        let mut database = Self::new(initial);
+        let mut next = corpus.first();
+        while let Some(id) = next {
+            if let Some(input) = corpus.get(id)?.borrow().input() {
+                database.insert(input.clone());
+            }
+            next = corpus.next(id);
+        }
+        Ok(database)
+    }
+
+    /// Adds a solution, after any solutions of the same length already present. The final state is
+    /// found by replaying the solution; solutions which cannot be replayed are still stored, but
+    /// are not indexed by their final state.
+    pub fn insert(&mut self, input: PGInput) {
+        let hash = self.final_hash(&input);
+
+        let index = self
+            .solutions
+            .partition_point(|solution| solution.len() <= input.len());
+        for existing in self.by_hash.values_mut() {
+            if *existing >= index {
+                *existing += 1;
+            }
+        }
+        self.solutions.insert(index, input);
+        if let Some(hash) = hash {
+            // keep pointing at the shortest solution finishing in this state
+            self.by_hash
+                .entry(hash)
+                .and_modify(|existing| *existing = (*existing).min(index))
+                .or_insert(index);
+        }
+    }
+
+    /// The shortest solution, or [`None`] if there are none. Of solutions with the same length, the
+    /// first inserted is returned.
+    pub fn shortest(&self) -> Option<&PGInput> {
+        self.solutions.first()
+    }
+
+    /// The solutions with exactly `n` moves, in the order they were inserted.
+    pub fn by_length(&self, n: usize) -> &[PGInput] {
+        let start = self
+            .solutions
+            .partition_point(|solution| solution.len() < n);
+        let end = self
+            .solutions
+            .partition_point(|solution| solution.len() <= n);
+        &self.solutions[start..end]
+    }
+
+    /// The number of solutions stored.
+    pub fn count(&self) -> usize {
+        self.solutions.len()
+    }
+
+    /// Whether any solution finishes in the state with the provided hash.
+    pub fn contains_hash(&self, h: u64) -> bool {
+        self.by_hash.contains_key(&h)
+    }
+
+    /// The shortest solution finishing in the state with the provided hash.
+    fn shortest_for_hash(&self, h: u64) -> Option<&PGInput> {
+        self.by_hash.get(&h).map(|&index| &self.solutions[index])
+    }
+
+    /// The hash of the state the input finishes in, or [`None`] if it cannot be replayed.
+    fn final_hash(&self, input: &PGInput) -> Option<u64> {
+        let mut state = self.initial.clone();
+        input.apply(&mut state).ok()?;
+        hash_state(&state)
+    }
+}
+
+/// How the solutions found by two fuzzing runs of the same puzzle differ, by the states they finish
+/// in; see [`compare_runs`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunComparison<'a> {
+    /// The solutions of the first run finishing in states which no solution of the second reaches.
+    pub only_in_a: Vec<PGInput>,
+    /// The solutions of the second run finishing in states which no solution of the first reaches.
+    pub only_in_b: Vec<PGInput>,
+    /// For each state reached by solutions of both runs, the shortest solution finishing there.
+    pub in_both: Vec<PGInput>,
+    /// The shortest solution of the first run, if any.
+    pub shortest_a: Option<&'a PGInput>,
+    /// The shortest solution of the second run, if any.
+    pub shortest_b: Option<&'a PGInput>,
+}
+
+/// Compares the solutions found by two runs, classifying each by the state it finishes in.
+/// Solutions which cannot be replayed finish in no state, so are left out. Each list is ordered
+/// from shortest to longest; ties are broken in favour of the first run.
+pub fn compare_runs<'a, T: BoardValue>(
+    a: &'a SolutionDatabase<T>,
+    b: &'a SolutionDatabase<T>,
+) -> RunComparison<'a> {
+    let only_in = |this: &SolutionDatabase<T>, other: &SolutionDatabase<T>| {
+        this.solutions
+            .iter()
+            .filter(|solution| {
+                this.final_hash(solution)
+                    .is_some_and(|hash| !other.contains_hash(hash))
+            })
+            .cloned()
+            .collect()
+    };
+
+    let mut in_both = a
+        .by_hash
+        .keys()
+        .filter_map(|&hash| {
+            let (ours, theirs) = (a.shortest_for_hash(hash)?, b.shortest_for_hash(hash)?);
+            Some(
+                if theirs.len() < ours.len() {
+                    theirs
+                } else {
+                    ours
+                }
+                .clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    in_both.sort_by_key(PGInput::len);
+
+    RunComparison {
+        only_in_a: only_in(a, b),
+        only_in_b: only_in(b, a),
+        in_both,
+        shortest_a: a.shortest(),
+        shortest_b: b.shortest(),
+    }
+}
+
+/// Inputs which crash, deduplicated by the state of the board when they crashed, i.e., just before
+/// the first invalid move. One representative input is kept for each such state.
+#[derive(Debug, Clone)]
+pub struct CrashInputDatabase<T> {
+    initial: State<T>,
+    crashes: HashMap<u64, PGInput>,
+}
+
+impl<T> CrashInputDatabase<T>
+where
+    T: BoardValue,
+{
+    /// Create an empty database for crashes from the provided initial state.
+    pub fn new(initial: State<T>) -> Self {
+        Self {
+            initial,
+            crashes: HashMap::new(),
+        }
+    }
+
+    /// Replay the input to find the state of the board when it crashed, and keep the input as the
+    /// representative of that state if there is none yet. Returns the hash of the state (see
+    /// [`hash_state`]), or [`None`] if the input does not crash.
+    pub fn insert(&mut self, input: &PGInput) -> Option<u64> {
+        let mut state = self.initial.clone();
+        input.apply(&mut state).err()?;
+        let hash = hash_state(&state).expect("States reached by valid moves are valid.");
+        self.crashes.entry(hash).or_insert_with(|| input.clone());
+        Some(hash)
+    }
+
+    /// The number of distinct states in which inputs crashed.
+    pub fn unique_crash_count(&self) -> usize {
+        self.crashes.len()
+    }
+
+    /// The first input recorded which crashed in the state with the provided hash.
+    pub fn representative_for_hash(&self, h: u64) -> Option<&PGInput> {
+        self.crashes.get(&h)
+    }
+}
+
+/// How often the mutations made by each mutator, known by its [`libafl_bolts::Named::name`], led to
+/// an input interesting enough to be added to the corpus.
+#[derive(Debug, Default, Clone)]
+pub struct CorrelationAnalysis {
+    counts: HashMap<Cow<'static, str>, (u64, u64)>,
+}
+
+impl CorrelationAnalysis {
+    /// Create an analysis with no mutations recorded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a mutation made by the named mutator, and whether the mutated input was interesting.
+    pub fn record(&mut self, name: Cow<'static, str>, interesting: bool) {
+        let (hits, total) = self.counts.entry(name).or_default();
+        *hits += u64::from(interesting);
+        *total += 1;
+    }
+
+    /// The numbers of interesting mutations and of all mutations made by the named mutator.
+    pub fn counts(&self, name: &str) -> Option<(u64, u64)> {
+        self.counts.get(name).copied()
+    }
+
+    /// The fraction of the mutations made by the named mutator which were interesting, or [`None`]
+    /// if it has not made any.
+    pub fn effectiveness(&self, name: &str) -> Option<f64> {
+        let (hits, total) = self.counts(name)?;
+        Some(hits as f64 / total as f64)
+    }
+}
+
+/// A summary of the entries of a corpus, for judging how well the fuzzer is exploring.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorpusStatistics {
+    /// The number of entries in the corpus.
+    pub size: usize,
+    /// The average number of moves per entry, or zero if the corpus is empty.
+    pub avg_length: f64,
+    /// The number of moves in the shortest entry, or zero if the corpus is empty.
+    pub min_length: usize,
+    /// The number of moves in the longest entry, or zero if the corpus is empty.
+    pub max_length: usize,
+    /// The number of distinct states the entries finish in. Entries which crash are not counted.
+    pub unique_final_states: usize,
+}
+
+impl Display for CorpusStatistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} entries of {}-{} moves ({:.1} on average), finishing in {} unique states",
+            self.size, self.min_length, self.max_length, self.avg_length, self.unique_final_states
+        )
+    }
+}
+
+/// Summarises the entries of the provided corpus, re-executing each from the provided initial state
+/// to find the state it finishes in.
+pub fn analyze_corpus<C, T>(corpus: &C, initial: &State<T>) -> CorpusStatistics
+where
+    C: Corpus<PGInput>,
+    T: BoardValue,
+{
+    let mut executor = PGExecutor::new(initial.clone(), ());
+    let mut state = NopState::<PGInput>::new();
+
+    let mut lengths = Vec::with_capacity(corpus.count());
+    let mut final_states = HashSet::new();
+    let mut next = corpus.first();
+    while let Some(id) = next {
+        next = corpus.next(id);
+        let Ok(testcase) = corpus.get(id) else {
+            continue;
+        };
+        let testcase = testcase.borrow();
+        let Some(input) = testcase.input() else {
+            continue;
+        };
+        lengths.push(input.len());
+        if let Ok((ExitKind::Ok, finished)) = executor.execute(&mut state, input)
+            && let Some(hash) = hash_state(&finished)
+        {
+            final_states.insert(hash);
+        }
+    }
+
+    CorpusStatistics {
+        size: lengths.len(),
+        avg_length: if lengths.is_empty() {
+            0.0
+        } else {
+            lengths.iter().sum::<usize>() as f64 / lengths.len() as f64
+        },
+        min_length: lengths.iter().copied().min().unwrap_or(0),
+        max_length: lengths.iter().copied().max().unwrap_or(0),
+        unique_final_states: final_states.len(),
+    }
+}
+
+/// Removes the entries of the provided corpus which finish in the same state as another once
+/// fuzzing is done, re-executing each from the provided initial state to find the state it finishes
+/// in. Of the entries finishing in each state, only the shortest is kept, or the earliest added of
+/// those. Entries which crash are all kept. Returns the number of entries removed.
+pub fn deduplicate_corpus<T: BoardValue>(
+    corpus: &mut InMemoryCorpus<PGInput>,
+    initial: &State<T>,
+) -> usize {
+    let mut executor = PGExecutor::new(initial.clone(), ());
+    let mut state = NopState::<PGInput>::new();
+
+    // the shortest entry finishing in each state so far, and its length
+    let mut kept = HashMap::<u64, (CorpusId, usize)>::new();
+    let mut duplicates = Vec::new();
+    let mut next = corpus.first();
+    while let Some(id) = next {
+        next = corpus.next(id);
+        let Ok(testcase) = corpus.get(id) else {
+            continue;
+        };
+        let testcase = testcase.borrow();
+        let Some(input) = testcase.input() else {
+            continue;
+        };
+        let Ok((ExitKind::Ok, finished)) = executor.execute(&mut state, input) else {
+            continue;
+        };
+        let Some(hash) = hash_state(&finished) else {
+            continue;
+        };
+        match kept.entry(hash) {
+            Entry::Vacant(entry) => {
+                entry.insert((id, input.len()));
+            }
+            Entry::Occupied(mut entry) if input.len() < entry.get().1 => {
+                duplicates.push(entry.insert((id, input.len())).0);
+            }
+            Entry::Occupied(_) => duplicates.push(id),
+        }
+    }
+
+    duplicates
+        .into_iter()
+        .filter(|&id| corpus.remove(id).is_ok())
+        .count()
+}
+
+/// A summary of the progress of a fuzzing run.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FuzzingReport {
+    /// When fuzzing started. This is not saved, and is only as precise as
+    /// [`FuzzingReport::elapsed_secs`] when read back with [`FuzzingReport::from_csv_row`].
+    #[serde(skip, default = "Instant::now")]
+    pub start_time: Instant,
+    /// The time spent fuzzing, in seconds.
+    pub elapsed_secs: f64,
+    /// The number of inputs executed.
+    pub total_executions: u64,
+    /// The number of entries in the corpus.
+    pub corpus_size: usize,
+    /// The number of solutions found.
+    pub solutions_count: usize,
+    /// The number of moves in the shortest solution found, if any.
+    pub best_solution_length: Option<usize>,
+    /// The proportion of executions which crashed, as counted by a
+    /// [`crate::observers::CrashRateObserver`], or NaN if none was counting.
+    pub crash_rate: f64,
+    /// The average number of inputs executed per second.
+    pub executions_per_second: f64,
+}
+
+/// The columns of [`FuzzingReport::to_csv_row`]; the start time is written as the elapsed time.
+const CSV_HEADER: &str = "elapsed_secs,total_executions,corpus_size,solutions_count,\
+                          best_solution_length,crash_rate,executions_per_second";
+
+impl FuzzingReport {
+    /// Summarise the provided fuzzer state, which has been fuzzing since the provided time.
+    pub fn from_state<S>(state: &S, start_time: Instant) -> Self
+    where
+        S: HasCorpus<PGInput> + HasSolutions<PGInput> + HasExecutions + HasMetadata,
+    {
+        let mut best_solution_length = None;
+        let mut next = state.solutions().first();
+        while let Some(id) = next {
+            let testcase = state.solutions().get(id).map(|testcase| testcase.borrow());
+            if let Ok(testcase) = testcase
+                && let Some(input) = testcase.input()
+            {
+                let len = input.len();
+                best_solution_length =
+                    Some(best_solution_length.map_or(len, |best: usize| best.min(len)));
+            }
+            next = state.solutions().next(id);
+        }
+
+        let crash_rate = state
+            .metadata::<ExecutionCountMetadata>()
+            .ok()
+            .filter(|counts| counts.total() > 0)
+            .map_or(f64::NAN, |counts| {
+                counts.crashes() as f64 / counts.total() as f64
+            });
+        let total_executions = *state.executions();
+        let elapsed_secs = start_time.elapsed().as_secs_f64();
+        Self {
+            start_time,
+            elapsed_secs,
+            total_executions,
+            corpus_size: state.corpus().count(),
+            solutions_count: state.solutions().count(),
+            best_solution_length,
+            crash_rate,
+            executions_per_second: if elapsed_secs > 0.0 {
+                total_executions as f64 / elapsed_secs
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// The header row naming the columns of [`FuzzingReport::to_csv_row`].
+    pub fn csv_header() -> &'static str {
+        CSV_HEADER
+    }
+
+    /// Formats this report as a row of comma-separated values, with the columns named by
+    /// [`FuzzingReport::csv_header`]. A missing best solution length is left empty, and NaN is
+    /// written as `NaN`.
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{}",
+            self.elapsed_secs,
+            self.total_executions,
+            self.corpus_size,
+            self.solutions_count,
+            self.best_solution_length
+                .map_or_else(String::new, |len| len.to_string()),
+            self.crash_rate,
+            self.executions_per_second
+        )
+    }
+
+    /// Parses a row written by [`FuzzingReport::to_csv_row`]. The start time is taken to be the
+    /// elapsed time before now.
+    pub fn from_csv_row(row: &str) -> Result<Self, ReportParseError> {
+        let fields = row.trim().split(',').collect::<Vec<_>>();
+        let columns = CSV_HEADER.split(',').collect::<Vec<_>>();
+        if fields.len() != columns.len() {
+            return Err(ReportParseError::FieldCount(fields.len()));
+        }
+        fn parse<V: FromStr>(column: &str, field: &str) -> Result<V, ReportParseError> {
+            field
+                .parse()
+                .map_err(|_| ReportParseError::InvalidField(column.to_string(), field.to_string()))
+        }
+
+        let elapsed_secs = parse::<f64>(columns[0], fields[0])?;
+        let start_time = Duration::try_from_secs_f64(elapsed_secs)
+            .ok()
+            .and_then(|elapsed| Instant::now().checked_sub(elapsed))
+            .ok_or_else(|| {
+                ReportParseError::InvalidField(columns[0].to_string(), fields[0].to_string())
+            })?;
+        Ok(Self {
+            start_time,
+            elapsed_secs,
+            total_executions: parse(columns[1], fields[1])?,
+            corpus_size: parse(columns[2], fields[2])?,
+            solutions_count: parse(columns[3], fields[3])?,
+            best_solution_length: match fields[4] {
+                "" => None,
+                field => Some(parse(columns[4], field)?),
+            },
+            crash_rate: parse(columns[5], fields[5])?,
+            executions_per_second: parse(columns[6], fields[6])?,
+        })
+    }
+}
+
+/// An error encountered while parsing a [`FuzzingReport`] with [`FuzzingReport::from_csv_row`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportParseError {
+    /// The row had the provided number of fields, rather than one per column.
+    FieldCount(usize),
+    /// The field for the named column could not be parsed.
+    InvalidField(String, String),
+}
+
+impl Display for ReportParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportParseError::FieldCount(count) => write!(
+                f,
+                "expected {} fields, found {count}",
+                CSV_HEADER.split(',').count()
+            ),
+            ReportParseError::InvalidField(column, field) => {
+                write!(f, "invalid {column} `{field}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReportParseError {}
+
+/// The width of the bars drawn by [`InputSizeHistogram::render`] for the fullest bucket.
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Fuzzer metadata counting the corpus entries by their number of moves, grouped into buckets of
+/// `bucket_size` lengths each, for seeing whether the corpus is growing towards longer inputs.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InputSizeHistogram {
+    buckets: HashMap<usize, u32>,
+    bucket_size: usize,
+}
+
+impl InputSizeHistogram {
+    /// The bucket size used when the histogram is created by [`InputSizeHistogram::record_corpus`].
+    pub const DEFAULT_BUCKET_SIZE: usize = 10;
+
+    /// Create an empty histogram grouping lengths into buckets of the provided size.
+    ///
+    /// Panics if `bucket_size` is zero.
+    pub fn new(bucket_size: usize) -> Self {
+        assert_ne!(bucket_size, 0, "The bucket size must be non-zero.");
+        Self {
+            buckets: HashMap::new(),
+            bucket_size,
+        }
+    }
+
+    /// The number of lengths in each bucket.
+    pub fn bucket_size(&self) -> usize {
+        self.bucket_size
+    }
+
+    /// The number of entries counted in each bucket, by the index of the bucket; bucket `i` holds
+    /// the entries of `i * bucket_size` up to `(i + 1) * bucket_size` moves. Empty buckets are
+    /// absent.
+    pub fn buckets(&self) -> &HashMap<usize, u32> {
+        &self.buckets
+    }
+
+    /// Count an entry of the provided length.
+    pub fn record(&mut self, input: &PGInput) {
+        *self
+            .buckets
+            .entry(input.len() / self.bucket_size)
+            .or_default() += 1;
+    }
+
+    /// Count the entries of the state's corpus from the `since`th onwards in the state's histogram,
+    /// creating one with the [`InputSizeHistogram::DEFAULT_BUCKET_SIZE`] if it has none.
+    pub fn record_corpus<S>(state: &mut S, since: usize) -> Result<(), Error>
+    where
+        S: HasCorpus<PGInput> + HasMetadata,
+    {
+        let mut added = Vec::new();
+        for nth in since..state.corpus().count() {
+            let id = state.corpus().nth(nth);
+            if let Some(input) = state.corpus().get(id)?.borrow().input() {
+                added.push(input.clone());
+            }
+        }
+        let histogram = state.metadata_or_insert_with(|| Self::new(Self::DEFAULT_BUCKET_SIZE));
+        for input in &added {
+            histogram.record(input);
+        }
+        Ok(())
+    }
+
+    /// Renders the histogram as text, one line per bucket from the shortest lengths to the longest,
+    /// each labelled with its range of lengths and drawn as a bar scaled to the fullest bucket.
+    pub fn render(&self) -> String {
+        let Some(&last) = self.buckets.keys().max() else {
+            return "(no entries)".to_string();
+        };
+        let fullest = self.buckets.values().copied().max().unwrap_or(0).max(1);
+        let label = |bucket: usize| {
+            format!(
+                "{}-{}",
+                bucket * self.bucket_size,
+                (bucket + 1) * self.bucket_size - 1
+            )
+        };
+        let label_width = label(last).len();
+        (0..=last)
+            .map(|bucket| {
+                let count = self.buckets.get(&bucket).copied().unwrap_or(0);
+                let bar = "#".repeat(count as usize * HISTOGRAM_WIDTH / fullest as usize);
+                format!("{:>label_width$} | {bar} {count}", label(bucket))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Print the [`InputSizeHistogram::render`]ed histogram.
+    pub fn print_histogram(&self) {
+        println!("{}", self.render());
+    }
+}
+
+impl_serdeany!(InputSizeHistogram);
+
+/// Renders the board (as with [`serialize_state`]) before any moves of the input are applied, then
+/// after each move in turn, so that an input may be followed step by step. If a move cannot be
+/// applied, the frames stop at the board before it.
+pub fn visualize_input<T: BoardValue>(input: &PGInput, initial: &State<T>) -> Vec<String> {
+    let mut state = initial.clone();
+    let mut frames = Vec::with_capacity(input.len() + 1);
+    frames.push(serialize_state(&state));
+    for &(car, direction) in input {
+        let moved = state
+            .board_mut()
+            .expect("The state should be valid.")
+            .shift_car(car.as_nonzero(), direction);
+        if moved.is_err() {
+            break;
+        }
+        frames.push(serialize_state(&state));
+    }
+    frames
+}
+
+/// How a cell differs between two boards, as found by [`board_diff`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CellChange {
+    /// The cell was occupied by the provided car, and is now empty.
+    EmptiedBy(CarId),
+    /// The cell was empty, and is now occupied by the provided car.
+    FilledBy(CarId),
+    /// The cell was occupied by one car, and is now occupied by another.
+    ReplacedBy {
+        /// The car which occupied the cell before.
+        from: CarId,
+        /// The car which occupies the cell now.
+        to: CarId,
+    },
+}
+
+/// The cells which differ between two states of the same dimensions, as `(row, column, change)` in
+/// row-major order. Useful for working out why two states hash differently.
+///
+/// Panics if either state is invalid, or if the dimensions of the states differ.
+pub fn board_diff<T: BoardValue>(a: &State<T>, b: &State<T>) -> Vec<(usize, usize, CellChange)> {
+    let rows = |state: &State<T>| (*state.dimensions().rows()).into();
+    let columns = |state: &State<T>| (*state.dimensions().columns()).into();
+    assert!(
+        rows(a) == rows(b) && columns(a) == columns(b),
+        "Only states of the same dimensions may be compared."
+    );
+    let a_board = a.board().expect("Only valid states may be compared.");
+    let b_board = b.board().expect("Only valid states may be compared.");
+
+    let columns = columns(a);
+    a_board
+        .concrete()
+        .iter()
+        .zip(b_board.concrete())
+        .enumerate()
+        .filter_map(|(i, (&before, &after))| {
+            let change = match (before, after) {
+                (Some(from), None) => CellChange::EmptiedBy(from.into()),
+                (None, Some(to)) => CellChange::FilledBy(to.into()),
+                (Some(from), Some(to)) if from != to => CellChange::ReplacedBy {
+                    from: from.into(),
+                    to: to.into(),
+                },
+                _ => return None,
+            };
+            Some((i / columns, i % columns, change))
+        })
+        .collect()
+}
+
+/// The cars in the lane of the objective car between it and the exit, from nearest to furthest; the
+/// path is clear if there are none. Returns an error if the state is invalid.
+pub fn car_blocking_path<T: BoardValue>(
+    state: &State<T>,
+) -> Result<Vec<CarId>, InvalidStateError<T>> {
+    Ok(blocking_path(&state.board()?))
+}
+
+/// The fewest moves which solve the puzzle, i.e., which leave nothing between the objective car and
+/// the wall ahead of it, or [`None`] if it cannot be solved within `depth_limit` moves. Found by
+/// breadth-first search, so this is the optimal length against which solutions may be compared.
+///
+/// Panics if the initial state is invalid.
+pub fn min_moves_to_solution<T: BoardValue>(
+    initial: &State<T>,
+    depth_limit: usize,
+) -> Option<usize> {
+    search(initial, depth_limit, |state| {
+        blocking_path(&state.board().expect("The state should be valid.")).is_empty()
+    })
+}
+
+/// Checks whether the puzzle can be solved within `depth_limit` moves.
+///
+/// Panics if the initial state is invalid.
+pub fn is_solvable<T: BoardValue>(initial: &State<T>, depth_limit: usize) -> bool {
+    min_moves_to_solution(initial, depth_limit).is_some()
+}
+
+/// Checks whether the provided car can be brought to `target_pos` (the position of its top-left
+/// cell) within `depth_limit` moves. Cars which are not on the board can reach nowhere.
+///
+/// Panics if the initial state is invalid.
+pub fn car_can_reach<T: BoardValue>(
+    initial: &State<T>,
+    car: CarId,
+    target_pos: Position<T>,
+    depth_limit: usize,
+) -> bool {
+    if initial.cars().len() < car.get() {
+        return false;
+    }
+    search(initial, depth_limit, |state| {
+        let (position, _) = state.cars()[car.get() - 1];
+        position.row() == target_pos.row() && position.column() == target_pos.column()
+    })
+    .is_some()
+}
+
+/// The moves which the cars blocking the objective car are forced to make. A car blocks the
+/// objective car if it is in its path, or if it is directly ahead of or behind a car which does; a
+/// move is forced if the car may move in that direction but not the other way along its axis.
+///
+/// Panics if the state is invalid.
+pub fn forced_moves<T: BoardValue>(state: &State<T>) -> Vec<(CarId, Direction)> {
+    let board = state.board().expect("The state should be valid.");
+    let cars = state.cars();
+
+    let mut chain = blocking_path(&board);
+    let mut i = 0;
+    while let Some(&car) = chain.get(i) {
+        let (position, car) = &cars[car.get() - 1];
+        for direction in directions_along(car) {
+            if let Some(Some(next)) = adjacent(&board, *position, car, direction)
+                && next.get() != 1
+                && !chain.contains(&next)
+            {
+                chain.push(next);
+            }
+        }
+        i += 1;
+    }
+
+    let mut forced = Vec::new();
+    for car in chain {
+        let (position, shape) = &cars[car.get() - 1];
+        let free = |direction| adjacent(&board, *position, shape, direction) == Some(None);
+        for direction in directions_along(shape) {
+            if free(direction) && !free(direction.opposite()) {
+                forced.push((car, direction));
+            }
+        }
+    }
+    forced
+}
+
+/// The directions in which the car moves: up or left, then down or right.
+fn directions_along<T>(car: &Car<T>) -> [Direction; 2] {
+    match car.orientation().axis() {
+        Axis::Vertical => [Direction::Up, Direction::Down],
+        Axis::Horizontal => [Direction::Left, Direction::Right],
+    }
+}
+
+/// The cell directly beyond the car in the provided direction, or [`None`] if off the board.
+pub(crate) fn adjacent<T: BoardValue>(
+    board: &Board<impl Deref<Target = State<T>>, T>,
+    position: Position<T>,
+    car: &Car<T>,
+    direction: Direction,
+) -> Option<Option<CarId>> {
+    let offset = match direction {
+        Direction::Up | Direction::Left => T::one(),
+        Direction::Down | Direction::Right => *car.length(),
+    };
+    position
+        .shift(direction, offset)
+        .and_then(|p| board.get(p))
+        .map(|cell| cell.map(CarId::from))
+}
+
+/// A transformation of the board which leaves a puzzle unchanged, as found by [`detect_symmetry`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BoardSymmetry {
+    /// No transformation leaves the puzzle unchanged.
+    None,
+    /// Mirroring the columns, left to right.
+    HorizontalFlip,
+    /// Mirroring the rows, top to bottom.
+    VerticalFlip,
+    /// Turning the board half way around.
+    Rotational180,
+}
+
+impl BoardSymmetry {
+    /// The direction a move in the provided direction becomes under this transformation.
+    pub fn mirror_direction(self, direction: Direction) -> Direction {
+        let (columns, rows) = self.flips();
+        match direction {
+            Direction::Left | Direction::Right if columns => direction.opposite(),
+            Direction::Up | Direction::Down if rows => direction.opposite(),
+            _ => direction,
+        }
+    }
+
+    /// Whether this transformation mirrors the columns and the rows, respectively.
+    fn flips(self) -> (bool, bool) {
+        match self {
+            BoardSymmetry::None => (false, false),
+            BoardSymmetry::HorizontalFlip => (true, false),
+            BoardSymmetry::VerticalFlip => (false, true),
+            BoardSymmetry::Rotational180 => (true, true),
+        }
+    }
+
+    /// The row and column the car at the provided position is moved to by this transformation.
+    fn reflect<T: BoardValue>(
+        self,
+        dimensions: (usize, usize),
+        position: &Position<T>,
+        car: &Car<T>,
+    ) -> (usize, usize) {
+        let (columns, rows) = self.flips();
+        let length: usize = (*car.length()).into();
+        let (height, width) = match car.orientation().axis() {
+            Axis::Horizontal => (1, length),
+            Axis::Vertical => (length, 1),
+        };
+        let mut row: usize = (*position.row()).into();
+        let mut column: usize = (*position.column()).into();
+        if rows {
+            row = dimensions.0 - row - height;
+        }
+        if columns {
+            column = dimensions.1 - column - width;
+        }
+        (row, column)
+    }
+}
+
+/// Find a transformation which leaves the provided puzzle unchanged: mirroring it horizontally,
+/// then vertically, then turning it around, returning the first which holds. Cars other than the
+/// objective car are interchangeable with those of the same length and orientation.
+///
+/// The positions of the cars are compared by hash, never building the board, so this takes time
+/// linear in the number of cars regardless of the size of the board.
+pub fn detect_symmetry<T: BoardValue>(state: &State<T>) -> BoardSymmetry {
+    let original = symmetry_hash(state, BoardSymmetry::None);
+    [
+        BoardSymmetry::HorizontalFlip,
+        BoardSymmetry::VerticalFlip,
+        BoardSymmetry::Rotational180,
+    ]
+    .into_iter()
+    .find(|&symmetry| symmetry_hash(state, symmetry) == original)
+    .unwrap_or(BoardSymmetry::None)
+}
+
+/// The car each car of the provided puzzle is moved onto by the provided transformation, by car
+/// index, or [`None`] if the transformation does not leave the puzzle unchanged.
+pub fn mirrored_cars<T: BoardValue>(
+    state: &State<T>,
+    symmetry: BoardSymmetry,
+) -> Option<Vec<CarId>> {
+    let dimensions = board_dimensions(state);
+    let places = state
+        .cars()
+        .iter()
+        .enumerate()
+        .map(|(i, (position, car))| {
+            let place = (
+                symmetry_key(i, car),
+                (*position.row()).into(),
+                (*position.column()).into(),
+            );
+            (place, CarId::new(i + 1).unwrap())
+        })
+        .collect::<HashMap<(usize, usize, usize), _>>();
+    state
+        .cars()
+        .iter()
+        .enumerate()
+        .map(|(i, (position, car))| {
+            let (row, column) = symmetry.reflect(dimensions, position, car);
+            places.get(&(symmetry_key(i, car), row, column)).copied()
+        })
+        .collect()
+}
+
+/// The hash of the provided puzzle after the provided transformation, identifying cars only by
+/// [`symmetry_key`].
+fn symmetry_hash<T: BoardValue>(state: &State<T>, symmetry: BoardSymmetry) -> u64 {
+    let dimensions = board_dimensions(state);
+    hash_car_positions(state.cars().iter().enumerate().map(|(i, (position, car))| {
+        let (row, column) = symmetry.reflect(dimensions, position, car);
+        (symmetry_key(i, car), row, column)
+    }))
+}
+
+/// What distinguishes the car with the provided index from others when looking for symmetries: the
+/// objective car is unique, while others are only told apart by their length and orientation.
+fn symmetry_key<T: BoardValue>(i: usize, car: &Car<T>) -> usize {
+    if i == 0 {
+        return 0;
+    }
+    let length: usize = (*car.length()).into();
+    length << 1 | usize::from(car.orientation().axis() == Axis::Vertical)
+}
+
+/// The number of rows and columns of the provided state's board.
+fn board_dimensions<T: BoardValue>(state: &State<T>) -> (usize, usize) {
+    (
+        (*state.dimensions().rows()).into(),
+        (*state.dimensions().columns()).into(),
+    )
+}
+
+/// The depth to which [`rate_difficulty`] explores the puzzle.
+const DIFFICULTY_DEPTH: usize = 50;
+
+/// How hard a puzzle is, from the shape of the states reachable from its initial state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DifficultyLevel {
+    /// Solved, or nearly so, from the start.
+    Trivial,
+    /// Solved with a handful of moves.
+    Easy,
+    /// Takes some thought.
+    Medium,
+    /// Takes a lot of thought.
+    Hard,
+    /// Takes more moves than we are willing to search, or close to it.
+    Expert,
+}
+
+/// The properties of a puzzle which make it hard to solve, as found by [`rate_difficulty`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapDifficulty {
+    /// The fewest moves which solve the puzzle, or [`None`] if it cannot be solved within the
+    /// explored depth.
+    pub min_solution_length: Option<usize>,
+    /// The number of distinct states reachable within the explored depth.
+    pub state_count: usize,
+    /// The average number of valid moves from each of those states.
+    pub branching_factor: f64,
+    /// The overall rating of the puzzle.
+    pub level: DifficultyLevel,
+}
+
+/// The least solution length and state count at which a puzzle is rated at each level above
+/// [`DifficultyLevel::Trivial`]. A puzzle is rated at the highest level either measure reaches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifficultyThresholds {
+    /// The solution lengths for [`DifficultyLevel::Easy`] through [`DifficultyLevel::Expert`].
+    pub moves: [usize; 4],
+    /// The state counts for [`DifficultyLevel::Easy`] through [`DifficultyLevel::Expert`].
+    pub states: [usize; 4],
+}
+
+impl DifficultyThresholds {
+    /// The level of a puzzle with the provided properties. Puzzles without a known solution are
+    /// always [`DifficultyLevel::Expert`].
+    pub fn level(&self, min_solution_length: Option<usize>, state_count: usize) -> DifficultyLevel {
+        const LEVELS: [DifficultyLevel; 5] = [
+            DifficultyLevel::Trivial,
+            DifficultyLevel::Easy,
+            DifficultyLevel::Medium,
+            DifficultyLevel::Hard,
+            DifficultyLevel::Expert,
+        ];
+        let Some(moves) = min_solution_length else {
+            return DifficultyLevel::Expert;
+        };
+        let by_moves = self.moves.iter().filter(|&&t| moves >= t).count();
+        let by_states = self.states.iter().filter(|&&t| state_count >= t).count();
+        LEVELS[by_moves.max(by_states)]
+    }
+}
+
+impl Default for DifficultyThresholds {
+    fn default() -> Self {
+        Self {
+            moves: [2, 10, 25, 45],
+            states: [50, 1_000, 10_000, 50_000],
+        }
+    }
+}
+
+/// Rates the difficulty of the puzzle with the default [`DifficultyThresholds`].
+///
+/// Panics if the initial state is invalid.
+pub fn rate_difficulty<T: BoardValue>(initial: &State<T>) -> MapDifficulty {
+    rate_difficulty_with(initial, &DifficultyThresholds::default())
+}
+
+/// Rates the difficulty of the puzzle by exploring every state reachable within 50 moves.
+///
+/// Panics if the initial state is invalid.
+pub fn rate_difficulty_with<T: BoardValue>(
+    initial: &State<T>,
+    thresholds: &DifficultyThresholds,
+) -> MapDifficulty {
+    let hash = |state: &State<T>| hash_state(state).expect("The state should be valid.");
+
+    let mut min_solution_length = None;
+    let mut moves = 0;
+    let mut seen = HashSet::from([hash(initial)]);
+    let mut queue = VecDeque::from([(initial.clone(), 0)]);
+    while let Some((state, depth)) = queue.pop_front() {
+        if min_solution_length.is_none()
+            && blocking_path(&state.board().expect("The state should be valid.")).is_empty()
+        {
+            min_solution_length = Some(depth);
+        }
+        let next = successors(&state);
+        moves += next.len();
+        if depth == DIFFICULTY_DEPTH {
+            continue;
+        }
+        for (_, next) in next {
+            if seen.insert(hash(&next)) {
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+
+    let state_count = seen.len();
+    MapDifficulty {
+        min_solution_length,
+        state_count,
+        branching_factor: moves as f64 / state_count as f64,
+        level: thresholds.level(min_solution_length, state_count),
+    }
+}
+
+/// Samples states reachable from the initial state, each by making `steps_per_sample` random moves
+/// (or fewer, should no move be possible). Duplicate states are only returned once, so at most
+/// `num_samples` states are returned. So long as `steps_per_sample` is small relative to the number
+/// of moves separating the furthest states, the samples are spread roughly evenly over the states
+/// reachable in that many moves.
+///
+/// Panics if the initial state is invalid.
+pub fn sample_reachable_states<T: BoardValue>(
+    initial: &State<T>,
+    num_samples: usize,
+    steps_per_sample: usize,
+    rng: &mut impl Rand,
+) -> Vec<State<T>> {
+    let mut seen = HashSet::new();
+    let mut samples = Vec::new();
+    for _ in 0..num_samples {
+        let mut state = initial.clone();
+        for _ in 0..steps_per_sample {
+            let Some((_, next)) = rng.choose(successors(&state)) else {
+                break;
+            };
+            state = next;
+        }
+        if seen.insert(hash_state(&state).expect("The state should be valid.")) {
+            samples.push(state);
+        }
+    }
+    samples
+}
+
+/// Breadth-first search for the fewest moves to a state satisfying `goal`, giving up on paths
+/// longer than `depth_limit`.
+fn search<T: BoardValue>(
+    initial: &State<T>,
+    depth_limit: usize,
+    goal: impl Fn(&State<T>) -> bool,
+) -> Option<usize> {
+    let hash = |state: &State<T>| hash_state(state).expect("The state should be valid.");
+
+    let mut seen = HashSet::from([hash(initial)]);
+    let mut queue = VecDeque::from([(initial.clone(), 0)]);
+    while let Some((state, depth)) = queue.pop_front() {
+        if goal(&state) {
+            return Some(depth);
+        }
+        if depth == depth_limit {
+            continue;
+        }
+        for (_, next) in successors(&state) {
+            if seen.insert(hash(&next)) {
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Each move which may be made from the provided state, paired with the state it leads to.
+pub(crate) fn successors<T: BoardValue>(state: &State<T>) -> Vec<((CarId, Direction), State<T>)> {
+    let mut successors = Vec::new();
+    for idx in 1..=state.cars().len() {
+        let car = CarId::new(idx).unwrap();
+        for direction in [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            let mut next = state.clone();
+            let moved = next
+                .board_mut()
+                .expect("The state should be valid.")
+                .shift_car(car.as_nonzero(), direction)
+                .is_ok();
+            if moved {
+                successors.push(((car, direction), next));
+            }
+        }
+    }
+    successors
+}
+
+/// How much each part of [`heuristic_score`] counts towards the score of an input.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HeuristicWeights {
+    /// The weight of the blockers term, which favours fewer cars in the path of the objective car.
+    pub blockers: f64,
+    /// The weight of the length term, which favours shorter inputs.
+    pub length: f64,
+    /// The weight of the forward term, which favours more room ahead of the objective car.
+    pub forward: f64,
+}
+
+impl HeuristicWeights {
+    /// Weights for the blockers, length, and forward terms respectively.
+    pub fn custom(blockers: f64, length: f64, forward: f64) -> Self {
+        Self {
+            blockers,
+            length,
+            forward,
+        }
+    }
+}
+
+impl Default for HeuristicWeights {
+    fn default() -> Self {
+        Self::custom(0.5, 0.3, 0.2)
+    }
+}
+
+/// Scores how close the provided input comes to a solution with the default [`HeuristicWeights`];
+/// higher is closer.
+pub fn heuristic_score<T: BoardValue>(
+    input: &PGInput,
+    view_meta: &ViewMetadata<T>,
+    near_solution_meta: &NearSolutionMetadata,
+) -> f64 {
+    heuristic_score_with(
+        &HeuristicWeights::default(),
+        input,
+        view_meta,
+        near_solution_meta,
+    )
+}
+
+/// Scores how close the provided input comes to a solution, from the cars left in the path of the
+/// objective car, the length of the input, and how much of the room the objective car has to move
+/// lies ahead of it. Each term is between 0 and 1, so the score is at most the sum of the weights.
+pub fn heuristic_score_with<T: BoardValue>(
+    weights: &HeuristicWeights,
+    input: &PGInput,
+    view_meta: &ViewMetadata<T>,
+    near_solution_meta: &NearSolutionMetadata,
+) -> f64 {
+    let blockers = 1.0 / (near_solution_meta.blockers() + 1) as f64;
+    let length = 1.0 / ((input.len() + 1) as f64).sqrt();
+    let forward = view_meta.views().next().map_or(0.0, |(_, view)| {
+        let ahead: usize = (*view.forward().distance()).into();
+        let total: usize = view.total_free_distance().into();
+        if total > 0 {
+            ahead as f64 / total as f64
+        } else {
+            // with no room to move, only a car already at the wall is as far forward as it can be
+            f64::from(view.forward().observed().is_none())
+        }
+    });
+    weights.blockers * blockers + weights.length * length + weights.forward * forward
+}
+
+/// A state in a [`StateTransitionGraph`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    /// The hash of the state, as computed by the
+    /// [`crate::observers::TransitionCountObserver`].
+    pub hash: u64,
+    /// Whether the objective car has nothing between it and the wall ahead of it in this state.
+    pub is_solution: bool,
+    /// The number of executions the fuzzer had run when this state was first reached.
+    pub first_seen_at: u64,
+}
+
+/// The states discovered while fuzzing and the moves seen between them, built from the
+/// [`TransitionMetadata`] collected by a [`crate::observers::TransitionCountObserver`] once fuzzing
+/// is done. Export it with [`StateTransitionGraph::to_dot`] to visualise it with GraphViz.
+#[derive(Debug, Clone, Default)]
+pub struct StateTransitionGraph {
+    nodes: HashMap<u64, GraphNode>,
+    edges: Vec<(u64, u64, CarId, Direction)>,
+}
+
+impl StateTransitionGraph {
+    /// Create a graph with no states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a graph of every transition in the provided metadata, and the states they connect.
+    pub fn from_metadata(metadata: &TransitionMetadata) -> Self {
+        let mut graph = Self::new();
+        let mut transitions = metadata.transitions().iter().collect::<Vec<_>>();
+        // keep the output stable between runs, as the metadata is unordered
+        transitions.sort_unstable_by_key(|&(&transition, _)| transition);
+        for (&(from, to), &(car, direction)) in transitions {
+            for hash in [from, to] {
+                graph.add_node(GraphNode {
+                    hash,
+                    is_solution: metadata.is_solved(hash),
+                    first_seen_at: metadata.first_seen(hash).unwrap_or(0),
+                });
+            }
+            graph.add_edge(from, to, car, direction);
+        }
+        graph
+    }
+
+    /// Create a graph from the [`TransitionMetadata`] of the provided fuzzer state. Fails if there
+    /// is none, i.e., no [`crate::observers::TransitionCountObserver`] was used.
+    pub fn from_state<S: HasMetadata>(state: &S) -> Result<Self, Error> {
+        state
+            .metadata::<TransitionMetadata>()
+            .map(Self::from_metadata)
+    }
+
+    /// Add the provided state, replacing any state with the same hash.
+    pub fn add_node(&mut self, node: GraphNode) {
+        self.nodes.insert(node.hash, node);
+    }
+
+    /// Add a transition between the states with the provided hashes, made by moving the provided
+    /// car in the provided direction. States not yet in the graph are added as unsolved states
+    /// first seen at the start of fuzzing.
+    pub fn add_edge(&mut self, from: u64, to: u64, car: CarId, direction: Direction) {
+        for hash in [from, to] {
+            self.nodes.entry(hash).or_insert(GraphNode {
+                hash,
+                is_solution: false,
+                first_seen_at: 0,
+            });
+        }
+        self.edges.push((from, to, car, direction));
+    }
+
+    /// The state with the provided hash, if it is in the graph.
+    pub fn node(&self, hash: u64) -> Option<&GraphNode> {
+        self.nodes.get(&hash)
+    }
+
+    /// The states in the graph, in no particular order.
+    pub fn nodes(&self) -> impl Iterator<Item = &GraphNode> {
+        self.nodes.values()
+    }
+
+    /// The transitions in the graph, in the order they were added.
+    pub fn edges(&self) -> &[(u64, u64, CarId, Direction)] {
+        &self.edges
+    }
+
+    /// The graph in the GraphViz DOT format. Solved states are green and other states gray, and
+    /// each transition is labelled with its move, such as `1→R`.
+    pub fn to_dot(&self) -> String {
+        let mut nodes = self.nodes.values().collect::<Vec<_>>();
+        nodes.sort_unstable_by_key(|node| (node.first_seen_at, node.hash));
+
+        let mut dot = String::from("digraph states {\n    node [style=filled];\n");
+        for node in nodes {
+            let color = if node.is_solution { "green" } else { "gray" };
+            dot.push_str(&format!(
+                "    s{:016x} [label=\"{:016x}\", fillcolor={color}];\n",
+                node.hash, node.hash
+            ));
+        }
+        for &(from, to, car, direction) in &self.edges {
+            dot.push_str(&format!(
+                "    s{from:016x} -> s{to:016x} [label=\"{car}→{}\"];\n",
+                direction_letter(direction)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The greatest number of transitions needed to reach one state of the graph from another,
+    /// taking the shortest path between each pair of states where there is one.
+    pub fn diameter(&self) -> usize {
+        let mut successors = HashMap::<u64, Vec<u64>>::new();
+        for &(from, to, _, _) in &self.edges {
+            successors.entry(from).or_default().push(to);
+        }
+
+        let mut diameter = 0;
+        for &start in self.nodes.keys() {
+            let mut distances = HashMap::from([(start, 0)]);
+            let mut queue = VecDeque::from([start]);
+            while let Some(hash) = queue.pop_front() {
+                let distance = distances[&hash];
+                diameter = diameter.max(distance);
+                for &next in successors.get(&hash).into_iter().flatten() {
+                    if let Entry::Vacant(entry) = distances.entry(next) {
+                        entry.insert(distance + 1);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        diameter
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::{
+        BoardSymmetry, CellChange, CorpusStatistics, CrashInputDatabase, DifficultyLevel,
+        DifficultyThresholds, FuzzingReport, GraphNode, HeuristicWeights, InputSizeHistogram,
+        ReportParseError, SolutionDatabase, StateTransitionGraph, analyze_corpus, board_diff,
+        car_blocking_path, car_can_reach, compare_runs, deduplicate_corpus, detect_symmetry,
+        forced_moves, heuristic_score, heuristic_score_with, is_solvable, min_moves_to_solution,
+        mirrored_cars, rate_difficulty, sample_reachable_states, search, visualize_input,
+    };
+    use crate::feedbacks::{NearSolutionMetadata, ViewMetadata};
+    use crate::input::PGInput;
+    use crate::map_format::car_name;
+    use crate::observers::{View, ViewFrom, hash_state};
+    use crate::types::CarId;
+    use libafl::HasMetadata;
+    use libafl::corpus::{Corpus, InMemoryCorpus, Testcase};
+    use libafl::state::{HasCorpus, NopState, StdState};
+    use libafl_bolts::rands::StdRand;
+    use parking_game::{Direction, State};
+    use std::collections::HashSet;
+    use std::error::Error;
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn solution_database() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo....\n......");
+        let mut database = SolutionDatabase::new(initial.clone());
+        assert_eq!(None, database.shortest());
+
+        let solutions = [
+            "1R 1R 1R 1R 1L 1R 1L",
+            "1R 1L 1R 1R 1R 1R",
+            "1R 1R 1R 1R 1L",
+            "1R 1R 1R 1L 1R",
+            "1R 1R 1L 1R 1R",
+            "1R 1R 1R 1R",
+            "1R 1L 1R 1L 1R",
+            // runs into the wall, so has no final state
+            "1R 1R 1R 1R 1R 1R",
+            "1R 1R 1R",
+            "1R 1R 1R 1L 1R 1R",
+        ];
+        for solution in solutions {
+            database.insert(solution.parse()?);
+        }
+
+        assert_eq!(10, database.count());
+        assert_eq!(Some(&"1R 1R 1R".parse()?), database.shortest());
+        let five = [
+            "1R 1R 1R 1R 1L",
+            "1R 1R 1R 1L 1R",
+            "1R 1R 1L 1R 1R",
+            "1R 1L 1R 1L 1R",
+        ];
+        assert_eq!(
+            five.map(|s| s.parse::<PGInput>().unwrap()),
+            database.by_length(5)
+        );
+        assert!(database.by_length(1).is_empty());
+        assert!(database.by_length(100).is_empty());
+
+        for input in ["1R 1R 1R", "1R 1R 1R 1R", "1R 1R 1R 1L"] {
+            let mut state = initial.clone();
+            input.parse::<PGInput>()?.apply(&mut state).unwrap();
+            let hash = hash_state(&state).unwrap();
+            // only the last of these states is not the end of any solution
+            assert_eq!(
+                input != "1R 1R 1R 1L",
+                database.contains_hash(hash),
+                "{input}"
+            );
+        }
+        assert!(!database.contains_hash(hash_state(&initial).unwrap()));
+
+        let mut corpus = InMemoryCorpus::<PGInput>::new();
+        for solution in solutions {
+            corpus.add(Testcase::new(solution.parse()?))?;
+        }
+        let from_corpus = SolutionDatabase::from_corpus(initial, &corpus)?;
+        assert_eq!(10, from_corpus.count());
+        assert_eq!(database.by_length(5), from_corpus.by_length(5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn corpus_statistics() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo......");
+        let mut corpus = InMemoryCorpus::<PGInput>::new();
+        assert_eq!(
+            CorpusStatistics {
+                size: 0,
+                avg_length: 0.0,
+                min_length: 0,
+                max_length: 0,
+                unique_final_states: 0,
+            },
+            analyze_corpus(&corpus, &initial)
+        );
+
+        for input in [
+            "1R 1R 1R 1R 1R",
+            "1R 1L 1R 1L 1R 1L 1R 1L 1R 1L",
+            "1R 1L 1R 1L 1R 1L 1R 1L 1R 1L 1R 1L 1R 1L 1R",
+        ] {
+            corpus.add(Testcase::new(input.parse()?))?;
+        }
+        let stats = analyze_corpus(&corpus, &initial);
+        assert_eq!(
+            CorpusStatistics {
+                size: 3,
+                avg_length: 10.0,
+                min_length: 5,
+                max_length: 15,
+                unique_final_states: 3,
+            },
+            stats
+        );
+        assert_eq!(
+            "3 entries of 5-15 moves (10.0 on average), finishing in 3 unique states",
+            stats.to_string()
+        );
+
+        // the same final state as the first entry, and one which crashes
+        corpus.add(Testcase::new("1R 1R 1R 1R 1L 1R 1R".parse()?))?;
+        corpus.add(Testcase::new("1L".parse()?))?;
+        let stats = analyze_corpus(&corpus, &initial);
+        assert_eq!(5, stats.size);
+        assert_eq!(1, stats.min_length);
+        assert_eq!(3, stats.unique_final_states);
+
+        Ok(())
+    }
+
+    #[test]
+    fn deduplicate() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo......");
+        let mut corpus = InMemoryCorpus::<PGInput>::new();
+        // the first and third entries finish in the same state, as do the second and fourth
+        for input in ["1R 1R", "1R 1L 1R", "1R 1L 1R 1R", "1R", "1R 1R 1R"] {
+            corpus.add(Testcase::new(input.parse()?))?;
+        }
+
+        assert_eq!(2, deduplicate_corpus(&mut corpus, &initial));
+        assert_eq!(3, corpus.count());
+        let mut remaining = Vec::new();
+        let mut next = corpus.first();
+        while let Some(id) = next {
+            next = corpus.next(id);
+            remaining.push(corpus.cloned_input_for_id(id)?.to_string());
+        }
+        assert_eq!(vec!["1R 1R", "1R", "1R 1R 1R"], remaining);
+        assert_eq!(0, deduplicate_corpus(&mut corpus, &initial));
+
+        Ok(())
+    }
+
+    #[test]
+    fn report_csv() -> Result<(), Box<dyn Error>> {
+        let columns = FuzzingReport::csv_header().split(',').count();
+        let report = FuzzingReport {
+            start_time: Instant::now() - Duration::from_secs(10),
+            elapsed_secs: 10.5,
+            total_executions: 12_345,
+            corpus_size: 67,
+            solutions_count: 2,
+            best_solution_length: Some(31),
+            crash_rate: 0.875,
+            executions_per_second: 1175.7142857142858,
+        };
+        let row = report.to_csv_row();
+        assert_eq!(columns, row.split(',').count());
+        let parsed = FuzzingReport::from_csv_row(&row)?;
+        assert_eq!(
+            FuzzingReport {
+                start_time: parsed.start_time,
+                ..report.clone()
+            },
+            parsed
+        );
+        let drift = parsed.start_time.elapsed().as_secs_f64() - report.elapsed_secs;
+        assert!((0.0..1.0).contains(&drift));
+
+        // nothing measured yet
+        let empty = FuzzingReport {
+            best_solution_length: None,
+            crash_rate: f64::NAN,
+            ..report
+        };
+        let row = empty.to_csv_row();
+        assert_eq!(columns, row.split(',').count());
+        assert!(row.contains(",NaN,"));
+        let parsed = FuzzingReport::from_csv_row(&row)?;
+        assert_eq!(None, parsed.best_solution_length);
+        assert!(parsed.crash_rate.is_nan());
+
+        assert_eq!(
+            ReportParseError::FieldCount(2),
+            FuzzingReport::from_csv_row("1.0,2").unwrap_err()
+        );
+        assert_eq!(
+            ReportParseError::InvalidField("corpus_size".to_string(), "x".to_string()),
+            FuzzingReport::from_csv_row("1.0,2,x,0,,NaN,2.0").unwrap_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn size_histogram() -> Result<(), Box<dyn Error>> {
+        let mut histogram = InputSizeHistogram::new(5);
+        assert_eq!("(no entries)", histogram.render());
+        // lengths 0 to 4 fall in the first bucket, and 5 to 9 in the second
+        for len in 0..10 {
+            histogram.record(&vec!["1R"; len].join(" ").parse()?);
+        }
+        assert_eq!(2, histogram.buckets().len());
+        assert_eq!(Some(&5), histogram.buckets().get(&0));
+        assert_eq!(Some(&5), histogram.buckets().get(&1));
+
+        histogram.record(&"1R 1L 1R 1L 1R 1L 1R 1L 1R 1L 1R 1L 1R 1L 1R 1L 1R".parse()?);
+        let rendered = histogram.render();
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(4, lines.len());
+        assert!(lines[0].starts_with("  0-4 | #"));
+        assert!(lines[0].ends_with(" 5"));
+        assert!(lines[1].starts_with("  5-9 | #"));
+        // empty buckets in between are still shown
+        assert_eq!("10-14 |  0", lines[2]);
+        assert!(lines[3].starts_with("15-19 | #"));
+        assert!(lines[3].ends_with(" 1"));
+        histogram.print_histogram();
+
+        // the state's histogram is created on demand, and only counts entries once
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )?;
+        for moves in ["", "1R", "1R 1L"] {
+            state
+                .corpus_mut()
+                .add(Testcase::new(moves.parse::<PGInput>()?))?;
+        }
+        InputSizeHistogram::record_corpus(&mut state, 0)?;
+        state
+            .corpus_mut()
+            .add(Testcase::new(["1R"; 12].join(" ").parse::<PGInput>()?))?;
+        InputSizeHistogram::record_corpus(&mut state, 3)?;
+        let histogram = state.metadata::<InputSizeHistogram>()?;
+        assert_eq!(
+            InputSizeHistogram::DEFAULT_BUCKET_SIZE,
+            histogram.bucket_size()
+        );
+        assert_eq!(Some(&3), histogram.buckets().get(&0));
+        assert_eq!(Some(&1), histogram.buckets().get(&1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("......\n1oo...\n1.....\n..22..");
+        let input = "2U 1R 1R".parse::<PGInput>()?;
+
+        let frames = visualize_input(&input, &initial);
+        assert_eq!(input.len() + 1, frames.len());
+        assert_eq!("......\n1oo...\n1.....\n..22..", frames[0]);
+        assert_eq!("1.....\n1..oo.\n......\n..22..", frames[3]);
+        for (&(car, _), pair) in input.moves().iter().zip(frames.windows(2)) {
+            // each move empties one cell and fills another, both with the car moved
+            let changed = pair[0]
+                .chars()
+                .zip(pair[1].chars())
+                .filter(|(before, after)| before != after)
+                .collect::<Vec<_>>();
+            let name = car_name(car).unwrap();
+            assert_eq!(2, changed.len());
+            assert!(changed.contains(&(name, '.')));
+            assert!(changed.contains(&('.', name)));
+        }
+
+        // the frames stop before the move which cannot be made
+        assert_eq!(2, visualize_input(&"1R 2R 1R".parse()?, &initial).len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn diff_boards() -> Result<(), Box<dyn Error>> {
+        let car = |id| CarId::new(id).unwrap();
+        let initial = crate::parse_map::<u8>("oo....\n......\n1.....\n1.....");
+        assert!(board_diff(&initial, &initial.clone()).is_empty());
+
+        let mut shifted = initial.clone();
+        "1R".parse::<PGInput>()?.apply(&mut shifted).unwrap();
+        assert_eq!(
+            vec![
+                (0, 0, CellChange::EmptiedBy(car(1))),
+                (0, 2, CellChange::FilledBy(car(1))),
+            ],
+            board_diff(&initial, &shifted)
+        );
+
+        // once the car has moved its whole length, every cell it covered is emptied
+        "1R".parse::<PGInput>()?.apply(&mut shifted).unwrap();
+        let diff = board_diff(&initial, &shifted);
+        assert_eq!(2 * 2, diff.len());
+        assert_eq!(
+            2,
+            diff.iter()
+                .filter(|(_, _, change)| *change == CellChange::EmptiedBy(car(1)))
+                .count()
+        );
+
+        // car 2 moves into the cells the objective car left
+        let mut replaced = shifted.clone();
+        "2U 2U".parse::<PGInput>()?.apply(&mut replaced).unwrap();
+        let diff = board_diff(&initial, &replaced);
+        assert!(diff.contains(&(
+            0,
+            0,
+            CellChange::ReplacedBy {
+                from: car(1),
+                to: car(2)
+            }
+        )));
+        assert!(diff.contains(&(3, 0, CellChange::EmptiedBy(car(2)))));
+        assert!(diff.contains(&(0, 3, CellChange::FilledBy(car(1)))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn blocking_path() -> Result<(), Box<dyn Error>> {
+        let car = |id| CarId::new(id).unwrap();
+        for (map, expected) in [
+            ("oo....\n..1...", vec![]),
+            // cars behind the objective car are not in its way
+            ("1oo...\n1.....", vec![]),
+            ("oo1...\n..1...", vec![car(2)]),
+            ("oo12..\n..12..", vec![car(2), car(3)]),
+            // the order is by distance from the objective car, not by id
+            ("oo.2.1\n...2.1", vec![car(3), car(2)]),
+            ("o.\no.\n1.\n1.\n22", vec![car(2), car(3)]),
+        ] {
+            let state = crate::parse_map::<u8>(map);
+            assert_eq!(expected, car_blocking_path(&state)?, "{map}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn optimal_solutions() -> Result<(), Box<dyn Error>> {
+        let optimal = [
+            (1, 30),
+            (2, 28),
+            (3, 30),
+            (4, 40),
+            (5, 42),
+            (6, 30),
+            (7, 44),
+            (8, 53),
+            (9, 57),
+            (10, 43),
+            (11, 48),
+            (12, 47),
+            (13, 54),
+            (15, 50),
+            (16, 53),
+            (17, 52),
+            (18, 49),
+            (19, 51),
+            (20, 55),
+            (21, 65),
+            (22, 58),
+            (23, 59),
+            (24, 58),
+            (25, 66),
+            (26, 76),
+            (27, 61),
+            (28, 72),
+            (29, 63),
+            (30, 68),
+            (31, 74),
+            (32, 60),
+            (33, 62),
+            (34, 73),
+            (35, 78),
+            (36, 78),
+        ];
+        for (map, moves) in optimal {
+            let path = format!("{}/maps/tokyo{map}.map", env!("CARGO_MANIFEST_DIR"));
+            let initial = crate::parse_map::<u8>(&fs::read_to_string(&path)?);
+            assert_eq!(Some(moves), min_moves_to_solution(&initial, 100), "{path}");
+        }
+
+        let tokyo1 = crate::parse_map::<u8>(&fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/maps/tokyo1.map"
+        ))?);
+        assert_eq!(None, min_moves_to_solution(&tokyo1, 29));
+
+        assert_eq!(
+            Some(0),
+            min_moves_to_solution(&crate::parse_map::<u8>("oo..\n.1.."), 10)
+        );
+        // car 2 fills its column, so can never get out of the way
+        let stuck = crate::parse_map::<u8>("oo1.\n..1.");
+        assert_eq!(None, min_moves_to_solution(&stuck, 100));
+
+        Ok(())
+    }
+
+    #[test]
+    fn solvability() -> Result<(), Box<dyn Error>> {
+        let clear = crate::parse_map::<u8>("oo..\n.1..\n.1..");
+        assert!(is_solvable(&clear, 0));
+
+        // car 2 fills its column, walling the objective car and car 3 into their own compartments
+        let locked = crate::parse_map::<u8>("oo1...\n..122.\n..1...");
+        assert!(!is_solvable(&locked, 100));
+        let two = CarId::new(3).unwrap();
+        assert!(car_can_reach(&locked, two, (1, 3).into(), 0));
+        assert!(car_can_reach(&locked, two, (1, 4).into(), 100));
+        assert!(!car_can_reach(&locked, two, (1, 4).into(), 0));
+        assert!(!car_can_reach(&locked, two, (1, 0).into(), 100));
+        assert!(!car_can_reach(
+            &locked,
+            CarId::new(1).unwrap(),
+            (0, 3).into(),
+            100
+        ));
+        assert!(!car_can_reach(
+            &locked,
+            CarId::new(4).unwrap(),
+            (0, 0).into(),
+            100
+        ));
+
+        let tokyo1 = crate::parse_map::<u8>(&fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/maps/tokyo1.map"
+        ))?);
+        assert!(is_solvable(&tokyo1, 30));
+        assert!(!is_solvable(&tokyo1, 29));
+
+        Ok(())
+    }
+
+    #[test]
+    fn difficulty() -> Result<(), Box<dyn Error>> {
+        let trivial = rate_difficulty(&crate::parse_map::<u8>("oo."));
+        assert_eq!(Some(0), trivial.min_solution_length);
+        assert_eq!(2, trivial.state_count);
+        assert_eq!(1.0, trivial.branching_factor);
+        assert_eq!(DifficultyLevel::Trivial, trivial.level);
+
+        let hardest = crate::parse_map::<u8>(&fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/maps/tokyo36.map"
+        ))?);
+        assert!(rate_difficulty(&hardest).level >= DifficultyLevel::Medium);
+
+        let thresholds = DifficultyThresholds::default();
+        assert_eq!(DifficultyLevel::Easy, thresholds.level(Some(2), 1));
+        assert_eq!(DifficultyLevel::Medium, thresholds.level(Some(2), 1_000));
+        assert_eq!(DifficultyLevel::Hard, thresholds.level(Some(30), 1));
+        assert_eq!(DifficultyLevel::Expert, thresholds.level(None, 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn forced() {
+        let one = CarId::new(2).unwrap();
+        let left_only = crate::parse_map::<u8>("oo.11");
+        assert_eq!(vec![(one, Direction::Left)], forced_moves(&left_only));
+
+        let down_only = crate::parse_map::<u8>("oo.1\n...1\n....");
+        assert_eq!(vec![(one, Direction::Down)], forced_moves(&down_only));
+
+        // car 1 is stuck, but car 2 is in its way and may only move down
+        let chained = crate::parse_map::<u8>("oo.1\n...1\n...2\n...2\n....");
+        assert_eq!(
+            vec![(CarId::new(3).unwrap(), Direction::Down)],
+            forced_moves(&chained)
+        );
+
+        let unconstrained = crate::parse_map::<u8>("....\noo.1\n...1\n....");
+        assert_eq!(
+            Vec::<(CarId, Direction)>::new(),
+            forced_moves(&unconstrained)
+        );
+        let clear = crate::parse_map::<u8>("oo..\n.1..\n.1..");
+        assert!(forced_moves(&clear).is_empty());
+    }
+
+    #[test]
+    fn symmetry() {
+        let two = CarId::new(2).unwrap();
+        let three = CarId::new(3).unwrap();
+        // cars 2 and 3 swap places when the board is mirrored
+        let horizontal = crate::parse_map::<u8>("1..2\n1..2\n.oo.\n....");
+        assert_eq!(BoardSymmetry::HorizontalFlip, detect_symmetry(&horizontal));
+        assert_eq!(
+            Some(vec![CarId::new(1).unwrap(), three, two]),
+            mirrored_cars(&horizontal, BoardSymmetry::HorizontalFlip)
+        );
+        assert_eq!(
+            None,
+            mirrored_cars(&horizontal, BoardSymmetry::VerticalFlip)
+        );
+
+        let vertical = crate::parse_map::<u8>("1..\n1..\n...\noo.\n...\n2..\n2..");
+        assert_eq!(BoardSymmetry::VerticalFlip, detect_symmetry(&vertical));
+        let rotational = crate::parse_map::<u8>("1...\n1...\n.oo.\n...2\n...2");
+        assert_eq!(BoardSymmetry::Rotational180, detect_symmetry(&rotational));
+        let asymmetric = crate::parse_map::<u8>("oo..\n....\n.1..\n.1..");
+        assert_eq!(BoardSymmetry::None, detect_symmetry(&asymmetric));
+
+        assert_eq!(
+            Direction::Up,
+            BoardSymmetry::HorizontalFlip.mirror_direction(Direction::Up)
+        );
+        assert_eq!(
+            Direction::Left,
+            BoardSymmetry::Rotational180.mirror_direction(Direction::Right)
+        );
+
+        // the same cars on a board with thousands of times the cells take no longer to check
+        let large = (0..250)
+            .map(|row| match row {
+                0 | 1 => format!("1{}2", ".".repeat(248)),
+                2 => format!("{}oo{}", ".".repeat(124), ".".repeat(124)),
+                _ => ".".repeat(250),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let large = crate::parse_map::<u16>(&large);
+        assert_eq!(BoardSymmetry::HorizontalFlip, detect_symmetry(&large));
+        let time = |state: &State<u16>| {
+            let start = Instant::now();
+            for _ in 0..10_000 {
+                std::hint::black_box(detect_symmetry(std::hint::black_box(state)));
+            }
+            start.elapsed()
+        };
+        let small = time(&crate::parse_map("1..2\n1..2\n.oo.\n...."));
+        assert!(time(&large) < small * 10 + Duration::from_millis(50));
+    }
+
+    #[test]
+    fn heuristic() -> Result<(), Box<dyn Error>> {
+        let views = ViewMetadata::new(vec![ViewFrom::new(
+            View::new(Direction::Left, None, 1u8),
+            View::new(Direction::Right, Some(CarId::new(2).unwrap()), 1),
+        )]);
+        let short = "1R 1L 1R".parse::<PGInput>()?;
+        let long = "1R 1L 1R 1L 1R 1L 1R 1L 1R 1L".parse::<PGInput>()?;
+        let clear = NearSolutionMetadata::new(0);
+        let blocked = NearSolutionMetadata::new(2);
+        assert!(heuristic_score(&short, &views, &clear) > heuristic_score(&long, &views, &blocked));
+        // 0.5 for no blockers, 0.3 / 2 for three moves, and 0.2 / 2 for half the room ahead
+        assert!((heuristic_score(&short, &views, &clear) - 0.75).abs() < 1e-9);
+
+        let only_length = HeuristicWeights::custom(0.0, 1.0, 0.0);
+        assert_eq!(
+            0.5,
+            heuristic_score_with(&only_length, &short, &views, &blocked)
+        );
+        let only_blockers = HeuristicWeights::custom(1.0, 0.0, 0.0);
+        assert_eq!(
+            heuristic_score_with(&only_blockers, &short, &views, &blocked),
+            heuristic_score_with(&only_blockers, &long, &views, &blocked)
+        );
+
+        // a car which may not move is only fully forward when it is at the wall
+        let only_forward = HeuristicWeights::custom(0.0, 0.0, 1.0);
+        let at_wall = ViewMetadata::new(vec![ViewFrom::new(
+            View::new(Direction::Left, Some(CarId::new(2).unwrap()), 0u8),
+            View::new(Direction::Right, None, 0),
+        )]);
+        assert_eq!(
+            1.0,
+            heuristic_score_with(&only_forward, &short, &at_wall, &clear)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn crash_database() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n....");
+        let mut crashes = CrashInputDatabase::new(initial.clone());
+
+        assert_eq!(None, crashes.insert(&"2D".parse::<PGInput>()?));
+        assert_eq!(0, crashes.unique_crash_count());
+
+        // both crash with car 2 moved down once
+        let first = "2D 1L".parse::<PGInput>()?;
+        let second = "2D 2U 2D 2D 2D".parse::<PGInput>()?;
+        let hash = crashes.insert(&first).unwrap();
+        assert_eq!(Some(hash), crashes.insert(&second));
+        assert_eq!(1, crashes.unique_crash_count());
+        assert_eq!(Some(&first), crashes.representative_for_hash(hash));
+
+        let elsewhere = "1L".parse::<PGInput>()?;
+        let other = crashes.insert(&elsewhere).unwrap();
+        assert_ne!(hash, other);
+        assert_eq!(2, crashes.unique_crash_count());
+        assert_eq!(Some(&elsewhere), crashes.representative_for_hash(other));
+        assert_eq!(None, crashes.representative_for_hash(hash ^ other ^ 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sample_states() {
+        let initial = crate::parse_map::<u8>("......\n1oo...\n1.....\n..22..");
+        let hash = |state: &State<u8>| hash_state(state).unwrap();
+        let mut rng = StdRand::with_seed(1337);
+
+        let samples = sample_reachable_states(&initial, 50, 6, &mut rng);
+        assert!(!samples.is_empty() && samples.len() <= 50);
+        let unique = samples.iter().map(hash).collect::<HashSet<_>>();
+        assert_eq!(samples.len(), unique.len());
+        for sample in &samples {
+            let target = hash(sample);
+            assert!(search(&initial, 6, |state| hash(state) == target).is_some());
+        }
+
+        let unmoved = sample_reachable_states(&initial, 10, 0, &mut rng);
+        assert_eq!(1, unmoved.len());
+        assert_eq!(hash(&initial), hash(&unmoved[0]));
+
+        // nothing may move, so every sample stays where it started
+        let stuck = crate::parse_map::<u8>("oo1\n..1");
+        assert_eq!(1, sample_reachable_states(&stuck, 10, 5, &mut rng).len());
+        assert!(sample_reachable_states(&initial, 0, 5, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn compare() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo....\n......");
+        let database = |solutions: &[&str]| -> Result<SolutionDatabase<u8>, Box<dyn Error>> {
+            let mut database = SolutionDatabase::new(initial.clone());
+            for solution in solutions {
+                database.insert(solution.parse()?);
+            }
+            Ok(database)
+        };
+        let a = database(&[
+            "1R 1R",
+            "1R 1R 1R 1L 1R",
+            "1R 1R 1R 1R",
+            "1R 1R 1R 1R 1R 1R",
+        ])?;
+        let b = database(&[
+            "1R 1L 1R 1R",
+            "1R 1R 1R",
+            "1R 1R 1R 1L 1R 1R 1L",
+            "1R 1L 1R",
+        ])?;
+        let parse = |solutions: &[&str]| {
+            solutions
+                .iter()
+                .map(|s| s.parse::<PGInput>().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let comparison = compare_runs(&a, &b);
+        assert_eq!(parse(&["1R 1R 1R 1R"]), comparison.only_in_a);
+        assert_eq!(parse(&["1R 1L 1R"]), comparison.only_in_b);
+        // both runs finish two and three cells along, each with a shorter solution for one of them
+        assert_eq!(parse(&["1R 1R", "1R 1R 1R"]), comparison.in_both);
+        assert_eq!(Some(&"1R 1R".parse()?), comparison.shortest_a);
+        assert_eq!(Some(&"1R 1R 1R".parse()?), comparison.shortest_b);
+
+        let empty = SolutionDatabase::new(initial.clone());
+        let comparison = compare_runs(&a, &empty);
+        assert_eq!(3, comparison.only_in_a.len());
+        assert!(comparison.only_in_b.is_empty() && comparison.in_both.is_empty());
+        assert_eq!(None, comparison.shortest_b);
+
+        Ok(())
+    }
+
+    #[test]
+    fn transition_graph() -> Result<(), Box<dyn Error>> {
+        let car = |id| CarId::new(id).unwrap();
+        let mut graph = StateTransitionGraph::new();
+        assert_eq!(0, graph.diameter());
+        assert!(StateTransitionGraph::from_state(&NopState::<PGInput>::new()).is_err());
+
+        graph.add_node(GraphNode {
+            hash: 3,
+            is_solution: true,
+            first_seen_at: 20,
+        });
+        graph.add_edge(1, 2, car(2), Direction::Up);
+        graph.add_edge(2, 3, car(1), Direction::Right);
+        assert_eq!(3, graph.nodes().count());
+        assert!(!graph.node(1).unwrap().is_solution);
+        assert!(graph.node(3).unwrap().is_solution);
+        assert_eq!(2, graph.diameter());
+
+        let dot = graph.to_dot();
+        let lines = dot.lines().collect::<Vec<_>>();
+        assert_eq!("digraph states {", lines[0]);
+        assert_eq!(Some(&"}"), lines.last());
+        assert!(
+            lines[1..lines.len() - 1]
+                .iter()
+                .all(|line| line.ends_with(';'))
+        );
+        assert_eq!(dot.matches('{').count(), dot.matches('}').count());
+        assert_eq!(dot.matches('"').count() % 2, 0);
+        assert_eq!(1, dot.matches("fillcolor=green").count());
+        assert_eq!(2, dot.matches("fillcolor=gray").count());
+        assert_eq!(2, dot.matches(" -> ").count());
+        assert!(dot.contains("    s0000000000000002 -> s0000000000000003 [label=\"1→R\"];"));
+
+        // moves may be undone, but that brings no state further away
+        graph.add_edge(3, 2, car(1), Direction::Left);
+        graph.add_edge(2, 1, car(2), Direction::Down);
+        assert_eq!(2, graph.diameter());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! A compact encoding of board states, for hashing states quickly during fuzzing.
+
+use parking_game::{Board, BoardValue, State};
+use std::num::NonZeroUsize;
+use std::ops::Deref;
+
+/// Marks a car id which does not fit in a single byte; the id follows as eight little-endian bytes.
+const WIDE: u8 = u8::MAX;
+
+/// The occupant of each cell of a board, one byte per cell: `0` for an empty cell, or the id of the
+/// car in it. The rare car ids of 255 and above are written as [`WIDE`] followed by the full id, so
+/// that distinct boards always have distinct encodings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompactBoard {
+    data: Vec<u8>,
+}
+
+impl CompactBoard {
+    /// Encodes the cells of the provided board.
+    pub fn from_board<T: BoardValue>(board: &Board<impl Deref<Target = State<T>>, T>) -> Self {
+        let ids = board
+            .concrete()
+            .iter()
+            .map(|cell| cell.map_or(0, NonZeroUsize::get));
+        // boards nearly always have few enough cars to skip checking each cell as it is encoded
+        if ids.clone().all(|id| id < usize::from(WIDE)) {
+            return Self {
+                data: ids.map(|id| id as u8).collect(),
+            };
+        }
+        let mut data = Vec::with_capacity(ids.len());
+        for id in ids {
+            if id < usize::from(WIDE) {
+                data.push(id as u8);
+            } else {
+                data.push(WIDE);
+                data.extend_from_slice(&(id as u64).to_le_bytes());
+            }
+        }
+        Self { data }
+    }
+
+    /// The encoded cells.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The 64-bit FNV-1a hash of the encoded cells.
+    pub fn hash(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+        self.data.iter().fold(OFFSET_BASIS, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compact::CompactBoard;
+    use parking_game::{Car, Direction, Orientation, State};
+    use std::error::Error;
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn encode_cells() -> Result<(), Box<dyn Error>> {
+        let mut state = State::<u8>::empty((3, 3))?;
+        let mut board = state.board_mut()?;
+        board.add_car((0, 0), Car::new(2, Orientation::LeftRight).unwrap())?;
+        board.add_car((0, 2), Car::new(2, Orientation::UpDown).unwrap())?;
+        drop(board);
+
+        let compact = CompactBoard::from_board(&state.board()?);
+        assert_eq!([1, 1, 2, 0, 0, 2, 0, 0, 0], compact.as_bytes());
+        assert_eq!(compact, CompactBoard::from_board(&state.clone().board()?));
+        assert_eq!(
+            compact.hash(),
+            CompactBoard::from_board(&state.board()?).hash()
+        );
+
+        let mut moved = state.clone();
+        moved
+            .board_mut()?
+            .shift_car(NonZeroUsize::new(2).unwrap(), Direction::Down)
+            .unwrap();
+        let shifted = CompactBoard::from_board(&moved.board()?);
+        assert_eq!([1, 1, 0, 0, 0, 2, 0, 0, 2], shifted.as_bytes());
+        assert_ne!(compact.hash(), shifted.hash());
+
+        Ok(())
+    }
+
+    #[test]
+    fn encode_wide_ids() -> Result<(), Box<dyn Error>> {
+        let mut state = State::<u16>::empty((1, 600))?;
+        let mut board = state.board_mut()?;
+        for col in 0..300 {
+            board.add_car((0, col * 2), Car::new(2, Orientation::LeftRight).unwrap())?;
+        }
+        drop(board);
+
+        let compact = CompactBoard::from_board(&state.board()?);
+        let bytes = compact.as_bytes();
+        assert_eq!(254 * 2, bytes.iter().take_while(|&&b| b != u8::MAX).count());
+        assert_eq!([u8::MAX, 255, 0, 0, 0, 0, 0, 0, 0], bytes[508..517]);
+        assert_eq!(254 * 2 + (300 - 254) * 2 * 9, bytes.len());
+
+        Ok(())
+    }
+}
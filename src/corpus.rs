@@ -0,0 +1,723 @@
+//! Corpora which extend LibAFL's [`InMemoryCorpus`], e.g., to bound how much memory long runs use,
+//! or to keep data about each entry at hand.
+
+use crate::feedbacks::FinalStateMetadata;
+use crate::input::PGInput;
+use libafl::corpus::{Corpus, CorpusId, InMemoryCorpus, Testcase};
+use libafl::{Error, HasMetadata};
+use libafl_bolts::impl_serdeany;
+use parking_game::BoardValue;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+
+/// Decides which entry a [`BoundedCorpus`] evicts once it is full: always the one at the front of
+/// its queue, so the policy only decides whether retrieving an entry moves it to the back.
+pub trait EvictionPolicy {
+    /// Whether [`Corpus::get`] moves the retrieved entry to the back of the queue.
+    const REQUEUE_ON_GET: bool;
+}
+
+/// Evict the least recently used entry, i.e., the one added or retrieved longest ago.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct LeastRecentlyUsed;
+
+impl EvictionPolicy for LeastRecentlyUsed {
+    const REQUEUE_ON_GET: bool = true;
+}
+
+/// Evict the oldest entry, i.e., the one added longest ago, however often it was retrieved.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct OldestFirst;
+
+impl EvictionPolicy for OldestFirst {
+    const REQUEUE_ON_GET: bool = false;
+}
+
+/// An [`InMemoryCorpus`] which holds at most `max_size` enabled entries. Adding an entry to a full
+/// corpus evicts another, chosen by the [`EvictionPolicy`]; by default, the least recently used.
+/// The current entry is never evicted, as the scheduler may still be using it.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "I: Serialize + DeserializeOwned")]
+pub struct BoundedCorpus<I, E = LeastRecentlyUsed> {
+    inner: InMemoryCorpus<I>,
+    max_size: usize,
+    queue: RefCell<VecDeque<CorpusId>>,
+    phantom: PhantomData<E>,
+}
+
+/// A [`BoundedCorpus`] which evicts the oldest entry.
+pub type OldestFirstCorpus<I> = BoundedCorpus<I, OldestFirst>;
+
+impl<I, E> BoundedCorpus<I, E> {
+    /// Create an empty corpus which holds at most `max_size` entries.
+    ///
+    /// Panics if `max_size` is zero, since every entry would be evicted as soon as it was added.
+    pub fn new(max_size: usize) -> Self {
+        assert!(max_size > 0, "The corpus should be able to hold an entry.");
+        Self {
+            inner: InMemoryCorpus::new(),
+            max_size,
+            queue: RefCell::new(VecDeque::new()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// The most entries this corpus holds at once.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    fn forget(&self, id: CorpusId) {
+        self.queue.borrow_mut().retain(|queued| *queued != id);
+    }
+}
+
+impl<I, E> Corpus<I> for BoundedCorpus<I, E>
+where
+    E: EvictionPolicy,
+{
+    fn count(&self) -> usize {
+        self.inner.count()
+    }
+
+    fn count_disabled(&self) -> usize {
+        self.inner.count_disabled()
+    }
+
+    fn count_all(&self) -> usize {
+        self.inner.count_all()
+    }
+
+    fn add(&mut self, testcase: Testcase<I>) -> Result<CorpusId, Error> {
+        let id = self.inner.add(testcase)?;
+        self.queue.get_mut().push_back(id);
+        while self.inner.count() > self.max_size {
+            let current = *self.inner.current();
+            let queue = self.queue.get_mut();
+            // the entry just added is at the back, so there is always another to evict
+            let Some(pos) = queue.iter().position(|queued| Some(*queued) != current) else {
+                break;
+            };
+            let evicted = queue.remove(pos).unwrap();
+            self.inner.remove(evicted)?;
+        }
+        Ok(id)
+    }
+
+    fn add_disabled(&mut self, testcase: Testcase<I>) -> Result<CorpusId, Error> {
+        // disabled entries are never scheduled, so they do not count towards the bound
+        self.inner.add_disabled(testcase)
+    }
+
+    fn replace(&mut self, id: CorpusId, testcase: Testcase<I>) -> Result<Testcase<I>, Error> {
+        self.inner.replace(id, testcase)
+    }
+
+    fn remove(&mut self, id: CorpusId) -> Result<Testcase<I>, Error> {
+        let testcase = self.inner.remove(id)?;
+        self.forget(id);
+        Ok(testcase)
+    }
+
+    fn get(&self, id: CorpusId) -> Result<&RefCell<Testcase<I>>, Error> {
+        let testcase = self.inner.get(id)?;
+        if E::REQUEUE_ON_GET {
+            self.forget(id);
+            self.queue.borrow_mut().push_back(id);
+        }
+        Ok(testcase)
+    }
+
+    fn get_from_all(&self, id: CorpusId) -> Result<&RefCell<Testcase<I>>, Error> {
+        self.inner.get_from_all(id)
+    }
+
+    fn current(&self) -> &Option<CorpusId> {
+        self.inner.current()
+    }
+
+    fn current_mut(&mut self) -> &mut Option<CorpusId> {
+        self.inner.current_mut()
+    }
+
+    fn next(&self, id: CorpusId) -> Option<CorpusId> {
+        self.inner.next(id)
+    }
+
+    fn peek_free_id(&self) -> CorpusId {
+        self.inner.peek_free_id()
+    }
+
+    fn prev(&self, id: CorpusId) -> Option<CorpusId> {
+        self.inner.prev(id)
+    }
+
+    fn first(&self) -> Option<CorpusId> {
+        self.inner.first()
+    }
+
+    fn last(&self) -> Option<CorpusId> {
+        self.inner.last()
+    }
+
+    fn nth(&self, nth: usize) -> CorpusId {
+        self.inner.nth(nth)
+    }
+
+    fn nth_from_all(&self, nth: usize) -> CorpusId {
+        self.inner.nth_from_all(nth)
+    }
+
+    fn load_input_into(&self, testcase: &mut Testcase<I>) -> Result<(), Error> {
+        self.inner.load_input_into(testcase)
+    }
+
+    fn store_input_from(&self, testcase: &Testcase<I>) -> Result<(), Error> {
+        self.inner.store_input_from(testcase)
+    }
+}
+
+/// The zstd compression level used by [`CompressedCorpus`]; moves repeat often, so even the fastest
+/// levels compress them well.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// The number of inputs a [`CompressedCorpus`] keeps decompressed by default.
+const DEFAULT_CACHE_LEN: usize = 16;
+
+/// Metadata holding the compressed input of a testcase in a [`CompressedCorpus`].
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct CompressedInputMetadata {
+    bytes: Vec<u8>,
+    uncompressed_len: usize,
+}
+
+impl CompressedInputMetadata {
+    /// The number of bytes the input takes up once compressed.
+    pub fn compressed_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// The number of bytes the input takes up when serialized, before it is compressed.
+    pub fn uncompressed_len(&self) -> usize {
+        self.uncompressed_len
+    }
+}
+
+impl_serdeany!(CompressedInputMetadata);
+
+/// An [`InMemoryCorpus`] which keeps the inputs of its entries serialized and compressed, as
+/// [`CompressedInputMetadata`], rather than as-is. Only the most recently retrieved inputs are kept
+/// decompressed; the inputs of other entries are loaded again with [`Testcase::load_input`], or
+/// when the entry is next retrieved with [`Corpus::get`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "I: Serialize + DeserializeOwned")]
+pub struct CompressedCorpus<I> {
+    inner: InMemoryCorpus<I>,
+    cache_len: usize,
+    cached: RefCell<VecDeque<CorpusId>>,
+    compressed_size: usize,
+    uncompressed_size: usize,
+}
+
+impl<I> CompressedCorpus<I> {
+    /// Create an empty corpus which keeps a few inputs decompressed.
+    pub fn new() -> Self {
+        Self::with_cache_len(DEFAULT_CACHE_LEN)
+    }
+
+    /// Create an empty corpus which keeps at most `cache_len` inputs decompressed.
+    pub fn with_cache_len(cache_len: usize) -> Self {
+        Self {
+            inner: InMemoryCorpus::new(),
+            cache_len,
+            cached: RefCell::new(VecDeque::new()),
+            compressed_size: 0,
+            uncompressed_size: 0,
+        }
+    }
+
+    /// The number of bytes taken up by all the inputs once compressed.
+    pub fn compressed_size_bytes(&self) -> usize {
+        self.compressed_size
+    }
+
+    /// The number of bytes all the inputs would take up if they were serialized but not compressed.
+    pub fn uncompressed_size_bytes(&self) -> usize {
+        self.uncompressed_size
+    }
+}
+
+impl<I> Default for CompressedCorpus<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> CompressedCorpus<I>
+where
+    I: Serialize + DeserializeOwned,
+{
+    /// Compress the input of the testcase into its metadata, leaving the testcase without an input.
+    fn compress(&mut self, testcase: &mut Testcase<I>) -> Result<(), Error> {
+        let Some(input) = testcase.input_mut().take() else {
+            return Ok(());
+        };
+        let serialized = bincode::serialize(&input).map_err(|e| Error::serialize(e.to_string()))?;
+        let compressed = CompressedInputMetadata {
+            bytes: zstd::encode_all(serialized.as_slice(), COMPRESSION_LEVEL)?,
+            uncompressed_len: serialized.len(),
+        };
+        self.compressed_size += compressed.compressed_len();
+        self.uncompressed_size += compressed.uncompressed_len();
+        testcase.add_metadata(compressed);
+        Ok(())
+    }
+
+    /// Stop counting the compressed input of a testcase which is no longer in the corpus.
+    fn discount(&mut self, testcase: &Testcase<I>) {
+        if let Ok(compressed) = testcase.metadata::<CompressedInputMetadata>() {
+            self.compressed_size -= compressed.compressed_len();
+            self.uncompressed_size -= compressed.uncompressed_len();
+        }
+    }
+
+    /// Remember that the input of the entry was decompressed, compressing the least recently
+    /// decompressed input again if too many are.
+    fn cache(&self, id: CorpusId) -> Result<(), Error> {
+        let mut cached = self.cached.borrow_mut();
+        cached.retain(|other| *other != id);
+        cached.push_back(id);
+        while cached.len() > self.cache_len {
+            let evicted = cached.pop_front().unwrap();
+            // the input is compressed already, so it need only be dropped; if the entry is in use,
+            // it will be dropped the next time another input is decompressed
+            match self.inner.get_from_all(evicted)?.try_borrow_mut() {
+                Ok(mut testcase) => *testcase.input_mut() = None,
+                Err(_) => {
+                    cached.push_back(evicted);
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<I> Corpus<I> for CompressedCorpus<I>
+where
+    I: Serialize + DeserializeOwned,
+{
+    fn count(&self) -> usize {
+        self.inner.count()
+    }
+
+    fn count_disabled(&self) -> usize {
+        self.inner.count_disabled()
+    }
+
+    fn count_all(&self) -> usize {
+        self.inner.count_all()
+    }
+
+    fn add(&mut self, mut testcase: Testcase<I>) -> Result<CorpusId, Error> {
+        self.compress(&mut testcase)?;
+        self.inner.add(testcase)
+    }
+
+    fn add_disabled(&mut self, mut testcase: Testcase<I>) -> Result<CorpusId, Error> {
+        self.compress(&mut testcase)?;
+        self.inner.add_disabled(testcase)
+    }
+
+    fn replace(&mut self, id: CorpusId, mut testcase: Testcase<I>) -> Result<Testcase<I>, Error> {
+        self.compress(&mut testcase)?;
+        let mut replaced = self.inner.replace(id, testcase)?;
+        self.discount(&replaced);
+        self.cached.get_mut().retain(|cached| *cached != id);
+        self.load_input_into(&mut replaced)?;
+        Ok(replaced)
+    }
+
+    fn remove(&mut self, id: CorpusId) -> Result<Testcase<I>, Error> {
+        let mut removed = self.inner.remove(id)?;
+        self.discount(&removed);
+        self.cached.get_mut().retain(|cached| *cached != id);
+        self.load_input_into(&mut removed)?;
+        Ok(removed)
+    }
+
+    fn get(&self, id: CorpusId) -> Result<&RefCell<Testcase<I>>, Error> {
+        let testcase = self.inner.get(id)?;
+        // an entry which is in use was retrieved recently, so its input is loaded already
+        if let Ok(mut loaded) = testcase.try_borrow_mut()
+            && loaded.input().is_none()
+        {
+            self.load_input_into(&mut loaded)?;
+            drop(loaded);
+            self.cache(id)?;
+        }
+        Ok(testcase)
+    }
+
+    fn get_from_all(&self, id: CorpusId) -> Result<&RefCell<Testcase<I>>, Error> {
+        self.inner.get_from_all(id)
+    }
+
+    fn current(&self) -> &Option<CorpusId> {
+        self.inner.current()
+    }
+
+    fn current_mut(&mut self) -> &mut Option<CorpusId> {
+        self.inner.current_mut()
+    }
+
+    fn next(&self, id: CorpusId) -> Option<CorpusId> {
+        self.inner.next(id)
+    }
+
+    fn peek_free_id(&self) -> CorpusId {
+        self.inner.peek_free_id()
+    }
+
+    fn prev(&self, id: CorpusId) -> Option<CorpusId> {
+        self.inner.prev(id)
+    }
+
+    fn first(&self) -> Option<CorpusId> {
+        self.inner.first()
+    }
+
+    fn last(&self) -> Option<CorpusId> {
+        self.inner.last()
+    }
+
+    fn nth(&self, nth: usize) -> CorpusId {
+        self.inner.nth(nth)
+    }
+
+    fn nth_from_all(&self, nth: usize) -> CorpusId {
+        self.inner.nth_from_all(nth)
+    }
+
+    fn load_input_into(&self, testcase: &mut Testcase<I>) -> Result<(), Error> {
+        if testcase.input().is_some() {
+            return Ok(());
+        }
+        let Ok(compressed) = testcase.metadata::<CompressedInputMetadata>() else {
+            return Ok(());
+        };
+        let serialized = zstd::decode_all(compressed.bytes.as_slice())?;
+        let input =
+            bincode::deserialize(&serialized).map_err(|e| Error::serialize(e.to_string()))?;
+        testcase.set_input(input);
+        Ok(())
+    }
+
+    fn store_input_from(&self, _testcase: &Testcase<I>) -> Result<(), Error> {
+        // inputs are compressed as they are added, and never change after that
+        Ok(())
+    }
+}
+
+/// An [`InMemoryCorpus`] which also keeps the snapshot of each entry, i.e., its
+/// [`FinalStateMetadata`], by id. Looking a snapshot up here is cheaper than downcasting it from
+/// the metadata of the testcase, which matters when it is done for every execution. Snapshots are
+/// taken from the metadata of testcases as they are added, and may also be set directly.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "T: BoardValue + Serialize + DeserializeOwned")]
+pub struct SnapshotCorpus<T> {
+    inner: InMemoryCorpus<PGInput>,
+    snapshots: HashMap<CorpusId, FinalStateMetadata<T>>,
+}
+
+impl<T> SnapshotCorpus<T> {
+    /// Create an empty corpus.
+    pub fn new() -> Self {
+        Self {
+            inner: InMemoryCorpus::new(),
+            snapshots: HashMap::new(),
+        }
+    }
+
+    /// The snapshot of the entry with the provided id, if it has one.
+    pub fn get_snapshot(&self, id: CorpusId) -> Option<&FinalStateMetadata<T>> {
+        self.snapshots.get(&id)
+    }
+
+    /// Set the snapshot of the entry with the provided id, replacing any it had.
+    pub fn set_snapshot(&mut self, id: CorpusId, snapshot: FinalStateMetadata<T>) {
+        self.snapshots.insert(id, snapshot);
+    }
+}
+
+impl<T> Default for SnapshotCorpus<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SnapshotCorpus<T>
+where
+    T: BoardValue,
+{
+    /// Keep the snapshot of the testcase just stored with the provided id, if it has one.
+    fn take_snapshot(&mut self, id: CorpusId, testcase: &Testcase<PGInput>) {
+        match testcase.metadata::<FinalStateMetadata<T>>() {
+            Ok(snapshot) => self.snapshots.insert(id, snapshot.clone()),
+            Err(_) => self.snapshots.remove(&id),
+        };
+    }
+}
+
+impl<T> Corpus<PGInput> for SnapshotCorpus<T>
+where
+    T: BoardValue,
+{
+    fn count(&self) -> usize {
+        self.inner.count()
+    }
+
+    fn count_disabled(&self) -> usize {
+        self.inner.count_disabled()
+    }
+
+    fn count_all(&self) -> usize {
+        self.inner.count_all()
+    }
+
+    fn add(&mut self, testcase: Testcase<PGInput>) -> Result<CorpusId, Error> {
+        let id = self.inner.peek_free_id();
+        self.take_snapshot(id, &testcase);
+        self.inner.add(testcase)
+    }
+
+    fn add_disabled(&mut self, testcase: Testcase<PGInput>) -> Result<CorpusId, Error> {
+        let id = self.inner.peek_free_id();
+        self.take_snapshot(id, &testcase);
+        self.inner.add_disabled(testcase)
+    }
+
+    fn replace(
+        &mut self,
+        id: CorpusId,
+        testcase: Testcase<PGInput>,
+    ) -> Result<Testcase<PGInput>, Error> {
+        self.take_snapshot(id, &testcase);
+        self.inner.replace(id, testcase)
+    }
+
+    fn remove(&mut self, id: CorpusId) -> Result<Testcase<PGInput>, Error> {
+        let testcase = self.inner.remove(id)?;
+        self.snapshots.remove(&id);
+        Ok(testcase)
+    }
+
+    fn get(&self, id: CorpusId) -> Result<&RefCell<Testcase<PGInput>>, Error> {
+        self.inner.get(id)
+    }
+
+    fn get_from_all(&self, id: CorpusId) -> Result<&RefCell<Testcase<PGInput>>, Error> {
+        self.inner.get_from_all(id)
+    }
+
+    fn current(&self) -> &Option<CorpusId> {
+        self.inner.current()
+    }
+
+    fn current_mut(&mut self) -> &mut Option<CorpusId> {
+        self.inner.current_mut()
+    }
+
+    fn next(&self, id: CorpusId) -> Option<CorpusId> {
+        self.inner.next(id)
+    }
+
+    fn peek_free_id(&self) -> CorpusId {
+        self.inner.peek_free_id()
+    }
+
+    fn prev(&self, id: CorpusId) -> Option<CorpusId> {
+        self.inner.prev(id)
+    }
+
+    fn first(&self) -> Option<CorpusId> {
+        self.inner.first()
+    }
+
+    fn last(&self) -> Option<CorpusId> {
+        self.inner.last()
+    }
+
+    fn nth(&self, nth: usize) -> CorpusId {
+        self.inner.nth(nth)
+    }
+
+    fn nth_from_all(&self, nth: usize) -> CorpusId {
+        self.inner.nth_from_all(nth)
+    }
+
+    fn load_input_into(&self, testcase: &mut Testcase<PGInput>) -> Result<(), Error> {
+        self.inner.load_input_into(testcase)
+    }
+
+    fn store_input_from(&self, testcase: &Testcase<PGInput>) -> Result<(), Error> {
+        self.inner.store_input_from(testcase)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::corpus::{BoundedCorpus, CompressedCorpus, OldestFirstCorpus, SnapshotCorpus};
+    use crate::feedbacks::FinalStateMetadata;
+    use crate::input::PGInput;
+    use crate::observers::hash_state;
+    use crate::types::CarId;
+    use libafl::HasMetadata;
+    use libafl::corpus::{Corpus, CorpusId, Testcase};
+    use parking_game::Direction;
+    use std::error::Error;
+
+    fn fill(
+        corpus: &mut impl Corpus<PGInput>,
+        inputs: &[&str],
+    ) -> Result<Vec<CorpusId>, Box<dyn Error>> {
+        inputs
+            .iter()
+            .map(|input| Ok(corpus.add(Testcase::new(input.parse()?))?))
+            .collect()
+    }
+
+    #[test]
+    fn evict_least_recently_used() -> Result<(), Box<dyn Error>> {
+        let mut corpus = BoundedCorpus::<PGInput>::new(3);
+        let ids = fill(&mut corpus, &["1R", "1L", "2U", "2D"])?;
+        assert_eq!(3, corpus.count());
+        assert!(corpus.get(ids[0]).is_err());
+        assert_eq!(ids[1..], *corpus.ids().collect::<Vec<_>>());
+
+        // using the oldest entry keeps it around for longer than the others
+        corpus.get(ids[1])?;
+        let added = fill(&mut corpus, &["3U"])?;
+        assert!(corpus.get(ids[2]).is_err());
+        assert_eq!(
+            [ids[1], ids[3], added[0]],
+            *corpus.ids().collect::<Vec<_>>()
+        );
+
+        // nor is the current entry evicted, however long ago it was used
+        *corpus.current_mut() = Some(ids[3]);
+        corpus.get(ids[1])?;
+        corpus.get(added[0])?;
+        let added = fill(&mut corpus, &["3D"])?;
+        assert_eq!(3, corpus.count());
+        assert!(corpus.get(ids[1]).is_err());
+        assert!(corpus.get(ids[3]).is_ok());
+        assert!(corpus.get(added[0]).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn compress_inputs() -> Result<(), Box<dyn Error>> {
+        let mut corpus = CompressedCorpus::<PGInput>::with_cache_len(1);
+        let repetitive = PGInput::new(vec![(CarId::new(1).unwrap(), Direction::Right); 1000]);
+        let inputs = [repetitive, "1R 2U 3L".parse()?, PGInput::default()];
+        let ids = inputs
+            .iter()
+            .map(|input| corpus.add(Testcase::new(input.clone())))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(3, corpus.count());
+        assert!(corpus.compressed_size_bytes() <= corpus.uncompressed_size_bytes());
+        assert!(corpus.compressed_size_bytes() < 1000);
+
+        // retrieving each in turn drops the last one that was decompressed
+        for (id, input) in ids.iter().zip(&inputs) {
+            assert_eq!(Some(input), corpus.get(*id)?.borrow().input().as_ref());
+            assert_eq!(*input, corpus.cloned_input_for_id(*id)?);
+        }
+        assert!(corpus.get_from_all(ids[0])?.borrow().input().is_none());
+        let mut testcase = corpus.get_from_all(ids[0])?.borrow_mut();
+        assert_eq!(inputs[0], *testcase.load_input(&corpus)?);
+        drop(testcase);
+
+        let replaced = corpus.replace(ids[1], Testcase::new("4D".parse()?))?;
+        assert_eq!(Some(&inputs[1]), replaced.input().as_ref());
+        assert_eq!(
+            Some(&"4D".parse()?),
+            corpus.get(ids[1])?.borrow().input().as_ref()
+        );
+        let compressed_size = corpus.compressed_size_bytes();
+        let removed = corpus.remove(ids[0])?;
+        assert_eq!(Some(&inputs[0]), removed.input().as_ref());
+        assert!(corpus.compressed_size_bytes() < compressed_size);
+        assert_eq!([ids[1], ids[2]], *corpus.ids().collect::<Vec<_>>());
+        assert_eq!(Some(ids[1]), corpus.first());
+        assert_eq!(Some(ids[2]), corpus.last());
+        assert_eq!(ids[2], corpus.nth(1));
+        assert!(corpus.get(ids[0]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn snapshots() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n....");
+        let mut moved = initial.clone();
+        assert_eq!(Ok(1), "2D".parse::<PGInput>()?.apply(&mut moved));
+        let hash = |snapshot: Option<&FinalStateMetadata<u8>>| hash_state(snapshot?.state());
+
+        let mut corpus = SnapshotCorpus::<u8>::new();
+        let mut testcase = Testcase::new("2D".parse()?);
+        testcase.add_metadata(FinalStateMetadata::new(moved.clone()));
+        let with_snapshot = corpus.add(testcase)?;
+        let without_snapshot = corpus.add(Testcase::new(PGInput::default()))?;
+        assert_eq!(hash_state(&moved), hash(corpus.get_snapshot(with_snapshot)));
+        assert!(corpus.get_snapshot(without_snapshot).is_none());
+        assert!(corpus.get_snapshot(CorpusId::from(7usize)).is_none());
+
+        corpus.set_snapshot(without_snapshot, FinalStateMetadata::new(initial.clone()));
+        assert_eq!(
+            hash_state(&initial),
+            hash(corpus.get_snapshot(without_snapshot))
+        );
+
+        // the rest is up to the inner corpus
+        assert_eq!(2, corpus.count());
+        assert_eq!(Some(with_snapshot), corpus.first());
+        assert_eq!(Some(without_snapshot), corpus.next(with_snapshot));
+        assert_eq!(without_snapshot, corpus.nth(1));
+        assert_eq!(
+            PGInput::default(),
+            corpus.cloned_input_for_id(without_snapshot)?
+        );
+        corpus.replace(with_snapshot, Testcase::new("1R".parse()?))?;
+        assert!(corpus.get_snapshot(with_snapshot).is_none());
+        corpus.remove(without_snapshot)?;
+        assert!(corpus.get_snapshot(without_snapshot).is_none());
+        assert_eq!(1, corpus.count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn evict_oldest() -> Result<(), Box<dyn Error>> {
+        let mut corpus = OldestFirstCorpus::<PGInput>::new(2);
+        let ids = fill(&mut corpus, &["1R", "1L"])?;
+        corpus.get(ids[0])?;
+        let added = fill(&mut corpus, &["2U"])?;
+        assert_eq!(2, corpus.count());
+        assert!(corpus.get(ids[0]).is_err());
+        assert_eq!([ids[1], added[0]], *corpus.ids().collect::<Vec<_>>());
+
+        // removed entries are no longer candidates for eviction
+        corpus.remove(ids[1])?;
+        fill(&mut corpus, &["2D"])?;
+        assert_eq!(2, corpus.count());
+        assert!(corpus.get(added[0]).is_ok());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! Tracking of which cells of the board have ever been occupied by a car, over every execution of
+//! the fuzzer.
+
+use libafl::HasMetadata;
+use libafl_bolts::impl_serdeany;
+use parking_game::{Board, BoardValue, State};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// The cells of a board which have been occupied by any car. Kept in the fuzzer's state as metadata
+/// by the [`crate::observers::CoverageMapObserver`], merging in the cells each execution visits.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CoverageMap<T> {
+    grid: Box<[Box<[bool]>]>,
+    rows: usize,
+    cols: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T> CoverageMap<T>
+where
+    T: BoardValue,
+{
+    /// Create a coverage map for boards of the same dimensions as the provided state, with no cells
+    /// visited.
+    pub fn new(state: &State<T>) -> Self {
+        let rows = (*state.dimensions().rows()).into();
+        let cols = (*state.dimensions().columns()).into();
+        Self {
+            grid: vec![vec![false; cols].into_boxed_slice(); rows].into_boxed_slice(),
+            rows,
+            cols,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Mark every cell occupied by a car on the provided board as visited, returning how many of
+    /// them had not been visited before.
+    pub fn mark_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) -> usize {
+        let mut new = 0;
+        for (i, cell) in board.concrete().iter().enumerate() {
+            if cell.is_some() {
+                let visited = &mut self.grid[i / self.cols][i % self.cols];
+                new += usize::from(!*visited);
+                *visited = true;
+            }
+        }
+        new
+    }
+}
+
+impl<T> CoverageMap<T> {
+    /// Create a coverage map with every cell counted at least once in the provided heatmap visited,
+    /// as collected by a [`crate::observers::HeatmapObserver`].
+    pub fn from_heatmap(heatmap: &[Vec<u32>]) -> Self {
+        Self {
+            grid: heatmap
+                .iter()
+                .map(|row| row.iter().map(|&count| count > 0).collect())
+                .collect(),
+            rows: heatmap.len(),
+            cols: heatmap.first().map_or(0, Vec::len),
+            phantom: PhantomData,
+        }
+    }
+
+    /// The number of rows of the board.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns of the board.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Whether the cell at the provided row and column has been visited, or [`None`] if it is not
+    /// on the board.
+    pub fn is_visited(&self, row: usize, col: usize) -> Option<bool> {
+        self.grid.get(row)?.get(col).copied()
+    }
+
+    /// The number of cells visited.
+    pub fn visited(&self) -> usize {
+        self.grid
+            .iter()
+            .flatten()
+            .filter(|&&visited| visited)
+            .count()
+    }
+
+    /// The fraction of the cells of the board which have been visited, between 0 and 1.
+    pub fn coverage_fraction(&self) -> f64 {
+        let cells = self.rows * self.cols;
+        if cells == 0 {
+            return 0.0;
+        }
+        self.visited() as f64 / cells as f64
+    }
+
+    /// Mark every cell visited in the other map as visited in this one, returning how many of them
+    /// had not been visited here before.
+    ///
+    /// # Panics
+    ///
+    /// If the maps are of different dimensions.
+    pub fn merge(&mut self, other: &Self) -> usize {
+        assert_eq!(
+            (self.rows, self.cols),
+            (other.rows, other.cols),
+            "Coverage maps should be of the same board."
+        );
+        let mut new = 0;
+        for (ours, theirs) in self
+            .grid
+            .iter_mut()
+            .flatten()
+            .zip(other.grid.iter().flatten())
+        {
+            new += usize::from(*theirs && !*ours);
+            *ours |= *theirs;
+        }
+        new
+    }
+}
+
+impl<T> CoverageMap<T>
+where
+    T: BoardValue + DeserializeOwned + Serialize + 'static,
+{
+    /// Merge this map into the coverage map kept in the metadata of the provided state, or keep a
+    /// copy of it there if there is none yet. Returns how many cells had not been visited there
+    /// before.
+    pub fn merge_into_metadata(&self, state: &mut impl HasMetadata) -> usize {
+        if let Ok(seen) = state.metadata_mut::<CoverageMap<T>>() {
+            seen.merge(self)
+        } else {
+            state.add_metadata(self.clone());
+            self.visited()
+        }
+    }
+}
+
+impl_serdeany!(CoverageMap<T: BoardValue + DeserializeOwned + Serialize + 'static>, <u8>, <u16>);
+
+#[cfg(test)]
+mod test {
+    use crate::coverage::CoverageMap;
+    use parking_game::Direction;
+    use std::error::Error;
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn coverage_fraction() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo..\n....\n.1..\n.1..");
+        let mut coverage = CoverageMap::new(&initial);
+        assert_eq!(0, coverage.visited());
+        assert_eq!(0.0, coverage.coverage_fraction());
+
+        assert_eq!(4, coverage.mark_board(&initial.board()?));
+        assert_eq!(0.25, coverage.coverage_fraction());
+        // cells already visited are not counted again
+        assert_eq!(0, coverage.mark_board(&initial.board()?));
+        assert_eq!(Some(true), coverage.is_visited(2, 1));
+        assert_eq!(Some(false), coverage.is_visited(1, 1));
+        assert_eq!(None, coverage.is_visited(4, 0));
+
+        let mut moved = initial.clone();
+        moved
+            .board_mut()?
+            .shift_car(NonZeroUsize::new(1).unwrap(), Direction::Right)?;
+        let mut other = CoverageMap::new(&moved);
+        other.mark_board(&moved.board()?);
+        assert_eq!(1, coverage.merge(&other));
+        assert_eq!(5.0 / 16.0, coverage.coverage_fraction());
+
+        Ok(())
+    }
+}
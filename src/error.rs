@@ -0,0 +1,140 @@
+//! The error type for the fuzzer as a whole, so that failures may be told apart at call sites.
+
+use crate::{ConfigError, MapParseError};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+/// An error encountered while setting up or running the fuzzer.
+#[derive(Debug)]
+pub enum FuzzerError {
+    /// The command line was not valid.
+    Config(ConfigError),
+    /// The map could not be loaded.
+    Map(MapParseError),
+    /// Something went wrong while executing an input.
+    Execution(String),
+    /// The fuzzer's state was not as expected.
+    State(String),
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// The corpus or the solutions could not be accessed.
+    Corpus(String),
+}
+
+impl Display for FuzzerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FuzzerError::Config(e) => write!(f, "invalid configuration: {e}"),
+            FuzzerError::Map(e) => write!(f, "could not load the map: {e}"),
+            FuzzerError::Execution(e) => write!(f, "execution failed: {e}"),
+            FuzzerError::State(e) => write!(f, "invalid fuzzer state: {e}"),
+            FuzzerError::Io(e) => write!(f, "I/O error: {e}"),
+            FuzzerError::Corpus(e) => write!(f, "corpus error: {e}"),
+        }
+    }
+}
+
+impl Error for FuzzerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FuzzerError::Config(e) => Some(e),
+            FuzzerError::Map(e) => Some(e),
+            FuzzerError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ConfigError> for FuzzerError {
+    fn from(e: ConfigError) -> Self {
+        Self::Config(e)
+    }
+}
+
+impl From<MapParseError> for FuzzerError {
+    fn from(e: MapParseError) -> Self {
+        Self::Map(e)
+    }
+}
+
+impl From<io::Error> for FuzzerError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<libafl_bolts::Error> for FuzzerError {
+    fn from(e: libafl_bolts::Error) -> Self {
+        use libafl_bolts::Error as E;
+        match e {
+            E::OsError(e, _, _) => Self::Io(e),
+            E::Empty(..) | E::KeyNotFound(..) | E::KeyExists(..) | E::InvalidCorpus(..) => {
+                Self::Corpus(e.to_string())
+            }
+            E::IllegalState(..) | E::EmptyOptional(..) => Self::State(e.to_string()),
+            e => Self::Execution(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::FuzzerError;
+    use crate::{ConfigError, MapParseError};
+    use std::io;
+
+    #[test]
+    fn conversions() {
+        assert!(matches!(
+            FuzzerError::from(ConfigError::MissingMap),
+            FuzzerError::Config(ConfigError::MissingMap)
+        ));
+        assert!(matches!(
+            FuzzerError::from(MapParseError::Empty),
+            FuzzerError::Map(MapParseError::Empty)
+        ));
+        assert!(matches!(
+            FuzzerError::from(io::Error::from(io::ErrorKind::NotFound)),
+            FuzzerError::Io(e) if e.kind() == io::ErrorKind::NotFound
+        ));
+
+        assert!(matches!(
+            FuzzerError::from(libafl_bolts::Error::from(io::Error::from(
+                io::ErrorKind::PermissionDenied
+            ))),
+            FuzzerError::Io(e) if e.kind() == io::ErrorKind::PermissionDenied
+        ));
+        assert!(matches!(
+            FuzzerError::from(libafl_bolts::Error::empty("No entries in corpus.")),
+            FuzzerError::Corpus(_)
+        ));
+        assert!(matches!(
+            FuzzerError::from(libafl_bolts::Error::illegal_state("not fuzzing")),
+            FuzzerError::State(_)
+        ));
+        assert!(matches!(
+            FuzzerError::from(libafl_bolts::Error::unknown("target went away")),
+            FuzzerError::Execution(_)
+        ));
+    }
+
+    #[test]
+    fn propagate() {
+        fn load(map: &str) -> Result<usize, FuzzerError> {
+            let state = crate::try_parse_map::<u8>(map)?;
+            Ok(state.cars().len())
+        }
+
+        assert_eq!(2, load("oo.\n..1\n..1").unwrap());
+        let e = load("11.").unwrap_err();
+        assert!(matches!(
+            e,
+            FuzzerError::Map(MapParseError::MissingObjective)
+        ));
+        assert_eq!(
+            "could not load the map: no objective car (`o`) was found",
+            e.to_string()
+        );
+    }
+}
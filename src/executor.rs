@@ -1,23 +1,44 @@
 //! Executor implementation for [`parking_game`] puzzles.
 
-use crate::input::PGInput;
-use crate::observers::PGObserverTuple;
+use crate::input::{PGInput, apply_moves};
+use crate::observers::{DirectionExt, PGObserverTuple, blockers_in_path};
+use crate::serialization::serialize_state;
+use crate::types::CarId;
 use libafl::executors::{Executor, ExitKind, HasObservers};
-use libafl::state::{HasCurrentTestcase, HasExecutions};
+use libafl::state::{HasCurrentTestcase, HasExecutions, NopState};
 use libafl_bolts::Error;
 use libafl_bolts::tuples::RefIndexable;
-use parking_game::{BoardValue, State};
+use parking_game::{BoardValue, Direction, Orientation, Position, State};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, Stdout, Write};
+use std::ops::ControlFlow;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// A callback for inputs which crash; see [`PGExecutor::set_crash_hook`].
+type CrashHook = Box<dyn FnMut(&PGInput)>;
 
 /// Executor which advances the state by "running" the move sequence provided.
 pub struct PGExecutor<T, OT> {
     initial: State<T>,
     observers: OT,
+    timeout: Option<Duration>,
+    move_delay: Option<Duration>,
+    checkpoint_interval: Option<usize>,
+    checkpoints: Vec<State<T>>,
+    walls: Vec<Position<T>>,
+    stats: Option<PGExecutorStats>,
+    crash_hook: Option<CrashHook>,
 }
 
 impl<T, OT> PGExecutor<T, OT> {
     /// Create a new executor for the provided state with the provided observers.
+    ///
+    /// To configure the executor further, use [`PGExecutorBuilder`] instead.
     pub fn new(initial: State<T>, observers: OT) -> Self {
-        Self { initial, observers }
+        PGExecutorBuilder::with_initial(initial).build(observers)
     }
 }
 
@@ -26,6 +47,199 @@ impl<T, OT> PGExecutor<T, OT> {
     pub fn initial(&self) -> &State<T> {
         &self.initial
     }
+
+    /// The maximum time that may be spent applying the moves of an input, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The delay added after each move to simulate cost, if any.
+    pub fn move_delay(&self) -> Option<Duration> {
+        self.move_delay
+    }
+
+    /// Change the delay added after each move; [`Duration::ZERO`] disables the delay.
+    pub fn set_move_delay(&mut self, delay: Duration) {
+        self.move_delay = Some(delay).filter(|delay| !delay.is_zero());
+    }
+
+    /// The number of moves between snapshots of the board, if snapshots should be taken.
+    pub fn checkpoint_interval(&self) -> Option<usize> {
+        self.checkpoint_interval
+    }
+
+    /// The snapshots of the board taken during the last execution, one after every
+    /// [`Self::checkpoint_interval`] moves applied, in order.
+    pub fn checkpoints(&self) -> &[State<T>] {
+        &self.checkpoints
+    }
+
+    /// The cells which no car may enter; moving a car into one crashes.
+    pub fn walls(&self) -> &[Position<T>] {
+        &self.walls
+    }
+
+    /// The statistics collected about the executions so far, if enabled with
+    /// [`PGExecutorBuilder::with_stats`].
+    pub fn stats(&self) -> Option<&PGExecutorStats> {
+        self.stats.as_ref()
+    }
+
+    /// Call the provided hook with each input which crashes, replacing any hook set before.
+    pub fn set_crash_hook(&mut self, hook: impl FnMut(&PGInput) + 'static) {
+        self.crash_hook = Some(Box::new(hook));
+    }
+
+    /// Clear the statistics collected so far, if they are enabled.
+    pub fn reset_stats(&mut self) {
+        if let Some(stats) = &mut self.stats {
+            *stats = PGExecutorStats::default();
+        }
+    }
+}
+
+/// Cloning an executor copies its configuration, observers and statistics, so that each thread of a
+/// parallel fuzzer may run its own. The crash hook cannot be cloned, so the clone has none.
+impl<T, OT> Clone for PGExecutor<T, OT>
+where
+    T: Clone,
+    OT: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            initial: self.initial.clone(),
+            observers: self.observers.clone(),
+            timeout: self.timeout,
+            move_delay: self.move_delay,
+            checkpoint_interval: self.checkpoint_interval,
+            checkpoints: self.checkpoints.clone(),
+            walls: self.walls.clone(),
+            stats: self.stats,
+            crash_hook: None,
+        }
+    }
+}
+
+/// Statistics about the executions of a [`PGExecutor`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PGExecutorStats {
+    /// The number of inputs executed.
+    pub total_runs: u64,
+    /// The number of moves applied successfully, across all executions.
+    pub total_moves_applied: u64,
+    /// The number of executions which ended with [`ExitKind::Crash`].
+    pub crash_count: u64,
+    /// The number of executions which ended with [`ExitKind::Ok`].
+    pub ok_count: u64,
+    /// The number of executions which ended with [`ExitKind::Timeout`].
+    pub timeout_count: u64,
+}
+
+impl PGExecutorStats {
+    fn record(&mut self, kind: ExitKind, applied: usize) {
+        self.total_runs += 1;
+        self.total_moves_applied += applied as u64;
+        match kind {
+            ExitKind::Ok => self.ok_count += 1,
+            ExitKind::Crash => self.crash_count += 1,
+            ExitKind::Timeout => self.timeout_count += 1,
+            _ => {}
+        }
+    }
+}
+
+/// Builder for [`PGExecutor`]s with additional options, all of which are disabled by default.
+///
+/// A builder may only be created from the initial state, so every executor has one:
+///
+/// ```
+/// # use libafl_bolts::tuples::tuple_list;
+/// # use parking_game_fuzzer::executor::PGExecutorBuilder;
+/// # use parking_game_fuzzer::observers::FinalStateObserver;
+/// # use std::time::Duration;
+/// let executor = PGExecutorBuilder::with_initial(parking_game_fuzzer::parse_map::<u8>("oo.."))
+///     .with_timeout(Duration::from_secs(1))
+///     .build(tuple_list!(FinalStateObserver::<u8>::default()));
+/// ```
+///
+/// ```compile_fail
+/// # use libafl_bolts::tuples::tuple_list;
+/// # use parking_game_fuzzer::executor::PGExecutorBuilder;
+/// # use parking_game_fuzzer::observers::FinalStateObserver;
+/// # use std::time::Duration;
+/// let executor = PGExecutorBuilder::<u8>::default()
+///     .with_timeout(Duration::from_secs(1))
+///     .build(tuple_list!(FinalStateObserver::<u8>::default()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PGExecutorBuilder<T> {
+    initial: State<T>,
+    timeout: Option<Duration>,
+    move_delay: Option<Duration>,
+    checkpoint_interval: Option<usize>,
+    walls: Vec<Position<T>>,
+    stats: bool,
+}
+
+impl<T> PGExecutorBuilder<T> {
+    /// Start building an executor for the provided state.
+    pub fn with_initial(initial: State<T>) -> Self {
+        Self {
+            initial,
+            timeout: None,
+            move_delay: None,
+            checkpoint_interval: None,
+            walls: Vec::new(),
+            stats: false,
+        }
+    }
+
+    /// Report [`ExitKind::Timeout`] if applying the moves of an input takes longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Wait for `delay` after each move to simulate the cost of more expensive targets.
+    pub fn with_move_delay(mut self, delay: Duration) -> Self {
+        self.move_delay = Some(delay);
+        self
+    }
+
+    /// Take a snapshot of the board every `interval` moves; see [`PGExecutor::checkpoints`]. An
+    /// interval of zero takes no snapshots.
+    pub fn with_checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoint_interval = Some(interval).filter(|&interval| interval > 0);
+        self
+    }
+
+    /// Treat the provided cells as walls, like the edges of the board: moving a car into one
+    /// crashes. See [`crate::MapMetadata::walls`].
+    pub fn with_walls(mut self, walls: impl IntoIterator<Item = Position<T>>) -> Self {
+        self.walls = walls.into_iter().collect();
+        self
+    }
+
+    /// Keep statistics about the executions.
+    pub fn with_stats(mut self) -> Self {
+        self.stats = true;
+        self
+    }
+
+    /// Build the executor with the provided observers.
+    pub fn build<OT>(self, observers: OT) -> PGExecutor<T, OT> {
+        PGExecutor {
+            initial: self.initial,
+            observers,
+            timeout: self.timeout,
+            move_delay: self.move_delay,
+            checkpoint_interval: self.checkpoint_interval,
+            checkpoints: Vec::new(),
+            walls: self.walls,
+            stats: self.stats.then(PGExecutorStats::default),
+            crash_hook: None,
+        }
+    }
 }
 
 // This allows other components to interact with the executors observers, when necessary.
@@ -41,19 +255,36 @@ impl<T, OT> HasObservers for PGExecutor<T, OT> {
     }
 }
 
-impl<EM, OT, S, T, Z> Executor<EM, PGInput, S, Z> for PGExecutor<T, OT>
+impl<T, OT> PGExecutor<T, OT>
 where
     OT: PGObserverTuple<T>,
-    S: HasExecutions + HasCurrentTestcase<PGInput>,
     T: BoardValue,
 {
-    fn run_target(
+    /// Runs the input as with [`Executor::run_target`], but also returns the state of the board
+    /// when the execution stopped.
+    pub(crate) fn execute<S>(
         &mut self,
-        _fuzzer: &mut Z,
         state: &mut S,
-        _mgr: &mut EM,
         input: &PGInput,
-    ) -> Result<ExitKind, Error> {
+    ) -> Result<(ExitKind, State<T>), Error>
+    where
+        S: HasExecutions + HasCurrentTestcase<PGInput>,
+    {
+        self.execute_verified(state, input, |_| Ok(()))
+    }
+
+    /// Runs the input as with [`Self::execute`], but also checks `invariant` against the board
+    /// before the first move and after each move. The execution stops at the first violation, and
+    /// crashes as though the move had been invalid.
+    pub(crate) fn execute_verified<S>(
+        &mut self,
+        state: &mut S,
+        input: &PGInput,
+        mut invariant: impl FnMut(&State<T>) -> Result<(), String>,
+    ) -> Result<(ExitKind, State<T>), Error>
+    where
+        S: HasExecutions + HasCurrentTestcase<PGInput>,
+    {
         // first: increment the executions for tracking how many times we've run so far
         *state.executions_mut() += 1;
 
@@ -74,85 +305,1646 @@ where
             // create a local copy of the initial instance and get the moves we're about to apply
             Ok::<_, Error>((self.initial.clone(), input.moves()))
         })()?;
+        // a state which breaks the invariant may not even make a valid board, so check it first
+        if invariant(&state).is_err() {
+            self.report(input, ExitKind::Crash, 0);
+            return Ok((ExitKind::Crash, state));
+        }
         // load the game board from the state, or return an error if there's something wrong
         let mut board = state
             .board_mut()
             .map_err(|e| Error::illegal_state(e.to_string()))?;
 
-        // TODO(pt.0): apply the moves in sequence
-        //  - check the docs for how to apply moves to a board
-        //    - see: https://docs.rs/parking-game/latest/parking_game/struct.Board.html
-        //  - if an error occurs during a move, return `Ok(ExitKind::Crash)`.
-        for (car,dir) in moves.into_iter() {
-            if board.shift_car(*car,*dir).is_err() {
-                return Ok(ExitKind::Crash);
+        // apply the moves in sequence; if an error occurs during a move, the input "crashed"
+        let start = Instant::now();
+        let timed_out = || {
+            self.timeout
+                .is_some_and(|timeout| start.elapsed() >= timeout)
+        };
+        // walls are empty cells of the board, so a car entering one is only caught after the move
+        let columns: usize = (*self.initial.dimensions().columns()).into();
+        let walls = self
+            .walls
+            .iter()
+            .map(|wall| {
+                let (row, column): (usize, usize) = ((*wall.row()).into(), (*wall.column()).into());
+                row * columns + column
+            })
+            .collect::<Vec<_>>();
+        let mut hit_wall = false;
+        let mut violated = false;
+        self.checkpoints.clear();
+        let (kind, applied) = if !moves.is_empty() && timed_out() {
+            (ExitKind::Timeout, 0)
+        } else {
+            // observers see each move before it is applied: the first here, the rest after the
+            // move before them
+            if let Some(&(car, dir)) = moves.first() {
+                self.observers.pre_move_all(&board, car, dir);
+            }
+            let applied = apply_moves(moves, &mut board, |i, board| {
+                let (car, dir) = moves[i];
+                if walls.iter().any(|&wall| board.concrete()[wall].is_some()) {
+                    hit_wall = true;
+                    return ControlFlow::Break(());
+                }
+                if invariant(board.state()).is_err() {
+                    violated = true;
+                    return ControlFlow::Break(());
+                }
+                self.observers.after_move_all(car, dir, board);
+                if self
+                    .checkpoint_interval
+                    .is_some_and(|interval| (i + 1) % interval == 0)
+                {
+                    self.checkpoints.push(board.state().clone());
+                }
+                if let Some(delay) = self.move_delay {
+                    sleep(delay);
+                }
+                if timed_out() {
+                    return ControlFlow::Break(());
+                }
+                if let Some(&(car, dir)) = moves.get(i + 1) {
+                    self.observers.pre_move_all(board, car, dir);
+                }
+                ControlFlow::Continue(())
+            });
+            match applied {
+                Err(applied) => (ExitKind::Crash, applied),
+                Ok(applied) if hit_wall => {
+                    // undo the move into the wall, as if it had failed like any other
+                    let (car, dir) = moves[applied - 1];
+                    board
+                        .shift_car(car.as_nonzero(), dir.opposite())
+                        .map_err(|e| Error::illegal_state(e.to_string()))?;
+                    (ExitKind::Crash, applied - 1)
+                }
+                Ok(applied) if violated => (ExitKind::Crash, applied),
+                Ok(applied) if applied < moves.len() => (ExitKind::Timeout, applied),
+                Ok(applied) => (ExitKind::Ok, applied),
             }
+        };
+        self.report(input, kind, applied);
+
+        // send the final board to all the observers, if the execution was successful
+        if kind == ExitKind::Ok {
+            self.observers.final_board_all(&board);
+        }
+        drop(board);
+        // catch observers which collected something inconsistent as soon as it happens
+        if cfg!(debug_assertions) {
+            self.observers
+                .validate_all()
+                .map_err(Error::illegal_state)?;
         }
-        // TODO(pt.3): add a microsecond delay *after each move* to simulate cost:
-        // sleep(Duration::from_micros(1));
 
-        // send the final board to all the observers
-        self.observers.final_board_all(&board);
+        Ok((kind, state))
+    }
 
-        // indicate successful execution
-        Ok(ExitKind::Ok)
+    /// Records the outcome of running the input in the statistics, and passes it to the crash hook
+    /// if it crashed.
+    fn report(&mut self, input: &PGInput, kind: ExitKind, applied: usize) {
+        if let Some(stats) = &mut self.stats {
+            stats.record(kind, applied);
+        }
+        if kind == ExitKind::Crash
+            && let Some(hook) = &mut self.crash_hook
+        {
+            hook(input);
+        }
+    }
+
+    /// Runs each of the provided inputs in turn, without a fuzzer, until one leaves nothing between
+    /// the objective car and the wall ahead of it, as with [`crate::feedbacks::SolvedFeedback`].
+    /// Returns the index of that input, or [`None`] if none of them solve the puzzle. Inputs which
+    /// do not finish never solve it. The observers are left as after the last input run.
+    pub fn run_until_solved(&mut self, inputs: &[PGInput]) -> Option<usize> {
+        let mut state = NopState::<PGInput>::new();
+        inputs.iter().position(|input| {
+            matches!(
+                self.execute(&mut state, input),
+                Ok((ExitKind::Ok, finished))
+                    if finished.board().is_ok_and(|board| blockers_in_path(&board) == 0)
+            )
+        })
     }
 }
 
-#[cfg(test)]
-mod test {
-    use crate::executor::PGExecutor;
-    use crate::input::PGInput;
-    use crate::observers::FinalStateObserver;
-    use libafl::NopFuzzer;
-    use libafl::events::SimpleEventManager;
-    use libafl::executors::{Executor, ExitKind, HasObservers};
-    use libafl::observers::ObserversTuple;
-    use libafl::state::NopState;
-    use libafl_bolts::tuples::tuple_list;
-    use parking_game::Direction;
-    use std::error::Error;
-    use std::num::NonZeroUsize;
+impl<EM, OT, S, T, Z> Executor<EM, PGInput, S, Z> for PGExecutor<T, OT>
+where
+    OT: PGObserverTuple<T>,
+    S: HasExecutions + HasCurrentTestcase<PGInput>,
+    T: BoardValue,
+{
+    fn run_target(
+        &mut self,
+        _fuzzer: &mut Z,
+        state: &mut S,
+        _mgr: &mut EM,
+        input: &PGInput,
+    ) -> Result<ExitKind, Error> {
+        self.execute(state, input).map(|(kind, _)| kind)
+    }
+}
 
-    #[test]
-    fn simple_run_check() -> Result<(), Box<dyn Error>> {
-        let initial = crate::parse_map::<u8>(
-            r#"
-        oo.
-        .22
-        "#,
-        );
-        let mut executor =
-            PGExecutor::new(initial, tuple_list!(FinalStateObserver::<u8>::default()));
+/// Executor which prints each move and the resulting board as an input is applied, then runs the
+/// input with the wrapped [`PGExecutor`]. Useful for watching a solution found by the fuzzer.
+pub struct ReplayExecutor<T, OT, W = Stdout> {
+    inner: PGExecutor<T, OT>,
+    verbose: bool,
+    writer: W,
+}
 
-        let mut fuzzer = NopFuzzer::new();
-        let mut state = NopState::<PGInput>::new();
-        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+impl<T, OT> ReplayExecutor<T, OT> {
+    /// Wrap the provided executor, printing to stdout only if `verbose` is set.
+    pub fn new(inner: PGExecutor<T, OT>, verbose: bool) -> Self {
+        Self::with_writer(inner, verbose, io::stdout())
+    }
+}
 
-        let first = PGInput::new(vec![(NonZeroUsize::new(1).unwrap(), Direction::Right)]);
-        executor.observers_mut().pre_exec_all(&mut state, &first)?;
-        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &first)?;
-        executor
-            .observers_mut()
-            .post_exec_all(&mut state, &first, &kind)?;
+impl<T, OT, W> ReplayExecutor<T, OT, W> {
+    /// Wrap the provided executor, printing to the provided writer only if `verbose` is set.
+    pub fn with_writer(inner: PGExecutor<T, OT>, verbose: bool, writer: W) -> Self {
+        Self {
+            inner,
+            verbose,
+            writer,
+        }
+    }
 
-        assert_eq!(ExitKind::Ok, kind);
-        assert_eq!(
-            1,
-            *executor.observers.0.final_state().as_ref().unwrap().cars()[0]
-                .0
-                .column()
-        );
+    /// The wrapped executor.
+    pub fn inner(&self) -> &PGExecutor<T, OT> {
+        &self.inner
+    }
 
-        let second = PGInput::new(vec![(NonZeroUsize::new(1).unwrap(), Direction::Down)]);
-        executor.observers_mut().pre_exec_all(&mut state, &second)?;
-        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &second)?;
-        executor
-            .observers_mut()
-            .post_exec_all(&mut state, &second, &kind)?;
+    /// The writer which moves are printed to.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+}
 
-        assert_eq!(ExitKind::Crash, kind);
-        assert!(executor.observers.0.final_state().is_none());
+impl<T, OT, W> HasObservers for ReplayExecutor<T, OT, W> {
+    type Observers = OT;
+
+    fn observers(&self) -> RefIndexable<&Self::Observers, Self::Observers> {
+        self.inner.observers()
+    }
+
+    fn observers_mut(&mut self) -> RefIndexable<&mut Self::Observers, Self::Observers> {
+        self.inner.observers_mut()
+    }
+}
+
+impl<EM, OT, S, T, W, Z> Executor<EM, PGInput, S, Z> for ReplayExecutor<T, OT, W>
+where
+    OT: PGObserverTuple<T>,
+    S: HasExecutions + HasCurrentTestcase<PGInput>,
+    T: BoardValue,
+    W: Write,
+{
+    fn run_target(
+        &mut self,
+        fuzzer: &mut Z,
+        state: &mut S,
+        mgr: &mut EM,
+        input: &PGInput,
+    ) -> Result<ExitKind, Error> {
+        if self.verbose {
+            // replay the moves on our own copy, stopping at the first invalid move
+            let mut replay = self.inner.initial().clone();
+            let mut board = replay
+                .board_mut()
+                .map_err(|e| Error::illegal_state(e.to_string()))?;
+            let moves = input.moves();
+            let mut written = Ok(());
+            let _ = apply_moves(moves, &mut board, |i, board| {
+                let (car, dir) = moves[i];
+                written = writeln!(self.writer, "Move {i}: Car {car} → {dir}")
+                    .and_then(|_| writeln!(self.writer, "{}", serialize_state(board.state())));
+                if written.is_ok() {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(())
+                }
+            });
+            written?;
+        }
+
+        self.inner.run_target(fuzzer, state, mgr, input)
+    }
+}
+
+/// Executor which remembers the result of each input it runs with the wrapped [`PGExecutor`], so
+/// that repeated inputs don't need to be run again. The least recently used results are forgotten
+/// once more than `max_entries` are stored.
+///
+/// A remembered result still counts as an execution: it is recorded in [`PGExecutor::stats`] and
+/// passed to the crash hook as though the input had been run. The moves are not applied again,
+/// though, so the observers only see the final board of inputs which finished, and none of the
+/// moves before it. Only use observers which look at the final board alone, like
+/// [`crate::observers::FinalStateObserver`].
+pub struct CachedExecutor<T, OT> {
+    inner: PGExecutor<T, OT>,
+    cache: HashMap<u64, CachedResult<T>>,
+    // the hashes of the remembered inputs by when they were last used, so the oldest is first
+    recent: BTreeMap<u64, u64>,
+    uses: u64,
+    hits: u64,
+    max_entries: usize,
+}
+
+/// The result of running an input with a [`CachedExecutor`].
+struct CachedResult<T> {
+    kind: ExitKind,
+    applied: usize,
+    final_state: Option<State<T>>,
+    last_used: u64,
+}
+
+impl<T, OT> CachedExecutor<T, OT> {
+    /// Wrap the provided executor, remembering at most `max_entries` results.
+    pub fn new(inner: PGExecutor<T, OT>, max_entries: usize) -> Self {
+        Self {
+            inner,
+            cache: HashMap::new(),
+            recent: BTreeMap::new(),
+            uses: 0,
+            hits: 0,
+            max_entries,
+        }
+    }
+
+    /// The wrapped executor.
+    pub fn inner(&self) -> &PGExecutor<T, OT> {
+        &self.inner
+    }
+
+    /// The number of results currently remembered.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Whether no results are currently remembered.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// The number of inputs whose result was remembered, rather than run again.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Marks the remembered input with the provided hash as the most recently used.
+    fn touch(&mut self, hash: u64, last_used: &mut u64) {
+        self.recent.remove(last_used);
+        *last_used = self.uses;
+        self.recent.insert(self.uses, hash);
+        self.uses += 1;
+    }
+}
+
+/// Identifies an input by the moves it contains.
+pub(crate) fn hash_input(input: &PGInput) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    input.moves().hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<T, OT> HasObservers for CachedExecutor<T, OT> {
+    type Observers = OT;
+
+    fn observers(&self) -> RefIndexable<&Self::Observers, Self::Observers> {
+        self.inner.observers()
+    }
+
+    fn observers_mut(&mut self) -> RefIndexable<&mut Self::Observers, Self::Observers> {
+        self.inner.observers_mut()
+    }
+}
+
+impl<EM, OT, S, T, Z> Executor<EM, PGInput, S, Z> for CachedExecutor<T, OT>
+where
+    OT: PGObserverTuple<T>,
+    S: HasExecutions + HasCurrentTestcase<PGInput>,
+    T: BoardValue,
+{
+    fn run_target(
+        &mut self,
+        _fuzzer: &mut Z,
+        state: &mut S,
+        _mgr: &mut EM,
+        input: &PGInput,
+    ) -> Result<ExitKind, Error> {
+        let hash = hash_input(input);
+        if let Some(mut cached) = self.cache.remove(&hash) {
+            *state.executions_mut() += 1;
+            self.hits += 1;
+            if let Some(final_state) = &cached.final_state {
+                let board = final_state
+                    .board()
+                    .map_err(|e| Error::illegal_state(e.to_string()))?;
+                self.inner.observers.final_board_all(&board);
+            }
+            self.inner.report(input, cached.kind, cached.applied);
+            // this is now the most recently used
+            self.touch(hash, &mut cached.last_used);
+            let kind = cached.kind;
+            self.cache.insert(hash, cached);
+            return Ok(kind);
+        }
+
+        // the moves applied are only needed for the statistics, so take them from there
+        let moves_applied = |inner: &PGExecutor<T, OT>| {
+            inner
+                .stats
+                .as_ref()
+                .map_or(0, |stats| stats.total_moves_applied)
+        };
+        let before = moves_applied(&self.inner);
+        let (kind, final_state) = self.inner.execute(state, input)?;
+        if self.max_entries > 0 {
+            if self.cache.len() >= self.max_entries
+                && let Some((_, evicted)) = self.recent.pop_first()
+            {
+                self.cache.remove(&evicted);
+            }
+            let mut cached = CachedResult {
+                kind,
+                applied: (moves_applied(&self.inner) - before) as usize,
+                final_state: (kind == ExitKind::Ok).then_some(final_state),
+                last_used: 0,
+            };
+            self.touch(hash, &mut cached.last_used);
+            self.cache.insert(hash, cached);
+        }
+        Ok(kind)
+    }
+}
+
+/// Executor which runs inputs with the wrapped [`PGExecutor`], checking an invariant of the board
+/// before the first move and after each move. If the invariant is ever violated, the execution
+/// stops there and the input is considered to have crashed, as with an invalid move: it counts
+/// towards the crashes in [`PGExecutor::stats`] and is passed to the crash hook. Useful for
+/// catching bugs in the game or in the executor itself.
+pub struct VerifiedExecutor<T, OT, F> {
+    inner: PGExecutor<T, OT>,
+    invariant: F,
+    violation: Option<String>,
+}
+
+impl<T, OT, F> VerifiedExecutor<T, OT, F>
+where
+    F: FnMut(&State<T>) -> Result<(), String>,
+{
+    /// Wrap the provided executor, checking the provided invariant.
+    pub fn new(inner: PGExecutor<T, OT>, invariant: F) -> Self {
+        Self {
+            inner,
+            invariant,
+            violation: None,
+        }
+    }
+}
+
+impl<T, OT> VerifiedExecutor<T, OT, fn(&State<T>) -> Result<(), String>>
+where
+    T: BoardValue,
+{
+    /// Wrap the provided executor, checking [`default_invariants`].
+    pub fn with_default_invariants(inner: PGExecutor<T, OT>) -> Self {
+        Self::new(inner, default_invariants)
+    }
+}
+
+impl<T, OT, F> VerifiedExecutor<T, OT, F> {
+    /// The wrapped executor.
+    pub fn inner(&self) -> &PGExecutor<T, OT> {
+        &self.inner
+    }
+
+    /// Why the invariant was violated by the last input run, if it was.
+    pub fn violation(&self) -> Option<&str> {
+        self.violation.as_deref()
+    }
+}
+
+/// Checks that every car is within the bounds of the board, that no two cars occupy the same cell,
+/// and that every car occupies as many cells as its length.
+pub fn default_invariants<T: BoardValue>(state: &State<T>) -> Result<(), String> {
+    let (rows, columns): (usize, usize) = (
+        (*state.dimensions().rows()).into(),
+        (*state.dimensions().columns()).into(),
+    );
+    let mut cells = vec![None; rows * columns];
+    for (idx, (position, car)) in state.cars().iter().enumerate() {
+        let car_idx = idx + 1;
+        let (row, column): (usize, usize) = ((*position.row()).into(), (*position.column()).into());
+        for offset in 0..(*car.length()).into() {
+            let (row, column) = match car.orientation() {
+                Orientation::LeftRight => (row, column + offset),
+                Orientation::UpDown => (row + offset, column),
+            };
+            if row >= rows || column >= columns {
+                return Err(format!(
+                    "car {car_idx} is out of bounds at ({row}, {column})"
+                ));
+            }
+            if let Some(other) = cells[row * columns + column].replace(car_idx) {
+                return Err(format!(
+                    "cars {other} and {car_idx} overlap at ({row}, {column})"
+                ));
+            }
+        }
+    }
+
+    let board = state.board().map_err(|e| e.to_string())?;
+    for (idx, (_, car)) in state.cars().iter().enumerate() {
+        let occupied = board
+            .concrete()
+            .iter()
+            .filter(|cell| cell.is_some_and(|cell| cell.get() == idx + 1))
+            .count();
+        let length: usize = (*car.length()).into();
+        if occupied != length {
+            return Err(format!(
+                "car {} occupies {occupied} cells, but has length {length}",
+                idx + 1
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+impl<T, OT, F> HasObservers for VerifiedExecutor<T, OT, F> {
+    type Observers = OT;
+
+    fn observers(&self) -> RefIndexable<&Self::Observers, Self::Observers> {
+        self.inner.observers()
+    }
+
+    fn observers_mut(&mut self) -> RefIndexable<&mut Self::Observers, Self::Observers> {
+        self.inner.observers_mut()
+    }
+}
+
+impl<EM, F, OT, S, T, Z> Executor<EM, PGInput, S, Z> for VerifiedExecutor<T, OT, F>
+where
+    F: FnMut(&State<T>) -> Result<(), String>,
+    OT: PGObserverTuple<T>,
+    S: HasExecutions + HasCurrentTestcase<PGInput>,
+    T: BoardValue,
+{
+    fn run_target(
+        &mut self,
+        _fuzzer: &mut Z,
+        state: &mut S,
+        _mgr: &mut EM,
+        input: &PGInput,
+    ) -> Result<ExitKind, Error> {
+        let mut violation = None;
+        let invariant = &mut self.invariant;
+        let (kind, _) = self.inner.execute_verified(state, input, |board| {
+            invariant(board).inspect_err(|e| violation = Some(e.clone()))
+        })?;
+        self.violation = violation;
+        Ok(kind)
+    }
+}
+
+/// Executor which applies each input to several maps in sequence, and only considers the input
+/// successful if it is valid for all of them. Useful for finding move sequences which generalise
+/// across variants of a puzzle.
+pub struct MultiMapExecutor<T, OT> {
+    maps: Vec<State<T>>,
+    observers: OT,
+}
+
+impl<T, OT> MultiMapExecutor<T, OT> {
+    /// Create a new executor for the provided maps with the provided observers.
+    ///
+    /// Panics if no maps are provided.
+    pub fn new(maps: Vec<State<T>>, observers: OT) -> Self {
+        assert!(!maps.is_empty(), "At least one map must be provided.");
+        Self { maps, observers }
+    }
+
+    /// The maps which this executor applies inputs to, in order.
+    pub fn maps(&self) -> &[State<T>] {
+        &self.maps
+    }
+}
+
+impl<T, OT> HasObservers for MultiMapExecutor<T, OT> {
+    type Observers = OT;
+
+    fn observers(&self) -> RefIndexable<&Self::Observers, Self::Observers> {
+        RefIndexable::from(&self.observers)
+    }
+
+    fn observers_mut(&mut self) -> RefIndexable<&mut Self::Observers, Self::Observers> {
+        RefIndexable::from(&mut self.observers)
+    }
+}
+
+impl<EM, OT, S, T, Z> Executor<EM, PGInput, S, Z> for MultiMapExecutor<T, OT>
+where
+    OT: PGObserverTuple<T>,
+    S: HasExecutions,
+    T: BoardValue,
+{
+    fn run_target(
+        &mut self,
+        _fuzzer: &mut Z,
+        state: &mut S,
+        _mgr: &mut EM,
+        input: &PGInput,
+    ) -> Result<ExitKind, Error> {
+        *state.executions_mut() += 1;
+
+        let mut last = None;
+        for map in &self.maps {
+            let mut map = map.clone();
+            let mut board = map
+                .board_mut()
+                .map_err(|e| Error::illegal_state(e.to_string()))?;
+            if apply_moves(input.moves(), &mut board, |_, _| ControlFlow::Continue(())).is_err() {
+                return Ok(ExitKind::Crash);
+            }
+            drop(board);
+            last = Some(map);
+        }
+
+        // the observers only see the last map
+        let last = last.unwrap();
+        let board = last
+            .board()
+            .map_err(|e| Error::illegal_state(e.to_string()))?;
+        self.observers.final_board_all(&board);
+
+        Ok(ExitKind::Ok)
+    }
+}
+
+/// A board on which moves may be undone, most recent first.
+pub trait ReversibleMoves<T> {
+    /// Apply the move to the board, remembering it so that it may be undone. Invalid moves are not
+    /// applied, and return an error.
+    fn push_move(&mut self, car: CarId, dir: Direction) -> Result<(), Error>;
+
+    /// Undo the most recently applied move which has not been undone yet, returning it, or
+    /// [`None`] if there are no moves to undo.
+    fn pop_move(&mut self) -> Result<Option<(CarId, Direction)>, Error>;
+}
+
+/// A state which remembers the moves applied to it, so that they may be undone with
+/// [`ReversibleMoves::pop_move`]. Each move shifts a car by a single space, so undoing one shifts
+/// the car back by a single space.
+#[derive(Debug, Clone)]
+pub struct ReversibleBoardState<T> {
+    state: State<T>,
+    history_stack: Vec<(CarId, Direction)>,
+}
+
+impl<T> ReversibleBoardState<T> {
+    /// Start from the provided state, with no moves to undo.
+    pub fn new(state: State<T>) -> Self {
+        Self {
+            state,
+            history_stack: Vec::new(),
+        }
+    }
+
+    /// The state after every move applied so far.
+    pub fn state(&self) -> &State<T> {
+        &self.state
+    }
+
+    /// The moves applied so far which have not been undone, oldest first.
+    pub fn moves(&self) -> impl Iterator<Item = (CarId, Direction)> + '_ {
+        self.history_stack.iter().copied()
+    }
+
+    /// The number of moves which may be undone.
+    pub fn depth(&self) -> usize {
+        self.history_stack.len()
+    }
+}
+
+impl<T> ReversibleMoves<T> for ReversibleBoardState<T>
+where
+    T: BoardValue,
+{
+    fn push_move(&mut self, car: CarId, dir: Direction) -> Result<(), Error> {
+        let mut board = self
+            .state
+            .board_mut()
+            .map_err(|e| Error::illegal_state(e.to_string()))?;
+        board
+            .shift_car(car.as_nonzero(), dir)
+            .map_err(|e| Error::illegal_argument(e.to_string()))?;
+        self.history_stack.push((car, dir));
+        Ok(())
+    }
+
+    fn pop_move(&mut self) -> Result<Option<(CarId, Direction)>, Error> {
+        let Some((car, dir)) = self.history_stack.pop() else {
+            return Ok(None);
+        };
+        let mut board = self
+            .state
+            .board_mut()
+            .map_err(|e| Error::illegal_state(e.to_string()))?;
+        // the car just left this space, so nothing can be in the way
+        board
+            .shift_car(car.as_nonzero(), dir.opposite())
+            .map_err(|e| Error::illegal_state(e.to_string()))?;
+        Ok(Some((car, dir)))
+    }
+}
+
+/// Executor which, rather than starting each input from the initial state, undoes moves until the
+/// board is at the longest prefix shared with the previous input, then applies only the rest. When
+/// inputs are run in depth-first order, e.g., by extending and truncating the same input, most
+/// executions then apply a single move.
+///
+/// The observers only see the moves applied by each execution, not those shared with the previous
+/// one, much like when [`PGExecutor`] resumes from a snapshot. Use [`BacktrackingExecutor::rewind`]
+/// between executions for the observers to see every move.
+pub struct BacktrackingExecutor<T, OT> {
+    board: ReversibleBoardState<T>,
+    observers: OT,
+}
+
+impl<T, OT> BacktrackingExecutor<T, OT> {
+    /// Create a new executor for the provided state with the provided observers.
+    pub fn new(initial: State<T>, observers: OT) -> Self {
+        Self {
+            board: ReversibleBoardState::new(initial),
+            observers,
+        }
+    }
+
+    /// The board as left by the last execution.
+    pub fn board(&self) -> &ReversibleBoardState<T> {
+        &self.board
+    }
+
+    /// Undo every move left applied by the last execution, returning the board to the initial
+    /// state. The next execution then applies all of its moves.
+    pub fn rewind(&mut self) -> Result<(), Error>
+    where
+        T: BoardValue,
+    {
+        while self.board.pop_move()?.is_some() {}
+        Ok(())
+    }
+}
+
+impl<T, OT> HasObservers for BacktrackingExecutor<T, OT> {
+    type Observers = OT;
+
+    fn observers(&self) -> RefIndexable<&Self::Observers, Self::Observers> {
+        RefIndexable::from(&self.observers)
+    }
+
+    fn observers_mut(&mut self) -> RefIndexable<&mut Self::Observers, Self::Observers> {
+        RefIndexable::from(&mut self.observers)
+    }
+}
+
+impl<EM, OT, S, T, Z> Executor<EM, PGInput, S, Z> for BacktrackingExecutor<T, OT>
+where
+    OT: PGObserverTuple<T>,
+    S: HasExecutions,
+    T: BoardValue,
+{
+    fn run_target(
+        &mut self,
+        _fuzzer: &mut Z,
+        state: &mut S,
+        _mgr: &mut EM,
+        input: &PGInput,
+    ) -> Result<ExitKind, Error> {
+        *state.executions_mut() += 1;
+
+        let moves = input.moves();
+        let shared = self
+            .board
+            .moves()
+            .zip(moves)
+            .take_while(|(applied, next)| applied == *next)
+            .count();
+        while self.board.depth() > shared {
+            self.board.pop_move()?;
+        }
+
+        for &(car, dir) in &moves[shared..] {
+            let board = self
+                .board
+                .state()
+                .board()
+                .map_err(|e| Error::illegal_state(e.to_string()))?;
+            self.observers.pre_move_all(&board, car, dir);
+            drop(board);
+            // the board is left as it was before the invalid move, ready for the next input
+            if self.board.push_move(car, dir).is_err() {
+                return Ok(ExitKind::Crash);
+            }
+            let board = self
+                .board
+                .state()
+                .board()
+                .map_err(|e| Error::illegal_state(e.to_string()))?;
+            self.observers.after_move_all(car, dir, &board);
+        }
+
+        let board = self
+            .board
+            .state()
+            .board()
+            .map_err(|e| Error::illegal_state(e.to_string()))?;
+        self.observers.final_board_all(&board);
+        Ok(ExitKind::Ok)
+    }
+}
+
+/// Runs batches of inputs against the same initial state in parallel, outside of the fuzzer's
+/// loop, e.g., to evaluate a whole corpus at once. Observers are not thread-safe, so each execution
+/// is observed by a fresh set of observers, which is returned with the result of the execution.
+pub struct ParallelPGExecutor<T> {
+    initial: State<T>,
+}
+
+impl<T> ParallelPGExecutor<T>
+where
+    T: BoardValue + Send + Sync,
+{
+    /// Create a new executor for the provided state.
+    pub fn new(initial: State<T>) -> Self {
+        Self { initial }
+    }
+
+    /// The initial state which each input is applied to.
+    pub fn initial(&self) -> &State<T> {
+        &self.initial
+    }
+
+    /// Run each of the inputs independently, returning the kind of exit of each along with the
+    /// state of the board when it finished, if it didn't crash. Results are in the order of the
+    /// inputs.
+    pub fn run_batch(&self, inputs: &[PGInput]) -> Vec<(ExitKind, Option<State<T>>)> {
+        self.run_batch_observed(inputs, || ())
+            .into_iter()
+            .map(|(kind, state, ())| (kind, state))
+            .collect()
+    }
+
+    /// Run each of the inputs as with [`ParallelPGExecutor::run_batch`], observing each execution
+    /// with the observers created for it by `observers`.
+    pub fn run_batch_observed<OT>(
+        &self,
+        inputs: &[PGInput],
+        observers: impl Fn() -> OT + Sync + Send,
+    ) -> Vec<(ExitKind, Option<State<T>>, OT)>
+    where
+        OT: PGObserverTuple<T> + Send,
+    {
+        inputs
+            .par_iter()
+            .map(|input| {
+                let mut observers = observers();
+                let (kind, state) = self.run_one(input, &mut observers);
+                (kind, state, observers)
+            })
+            .collect()
+    }
+
+    /// Apply the input to a copy of the initial state, as [`PGExecutor`] would.
+    fn run_one<OT>(&self, input: &PGInput, observers: &mut OT) -> (ExitKind, Option<State<T>>)
+    where
+        OT: PGObserverTuple<T>,
+    {
+        let mut state = self.initial.clone();
+        let Ok(mut board) = state.board_mut() else {
+            return (ExitKind::Crash, None);
+        };
+        let moves = input.moves();
+        if let Some(&(car, dir)) = moves.first() {
+            observers.pre_move_all(&board, car, dir);
+        }
+        let applied = apply_moves(moves, &mut board, |i, board| {
+            let (car, dir) = moves[i];
+            observers.after_move_all(car, dir, board);
+            if let Some(&(car, dir)) = moves.get(i + 1) {
+                observers.pre_move_all(board, car, dir);
+            }
+            ControlFlow::Continue(())
+        });
+        if applied.is_err() {
+            return (ExitKind::Crash, None);
+        }
+        observers.final_board_all(&board);
+        drop(board);
+        (ExitKind::Ok, Some(state))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::executor::{
+        BacktrackingExecutor, CachedExecutor, MultiMapExecutor, PGExecutor, PGExecutorBuilder,
+        PGExecutorStats, ParallelPGExecutor, ReplayExecutor, ReversibleBoardState, ReversibleMoves,
+        VerifiedExecutor,
+    };
+    use crate::input::PGInput;
+    use crate::observers::{FinalStateObserver, PGObserver, hash_state};
+    use crate::serialization::serialize_state;
+    use crate::types::CarId;
+    use libafl::NopFuzzer;
+    use libafl::events::SimpleEventManager;
+    use libafl::executors::{Executor, ExitKind, HasObservers};
+    use libafl::observers::ObserversTuple;
+    use libafl::state::{HasExecutions, NopState};
+    use libafl_bolts::tuples::tuple_list;
+    use parking_game::{Board, BoardValue, Direction, State};
+    use std::error::Error;
+    use std::ops::Deref;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn simple_run_check() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>(
+            r#"
+        oo.
+        .22
+        "#,
+        );
+        let mut executor =
+            PGExecutor::new(initial, tuple_list!(FinalStateObserver::<u8>::default()));
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let first = PGInput::new(vec![(CarId::new(1).unwrap(), Direction::Right)]);
+        executor.observers_mut().pre_exec_all(&mut state, &first)?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &first)?;
+        executor
+            .observers_mut()
+            .post_exec_all(&mut state, &first, &kind)?;
+
+        assert_eq!(ExitKind::Ok, kind);
+        assert_eq!(
+            1,
+            *executor.observers.0.final_state().as_ref().unwrap().cars()[0]
+                .0
+                .column()
+        );
+
+        let second = PGInput::new(vec![(CarId::new(1).unwrap(), Direction::Down)]);
+        executor.observers_mut().pre_exec_all(&mut state, &second)?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &second)?;
+        executor
+            .observers_mut()
+            .post_exec_all(&mut state, &second, &kind)?;
+
+        assert_eq!(ExitKind::Crash, kind);
+        assert!(executor.observers.0.final_state().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn builder_timeout() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo..");
+        let mut executor = PGExecutorBuilder::with_initial(initial)
+            .with_timeout(Duration::ZERO)
+            .build(tuple_list!(FinalStateObserver::<u8>::default()));
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for input in ["1R", "1R 1R", "1R 1R 1R"] {
+            let input = input.parse::<PGInput>()?;
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            assert_eq!(ExitKind::Timeout, kind);
+        }
+        // nothing to time out on
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &PGInput::default())?;
+        assert_eq!(ExitKind::Ok, kind);
+
+        Ok(())
+    }
+
+    /// Records the column of the objective car before each move, and counts the moves applied.
+    #[derive(Default)]
+    struct MoveCounter {
+        columns_before: Vec<usize>,
+        applied: usize,
+    }
+
+    impl<T: BoardValue> PGObserver<T> for MoveCounter {
+        fn pre_move(
+            &mut self,
+            board: &Board<impl Deref<Target = State<T>>, T>,
+            _car: CarId,
+            _direction: Direction,
+        ) {
+            let (position, _) = board.state().cars()[0];
+            self.columns_before.push((*position.column()).into());
+        }
+
+        fn after_move(
+            &mut self,
+            _car: CarId,
+            _direction: Direction,
+            _board: &Board<impl Deref<Target = State<T>>, T>,
+        ) {
+            self.applied += 1;
+        }
+    }
+
+    #[test]
+    fn pre_move() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo....\n......");
+        let mut executor = PGExecutor::new(initial, tuple_list!(MoveCounter::default()));
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let input = "1R 1R 1R".parse::<PGInput>()?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Ok, kind);
+        assert_eq!(vec![0, 1, 2], executor.observers.0.columns_before);
+        assert_eq!(3, executor.observers.0.applied);
+
+        // the invalid move is still seen beforehand, but never applied
+        executor.observers.0 = MoveCounter::default();
+        let input = "1R 1U 1R".parse::<PGInput>()?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Crash, kind);
+        assert_eq!(vec![0, 1], executor.observers.0.columns_before);
+        assert_eq!(1, executor.observers.0.applied);
+
+        Ok(())
+    }
+
+    #[test]
+    fn builder_walls() -> Result<(), Box<dyn Error>> {
+        let (initial, map) =
+            crate::parse_map_with_options::<u8>("oo...\n11.#.", Default::default());
+        let mut executor = PGExecutorBuilder::with_initial(initial)
+            .with_walls(map.walls().iter().copied())
+            .with_stats()
+            .build(tuple_list!(FinalStateObserver::<u8>::default()));
+        assert_eq!(1, executor.walls().len());
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &"2R".parse()?)?;
+        assert_eq!(ExitKind::Ok, kind);
+        // the second move puts car 2 into the wall, like moving it off the board
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &"2R 2R".parse()?)?;
+        assert_eq!(ExitKind::Crash, kind);
+        // other cars may pass the wall's column
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &"1R 1R 1R".parse()?)?;
+        assert_eq!(ExitKind::Ok, kind);
+
+        let stats = executor.stats().unwrap();
+        assert_eq!(1, stats.crash_count);
+        assert_eq!(5, stats.total_moves_applied);
+
+        Ok(())
+    }
+
+    #[test]
+    fn clone_executor() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo...\n.....\n...1.\n...1.");
+        let mut first = PGExecutorBuilder::with_initial(initial)
+            .with_stats()
+            .build(tuple_list!(FinalStateObserver::<u8>::default()));
+        let mut second = first.clone();
+        assert_eq!(
+            serialize_state(first.initial()),
+            serialize_state(second.initial())
+        );
+        assert!(second.crash_hook.is_none());
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let kind = first.run_target(&mut fuzzer, &mut state, &mut mgr, &"1R 1R".parse()?)?;
+        assert_eq!(ExitKind::Ok, kind);
+        let kind = second.run_target(&mut fuzzer, &mut state, &mut mgr, &"2U 2U".parse()?)?;
+        assert_eq!(ExitKind::Ok, kind);
+        let kind = second.run_target(&mut fuzzer, &mut state, &mut mgr, &"2U 2U 2U".parse()?)?;
+        assert_eq!(ExitKind::Crash, kind);
+
+        // each clone observed and counted only its own executions
+        let first_final = first.observers.0.final_state().unwrap();
+        assert_eq!(2, *first_final.cars()[0].0.column());
+        assert_eq!(2, *first_final.cars()[1].0.row());
+        // the crash was not observed, so the last final state is still that of the first run
+        let second_final = second.observers.0.final_state().unwrap();
+        assert_eq!(0, *second_final.cars()[0].0.column());
+        assert_eq!(0, *second_final.cars()[1].0.row());
+        assert_eq!(1, first.stats().unwrap().total_runs);
+        assert_eq!(2, second.stats().unwrap().total_runs);
+        assert_eq!(1, second.stats().unwrap().crash_count);
+        assert_eq!(
+            serialize_state(first.initial()),
+            serialize_state(second.initial())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn builder_move_delay() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo....");
+        let delay = Duration::from_millis(2);
+        let mut executor = PGExecutorBuilder::with_initial(initial)
+            .with_move_delay(delay)
+            .build(tuple_list!(FinalStateObserver::<u8>::default()));
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for (input, moves) in [("1R", 1), ("1R 1R", 2), ("1R 1R 1R 1R", 4)] {
+            let input = input.parse::<PGInput>()?;
+            let start = Instant::now();
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            assert_eq!(ExitKind::Ok, kind);
+            assert!(start.elapsed() >= delay * moves);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn builder_checkpoints() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo......");
+        let mut executor = PGExecutorBuilder::with_initial(initial)
+            .with_checkpoint_interval(2)
+            .build(tuple_list!(FinalStateObserver::<u8>::default()));
+        assert_eq!(Some(2), executor.checkpoint_interval());
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+        let column = |snapshot: &State<u8>| *snapshot.cars()[0].0.column();
+
+        let input = "1R 1R 1R 1R 1R".parse::<PGInput>()?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Ok, kind);
+        let columns = executor
+            .checkpoints()
+            .iter()
+            .map(column)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![2, 4], columns);
+
+        // only the moves applied before the crash are snapshotted, and the last run is forgotten
+        let input = "1R 1R 1R 1U 1R".parse::<PGInput>()?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Crash, kind);
+        let columns = executor
+            .checkpoints()
+            .iter()
+            .map(column)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![2], columns);
+
+        // without an interval, no snapshots are taken
+        let initial = crate::parse_map::<u8>("oo......");
+        let mut executor = PGExecutorBuilder::with_initial(initial)
+            .with_checkpoint_interval(0)
+            .build(tuple_list!(FinalStateObserver::<u8>::default()));
+        assert_eq!(None, executor.checkpoint_interval());
+        let input = "1R 1R 1R 1R".parse::<PGInput>()?;
+        executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert!(executor.checkpoints().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_move_delay() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.....");
+        let mut executor =
+            PGExecutor::new(initial, tuple_list!(FinalStateObserver::<u8>::default()));
+        assert_eq!(None, executor.move_delay());
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+        let input = "1R 1R 1R 1R 1R".parse::<PGInput>()?;
+
+        executor.set_move_delay(Duration::from_millis(1));
+        let start = Instant::now();
+        executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert!(start.elapsed() >= Duration::from_millis(5));
+
+        executor.set_move_delay(Duration::ZERO);
+        assert_eq!(None, executor.move_delay());
+
+        Ok(())
+    }
+
+    #[test]
+    fn stats() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.....");
+        let mut executor = PGExecutorBuilder::with_initial(initial)
+            .with_timeout(Duration::from_millis(20))
+            .with_stats()
+            .build(tuple_list!(FinalStateObserver::<u8>::default()));
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for (input, expected) in [
+            ("1R 1L 1R 1L 1R", ExitKind::Ok),
+            ("1R 1L 1R", ExitKind::Ok),
+            ("1R 1R 1R 1R 1R 1L 1L", ExitKind::Ok),
+            ("1R 1L 1L", ExitKind::Crash),
+            ("1R 1R 1L 1L 1L", ExitKind::Crash),
+        ] {
+            let input = input.parse::<PGInput>()?;
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            assert_eq!(expected, kind);
+        }
+        // the first move takes longer than the timeout
+        executor.set_move_delay(Duration::from_millis(30));
+        let input = "1R 1R".parse::<PGInput>()?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Timeout, kind);
+
+        assert_eq!(
+            Some(&PGExecutorStats {
+                total_runs: 6,
+                total_moves_applied: 5 + 3 + 7 + 2 + 4 + 1,
+                crash_count: 2,
+                ok_count: 3,
+                timeout_count: 1,
+            }),
+            executor.stats()
+        );
+
+        executor.reset_stats();
+        assert_eq!(Some(&PGExecutorStats::default()), executor.stats());
+
+        // not enabled by default
+        assert!(
+            PGExecutor::new(crate::parse_map::<u8>("oo."), ())
+                .stats()
+                .is_none()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn replay() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>(
+            r#"
+        ...
+        ..1
+        oo1
+        "#,
+        );
+        let mut executor = ReplayExecutor::with_writer(
+            PGExecutor::new(initial, tuple_list!(FinalStateObserver::<u8>::default())),
+            true,
+            Vec::new(),
+        );
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let input = "2U 1R 1L".parse::<PGInput>()?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Ok, kind);
+        assert_eq!(
+            "Move 0: Car 2 → up\n..1\n..1\noo.\n\
+             Move 1: Car 1 → right\n..1\n..1\n.oo\n\
+             Move 2: Car 1 → left\n..1\n..1\noo.\n",
+            String::from_utf8(executor.writer().clone())?
+        );
+
+        // nothing is printed unless verbose
+        let mut executor = ReplayExecutor::with_writer(
+            PGExecutor::new(crate::parse_map::<u8>("oo."), ()),
+            false,
+            Vec::new(),
+        );
+        let input = "1R".parse::<PGInput>()?;
+        executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert!(executor.writer().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cached() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo...");
+        let mut executor = CachedExecutor::new(
+            PGExecutorBuilder::with_initial(initial)
+                .with_stats()
+                .build(tuple_list!(FinalStateObserver::<u8>::default())),
+            2,
+        );
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+        type Cached = CachedExecutor<u8, (FinalStateObserver<u8>, ())>;
+        let mut run = |executor: &mut Cached, input: &str| {
+            let input = input.parse::<PGInput>()?;
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            executor
+                .observers_mut()
+                .post_exec_all(&mut state, &input, &kind)?;
+            Ok::<_, Box<dyn Error>>(kind)
+        };
+        let hits = |executor: &Cached| executor.hits();
+
+        assert_eq!(ExitKind::Ok, run(&mut executor, "1R 1R")?);
+        assert_eq!(ExitKind::Ok, run(&mut executor, "1R 1R")?);
+        assert_eq!(1, hits(&executor));
+        // the observers still see the final state
+        let observers = executor.observers();
+        assert_eq!(2, *observers.0.final_state().unwrap().cars()[0].0.column());
+
+        assert_eq!(ExitKind::Crash, run(&mut executor, "1L")?);
+        assert_eq!(ExitKind::Crash, run(&mut executor, "1L")?);
+        assert!(executor.observers().0.final_state().is_none());
+        assert_eq!(2, hits(&executor));
+        assert_eq!(2, executor.len());
+        // remembered results are recorded as though they had been run
+        let stats = executor.inner().stats().unwrap();
+        assert_eq!(4, stats.total_runs);
+        assert_eq!(2, stats.crash_count);
+        assert_eq!(4, stats.total_moves_applied);
+
+        // "1R 1R" was used less recently than "1L", so it is evicted
+        assert_eq!(ExitKind::Ok, run(&mut executor, "1R")?);
+        assert_eq!(2, hits(&executor));
+        assert_eq!(2, executor.len());
+        run(&mut executor, "1L")?;
+        assert_eq!(3, hits(&executor));
+        run(&mut executor, "1R 1R")?;
+        assert_eq!(3, hits(&executor));
+
+        Ok(())
+    }
+
+    #[test]
+    fn verified() -> Result<(), Box<dyn Error>> {
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+        let input = "1R".parse::<PGInput>()?;
+
+        let initial = crate::parse_map::<u8>(
+            r#"
+        oo.
+        22.
+        "#,
+        );
+        let mut executor =
+            VerifiedExecutor::with_default_invariants(PGExecutor::new(initial.clone(), ()));
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Ok, kind);
+        assert_eq!(None, executor.violation());
+
+        // move car 2 on top of the objective car, which can't be done through the board
+        let mut overlapping = serde_json::to_value(&initial)?;
+        overlapping["cars"][1][0]["row"] = 0.into();
+        let overlapping = serde_json::from_value::<State<u8>>(overlapping)?;
+        let mut executor = VerifiedExecutor::with_default_invariants(
+            PGExecutorBuilder::with_initial(overlapping)
+                .with_stats()
+                .build(()),
+        );
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Crash, kind);
+        assert_eq!(Some("cars 1 and 2 overlap at (0, 0)"), executor.violation());
+        // the input is only run once, by the wrapped executor, which reports the crash
+        let stats = executor.inner().stats().unwrap();
+        assert_eq!(
+            (1, 1, 0),
+            (
+                stats.total_runs,
+                stats.crash_count,
+                stats.total_moves_applied
+            )
+        );
+
+        // custom invariants are checked after every move
+        let mut executor = VerifiedExecutor::new(
+            PGExecutorBuilder::with_initial(initial)
+                .with_stats()
+                .build(tuple_list!(FinalStateObserver::<u8>::default())),
+            |state| {
+                if *state.cars()[0].0.column() == 0 {
+                    Ok(())
+                } else {
+                    Err("the objective car moved".to_string())
+                }
+            },
+        );
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Crash, kind);
+        assert_eq!(Some("the objective car moved"), executor.violation());
+        assert!(executor.observers().0.final_state().is_none());
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &PGInput::default())?;
+        assert_eq!(ExitKind::Ok, kind);
+        assert_eq!(None, executor.violation());
+        assert!(executor.observers().0.final_state().is_some());
+        let stats = executor.inner().stats().unwrap();
+        assert_eq!((2, 1), (stats.total_runs, stats.crash_count));
+
+        Ok(())
+    }
+
+    #[test]
+    fn multi_map() -> Result<(), Box<dyn Error>> {
+        let a = crate::parse_map::<u8>(
+            r#"
+        oo..
+        ....
+        "#,
+        );
+        let b = crate::parse_map::<u8>(
+            r#"
+        oo.2
+        ...2
+        "#,
+        );
+        let mut executor =
+            MultiMapExecutor::new(vec![a, b], tuple_list!(FinalStateObserver::<u8>::default()));
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        // valid on a, but the objective car hits car 2 on b
+        let input = "1R 1R".parse::<PGInput>()?;
+        executor.observers_mut().pre_exec_all(&mut state, &input)?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Crash, kind);
+        assert!(executor.observers().0.final_state().is_none());
+
+        let input = "1R".parse::<PGInput>()?;
+        executor.observers_mut().pre_exec_all(&mut state, &input)?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(ExitKind::Ok, kind);
+        let observers = executor.observers();
+        let final_state = observers.0.final_state().unwrap();
+        assert_eq!(2, final_state.cars().len());
+        assert_eq!(1, *final_state.cars()[0].0.column());
+
+        Ok(())
+    }
+
+    #[test]
+    fn matches_apply() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>(
+            r#"
+        oo...
+        1....
+        1....
+        .....
+        "#,
+        );
+        let mut executor = PGExecutor::new(
+            initial.clone(),
+            tuple_list!(FinalStateObserver::<u8>::default()),
+        );
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for input in ["1R 1R 2U 2D 2D", "1R 2U 2U 1R"] {
+            let input = input.parse::<PGInput>()?;
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            executor
+                .observers_mut()
+                .post_exec_all(&mut state, &input, &kind)?;
+
+            let mut applied = initial.clone();
+            match input.apply(&mut applied) {
+                Ok(_) => {
+                    assert_eq!(ExitKind::Ok, kind);
+                    assert_eq!(
+                        format!("{applied:?}"),
+                        format!("{:?}", executor.observers.0.final_state().unwrap())
+                    );
+                }
+                Err(_) => assert_eq!(ExitKind::Crash, kind),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parallel() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n22..");
+        let inputs = [
+            "1R", "2D 1R 1R", "1L", "3R 3R", "2U", "2D 2U 2D", "", "3R 1R 3L",
+        ]
+        .iter()
+        .map(|s| s.parse::<PGInput>())
+        .collect::<Result<Vec<_>, _>>()?;
+        let executor = ParallelPGExecutor::new(initial.clone());
+        assert!(executor.run_batch(&[]).is_empty());
+
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+        let results = pool.install(|| executor.run_batch(&inputs));
+        assert_eq!(8, results.len());
+        for (input, (kind, state)) in inputs.iter().zip(&results) {
+            // the same as applying each input in turn
+            let mut expected = initial.clone();
+            match input.apply(&mut expected) {
+                Ok(_) => {
+                    assert_eq!(ExitKind::Ok, *kind);
+                    assert_eq!(hash_state(&expected), state.as_ref().and_then(hash_state));
+                }
+                Err(_) => {
+                    assert_eq!(ExitKind::Crash, *kind);
+                    assert!(state.is_none());
+                }
+            }
+        }
+
+        // each execution is observed separately
+        let observed = executor
+            .run_batch_observed(&inputs, || tuple_list!(FinalStateObserver::<u8>::default()));
+        for ((kind, state, (observer, ())), (expected_kind, _)) in observed.iter().zip(&results) {
+            assert_eq!(expected_kind, kind);
+            assert_eq!(
+                state.as_ref().and_then(hash_state),
+                observer.final_state().and_then(hash_state)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn reversible() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n22..");
+        let mut board = ReversibleBoardState::new(initial.clone());
+        let (one, two) = (CarId::new(1).unwrap(), CarId::new(2).unwrap());
+        assert_eq!(None, board.pop_move()?);
+
+        let before = hash_state(board.state());
+        board.push_move(one, Direction::Right)?;
+        assert_ne!(before, hash_state(board.state()));
+        assert_eq!(Some((one, Direction::Right)), board.pop_move()?);
+        assert_eq!(before, hash_state(board.state()));
+
+        // moves are undone most recent first, and invalid moves are never applied
+        board.push_move(two, Direction::Down)?;
+        let middle = hash_state(board.state());
+        board.push_move(one, Direction::Right)?;
+        assert!(board.push_move(two, Direction::Down).is_err());
+        assert_eq!(2, board.depth());
+        assert_eq!(Some((one, Direction::Right)), board.pop_move()?);
+        assert_eq!(middle, hash_state(board.state()));
+        assert_eq!(Some((two, Direction::Down)), board.pop_move()?);
+        assert_eq!(before, hash_state(board.state()));
+        assert_eq!(0, board.depth());
+
+        Ok(())
+    }
+
+    #[test]
+    fn backtracking() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n22..");
+        let mut executor = BacktrackingExecutor::new(
+            initial.clone(),
+            tuple_list!(FinalStateObserver::<u8>::default()),
+        );
+        let mut state = NopState::<PGInput>::new();
+        let mut fuzzer = NopFuzzer::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        // in depth-first order, then back out to something else entirely
+        for (moves, expected) in [
+            ("2D", ExitKind::Ok),
+            ("2D 1R", ExitKind::Ok),
+            ("2D 1R 1R", ExitKind::Ok),
+            ("2D 1R 2D", ExitKind::Crash),
+            ("2D 1R 1L", ExitKind::Ok),
+            ("3R 3R", ExitKind::Ok),
+            ("", ExitKind::Ok),
+        ] {
+            let input = moves.parse::<PGInput>()?;
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            assert_eq!(expected, kind, "{moves}");
+            if kind == ExitKind::Ok {
+                let mut fresh = initial.clone();
+                input.apply(&mut fresh).unwrap();
+                assert_eq!(hash_state(&fresh), hash_state(executor.board().state()));
+                assert_eq!(
+                    hash_state(&fresh),
+                    executor.observers().0.final_state().and_then(hash_state)
+                );
+            }
+        }
+        assert_eq!(7, *state.executions());
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_until_solved() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n....");
+        let mut executor = PGExecutor::new(
+            initial.clone(),
+            tuple_list!(FinalStateObserver::<u8>::default()),
+        );
+        let inputs = ["1R", "2D", "2D 2U"].map(|moves| moves.parse::<PGInput>().unwrap());
+
+        assert_eq!(None, executor.run_until_solved(&[]));
+        assert_eq!(Some(1), executor.run_until_solved(&inputs));
+        // no input is run after the solution
+        let mut solved = initial.clone();
+        inputs[1].apply(&mut solved).unwrap();
+        assert_eq!(
+            hash_state(&solved),
+            executor.observers().0.final_state().and_then(hash_state)
+        );
+
+        // the objective car is against the edge of the board, so the first input crashes
+        let unsolved = ["1L 2D", "1R 1R"].map(|moves| moves.parse::<PGInput>().unwrap());
+        assert_eq!(None, executor.run_until_solved(&unsolved));
+
+        Ok(())
+    }
+
+    #[test]
+    fn backtracking_rewind() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n22..");
+        let mut backtracking = BacktrackingExecutor::new(
+            initial.clone(),
+            tuple_list!(FinalStateObserver::<u8>::default()),
+        );
+        let mut replaying = PGExecutor::new(
+            initial.clone(),
+            tuple_list!(FinalStateObserver::<u8>::default()),
+        );
+        let mut state = NopState::<PGInput>::new();
+        let mut fuzzer = NopFuzzer::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for moves in ["2D 1R", "2D 1R 1R", "2D 1R 2D", "3R 3R 2D", "1R 1L 3R"] {
+            let input = moves.parse::<PGInput>()?;
+            backtracking
+                .observers_mut()
+                .pre_exec_all(&mut state, &input)?;
+            replaying.observers_mut().pre_exec_all(&mut state, &input)?;
+            let kind = backtracking.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            assert_eq!(
+                replaying.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?,
+                kind,
+                "{moves}"
+            );
+            if kind == ExitKind::Ok {
+                assert_eq!(
+                    replaying.observers().0.final_state().and_then(hash_state),
+                    backtracking
+                        .observers()
+                        .0
+                        .final_state()
+                        .and_then(hash_state),
+                    "{moves}"
+                );
+            }
+
+            backtracking.rewind()?;
+            assert_eq!(0, backtracking.board().depth());
+            assert_eq!(
+                hash_state(&initial),
+                hash_state(backtracking.board().state())
+            );
+        }
 
         Ok(())
     }
@@ -1,22 +1,36 @@
 //! Feedbacks which assist in the solving of [`parking_game`] puzzles by interpreting data collected
 //! by the [`libafl::observers::Observer`] implementations in [`crate::observers`].
 
+use crate::MapMetadata;
+use crate::analysis::{HeuristicWeights, heuristic_score_with};
+use crate::coverage::CoverageMap;
+use crate::executor::hash_input;
 use crate::input::PGInput;
-use crate::observers::{FinalStateObserver, ViewFrom, ViewObserver};
+use crate::observers::{
+    CarMovementObserver, CoverageMapObserver, FinalStateObserver, HeatmapObserver,
+    MoveRepetitionObserver, NearSolutionObserver, SolutionLengthTracker, TransitionCountObserver,
+    View, ViewFrom, ViewObserver,
+};
+use crate::types::CarId;
 use libafl::HasMetadata;
+use libafl::NopFuzzer;
 use libafl::corpus::Testcase;
 use libafl::events::{Event, EventFirer, EventWithStats, ExecStats};
-use libafl::executors::ExitKind;
+use libafl::executors::{Executor, ExitKind, HasObservers};
 use libafl::feedbacks::{Feedback, StateInitializer};
 use libafl::monitors::stats::{AggregatorOps, UserStats};
+use libafl::observers::{ObserverWithHashField, ObserversTuple};
+use libafl::state::{HasCurrentTestcase, NopState};
 use libafl_bolts::tuples::{Handle, Handled, MatchNameRef};
 use libafl_bolts::{Error, Named, current_time, impl_serdeany};
-use parking_game::{BoardValue, State};
+use parking_game::{BoardValue, Direction, Position, State};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, BuildHasherDefault, DefaultHasher, Hash};
 use std::marker::PhantomData;
-use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
 
 /// Feedback which works out how far away obstacles are from each car, and which obstacles they are.
 pub struct ViewFeedback<T> {
@@ -28,6 +42,11 @@ impl<T> ViewFeedback<T> {
     pub fn new(obs: &ViewObserver<T>) -> Self {
         Self { obs: obs.handle() }
     }
+
+    /// Create a new [`ViewFeedback`] from a handle to the [`ViewObserver`].
+    pub fn with_handle(obs: Handle<ViewObserver<T>>) -> Self {
+        Self { obs }
+    }
 }
 
 impl<S, T> StateInitializer<S> for ViewFeedback<T> {}
@@ -46,12 +65,17 @@ pub struct ViewMetadata<T> {
 }
 
 impl<T> ViewMetadata<T> {
+    /// Create metadata holding the provided views, with the objective car's first.
+    pub fn new(views: Vec<ViewFrom<T>>) -> Self {
+        Self { views }
+    }
+
     /// An iterator over what's viewable by each car. The objective car will be the first entry.
-    pub fn views(&self) -> impl Iterator<Item = (NonZeroUsize, &ViewFrom<T>)> {
+    pub fn views(&self) -> impl Iterator<Item = (CarId, &ViewFrom<T>)> {
         self.views
             .iter()
             .enumerate()
-            .map(|(i, e)| (NonZeroUsize::new(i + 1).unwrap(), e))
+            .map(|(i, e)| (CarId::new(i + 1).unwrap(), e))
     }
 }
 
@@ -115,10 +139,20 @@ impl<T> Named for FinalStateFeedback<T> {
 /// Metadata which holds the final state of the board after an execution of the associated testcase.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FinalStateMetadata<T> {
+    #[serde(with = "crate::serialization::state_bytes")]
+    #[serde(bound(
+        serialize = "T: BoardValue + Serialize",
+        deserialize = "T: BoardValue + DeserializeOwned"
+    ))]
     state: State<T>,
 }
 
 impl<T> FinalStateMetadata<T> {
+    /// Create metadata holding the provided state.
+    pub fn new(state: State<T>) -> Self {
+        Self { state }
+    }
+
     /// The state contained within this metadata.
     pub fn state(&self) -> &State<T> {
         &self.state
@@ -154,16 +188,133 @@ where
     }
 }
 
+/// Feedback which attaches the final state reported by the [`FinalStateObserver`] to each testcase
+/// as [`FinalStateMetadata`], so that later stages may start from it rather than re-executing the
+/// input. Never considers an input interesting by itself.
+pub struct FinalStateMetadataFeedback<T> {
+    obs: Handle<FinalStateObserver<T>>,
+}
+
+impl<T> FinalStateMetadataFeedback<T> {
+    /// Create a [`FinalStateMetadataFeedback`] which will collect the final state from the provided
+    /// [`FinalStateObserver`].
+    pub fn new(obs: &FinalStateObserver<T>) -> Self {
+        Self::with_handle(obs.handle())
+    }
+
+    /// Create a [`FinalStateMetadataFeedback`] from a handle to the [`FinalStateObserver`], such as
+    /// those provided by [`crate::observers::ObserverBundle`].
+    pub fn with_handle(obs: Handle<FinalStateObserver<T>>) -> Self {
+        Self { obs }
+    }
+}
+
+impl<S, T> StateInitializer<S> for FinalStateMetadataFeedback<T> {}
+
+impl<T> Named for FinalStateMetadataFeedback<T> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_state_metadata_fb");
+        &NAME
+    }
+}
+
+impl<EM, OT, S, T> Feedback<EM, PGInput, OT, S> for FinalStateMetadataFeedback<T>
+where
+    OT: MatchNameRef,
+    T: BoardValue + DeserializeOwned + Serialize + 'static,
+{
+    fn is_interesting(
+        &mut self,
+        _state: &mut S,
+        _manager: &mut EM,
+        _input: &PGInput,
+        _observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        Ok(false)
+    }
+
+    fn append_metadata(
+        &mut self,
+        _state: &mut S,
+        _manager: &mut EM,
+        observers: &OT,
+        testcase: &mut Testcase<PGInput>,
+    ) -> Result<(), Error> {
+        // crashing inputs have no final state to attach
+        if let Some(state) = observers.get(&self.obs).unwrap().final_state() {
+            testcase.add_metadata(FinalStateMetadata {
+                state: state.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
 /// Feedback which interprets the view data from [`ViewObserver`] to determine if the board is in a
-/// solved state (i.e., when the objective vehicle sees the wall).
+/// solved state (i.e., when the objective vehicle sees the wall). Unless the exit is placed
+/// elsewhere with [`SolvedFeedback::with_map`], the wall is the one ahead of the objective car.
 pub struct SolvedFeedback<T> {
-    obs: Handle<ViewObserver<T>>,
+    obs: SolvedSource<T>,
+    lengths: Option<SolutionLengthTracker>,
+    exit_behind: bool,
+}
+
+/// The observer a [`SolvedFeedback`] learns whether the objective car sees the wall from.
+enum SolvedSource<T> {
+    Views(Handle<ViewObserver<T>>),
+    NearSolution(Handle<NearSolutionObserver>),
 }
 
 impl<T> SolvedFeedback<T> {
     /// Create a [`SolvedFeedback`] which will interpret the result from the [`ViewObserver`].
     pub fn new(obs: &ViewObserver<T>) -> Self {
-        Self { obs: obs.handle() }
+        Self::with_handle(obs.handle())
+    }
+
+    /// Create a [`SolvedFeedback`] from a handle to the [`ViewObserver`], such as those provided by
+    /// [`crate::observers::ObserverBundle`].
+    pub fn with_handle(obs: Handle<ViewObserver<T>>) -> Self {
+        Self {
+            obs: SolvedSource::Views(obs),
+            lengths: None,
+            exit_behind: false,
+        }
+    }
+
+    /// Create a [`SolvedFeedback`] which considers the board solved once the
+    /// [`NearSolutionObserver`] counts no cars in the path of the objective car. That observer
+    /// only looks ahead of the objective car and does not see walls, so
+    /// [`SolvedFeedback::with_map`] has no effect on this feedback.
+    pub fn from_near_solution(obs: &NearSolutionObserver) -> Self {
+        Self {
+            obs: SolvedSource::NearSolution(obs.handle()),
+            lengths: None,
+            exit_behind: false,
+        }
+    }
+
+    /// Considers the board solved when the objective car sees the wall at the exit described by
+    /// the provided map metadata, e.g., from [`crate::parse_map_with_options`].
+    pub fn with_map(mut self, map: &MapMetadata<T>) -> Self {
+        self.exit_behind = matches!(map.exit_direction(), Direction::Left | Direction::Up);
+        self
+    }
+
+    /// Records the length of each solution found with the provided tracker, e.g., the one from a
+    /// [`crate::observers::SolutionLengthObserver`].
+    pub fn with_length_tracker(mut self, tracker: SolutionLengthTracker) -> Self {
+        self.lengths = Some(tracker);
+        self
+    }
+
+    /// The view from the objective car toward the exit, which is solved once it sees no car.
+    fn exit_view<'a>(&self, view: &'a ViewFrom<T>) -> &'a View<T> {
+        if self.exit_behind {
+            view.backward()
+        } else {
+            view.forward()
+        }
     }
 }
 
@@ -184,198 +335,1316 @@ where
         &mut self,
         _state: &mut S,
         _manager: &mut EM,
-        _input: &PGInput,
+        input: &PGInput,
         observers: &OT,
         _exit_kind: &ExitKind,
     ) -> Result<bool, Error> {
         // "the objective car sees the wall ahead of it" (i.e., no car between us and wall)
-        Ok(observers
-            .get(&self.obs)
-            .unwrap()
-            .views()
-            .next()
-            .unwrap() // hint: crashed on this line? your feedback in main.rs is wrong!
-            .1
-            .forward()
-            .observed()
-            .is_none())
+        let solved = match &self.obs {
+            SolvedSource::Views(obs) => {
+                let view = observers
+                    .get(obs)
+                    .unwrap()
+                    .views()
+                    .next()
+                    .unwrap() // hint: crashed on this line? your feedback in main.rs is wrong!
+                    .1;
+                self.exit_view(view).observed().is_none()
+            }
+            SolvedSource::NearSolution(obs) => {
+                observers.get(obs).unwrap().blockers_in_path() == Some(0)
+            }
+        };
+        if solved && let Some(lengths) = &self.lengths {
+            lengths.record(input.len());
+        }
+        Ok(solved)
     }
 }
 
-/// Feedback which measures and reports the crash rate of the executor.
-pub struct CrashRateFeedback;
+/// Feedback which considers an input interesting if it made a transition between states which no
+/// earlier execution made, as reported by the [`TransitionCountObserver`].
+pub struct StateTransitionFeedback {
+    obs: Handle<TransitionCountObserver>,
+}
 
-/// Metadata which tracks the crash rate of the fuzzer.
-///
-/// TODO(pt.1): add the necessary `#[derive(...)]` statements for metadata.
-pub struct CrashRateMetadata {
-    // TODO(pt.1): what fields do we need to track in the metadata?
-    //  - hint: do this while implementing CrashRateFeedback::is_interesting
+impl StateTransitionFeedback {
+    /// Create a [`StateTransitionFeedback`] which will interpret the result from the
+    /// [`TransitionCountObserver`].
+    pub fn new(obs: &TransitionCountObserver) -> Self {
+        Self { obs: obs.handle() }
+    }
 }
 
-// TODO(pt.1): other implementation details needed for the metadata
+impl<S> StateInitializer<S> for StateTransitionFeedback {}
 
-impl<S> StateInitializer<S> for CrashRateFeedback
+impl Named for StateTransitionFeedback {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_transition_fb");
+        &NAME
+    }
+}
+
+impl<EM, OT, S> Feedback<EM, PGInput, OT, S> for StateTransitionFeedback
 where
-    S:, // TODO(pt.1): what traits do we require on the state (S)?
+    OT: MatchNameRef,
 {
-    fn init_state(&mut self, state: &mut S) -> Result<(), Error> {
-        todo!("(pt.1) add a default CrashRateMetadata to the state")
+    fn is_interesting(
+        &mut self,
+        _state: &mut S,
+        _manager: &mut EM,
+        _input: &PGInput,
+        observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        // the observer has already added this execution's transitions to the metadata
+        Ok(observers.get(&self.obs).unwrap().new_transitions() > 0)
     }
 }
 
-impl Named for CrashRateFeedback {
+/// Feedback which considers an input interesting if it moved a car onto any cell of the board which
+/// no earlier execution did. This is reported by a [`CoverageMapObserver`], or worked out from the
+/// cells counted by a [`HeatmapObserver`], which are merged into the [`CoverageMap`] in the
+/// fuzzer's metadata.
+pub struct CoverageGrowthFeedback<T> {
+    obs: CoverageSource<T>,
+}
+
+/// The observer a [`CoverageGrowthFeedback`] learns the visited cells from.
+enum CoverageSource<T> {
+    CoverageMap(Handle<CoverageMapObserver<T>>),
+    Heatmap(Handle<HeatmapObserver>),
+}
+
+impl<T> CoverageGrowthFeedback<T> {
+    /// Create a [`CoverageGrowthFeedback`] which will interpret the result from the
+    /// [`CoverageMapObserver`].
+    pub fn new(obs: &CoverageMapObserver<T>) -> Self {
+        Self {
+            obs: CoverageSource::CoverageMap(obs.handle()),
+        }
+    }
+
+    /// Create a [`CoverageGrowthFeedback`] which will interpret the result from the
+    /// [`HeatmapObserver`], keeping the cells visited so far in the fuzzer's metadata itself.
+    pub fn from_heatmap(obs: &HeatmapObserver) -> Self {
+        Self {
+            obs: CoverageSource::Heatmap(obs.handle()),
+        }
+    }
+}
+
+impl<S, T> StateInitializer<S> for CoverageGrowthFeedback<T> {}
+
+impl<T> Named for CoverageGrowthFeedback<T> {
     fn name(&self) -> &Cow<'static, str> {
-        todo!("(pt.1) give the feedback an appropriate name")
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_coverage_growth_fb");
+        &NAME
     }
 }
 
-impl<EM, I, OT, S> Feedback<EM, I, OT, S> for CrashRateFeedback
+impl<EM, OT, S, T> Feedback<EM, PGInput, OT, S> for CoverageGrowthFeedback<T>
 where
-    EM: EventFirer<I, S>,
-    S:, // TODO(pt.1) what traits do we require on the state (S)?
+    OT: MatchNameRef,
+    S: HasMetadata,
+    T: BoardValue + DeserializeOwned + Serialize + 'static,
 {
     fn is_interesting(
         &mut self,
         state: &mut S,
         _manager: &mut EM,
-        _input: &I,
-        _observers: &OT,
-        exit_kind: &ExitKind,
+        _input: &PGInput,
+        observers: &OT,
+        _exit_kind: &ExitKind,
     ) -> Result<bool, Error> {
-        // TODO(pt.1) update the number of crashes observed so far
-        //  - get a mutable reference to the CrashRateMetadata from the state
-        //    - hint: you may need a turbofish: https://turbo.fish/
-        //  - update the number of crashes in metadata so far by checking exit_kind
-
-        Ok(false)
+        match &self.obs {
+            // the observer has already merged this execution's cells into the coverage map
+            CoverageSource::CoverageMap(obs) => Ok(observers.get(obs).unwrap().new_cells() > 0),
+            CoverageSource::Heatmap(obs) => {
+                let heatmap = observers.get(obs).unwrap().heatmap();
+                // no cells are counted if no moves were made
+                if heatmap.is_empty() {
+                    return Ok(false);
+                }
+                let coverage = CoverageMap::<T>::from_heatmap(heatmap);
+                Ok(coverage.merge_into_metadata(state) > 0)
+            }
+        }
     }
+}
 
-    fn append_metadata(
-        &mut self,
-        state: &mut S,
-        manager: &mut EM,
-        _observers: &OT,
-        _testcase: &mut Testcase<I>,
-    ) -> Result<(), Error> {
-        // TODO(pt.1) get the crash metadata and execution counts
-        let crashes = 0;
-        let executions = 0;
+/// Metadata which records how many cars stood between the objective car and the wall after an
+/// execution of the associated testcase.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct NearSolutionMetadata {
+    blockers: usize,
+}
 
-        manager.fire(
-            state,
-            EventWithStats::new(
-                Event::UpdateUserStats {
-                    name: self.name().clone(),
-                    value: UserStats::new(
-                        todo!("(pt.1) report the ratio of crashes to executions"),
-                        AggregatorOps::Avg, // if aggregated, report the average number
-                    ),
-                    phantom: PhantomData,
-                },
-                ExecStats::new(current_time(), executions),
-            ),
-        )?;
+impl NearSolutionMetadata {
+    /// Create metadata recording the provided number of blockers.
+    pub fn new(blockers: usize) -> Self {
+        Self { blockers }
+    }
 
-        Ok(())
+    /// The number of cars in the path of the objective car; zero when the board is solved.
+    pub fn blockers(&self) -> usize {
+        self.blockers
     }
 }
 
-#[cfg(test)]
-mod test {
-    use crate::feedbacks::SolvedFeedback;
-    use crate::input::PGInput;
-    use crate::observers::{PGObserverTuple, View, ViewObserver};
-    use libafl::events::SimpleEventManager;
-    use libafl::executors::ExitKind;
-    use libafl::feedbacks::Feedback;
-    use libafl::observers::ObserversTuple;
-    use libafl::state::NopState;
-    use libafl_bolts::tuples::tuple_list;
-    use parking_game::Direction;
-    use std::error::Error;
-
-    #[test]
-    fn simple_solved() -> Result<(), Box<dyn Error>> {
-        let initial = crate::parse_map::<u8>("oo...");
-        let obs = ViewObserver::<u8>::default();
-        let mut solved = SolvedFeedback::new(&obs);
+impl_serdeany!(NearSolutionMetadata);
 
-        let mut observers = tuple_list!(obs);
+/// Metadata which holds the final position of each car over every execution seen by
+/// [`FinalPositionFeedback`], as pairs of the car's index (its id, less one) and position hash.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct FinalPositionMetadata {
+    positions: HashSet<(usize, u64)>,
+}
 
-        let mut state = NopState::<PGInput>::new();
-        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+impl FinalPositionMetadata {
+    /// The final positions seen so far.
+    pub fn positions(&self) -> &HashSet<(usize, u64)> {
+        &self.positions
+    }
+}
 
-        let nop_input = PGInput::new(vec![]);
-        observers.pre_exec_all(&mut state, &nop_input)?;
-        observers.final_board_all(&initial.board()?);
-        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+impl_serdeany!(FinalPositionMetadata);
 
-        assert!(solved.is_interesting(
-            &mut state,
-            &mut mgr,
-            &nop_input,
-            &observers,
-            &ExitKind::Ok
-        )?);
+/// Feedback which considers an input interesting if any car ends up somewhere it has never ended up
+/// before, as reported by the [`FinalStateObserver`]. This is finer-grained than hashing the whole
+/// board, as moving a single car somewhere new is rewarded regardless of where the others are.
+pub struct FinalPositionFeedback<T> {
+    obs: Handle<FinalStateObserver<T>>,
+}
 
-        Ok(())
+impl<T> FinalPositionFeedback<T> {
+    /// Create a [`FinalPositionFeedback`] which will interpret the result from the
+    /// [`FinalStateObserver`].
+    pub fn new(obs: &FinalStateObserver<T>) -> Self {
+        Self { obs: obs.handle() }
     }
+}
 
-    #[test]
-    fn simple_unsolved() -> Result<(), Box<dyn Error>> {
-        let initial = crate::parse_map::<u8>("oo11.");
-        let obs = ViewObserver::<u8>::default();
-        let mut solved = SolvedFeedback::new(&obs);
-
-        let mut observers = tuple_list!(obs);
-
-        let mut state = NopState::<PGInput>::new();
-        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+/// The hash identifying a position; the row and column are hashed separately.
+fn position_hash<T: Hash>(position: &Position<T>) -> u64 {
+    // the default hasher is unseeded, so the hashes are the same between runs
+    let hasher = BuildHasherDefault::<DefaultHasher>::default();
+    hasher.hash_one(position.row()) ^ hasher.hash_one(position.column())
+}
 
-        let nop_input = PGInput::new(vec![]);
-        observers.pre_exec_all(&mut state, &nop_input)?;
-        observers.final_board_all(&initial.board()?);
-        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+impl<S, T> StateInitializer<S> for FinalPositionFeedback<T> {}
 
-        assert!(!solved.is_interesting(
-            &mut state,
-            &mut mgr,
-            &nop_input,
-            &observers,
-            &ExitKind::Ok
-        )?);
+impl<T> Named for FinalPositionFeedback<T> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_final_position_fb");
+        &NAME
+    }
+}
 
-        Ok(())
+impl<EM, OT, S, T> Feedback<EM, PGInput, OT, S> for FinalPositionFeedback<T>
+where
+    OT: MatchNameRef,
+    S: HasMetadata,
+    T: Hash,
+{
+    fn is_interesting(
+        &mut self,
+        state: &mut S,
+        _manager: &mut EM,
+        _input: &PGInput,
+        observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        let Some(final_state) = observers.get(&self.obs).unwrap().final_state() else {
+            return Ok(false);
+        };
+        let seen = state.metadata_or_insert_with(FinalPositionMetadata::default);
+        let mut interesting = false;
+        for (i, (position, _)) in final_state.cars().iter().enumerate() {
+            interesting |= seen.positions.insert((i, position_hash(position)));
+        }
+        Ok(interesting)
     }
+}
 
-    #[test]
-    fn example_observation() -> Result<(), Box<dyn Error>> {
-        let initial = crate::parse_map::<u8>(
-            r#"
-        ......
-        ......
-        ...2oo
-        .332.4
-        .5.2.4
-        .5.664
-        "#,
-        );
-        let obs = ViewObserver::<u8>::default();
-        let mut solved = SolvedFeedback::new(&obs);
+/// Feedback which rejects inputs that move any one car more than a budget of times, so that the
+/// corpus is not filled with inputs which shuffle a single car back and forth. Every other input is
+/// considered interesting, so this is meant to be combined with other feedbacks via
+/// [`libafl::feedback_and`]. Only the moves of the input are inspected; nothing is observed.
+pub struct MovementBudgetFeedback {
+    max_per_car: usize,
+}
 
-        let mut observers = tuple_list!(obs);
+impl MovementBudgetFeedback {
+    /// The budget used by [`MovementBudgetFeedback::default`].
+    pub const DEFAULT_MAX_PER_CAR: usize = 20;
 
-        let mut state = NopState::<PGInput>::new();
-        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+    /// Create a [`MovementBudgetFeedback`] which rejects inputs moving any car more than
+    /// `max_per_car` times.
+    pub fn new(max_per_car: usize) -> Self {
+        Self { max_per_car }
+    }
 
-        let nop_input = PGInput::new(vec![]);
-        observers.pre_exec_all(&mut state, &nop_input)?;
-        observers.final_board_all(&initial.board()?);
-        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+    /// The most times any one car may be moved by an input.
+    pub fn max_per_car(&self) -> usize {
+        self.max_per_car
+    }
+}
 
-        let (_car, seen) = observers.0.views().next().unwrap();
-        assert_eq!(*seen.forward(), View::new(Direction::Right, None, 0));
+impl Default for MovementBudgetFeedback {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_PER_CAR)
+    }
+}
+
+impl<S> StateInitializer<S> for MovementBudgetFeedback {}
+
+impl Named for MovementBudgetFeedback {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_movement_budget_fb");
+        &NAME
+    }
+}
+
+impl<EM, OT, S> Feedback<EM, PGInput, OT, S> for MovementBudgetFeedback {
+    fn is_interesting(
+        &mut self,
+        _state: &mut S,
+        _manager: &mut EM,
+        input: &PGInput,
+        _observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        Ok(input
+            .car_histogram()
+            .values()
+            .all(|&count| count <= self.max_per_car))
+    }
+}
+
+/// Metadata which records the best diversity score of any input seen by [`DiversityFeedback`].
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize)]
+pub struct DiversityMetadata {
+    best_score: f64,
+}
+
+impl DiversityMetadata {
+    /// The highest proportion of distinct moves in any input so far.
+    pub fn best_score(&self) -> f64 {
+        self.best_score
+    }
+}
+
+impl_serdeany!(DiversityMetadata);
+
+/// Feedback which considers an input interesting if it repeats its moves less than any input before
+/// it, i.e., if it has the highest proportion of distinct moves (per the
+/// [`MoveRepetitionObserver`]) seen so far.
+pub struct DiversityFeedback {
+    obs: Handle<MoveRepetitionObserver>,
+}
+
+impl DiversityFeedback {
+    /// Create a [`DiversityFeedback`] which will interpret the result from the
+    /// [`MoveRepetitionObserver`].
+    pub fn new(obs: &MoveRepetitionObserver) -> Self {
+        Self { obs: obs.handle() }
+    }
+}
+
+impl<S> StateInitializer<S> for DiversityFeedback {}
+
+impl Named for DiversityFeedback {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_diversity_fb");
+        &NAME
+    }
+}
+
+impl<EM, OT, S> Feedback<EM, PGInput, OT, S> for DiversityFeedback
+where
+    OT: MatchNameRef,
+    S: HasMetadata,
+{
+    fn is_interesting(
+        &mut self,
+        state: &mut S,
+        _manager: &mut EM,
+        input: &PGInput,
+        observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        let Some(score) = self.score(input, observers) else {
+            return Ok(false);
+        };
+        let best = state.metadata_or_insert_with(DiversityMetadata::default);
+        if score > best.best_score {
+            best.best_score = score;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+}
+
+impl<OT> ScoredFeedback<OT> for DiversityFeedback
+where
+    OT: MatchNameRef,
+{
+    /// The proportion of distinct moves in the input, or [`None`] if it has no moves.
+    fn score(&self, _input: &PGInput, observers: &OT) -> Option<f64> {
+        let obs = observers.get(&self.obs).unwrap();
+        (obs.total_moves() > 0).then(|| obs.unique_moves() as f64 / obs.total_moves() as f64)
+    }
+}
+
+/// Metadata which holds the cars which [`CarUnlockedFeedback`] has seen unlocked.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct UnlockedCarsMetadata {
+    unlocked: HashSet<CarId>,
+}
+
+impl UnlockedCarsMetadata {
+    /// The cars seen moving after being unable to move in either direction.
+    pub fn unlocked(&self) -> &HashSet<CarId> {
+        &self.unlocked
+    }
+}
+
+impl_serdeany!(UnlockedCarsMetadata);
+
+/// Feedback which considers an input interesting the first time it moves a car which could not move
+/// in either direction in the testcase it was derived from, per that testcase's [`ViewMetadata`]
+/// (see [`ViewFeedback`]) and the [`CarMovementObserver`]. Each car is only rewarded once.
+pub struct CarUnlockedFeedback<T> {
+    obs: Handle<CarMovementObserver>,
+    phantom: PhantomData<T>,
+}
+
+impl<T> CarUnlockedFeedback<T> {
+    /// Create a [`CarUnlockedFeedback`] which will interpret the result from the
+    /// [`CarMovementObserver`].
+    pub fn new(obs: &CarMovementObserver) -> Self {
+        Self {
+            obs: obs.handle(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, T> StateInitializer<S> for CarUnlockedFeedback<T> {}
+
+impl<T> Named for CarUnlockedFeedback<T> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_car_unlocked_fb");
+        &NAME
+    }
+}
+
+impl<EM, OT, S, T> Feedback<EM, PGInput, OT, S> for CarUnlockedFeedback<T>
+where
+    OT: MatchNameRef,
+    S: HasCurrentTestcase<PGInput> + HasMetadata,
+    T: BoardValue + DeserializeOwned + Serialize + 'static,
+{
+    fn is_interesting(
+        &mut self,
+        state: &mut S,
+        _manager: &mut EM,
+        _input: &PGInput,
+        observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        // inputs not derived from a testcase (e.g., the seeds) have nothing to compare to
+        let Ok(parent) = state.current_testcase() else {
+            return Ok(false);
+        };
+        let Ok(views) = parent.metadata::<ViewMetadata<T>>() else {
+            return Ok(false);
+        };
+        let moved = observers.get(&self.obs).unwrap();
+        let unlocked = views
+            .views()
+            .filter(|(car, view)| view.is_completely_blocked() && moved.moved(*car))
+            .map(|(car, _)| car)
+            .collect::<Vec<_>>();
+        drop(parent);
+
+        let seen = state.metadata_or_insert_with(UnlockedCarsMetadata::default);
+        let mut interesting = false;
+        for car in unlocked {
+            interesting |= seen.unlocked.insert(car);
+        }
+        Ok(interesting)
+    }
+}
+
+/// Metadata which records the fewest cars between the objective car and the wall seen by
+/// [`NearSolutionFeedback`] in any execution so far.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct BestBlockersMetadata {
+    best_blockers: usize,
+}
+
+impl BestBlockersMetadata {
+    /// The fewest cars seen in the path of the objective car, or [`usize::MAX`] if none have been
+    /// counted yet.
+    pub fn best_blockers(&self) -> usize {
+        self.best_blockers
+    }
+}
+
+impl Default for BestBlockersMetadata {
+    fn default() -> Self {
+        Self {
+            best_blockers: usize::MAX,
+        }
+    }
+}
+
+impl_serdeany!(BestBlockersMetadata);
+
+/// Feedback which considers an input interesting if it leaves fewer cars in the path of the
+/// objective car than any execution before it, as reported by the [`NearSolutionObserver`]. The
+/// count is also attached to each testcase as [`NearSolutionMetadata`], for use by
+/// [`crate::schedulers::PGPriorityScheduler`].
+pub struct NearSolutionFeedback {
+    obs: Handle<NearSolutionObserver>,
+}
+
+impl NearSolutionFeedback {
+    /// Create a [`NearSolutionFeedback`] which will interpret the result from the
+    /// [`NearSolutionObserver`].
+    pub fn new(obs: &NearSolutionObserver) -> Self {
+        Self { obs: obs.handle() }
+    }
+}
+
+impl<S> StateInitializer<S> for NearSolutionFeedback {}
+
+impl Named for NearSolutionFeedback {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_near_solution_fb");
+        &NAME
+    }
+}
+
+impl<EM, OT, S> Feedback<EM, PGInput, OT, S> for NearSolutionFeedback
+where
+    OT: MatchNameRef,
+    S: HasMetadata,
+{
+    fn is_interesting(
+        &mut self,
+        state: &mut S,
+        _manager: &mut EM,
+        _input: &PGInput,
+        observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        let Some(blockers) = observers.get(&self.obs).unwrap().blockers_in_path() else {
+            return Ok(false);
+        };
+        let best = state.metadata_or_insert_with(BestBlockersMetadata::default);
+        if blockers < best.best_blockers {
+            best.best_blockers = blockers;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn append_metadata(
+        &mut self,
+        _state: &mut S,
+        _manager: &mut EM,
+        observers: &OT,
+        testcase: &mut Testcase<PGInput>,
+    ) -> Result<(), Error> {
+        if let Some(blockers) = observers.get(&self.obs).unwrap().blockers_in_path() {
+            testcase.add_metadata(NearSolutionMetadata::new(blockers));
+        }
+        Ok(())
+    }
+}
+
+impl<OT> ScoredFeedback<OT> for NearSolutionFeedback
+where
+    OT: MatchNameRef,
+{
+    /// The number of cars in the path of the objective car, negated so that fewer scores higher.
+    fn score(&self, _input: &PGInput, observers: &OT) -> Option<f64> {
+        let blockers = observers.get(&self.obs).unwrap().blockers_in_path()?;
+        Some(-(blockers as f64))
+    }
+}
+
+/// Metadata which records the [`crate::analysis::heuristic_score`] of a testcase: higher is closer
+/// to a solution.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct HeuristicScoreMetadata {
+    /// The score of the testcase.
+    pub score: f64,
+}
+
+impl_serdeany!(HeuristicScoreMetadata);
+
+/// Feedback which attaches [`HeuristicScoreMetadata`] to each testcase added to the corpus, for use
+/// by [`crate::schedulers::PGPriorityScheduler`]. The score is computed from the [`ViewMetadata`]
+/// and [`NearSolutionMetadata`] already attached to the testcase, so this must follow the
+/// [`ViewFeedback`] and [`NearSolutionFeedback`] when combined with them; testcases lacking either
+/// are not scored. This never considers an input interesting by itself.
+pub struct ScoringFeedback<T> {
+    weights: HeuristicWeights,
+    phantom: PhantomData<T>,
+}
+
+impl<T> ScoringFeedback<T> {
+    /// Create a [`ScoringFeedback`] which scores with the default [`HeuristicWeights`].
+    pub fn new() -> Self {
+        Self::with_weights(HeuristicWeights::default())
+    }
+
+    /// Create a [`ScoringFeedback`] which scores with the provided weights.
+    pub fn with_weights(weights: HeuristicWeights) -> Self {
+        Self {
+            weights,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ScoringFeedback<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, T> StateInitializer<S> for ScoringFeedback<T> {}
+
+impl<T> Named for ScoringFeedback<T> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_scoring_fb");
+        &NAME
+    }
+}
+
+impl<EM, OT, S, T> Feedback<EM, PGInput, OT, S> for ScoringFeedback<T>
+where
+    T: BoardValue + DeserializeOwned + Serialize + 'static,
+{
+    fn is_interesting(
+        &mut self,
+        _state: &mut S,
+        _manager: &mut EM,
+        _input: &PGInput,
+        _observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        Ok(false)
+    }
+
+    fn append_metadata(
+        &mut self,
+        _state: &mut S,
+        _manager: &mut EM,
+        _observers: &OT,
+        testcase: &mut Testcase<PGInput>,
+    ) -> Result<(), Error> {
+        let (Some(input), Ok(views), Ok(near)) = (
+            testcase.input().as_ref(),
+            testcase.metadata::<ViewMetadata<T>>(),
+            testcase.metadata::<NearSolutionMetadata>(),
+        ) else {
+            return Ok(());
+        };
+        let score = heuristic_score_with(&self.weights, input, views, near);
+        testcase.add_metadata(HeuristicScoreMetadata { score });
+        Ok(())
+    }
+}
+
+/// Metadata which records the last input on which the executors of a [`DifferentialFeedback`]
+/// disagreed, with the hashes of the final states they reached. An executor which did not finish
+/// the input has a hash of 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DivergenceMetadata {
+    /// The hash of the moves of the input.
+    pub input_hash: u64,
+    /// The hash of the final state reached by the first executor.
+    pub executor1_hash: u64,
+    /// The hash of the final state reached by the second executor.
+    pub executor2_hash: u64,
+}
+
+impl_serdeany!(DivergenceMetadata);
+
+/// Feedback which runs each input on two executors of its own which should agree, e.g., an
+/// experimental executor and a [`crate::executor::PGExecutor`] as a reference, and considers the
+/// input interesting if the final states they reach differ, as compared by the hashes of their
+/// [`FinalStateObserver`]s. The executors are run outside of the fuzzer's state, so they do not
+/// count towards its executions. The last divergence found is kept in the fuzzer's metadata as a
+/// [`DivergenceMetadata`].
+pub struct DifferentialFeedback<E1, E2, T> {
+    first: E1,
+    second: E2,
+    obs: Handle<FinalStateObserver<T>>,
+}
+
+impl<E1, E2, T> DifferentialFeedback<E1, E2, T> {
+    /// Create a [`DifferentialFeedback`] which compares the provided executors, each of which must
+    /// observe with a [`FinalStateObserver`].
+    pub fn new(first: E1, second: E2) -> Self {
+        Self {
+            first,
+            second,
+            obs: FinalStateObserver::<T>::default().handle(),
+        }
+    }
+
+    /// The first executor compared.
+    pub fn first(&self) -> &E1 {
+        &self.first
+    }
+
+    /// The second executor compared.
+    pub fn second(&self) -> &E2 {
+        &self.second
+    }
+}
+
+impl<E1, E2, S, T> StateInitializer<S> for DifferentialFeedback<E1, E2, T> {}
+
+impl<E1, E2, T> Named for DifferentialFeedback<E1, E2, T> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_differential_fb");
+        &NAME
+    }
+}
+
+/// Runs the input on the executor with a throwaway state, returning the hash of the final state
+/// observed, if any.
+fn final_state_hash<E, EM, T>(
+    executor: &mut E,
+    manager: &mut EM,
+    input: &PGInput,
+    obs: &Handle<FinalStateObserver<T>>,
+) -> Result<Option<u64>, Error>
+where
+    E: Executor<EM, PGInput, NopState<PGInput>, NopFuzzer> + HasObservers,
+    E::Observers: ObserversTuple<PGInput, NopState<PGInput>> + MatchNameRef,
+    T: BoardValue,
+{
+    let mut state = NopState::new();
+    executor.observers_mut().pre_exec_all(&mut state, input)?;
+    let kind = executor.run_target(&mut NopFuzzer::new(), &mut state, manager, input)?;
+    executor
+        .observers_mut()
+        .post_exec_all(&mut state, input, &kind)?;
+    Ok(executor.observers().get(obs).and_then(|obs| obs.hash()))
+}
+
+impl<E1, E2, EM, OT, S, T> Feedback<EM, PGInput, OT, S> for DifferentialFeedback<E1, E2, T>
+where
+    E1: Executor<EM, PGInput, NopState<PGInput>, NopFuzzer> + HasObservers,
+    E1::Observers: ObserversTuple<PGInput, NopState<PGInput>> + MatchNameRef,
+    E2: Executor<EM, PGInput, NopState<PGInput>, NopFuzzer> + HasObservers,
+    E2::Observers: ObserversTuple<PGInput, NopState<PGInput>> + MatchNameRef,
+    S: HasMetadata,
+    T: BoardValue,
+{
+    fn is_interesting(
+        &mut self,
+        state: &mut S,
+        manager: &mut EM,
+        input: &PGInput,
+        _observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        let first = final_state_hash(&mut self.first, manager, input, &self.obs)?;
+        let second = final_state_hash(&mut self.second, manager, input, &self.obs)?;
+        if first == second {
+            return Ok(false);
+        }
+        state.add_metadata(DivergenceMetadata {
+            input_hash: hash_input(input),
+            executor1_hash: first.unwrap_or(0),
+            executor2_hash: second.unwrap_or(0),
+        });
+        Ok(true)
+    }
+}
+
+/// A feedback which can score the inputs it inspects, higher being better, so that it can be
+/// weighed against other objectives by a [`MultiObjectiveFeedback`].
+pub trait ScoredFeedback<OT> {
+    /// The score of the provided input, as observed by the observers of its execution, or [`None`]
+    /// if it cannot be scored.
+    fn score(&self, input: &PGInput, observers: &OT) -> Option<f64>;
+}
+
+/// Metadata which holds the Pareto front of the scores seen by a [`MultiObjectiveFeedback`]: the
+/// pairs of scores which no other pair seen is at least as good as in both objectives.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct ParetoFrontMetadata {
+    points: Vec<(f64, f64)>,
+}
+
+impl ParetoFrontMetadata {
+    /// Whether the scores `a` are at least as good as the scores `b` in both objectives.
+    pub fn dominates(a: (f64, f64), b: (f64, f64)) -> bool {
+        a.0 >= b.0 && a.1 >= b.1
+    }
+
+    /// The pairs of scores on the front, in the order they were added.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Add the provided scores to the front, removing every point they dominate, unless a point
+    /// already on the front dominates them. Returns whether they were added.
+    pub fn insert(&mut self, point: (f64, f64)) -> bool {
+        if self
+            .points
+            .iter()
+            .any(|&other| Self::dominates(other, point))
+        {
+            return false;
+        }
+        self.points.retain(|&other| !Self::dominates(point, other));
+        self.points.push(point);
+        true
+    }
+}
+
+impl_serdeany!(ParetoFrontMetadata);
+
+/// Feedback which weighs the scores of two [`ScoredFeedback`]s against each other, considering an
+/// input interesting if no input before it scored at least as well in both, per the
+/// [`ParetoFrontMetadata`] kept in the fuzzer's metadata. Only the scores of the wrapped feedbacks
+/// are used, not whether they would consider the input interesting themselves, and inputs which
+/// either cannot score are never interesting. Both still attach their metadata to testcases.
+pub struct MultiObjectiveFeedback<F1, F2> {
+    first: F1,
+    second: F2,
+}
+
+impl<F1, F2> MultiObjectiveFeedback<F1, F2> {
+    /// Create a [`MultiObjectiveFeedback`] which weighs the scores of the provided feedbacks.
+    pub fn new(first: F1, second: F2) -> Self {
+        Self { first, second }
+    }
+
+    /// The feedback scoring the first objective.
+    pub fn first(&self) -> &F1 {
+        &self.first
+    }
+
+    /// The feedback scoring the second objective.
+    pub fn second(&self) -> &F2 {
+        &self.second
+    }
+}
+
+impl<F1, F2, S> StateInitializer<S> for MultiObjectiveFeedback<F1, F2>
+where
+    F1: StateInitializer<S>,
+    F2: StateInitializer<S>,
+{
+    fn init_state(&mut self, state: &mut S) -> Result<(), Error> {
+        self.first.init_state(state)?;
+        self.second.init_state(state)
+    }
+}
+
+impl<F1, F2> Named for MultiObjectiveFeedback<F1, F2> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_multi_objective_fb");
+        &NAME
+    }
+}
+
+impl<EM, F1, F2, OT, S> Feedback<EM, PGInput, OT, S> for MultiObjectiveFeedback<F1, F2>
+where
+    F1: Feedback<EM, PGInput, OT, S> + ScoredFeedback<OT>,
+    F2: Feedback<EM, PGInput, OT, S> + ScoredFeedback<OT>,
+    S: HasMetadata,
+{
+    fn is_interesting(
+        &mut self,
+        state: &mut S,
+        _manager: &mut EM,
+        input: &PGInput,
+        observers: &OT,
+        _exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        let (Some(first), Some(second)) = (
+            self.first.score(input, observers),
+            self.second.score(input, observers),
+        ) else {
+            return Ok(false);
+        };
+        Ok(state
+            .metadata_or_insert_with(ParetoFrontMetadata::default)
+            .insert((first, second)))
+    }
+
+    fn append_metadata(
+        &mut self,
+        state: &mut S,
+        manager: &mut EM,
+        observers: &OT,
+        testcase: &mut Testcase<PGInput>,
+    ) -> Result<(), Error> {
+        self.first
+            .append_metadata(state, manager, observers, testcase)?;
+        self.second
+            .append_metadata(state, manager, observers, testcase)
+    }
+
+    fn discard_metadata(&mut self, state: &mut S, input: &PGInput) -> Result<(), Error> {
+        self.first.discard_metadata(state, input)?;
+        self.second.discard_metadata(state, input)
+    }
+}
+
+/// Feedback which wraps another to measure how long each call of its [`Feedback::is_interesting`]
+/// takes, e.g., to find which feedback slows the fuzzer down. Otherwise, it behaves exactly as the
+/// feedback it wraps.
+#[derive(Debug)]
+pub struct FeedbackMetrics<F> {
+    inner: F,
+    total_calls: u64,
+    total_nanos: u64,
+    max_nanos: u64,
+}
+
+impl<F> FeedbackMetrics<F> {
+    /// Create a [`FeedbackMetrics`] which measures the provided feedback.
+    pub fn new(inner: F) -> Self {
+        Self {
+            inner,
+            total_calls: 0,
+            total_nanos: 0,
+            max_nanos: 0,
+        }
+    }
+
+    /// The feedback being measured.
+    pub fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// The number of times the feedback was asked whether an input is interesting.
+    pub fn total_calls(&self) -> u64 {
+        self.total_calls
+    }
+
+    /// The average time taken to decide whether an input is interesting, or zero if the feedback
+    /// has not been asked yet.
+    pub fn avg_latency(&self) -> Duration {
+        Duration::from_nanos(self.total_nanos.checked_div(self.total_calls).unwrap_or(0))
+    }
+
+    /// The longest time taken to decide whether an input is interesting.
+    pub fn max_latency(&self) -> Duration {
+        Duration::from_nanos(self.max_nanos)
+    }
+}
+
+impl<F, S> StateInitializer<S> for FeedbackMetrics<F>
+where
+    F: StateInitializer<S>,
+{
+    fn init_state(&mut self, state: &mut S) -> Result<(), Error> {
+        self.inner.init_state(state)
+    }
+}
+
+impl<F> Named for FeedbackMetrics<F>
+where
+    F: Named,
+{
+    fn name(&self) -> &Cow<'static, str> {
+        self.inner.name()
+    }
+}
+
+impl<EM, F, I, OT, S> Feedback<EM, I, OT, S> for FeedbackMetrics<F>
+where
+    F: Feedback<EM, I, OT, S>,
+{
+    fn is_interesting(
+        &mut self,
+        state: &mut S,
+        manager: &mut EM,
+        input: &I,
+        observers: &OT,
+        exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        let start = Instant::now();
+        let interesting = self
+            .inner
+            .is_interesting(state, manager, input, observers, exit_kind);
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.total_calls += 1;
+        self.total_nanos = self.total_nanos.saturating_add(nanos);
+        self.max_nanos = self.max_nanos.max(nanos);
+        interesting
+    }
+
+    fn append_metadata(
+        &mut self,
+        state: &mut S,
+        manager: &mut EM,
+        observers: &OT,
+        testcase: &mut Testcase<I>,
+    ) -> Result<(), Error> {
+        self.inner
+            .append_metadata(state, manager, observers, testcase)
+    }
+
+    fn discard_metadata(&mut self, state: &mut S, input: &I) -> Result<(), Error> {
+        self.inner.discard_metadata(state, input)
+    }
+}
+
+/// Feedback which measures and reports the crash rate of the executor.
+pub struct CrashRateFeedback;
+
+/// Metadata which tracks the crash rate of the fuzzer.
+///
+/// TODO(pt.1): add the necessary `#[derive(...)]` statements for metadata.
+pub struct CrashRateMetadata {
+    // TODO(pt.1): what fields do we need to track in the metadata?
+    //  - hint: do this while implementing CrashRateFeedback::is_interesting
+}
+
+// TODO(pt.1): other implementation details needed for the metadata
+
+impl<S> StateInitializer<S> for CrashRateFeedback
+where
+    S:, // TODO(pt.1): what traits do we require on the state (S)?
+{
+    fn init_state(&mut self, state: &mut S) -> Result<(), Error> {
+        todo!("(pt.1) add a default CrashRateMetadata to the state")
+    }
+}
+
+impl Named for CrashRateFeedback {
+    fn name(&self) -> &Cow<'static, str> {
+        todo!("(pt.1) give the feedback an appropriate name")
+    }
+}
+
+impl<EM, I, OT, S> Feedback<EM, I, OT, S> for CrashRateFeedback
+where
+    EM: EventFirer<I, S>,
+    S:, // TODO(pt.1) what traits do we require on the state (S)?
+{
+    fn is_interesting(
+        &mut self,
+        state: &mut S,
+        _manager: &mut EM,
+        _input: &I,
+        _observers: &OT,
+        exit_kind: &ExitKind,
+    ) -> Result<bool, Error> {
+        // TODO(pt.1) update the number of crashes observed so far
+        //  - get a mutable reference to the CrashRateMetadata from the state
+        //    - hint: you may need a turbofish: https://turbo.fish/
+        //  - update the number of crashes in metadata so far by checking exit_kind
+
+        Ok(false)
+    }
+
+    fn append_metadata(
+        &mut self,
+        state: &mut S,
+        manager: &mut EM,
+        _observers: &OT,
+        _testcase: &mut Testcase<I>,
+    ) -> Result<(), Error> {
+        // TODO(pt.1) get the crash metadata and execution counts
+        let crashes = 0;
+        let executions = 0;
+
+        manager.fire(
+            state,
+            EventWithStats::new(
+                Event::UpdateUserStats {
+                    name: self.name().clone(),
+                    value: UserStats::new(
+                        todo!("(pt.1) report the ratio of crashes to executions"),
+                        AggregatorOps::Avg, // if aggregated, report the average number
+                    ),
+                    phantom: PhantomData,
+                },
+                ExecStats::new(current_time(), executions),
+            ),
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::MapParseOptions;
+    use crate::coverage::CoverageMap;
+    use crate::executor::PGExecutorBuilder;
+    use crate::feedbacks::{
+        BestBlockersMetadata, CarUnlockedFeedback, CoverageGrowthFeedback, DifferentialFeedback,
+        DivergenceMetadata, DiversityFeedback, DiversityMetadata, FeedbackMetrics,
+        FinalPositionFeedback, FinalPositionMetadata, FinalStateMetadata,
+        FinalStateMetadataFeedback, MovementBudgetFeedback, MultiObjectiveFeedback,
+        NearSolutionFeedback, NearSolutionMetadata, ParetoFrontMetadata, ScoredFeedback,
+        SolvedFeedback, StateTransitionFeedback, UnlockedCarsMetadata, ViewMetadata,
+    };
+    use crate::input::PGInput;
+    use crate::observers::{
+        CarMovementObserver, CoverageMapObserver, FinalStateObserver, HeatmapObserver,
+        MoveRepetitionObserver, NearSolutionObserver, ObserverBundle, PGObserver, PGObserverTuple,
+        SolutionLengthObserver, TransitionCountObserver, View, ViewFrom, ViewObserver, hash_state,
+    };
+    use crate::types::CarId;
+    use libafl::HasMetadata;
+    use libafl::NopFuzzer;
+    use libafl::corpus::{Corpus, InMemoryCorpus, Testcase};
+    use libafl::events::SimpleEventManager;
+    use libafl::executors::{Executor, ExitKind, HasObservers};
+    use libafl::feedbacks::{Feedback, StateInitializer};
+    use libafl::observers::{Observer, ObserversTuple};
+    use libafl::state::{HasCorpus, HasCurrentCorpusId, HasExecutions, NopState, StdState};
+    use libafl_bolts::Named;
+    use libafl_bolts::rands::StdRand;
+    use libafl_bolts::tuples::{Handled, MatchNameRef, tuple_list};
+    use parking_game::Direction;
+    use std::borrow::Cow;
+    use std::error::Error;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn simple_solved() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo...");
+        let obs = ViewObserver::<u8>::default();
+        let mut solved = SolvedFeedback::new(&obs);
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let nop_input = PGInput::new(vec![]);
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.final_board_all(&initial.board()?);
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+
+        assert!(solved.is_interesting(
+            &mut state,
+            &mut mgr,
+            &nop_input,
+            &observers,
+            &ExitKind::Ok
+        )?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn near_solution_solved() -> Result<(), Box<dyn Error>> {
+        let obs = NearSolutionObserver::default();
+        let mut solved = SolvedFeedback::<u8>::from_near_solution(&obs);
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+        let nop_input = PGInput::new(vec![]);
+
+        for (map, expected) in [("oo...", true), ("oo11.", false), ("oo.1\n...1", false)] {
+            let initial = crate::parse_map::<u8>(map);
+            observers.pre_exec_all(&mut state, &nop_input)?;
+            // an execution which did not complete is never solved
+            assert!(!solved.is_interesting(
+                &mut state,
+                &mut mgr,
+                &nop_input,
+                &observers,
+                &ExitKind::Ok
+            )?);
+            observers.final_board_all(&initial.board()?);
+            observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+            assert_eq!(
+                expected,
+                solved.is_interesting(
+                    &mut state,
+                    &mut mgr,
+                    &nop_input,
+                    &observers,
+                    &ExitKind::Ok
+                )?,
+                "{map}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn bundle_solved() -> Result<(), Box<dyn Error>> {
+        // solving through the bundle's view observer is covered by `simple_solved`
+        let initial = crate::parse_map::<u8>("oo...");
+        let bundle = ObserverBundle::<u8>::new();
+        let (view, final_state) = (bundle.view_handle(), bundle.final_handle());
+        let solved = SolvedFeedback::with_handle(bundle.view_handle());
+        assert_eq!("pg_solved", solved.name());
+
+        let (mut observers, view_part, final_part) = bundle.into_parts();
+        assert_eq!(view.name(), view_part.name());
+        assert_eq!(final_state.name(), final_part.name());
+        assert!(observers.get(&view).unwrap().views().next().is_none());
+        assert!(observers.get(&final_state).unwrap().final_state().is_none());
+
+        // the handles refer to the observers which are passed to the executor
+        let mut state = NopState::<PGInput>::new();
+        let nop_input = PGInput::new(vec![]);
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.1.0.final_board(&initial.board()?);
+        let observed = observers.get(&final_state).unwrap().final_state().unwrap();
+        assert_eq!(hash_state(&initial), hash_state(observed));
+
+        Ok(())
+    }
+
+    #[test]
+    fn solution_lengths() -> Result<(), Box<dyn Error>> {
+        let mut lengths = SolutionLengthObserver::default();
+        // the tracker a solved feedback records each solution with
+        let tracker = lengths.tracker();
+        let mut state = NopState::<PGInput>::new();
+
+        for (input, solved, expected) in [
+            ("1L 2L 3L", false, None),
+            ("1R 1R 1L 1L", true, Some(4)),
+            ("1R 1R 1L", true, Some(3)),
+            ("1R 1R 1L 1L 1R", true, Some(3)),
+        ] {
+            let input = input.parse::<PGInput>()?;
+            Observer::<PGInput, _>::pre_exec(&mut lengths, &mut state, &input)?;
+            if solved {
+                tracker.record(input.len());
+            }
+
+            assert_eq!(input.len(), lengths.last_length());
+            assert_eq!(expected, lengths.min_length_ever());
+            assert_eq!(expected, tracker.min_length());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn exit_position() {
+        let car = |id| CarId::new(id);
+        let left_right = |backward, forward| {
+            ViewFrom::new(
+                View::new(Direction::Left, backward, 0u8),
+                View::new(Direction::Right, forward, 1),
+            )
+        };
+        let up_down = |backward, forward| {
+            ViewFrom::new(
+                View::new(Direction::Up, backward, 0u8),
+                View::new(Direction::Down, forward, 0),
+            )
+        };
+
+        for (map, view, exit, expected) in [
+            // the objective car has reached the left edge, but is blocked from the right one
+            ("oo.11.", left_right(None, car(2)), Some((0, 0)), true),
+            ("oo.11.", left_right(None, car(2)), None, false),
+            ("oo.11.", left_right(None, car(2)), Some((0, 5)), false),
+            ("11.oo.", left_right(car(2), None), Some((0, 0)), false),
+            ("11.oo.", left_right(car(2), None), None, true),
+            // the same goes for the top and bottom of a car moving up and down
+            (".o\n.o\n.1\n.1", up_down(None, car(2)), Some((0, 1)), true),
+            (".o\n.o\n.1\n.1", up_down(None, car(2)), None, false),
+            (".o\n.o\n.1\n.1", up_down(None, car(2)), Some((3, 1)), false),
+        ] {
+            let (_, metadata) = crate::parse_map_with_options::<u8>(
+                map,
+                MapParseOptions {
+                    exit_position: exit.map(Into::into),
+                },
+            );
+            let handle = ViewObserver::<u8>::default().handle();
+            let solved = SolvedFeedback::with_handle(handle.clone()).with_map(&metadata);
+            assert_eq!(
+                expected,
+                solved.exit_view(&view).observed().is_none(),
+                "{map}"
+            );
+            // by default, the exit is where it always has been
+            if exit.is_none() {
+                let default = SolvedFeedback::with_handle(handle);
+                assert_eq!(expected, default.exit_view(&view).observed().is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn simple_unsolved() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo11.");
+        let obs = ViewObserver::<u8>::default();
+        let mut solved = SolvedFeedback::new(&obs);
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let nop_input = PGInput::new(vec![]);
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.final_board_all(&initial.board()?);
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+
+        assert!(!solved.is_interesting(
+            &mut state,
+            &mut mgr,
+            &nop_input,
+            &observers,
+            &ExitKind::Ok
+        )?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn example_observation() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>(
+            r#"
+        ......
+        ......
+        ...2oo
+        .332.4
+        .5.2.4
+        .5.664
+        "#,
+        );
+        let obs = ViewObserver::<u8>::default();
+        let mut solved = SolvedFeedback::new(&obs);
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let nop_input = PGInput::new(vec![]);
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.final_board_all(&initial.board()?);
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+
+        let (_car, seen) = observers.0.views().next().unwrap();
+        assert_eq!(*seen.forward(), View::new(Direction::Right, None, 0));
 
         assert!(solved.is_interesting(
             &mut state,
@@ -387,4 +1656,613 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn new_transitions() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("......\n.oo...\n......\n.1....");
+        let obs = TransitionCountObserver::default();
+        let mut transitions = StateTransitionFeedback::new(&obs);
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for (input, expected) in [("1R 2U", true), ("1R 2U", false), ("2U 1R", true)] {
+            let input = input.parse::<PGInput>()?;
+            let mut current = initial.clone();
+            observers.pre_exec_all(&mut state, &input)?;
+            for &(car, dir) in input.moves() {
+                let mut board = current.board_mut()?;
+                board.shift_car(car.as_nonzero(), dir)?;
+                observers.after_move_all(car, dir, &board);
+            }
+            observers.post_exec_all(&mut state, &input, &ExitKind::Ok)?;
+
+            assert_eq!(
+                expected,
+                transitions.is_interesting(
+                    &mut state,
+                    &mut mgr,
+                    &input,
+                    &observers,
+                    &ExitKind::Ok
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn coverage_growth() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("......\n.oo...\n......\n.1....");
+        let obs = CoverageMapObserver::<u8>::default();
+        let mut growth = CoverageGrowthFeedback::new(&obs);
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for (input, expected) in [
+            ("1R", true),
+            ("1R", false),
+            ("1R 1R", true),
+            // every cell on the way back has been visited already
+            ("1R 1R 1L", false),
+            ("", false),
+        ] {
+            let input = input.parse::<PGInput>()?;
+            let mut current = initial.clone();
+            observers.pre_exec_all(&mut state, &input)?;
+            for &(car, dir) in input.moves() {
+                let mut board = current.board_mut()?;
+                board.shift_car(car.as_nonzero(), dir)?;
+                observers.after_move_all(car, dir, &board);
+            }
+            observers.post_exec_all(&mut state, &input, &ExitKind::Ok)?;
+
+            assert_eq!(
+                expected,
+                growth.is_interesting(&mut state, &mut mgr, &input, &observers, &ExitKind::Ok)?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn coverage_growth_from_heatmap() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("......\n.oo...\n......\n.1....");
+        let obs = HeatmapObserver::default();
+        let mut growth = CoverageGrowthFeedback::<u8>::from_heatmap(&obs);
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for (input, expected) in [
+            ("1R", true),
+            ("1R", false),
+            ("1R 1R", true),
+            // every cell on the way back has been visited already
+            ("1R 1R 1L", false),
+            ("2U", true),
+            ("", false),
+        ] {
+            let input = input.parse::<PGInput>()?;
+            let mut current = initial.clone();
+            observers.pre_exec_all(&mut state, &input)?;
+            for &(car, dir) in input.moves() {
+                let mut board = current.board_mut()?;
+                board.shift_car(car.as_nonzero(), dir)?;
+                observers.after_move_all(car, dir, &board);
+            }
+            observers.post_exec_all(&mut state, &input, &ExitKind::Ok)?;
+
+            assert_eq!(
+                expected,
+                growth.is_interesting(&mut state, &mut mgr, &input, &observers, &ExitKind::Ok)?
+            );
+        }
+        assert_eq!(6, state.metadata::<CoverageMap<u8>>()?.visited());
+
+        Ok(())
+    }
+
+    #[test]
+    fn differential() -> Result<(), Box<dyn Error>> {
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let reference = crate::parse_map::<u8>("......\n.oo...\n......\n.1....");
+        // the same board, but with the objective car one cell further right
+        let mismatched = crate::parse_map::<u8>("......\n..oo..\n......\n.1....");
+        let executor = |initial| {
+            PGExecutorBuilder::with_initial(initial)
+                .build(tuple_list!(FinalStateObserver::<u8>::default()))
+        };
+
+        let mut agreeing = DifferentialFeedback::<_, _, u8>::new(
+            executor(reference.clone()),
+            executor(reference.clone()),
+        );
+        let mut feedback =
+            DifferentialFeedback::<_, _, u8>::new(executor(reference), executor(mismatched));
+        let observers = tuple_list!();
+
+        for input in ["", "2U", "1R 1L"] {
+            let input = input.parse::<PGInput>()?;
+            assert!(!agreeing.is_interesting(
+                &mut state,
+                &mut mgr,
+                &input,
+                &observers,
+                &ExitKind::Ok
+            )?);
+            assert!(feedback.is_interesting(
+                &mut state,
+                &mut mgr,
+                &input,
+                &observers,
+                &ExitKind::Ok
+            )?);
+        }
+        let divergence = *state.metadata::<DivergenceMetadata>()?;
+        assert_ne!(divergence.executor1_hash, divergence.executor2_hash);
+        assert_ne!(0, divergence.executor2_hash);
+
+        // the objective car of the second executor is driven off the board
+        let input = "1R 1R 1R".parse::<PGInput>()?;
+        assert!(feedback.is_interesting(
+            &mut state,
+            &mut mgr,
+            &input,
+            &observers,
+            &ExitKind::Ok
+        )?);
+        assert_eq!(0, state.metadata::<DivergenceMetadata>()?.executor2_hash);
+        // the feedback's executors do not count towards the fuzzer's executions
+        assert_eq!(0, *state.executions());
+
+        Ok(())
+    }
+
+    #[test]
+    fn final_state_metadata() -> Result<(), Box<dyn Error>> {
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let initial = crate::parse_map::<u8>("......\n.oo...\n......\n.1....");
+        let obs = FinalStateObserver::<u8>::default();
+        let mut feedback = FinalStateMetadataFeedback::new(&obs);
+        let mut executor = PGExecutorBuilder::with_initial(initial).build(tuple_list!(obs));
+
+        let input = "1R 2U 1L".parse::<PGInput>()?;
+        executor.observers_mut().pre_exec_all(&mut state, &input)?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert!(!feedback.is_interesting(
+            &mut state,
+            &mut mgr,
+            &input,
+            &*executor.observers(),
+            &kind
+        )?);
+        let mut testcase = Testcase::new(input);
+        feedback.append_metadata(&mut state, &mut mgr, &*executor.observers(), &mut testcase)?;
+
+        let final_state = executor.observers().0.final_state().unwrap().clone();
+        let metadata = testcase.metadata::<FinalStateMetadata<u8>>()?;
+        assert_eq!(
+            final_state.board()?.concrete(),
+            metadata.state().board()?.concrete()
+        );
+
+        // picking up from the metadata without any further moves ends up in the same place
+        let mut resumed = PGExecutorBuilder::with_initial(metadata.state().clone())
+            .build(tuple_list!(FinalStateObserver::<u8>::default()));
+        let input = PGInput::default();
+        resumed.observers_mut().pre_exec_all(&mut state, &input)?;
+        resumed.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        assert_eq!(
+            final_state.board()?.concrete(),
+            resumed
+                .observers()
+                .0
+                .final_state()
+                .unwrap()
+                .board()?
+                .concrete()
+        );
+
+        // crashing inputs have no final state, so nothing is attached
+        let input = "1U".parse::<PGInput>()?;
+        executor.observers_mut().pre_exec_all(&mut state, &input)?;
+        executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        let mut testcase = Testcase::new(input);
+        feedback.append_metadata(&mut state, &mut mgr, &*executor.observers(), &mut testcase)?;
+        assert!(!testcase.has_metadata::<FinalStateMetadata<u8>>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn final_positions() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("......\n..oo..\n......\n.1..2.\n.1..2.");
+        let obs = FinalStateObserver::<u8>::default();
+        let mut positions = FinalPositionFeedback::new(&obs);
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let mut seen = 0;
+        for (input, expected) in [
+            ("2U", true),
+            ("2U", false),
+            // only car 3 has moved anywhere new
+            ("3U", true),
+            // a board never seen before, but each car has ended up in these positions before
+            ("2U 3U", false),
+            // car 2 ends up somewhere new, while the others stay where they have been before
+            ("2U 2U", true),
+        ] {
+            let input = input.parse::<PGInput>()?;
+            let mut current = initial.clone();
+            for &(car, dir) in input.moves() {
+                current.board_mut()?.shift_car(car.as_nonzero(), dir)?;
+            }
+            observers.pre_exec_all(&mut state, &input)?;
+            observers.final_board_all(&current.board()?);
+            observers.post_exec_all(&mut state, &input, &ExitKind::Ok)?;
+
+            assert_eq!(
+                expected,
+                positions.is_interesting(
+                    &mut state,
+                    &mut mgr,
+                    &input,
+                    &observers,
+                    &ExitKind::Ok
+                )?,
+                "{input}"
+            );
+            let now = state.metadata::<FinalPositionMetadata>()?.positions().len();
+            assert!(now >= seen);
+            seen = now;
+        }
+        assert_eq!(6, seen);
+
+        Ok(())
+    }
+
+    #[test]
+    fn movement_budget() -> Result<(), Box<dyn Error>> {
+        let mut budget = MovementBudgetFeedback::default();
+        assert_eq!(20, budget.max_per_car());
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let car = |id| CarId::new(id).unwrap();
+        let mut within = PGInput::default();
+        for _ in 0..20 {
+            within.push(car(1), Direction::Right);
+            within.push(car(2), Direction::Up);
+        }
+        let mut over = within.clone();
+        for _ in 0..5 {
+            over.push(car(1), Direction::Left);
+        }
+
+        // the inputs are never executed, so there are no observers to look at
+        for (input, expected) in [(&within, true), (&over, false), (&PGInput::default(), true)] {
+            assert_eq!(
+                expected,
+                budget.is_interesting(&mut state, &mut mgr, input, &(), &ExitKind::Crash)?
+            );
+        }
+        assert!(MovementBudgetFeedback::new(25).is_interesting(
+            &mut state,
+            &mut mgr,
+            &over,
+            &(),
+            &ExitKind::Ok
+        )?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn diversity() -> Result<(), Box<dyn Error>> {
+        let obs = MoveRepetitionObserver::default();
+        let mut diversity = DiversityFeedback::new(&obs);
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for (input, expected, best) in [
+            ("", false, 0.0),
+            // the first input with any moves has the best score so far
+            ("1R 1R 1R 1R", true, 0.25),
+            ("1R 1L 1R 1L", true, 0.5),
+            ("1R 1R 1L 1L", false, 0.5),
+            ("1R 2L 3D 1L", true, 1.0),
+            ("2U", false, 1.0),
+        ] {
+            let input = input.parse::<PGInput>()?;
+            observers.pre_exec_all(&mut state, &input)?;
+            observers.post_exec_all(&mut state, &input, &ExitKind::Ok)?;
+
+            assert_eq!(
+                expected,
+                diversity.is_interesting(
+                    &mut state,
+                    &mut mgr,
+                    &input,
+                    &observers,
+                    &ExitKind::Ok
+                )?,
+                "{input}"
+            );
+            assert_eq!(
+                best,
+                state
+                    .metadata::<DiversityMetadata>()
+                    .map_or(0.0, DiversityMetadata::best_score)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn car_unlocked() -> Result<(), Box<dyn Error>> {
+        // car 3 (`2`) is stuck between cars 2 and 4 until car 4 moves up
+        let initial = crate::parse_map::<u8>("......\noo.3..\n1223..\n1.....");
+
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )?;
+
+        // the views of the initial board, as a view feedback would have recorded them
+        let car = |id| CarId::new(id);
+        let mut parent = Testcase::new(PGInput::default());
+        parent.add_metadata(ViewMetadata::<u8>::new(vec![
+            ViewFrom::new(
+                View::new(Direction::Left, None, 0),
+                View::new(Direction::Right, car(4), 1),
+            ),
+            ViewFrom::new(
+                View::new(Direction::Up, car(1), 0),
+                View::new(Direction::Down, None, 0),
+            ),
+            ViewFrom::new(
+                View::new(Direction::Left, car(2), 0),
+                View::new(Direction::Right, car(4), 0),
+            ),
+            ViewFrom::new(
+                View::new(Direction::Up, None, 1),
+                View::new(Direction::Down, None, 1),
+            ),
+        ]));
+        let parent = state.corpus_mut().add(parent)?;
+
+        let obs = CarMovementObserver::default();
+        let mut unlocked = CarUnlockedFeedback::<u8>::new(&obs);
+        let mut observers = tuple_list!(obs);
+
+        let mut execute = |state: &mut StdState<_, _, _, _>, input: &str| {
+            let input = input.parse::<PGInput>().unwrap();
+            let mut current = initial.clone();
+            observers.pre_exec_all(state, &input).unwrap();
+            for &(car, dir) in input.moves() {
+                let mut board = current.board_mut().unwrap();
+                board.shift_car(car.as_nonzero(), dir).unwrap();
+                observers.after_move_all(car, dir, &board);
+            }
+            observers
+                .post_exec_all(state, &input, &ExitKind::Ok)
+                .unwrap();
+            unlocked
+                .is_interesting(state, &mut (), &input, &observers, &ExitKind::Ok)
+                .unwrap()
+        };
+
+        // without a parent testcase, there is nothing to compare to
+        assert!(!execute(&mut state, "4U 3R"));
+
+        state.set_corpus_id(parent)?;
+        assert!(!execute(&mut state, "4U"));
+        assert!(execute(&mut state, "4U 3R"));
+        assert!(!execute(&mut state, "4U 3R 3L"));
+        assert_eq!(
+            1,
+            state.metadata::<UnlockedCarsMetadata>()?.unlocked().len()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn near_solution() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1.2.3\n...1.2.3\n........\n........");
+        let obs = NearSolutionObserver::default();
+        let mut near = NearSolutionFeedback::new(&obs);
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for (input, expected, best) in [
+            ("", true, 3),
+            ("2D 2D", true, 2),
+            ("3D 3D", false, 2),
+            ("2D 2D 3D 3D", true, 1),
+            ("2D", false, 1),
+        ] {
+            let input = input.parse::<PGInput>()?;
+            let mut current = initial.clone();
+            for &(car, dir) in input.moves() {
+                current.board_mut()?.shift_car(car.as_nonzero(), dir)?;
+            }
+            observers.pre_exec_all(&mut state, &input)?;
+            observers.final_board_all(&current.board()?);
+            observers.post_exec_all(&mut state, &input, &ExitKind::Ok)?;
+
+            assert_eq!(
+                expected,
+                near.is_interesting(&mut state, &mut mgr, &input, &observers, &ExitKind::Ok)?,
+                "{input}"
+            );
+            assert_eq!(
+                best,
+                state.metadata::<BestBlockersMetadata>()?.best_blockers()
+            );
+        }
+
+        let mut testcase = Testcase::new(PGInput::default());
+        near.append_metadata(&mut state, &mut mgr, &observers, &mut testcase)?;
+        assert_eq!(2, testcase.metadata::<NearSolutionMetadata>()?.blockers());
+
+        Ok(())
+    }
+
+    #[test]
+    fn pareto_front() {
+        assert!(ParetoFrontMetadata::dominates((5.0, 2.0), (3.0, 1.0)));
+        assert!(!ParetoFrontMetadata::dominates((5.0, 2.0), (3.0, 4.0)));
+        assert!(!ParetoFrontMetadata::dominates((3.0, 4.0), (5.0, 2.0)));
+
+        let mut front = ParetoFrontMetadata::default();
+        assert!(front.insert((3.0, 4.0)));
+        assert!(front.insert((5.0, 2.0)));
+        assert_eq!(&[(3.0, 4.0), (5.0, 2.0)], front.points());
+        assert!(!front.insert((3.0, 1.0)));
+        assert!(!front.insert((5.0, 2.0)));
+        assert!(front.insert((4.0, 3.0)));
+        assert_eq!(3, front.points().len());
+        assert!(front.insert((5.0, 4.0)));
+        assert_eq!(&[(5.0, 4.0)], front.points());
+    }
+
+    #[test]
+    fn multi_objective() -> Result<(), Box<dyn Error>> {
+        /// Feedback which scores inputs by how many times they move a car.
+        struct CarMoves(usize);
+
+        impl<S> StateInitializer<S> for CarMoves {}
+
+        impl Named for CarMoves {
+            fn name(&self) -> &Cow<'static, str> {
+                static NAME: Cow<'static, str> = Cow::Borrowed("car_moves");
+                &NAME
+            }
+        }
+
+        impl<EM, OT, S> Feedback<EM, PGInput, OT, S> for CarMoves {
+            fn is_interesting(
+                &mut self,
+                _state: &mut S,
+                _manager: &mut EM,
+                _input: &PGInput,
+                _observers: &OT,
+                _exit_kind: &ExitKind,
+            ) -> Result<bool, libafl::Error> {
+                Ok(false)
+            }
+        }
+
+        impl<OT> ScoredFeedback<OT> for CarMoves {
+            fn score(&self, input: &PGInput, _observers: &OT) -> Option<f64> {
+                let car = CarId::new(self.0).unwrap();
+                Some(input.moves_for_car(car).count() as f64)
+            }
+        }
+
+        let mut feedback = MultiObjectiveFeedback::new(CarMoves(1), CarMoves(2));
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let mut corpus_size = 0;
+        for (input, expected, front) in [
+            ("1R 1R 1R 2U 2U 2U 2U", true, 1),
+            ("1R 1R 1R 1R 1R 2U 2U", true, 2),
+            ("1R 1R 1R 2U", false, 2),
+            ("1R 1R 1R 1R 2U 2U 2U", true, 3),
+            // better than every point in both objectives
+            ("1R 1R 1R 1R 1R 2U 2U 2U 2U", true, 1),
+            ("", false, 1),
+        ] {
+            let input = input.parse::<PGInput>()?;
+            let interesting =
+                feedback.is_interesting(&mut state, &mut mgr, &input, &(), &ExitKind::Ok)?;
+            assert_eq!(expected, interesting);
+            corpus_size += usize::from(interesting);
+            let points = state.metadata::<ParetoFrontMetadata>()?.points().len();
+            assert_eq!(front, points);
+            assert!(points <= corpus_size);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn feedback_metrics() -> Result<(), Box<dyn Error>> {
+        /// Feedback which takes a known amount of time to decide.
+        struct SleepFeedback(Duration);
+
+        impl<S> StateInitializer<S> for SleepFeedback {}
+
+        impl Named for SleepFeedback {
+            fn name(&self) -> &Cow<'static, str> {
+                static NAME: Cow<'static, str> = Cow::Borrowed("sleep");
+                &NAME
+            }
+        }
+
+        impl<EM, OT, S> Feedback<EM, PGInput, OT, S> for SleepFeedback {
+            fn is_interesting(
+                &mut self,
+                _state: &mut S,
+                _manager: &mut EM,
+                _input: &PGInput,
+                _observers: &OT,
+                _exit_kind: &ExitKind,
+            ) -> Result<bool, libafl::Error> {
+                thread::sleep(self.0);
+                Ok(true)
+            }
+        }
+
+        let latency = Duration::from_millis(1);
+        let mut metrics = FeedbackMetrics::new(SleepFeedback(latency));
+        assert_eq!("sleep", metrics.name());
+        assert_eq!(Duration::ZERO, metrics.avg_latency());
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+        let input = PGInput::default();
+        for _ in 0..100 {
+            assert!(metrics.is_interesting(&mut state, &mut mgr, &input, &(), &ExitKind::Ok)?);
+        }
+        assert_eq!(100, metrics.total_calls());
+        // sleeping takes at least as long as asked, and rarely much longer
+        assert!(metrics.avg_latency() >= latency);
+        assert!(metrics.avg_latency() < latency * 10);
+        assert!(metrics.max_latency() >= metrics.avg_latency());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,415 @@
+//! Procedural generation of [`parking_game`] puzzles, for when the provided maps aren't enough, and
+//! of inputs to solve them with.
+
+use crate::analysis::is_solvable;
+use crate::input::PGInput;
+use crate::types::CarId;
+use libafl::generators::Generator;
+use libafl::state::HasRand;
+use libafl_bolts::rands::Rand;
+use parking_game::{BoardValue, Car, Direction, Orientation, State};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+
+/// The number of times we attempt to place each car before giving up.
+const PLACEMENT_ATTEMPTS: usize = 100;
+
+/// The maximum number of moves that [`generate_solvable_map`] will search for a solution.
+const SOLVABLE_DEPTH: usize = 20;
+
+/// An error encountered while generating a map.
+#[derive(Debug)]
+pub enum GenerationError {
+    /// The requested dimensions cannot hold the objective car.
+    InvalidDimensions,
+    /// The car with the provided index could not be placed after many attempts.
+    PlacementFailed(CarId),
+    /// The generated map could not be solved within the search depth.
+    Unsolvable,
+}
+
+impl Display for GenerationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerationError::InvalidDimensions => {
+                write!(f, "the dimensions cannot fit the objective car")
+            }
+            GenerationError::PlacementFailed(car) => write!(f, "could not place car {car}"),
+            GenerationError::Unsolvable => write!(f, "the generated map is not solvable"),
+        }
+    }
+}
+
+impl Error for GenerationError {}
+
+/// Randomly generate a map with `num_cars` cars (including the objective car).
+///
+/// The objective car is always placed first as a horizontal car of length 2 in a random row. Every
+/// other car has a random orientation, length (2 or 3), and position which doesn't overlap with any
+/// previously placed car.
+pub fn generate_map<T: BoardValue>(
+    rows: T,
+    cols: T,
+    num_cars: usize,
+    rng: &mut impl Rand,
+) -> Result<State<T>, GenerationError> {
+    let mut state = State::empty((rows, cols)).map_err(|_| GenerationError::InvalidDimensions)?;
+    let (rows, cols): (usize, usize) = (rows.into(), cols.into());
+    if cols < 2 {
+        return Err(GenerationError::InvalidDimensions);
+    }
+    let convert = |v: usize| T::try_from(v).unwrap_or_else(|_| unreachable!());
+
+    let mut board = state.board_mut().unwrap();
+    for idx in 1..=num_cars {
+        let car = CarId::new(idx).unwrap();
+        let placed = (0..PLACEMENT_ATTEMPTS).any(|_| {
+            let (orientation, length) = if idx == 1 {
+                (Orientation::LeftRight, 2)
+            } else {
+                let orientation = if rng.coinflip(0.5) {
+                    Orientation::LeftRight
+                } else {
+                    Orientation::UpDown
+                };
+                (orientation, rng.between(2, 3))
+            };
+            // the number of positions the car could start from in each dimension
+            let (row_starts, col_starts) = match orientation {
+                Orientation::LeftRight => (Some(rows), cols.checked_sub(length - 1)),
+                Orientation::UpDown => (rows.checked_sub(length - 1), Some(cols)),
+            };
+            let (Some(max_row), Some(max_col)) = (
+                row_starts.and_then(NonZeroUsize::new),
+                col_starts.and_then(NonZeroUsize::new),
+            ) else {
+                return false;
+            };
+            let position = (convert(rng.below(max_row)), convert(rng.below(max_col)));
+            board
+                .add_car(position, Car::new(convert(length), orientation).unwrap())
+                .is_ok()
+        });
+        if !placed {
+            return Err(GenerationError::PlacementFailed(car));
+        }
+    }
+    drop(board);
+
+    Ok(state)
+}
+
+/// Randomly generate a map as with [`generate_map`], but additionally verify that the puzzle can be
+/// solved within a small number of moves.
+pub fn generate_solvable_map<T: BoardValue>(
+    rows: T,
+    cols: T,
+    num_cars: usize,
+    rng: &mut impl Rand,
+) -> Result<State<T>, GenerationError> {
+    let state = generate_map(rows, cols, num_cars, rng)?;
+    if is_solvable(&state, SOLVABLE_DEPTH) {
+        Ok(state)
+    } else {
+        Err(GenerationError::Unsolvable)
+    }
+}
+
+/// Generates random inputs for the puzzle it was created for, e.g., to seed the corpus with
+/// [`libafl::fuzzer::Evaluator`]. Unlike [`PGInput::random`], the length of each input is random
+/// too, and shorter inputs are favoured.
+#[derive(Debug, Clone)]
+pub struct PGInputGenerator<T> {
+    car_count: usize,
+    max_depth: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T> PGInputGenerator<T>
+where
+    T: BoardValue,
+{
+    /// Create a generator of inputs of at most `max_depth` moves of the cars of the provided state.
+    pub fn new(initial: &State<T>, max_depth: usize) -> Self {
+        Self {
+            car_count: initial.cars().len(),
+            max_depth,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> PGInputGenerator<T> {
+    /// A new input of at most `max_depth` moves, each of a car chosen uniformly from
+    /// `1..=car_count` in a direction chosen uniformly from all four. The moves need not be valid.
+    ///
+    /// Moves are added one at a time, stopping before the move at depth `d` (counting from zero)
+    /// with probability `(d + 1) / (max_depth + 1)`, so even the empty input is possible.
+    ///
+    /// Panics if `car_count` is zero, since there are no cars to move.
+    pub fn generate(car_count: usize, max_depth: usize, rng: &mut impl Rand) -> PGInput {
+        let car_count = NonZeroUsize::new(car_count).expect("There should be a car to move.");
+        let mut input = PGInput::with_capacity(max_depth);
+        descend(&mut input, 0, car_count, max_depth, rng);
+        input
+    }
+}
+
+/// Add the move at the provided depth to the input unless we stop here, then the moves after it.
+fn descend(
+    input: &mut PGInput,
+    depth: usize,
+    car_count: NonZeroUsize,
+    max_depth: usize,
+    rng: &mut impl Rand,
+) {
+    let bound = NonZeroUsize::new(max_depth + 1).unwrap();
+    if rng.below(bound) <= depth {
+        return;
+    }
+    let car = CarId::new(rng.below(car_count) + 1).unwrap();
+    let direction = rng
+        .choose([
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ])
+        .unwrap();
+    input.push(car, direction);
+    descend(input, depth + 1, car_count, max_depth, rng);
+}
+
+impl<S, T> Generator<PGInput, S> for PGInputGenerator<T>
+where
+    S: HasRand,
+{
+    fn generate(&mut self, state: &mut S) -> Result<PGInput, libafl_bolts::Error> {
+        Ok(Self::generate(
+            self.car_count,
+            self.max_depth,
+            state.rand_mut(),
+        ))
+    }
+}
+
+/// Generates seeds for the puzzle it was created for from known partial solutions, i.e., sequences
+/// of moves which bring the puzzle closer to being solved. Much like a dictionary of tokens for
+/// fuzzing a parser, this embeds what we already know about effective moves in the corpus from the
+/// start, leaving the fuzzer to find how they fit together.
+#[derive(Debug, Clone)]
+pub struct GuidedSeedGenerator<T> {
+    partial_solutions: Vec<PGInput>,
+    max_len: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T> GuidedSeedGenerator<T>
+where
+    T: BoardValue,
+{
+    /// Create a generator of seeds of at most `max_len` moves for the provided state, from the
+    /// provided partial solutions. Partial solutions which move cars not on the board are dropped.
+    pub fn new(initial: &State<T>, partial_solutions: Vec<PGInput>, max_len: usize) -> Self {
+        let car_count = initial.cars().len();
+        Self {
+            partial_solutions: partial_solutions
+                .into_iter()
+                .filter(|partial| {
+                    partial
+                        .moves()
+                        .iter()
+                        .all(|(car, _)| car.get() <= car_count)
+                })
+                .collect(),
+            max_len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> GuidedSeedGenerator<T> {
+    /// The partial solutions seeds are made from.
+    pub fn partial_solutions(&self) -> &[PGInput] {
+        &self.partial_solutions
+    }
+
+    /// A new seed: a random subset of the partial solutions (of at least one, if there are any),
+    /// concatenated in random order and cut off after `max_len` moves.
+    pub fn generate_seed(&self, rng: &mut impl Rand) -> PGInput {
+        let mut chosen = self
+            .partial_solutions
+            .iter()
+            .filter(|_| rng.coinflip(0.5))
+            .collect::<Vec<_>>();
+        if chosen.is_empty()
+            && let Some(partial) = rng.choose(&self.partial_solutions)
+        {
+            chosen.push(partial);
+        }
+        // Fisher-Yates, so that every order is equally likely
+        for i in (1..chosen.len()).rev() {
+            let j = rng.below(NonZeroUsize::new(i + 1).unwrap());
+            chosen.swap(i, j);
+        }
+
+        let mut seed = PGInput::with_capacity(self.max_len);
+        for &(car, direction) in chosen
+            .into_iter()
+            .flat_map(|partial| partial.moves())
+            .take(self.max_len)
+        {
+            seed.push(car, direction);
+        }
+        seed
+    }
+
+    /// Generate the provided number of seeds, e.g., to add to the corpus before fuzzing.
+    pub fn into_seeds(self, count: usize, rng: &mut impl Rand) -> Vec<PGInput> {
+        (0..count).map(|_| self.generate_seed(rng)).collect()
+    }
+}
+
+impl<S, T> Generator<PGInput, S> for GuidedSeedGenerator<T>
+where
+    S: HasRand,
+{
+    fn generate(&mut self, state: &mut S) -> Result<PGInput, libafl_bolts::Error> {
+        Ok(self.generate_seed(state.rand_mut()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::generator::{
+        GenerationError, GuidedSeedGenerator, PGInputGenerator, generate_map, generate_solvable_map,
+    };
+    use crate::input::PGInput;
+    use crate::types::CarId;
+    use libafl::generators::Generator;
+    use libafl::state::NopState;
+    use libafl_bolts::rands::StdRand;
+    use parking_game::Direction;
+    use std::collections::HashSet;
+    use std::error::Error;
+
+    #[test]
+    fn no_overlaps() -> Result<(), Box<dyn Error>> {
+        let mut rng = StdRand::with_seed(1337);
+        for _ in 0..100 {
+            let state = generate_map::<u8>(6, 6, 8, &mut rng)?;
+            assert_eq!(8, state.cars().len());
+
+            // the board cannot be constructed if any cars overlap or are out of bounds
+            let board = state.board()?;
+            let occupied = board
+                .concrete()
+                .iter()
+                .filter(|cell| cell.is_some())
+                .count();
+            let lengths = state
+                .cars()
+                .iter()
+                .map(|(_, car)| usize::from(*car.length()))
+                .sum::<usize>();
+            assert_eq!(lengths, occupied);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn placement_failure() {
+        let mut rng = StdRand::with_seed(1337);
+        assert!(matches!(
+            generate_map::<u8>(2, 2, 3, &mut rng),
+            Err(GenerationError::PlacementFailed(car)) if car.get() == 3
+        ));
+        assert!(matches!(
+            generate_map::<u8>(3, 1, 1, &mut rng),
+            Err(GenerationError::InvalidDimensions)
+        ));
+    }
+
+    #[test]
+    fn solvable() -> Result<(), Box<dyn Error>> {
+        let mut rng = StdRand::with_seed(1337);
+        // an empty board beyond the objective car is always solvable
+        generate_solvable_map::<u8>(6, 6, 1, &mut rng)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn generate_inputs() {
+        let mut rng = StdRand::with_seed(1337);
+        let (car_count, max_depth) = (5, 20);
+        let mut total = 0;
+        let mut empty = 0;
+        for _ in 0..1000 {
+            let input = PGInputGenerator::<u8>::generate(car_count, max_depth, &mut rng);
+            assert!(input.len() <= max_depth);
+            assert!(
+                input
+                    .moves()
+                    .iter()
+                    .all(|(car, _)| (1..=car_count).contains(&car.get()))
+            );
+            total += input.len();
+            empty += usize::from(input.is_empty());
+        }
+        assert!(total / 1000 < max_depth);
+        // the generator stops before the first move one time in 21
+        assert!(empty > 0);
+    }
+
+    #[test]
+    fn generate_from_state() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n22..");
+        let mut generator = PGInputGenerator::new(&initial, 10);
+        let mut state = NopState::<PGInput>::new();
+        for _ in 0..100 {
+            let input = generator.generate(&mut state)?;
+            assert!(input.len() <= 10);
+            assert!(input.moves().iter().all(|(car, _)| car.get() <= 3));
+        }
+
+        Ok(())
+    }
+    #[test]
+    fn guided_seeds() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n22..");
+        let mut rng = StdRand::with_seed(1337);
+        let partials = ["1R", "2L", "2D 2U", "3R 3L 3R", "4R"]
+            .iter()
+            .map(|s| s.parse::<PGInput>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let generator = GuidedSeedGenerator::new(&initial, partials, 4);
+        // car 4 isn't on the board
+        assert_eq!(4, generator.partial_solutions().len());
+        let seeds = generator.into_seeds(100, &mut rng);
+        assert_eq!(100, seeds.len());
+        for seed in &seeds {
+            assert!(!seed.is_empty() && seed.len() <= 4);
+            assert!(seed.moves().iter().all(|(car, _)| car.get() <= 3));
+        }
+
+        let partials = vec!["1R".parse()?, "2L".parse()?];
+        let seeds = GuidedSeedGenerator::new(&initial, partials, 2).into_seeds(20, &mut rng);
+        let moves = seeds
+            .iter()
+            .flat_map(|seed| seed.moves())
+            .copied()
+            .collect::<HashSet<_>>();
+        let (one, two) = (CarId::new(1).unwrap(), CarId::new(2).unwrap());
+        assert!(moves.contains(&(one, Direction::Right)));
+        assert!(moves.contains(&(two, Direction::Left)));
+
+        let mut empty = GuidedSeedGenerator::new(&initial, Vec::new(), 4);
+        assert!(empty.generate(&mut NopState::<PGInput>::new())?.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! Hashing of board states which is updated as each car moves, rather than recomputed from every
+//! cell of the board.
+
+use crate::types::CarId;
+use parking_game::{BoardValue, Position, State};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// A hash of a board state which is updated in constant time as each car moves. Each car
+/// contributes a hash of its id and position, and these are XORed together for the hash of the
+/// state, so a move need only replace the contribution of the car which moved.
+///
+/// Unlike [`crate::observers::hash_state`], this hashes the positions of the cars rather than the
+/// cells of the board, so it is only comparable between states with the same cars.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IncrementalHasher<T> {
+    hash: u64,
+    car_hashes: Vec<u64>,
+    phantom: PhantomData<T>,
+}
+
+impl<T> IncrementalHasher<T>
+where
+    T: BoardValue,
+{
+    /// Hash the positions of all the cars in the provided state.
+    pub fn new(state: &State<T>) -> Self {
+        let car_hashes = state
+            .cars()
+            .iter()
+            .enumerate()
+            .map(|(i, (position, _))| position_hash(i, position))
+            .collect::<Vec<_>>();
+        Self {
+            hash: car_hashes.iter().fold(0, |hash, car| hash ^ car),
+            car_hashes,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The hash of the state as of the last update.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Update the hash after the provided car moved from `old_pos` to `new_pos`.
+    pub fn update(&mut self, car_id: CarId, old_pos: Position<T>, new_pos: Position<T>) {
+        let i = car_id.get() - 1;
+        let car_hash = &mut self.car_hashes[i];
+        debug_assert_eq!(
+            *car_hash,
+            position_hash(i, &old_pos),
+            "car {car_id} should have moved from where it was last"
+        );
+        let moved = position_hash(i, &new_pos);
+        self.hash ^= *car_hash ^ moved;
+        *car_hash = moved;
+    }
+}
+
+/// The hash an [`IncrementalHasher`] would have for a state with the provided cars, given as each
+/// car's index (its id, less one) with the row and column of its position. This allows states which
+/// would be expensive to build to be compared with those which exist.
+pub(crate) fn hash_car_positions(cars: impl IntoIterator<Item = (usize, usize, usize)>) -> u64 {
+    cars.into_iter().fold(0, |hash, (car, row, column)| {
+        hash ^ cell_hash(car, row, column)
+    })
+}
+
+/// The contribution of the car with the provided index at the provided position to the hash of a
+/// state.
+fn position_hash<T: BoardValue>(car: usize, position: &Position<T>) -> u64 {
+    cell_hash(car, (*position.row()).into(), (*position.column()).into())
+}
+
+/// The contribution of the car with the provided index at the provided row and column to the hash
+/// of a state, mixing each in turn with the SplitMix64 finalizer.
+fn cell_hash(car: usize, row: usize, column: usize) -> u64 {
+    fn mix(z: u64) -> u64 {
+        let z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        let z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    mix(mix(mix(car as u64) ^ row as u64) ^ column as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compact::CompactBoard;
+    use crate::hash::IncrementalHasher;
+    use crate::input::PGInput;
+    use libafl_bolts::rands::StdRand;
+    use std::collections::HashMap;
+    use std::collections::hash_map::Entry;
+    use std::error::Error;
+    use std::fs;
+
+    #[test]
+    fn incremental_hash() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>(&fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/maps/tokyo1.map"
+        ))?);
+        let mut rng = StdRand::with_seed(1337);
+        let mut seen = HashMap::new();
+
+        for _ in 0..1000 {
+            let mut state = initial.clone();
+            let mut hasher = IncrementalHasher::new(&state);
+            for (car, dir) in PGInput::random(initial.cars().len(), 20, &mut rng) {
+                let old_pos = state.cars()[car.get() - 1].0;
+                // invalid moves are skipped, as the executor would stop at them
+                let Ok(new_pos) = state.board_mut()?.shift_car(car.as_nonzero(), dir) else {
+                    continue;
+                };
+                hasher.update(car, old_pos, new_pos);
+                assert_eq!(IncrementalHasher::new(&state).hash(), hasher.hash());
+
+                let board = CompactBoard::from_board(&state.board()?);
+                match seen.entry(hasher.hash()) {
+                    Entry::Occupied(entry) => assert_eq!(entry.get(), &board),
+                    Entry::Vacant(entry) => {
+                        entry.insert(board);
+                    }
+                }
+            }
+        }
+        // the walks should have reached plenty of distinct states to compare
+        assert!(seen.len() > 100);
+
+        Ok(())
+    }
+}
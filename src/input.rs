@@ -1,34 +1,1486 @@
 //! Input representations for fuzzing of [`parking_game`] puzzles.
 
+use crate::observers::{DirectionExt, blockers_in_path, hash_state};
+use crate::types::CarId;
 use libafl::inputs::Input;
-use parking_game::Direction;
+use libafl_bolts::rands::Rand;
+use parking_game::{Board, BoardValue, Direction, State};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use std::num::NonZeroUsize;
+use std::ops::{ControlFlow, DerefMut, Index};
+use std::str::FromStr;
 
 /// An input for solving the parking game problems.
-#[derive(Debug, Default, Clone, Hash, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct PGInput {
-    moves: Vec<(NonZeroUsize, Direction)>,
+    moves: Vec<(CarId, Direction)>,
 }
 
 impl PGInput {
     /// Create a new [`PGInput`] from the provided sequence of moves.
-    pub fn new(moves: Vec<(NonZeroUsize, Direction)>) -> Self {
+    pub fn new(moves: Vec<(CarId, Direction)>) -> Self {
         Self { moves }
     }
 
+    /// Create a new [`PGInput`] which owns the provided moves, without copying them. This is the
+    /// inverse of [`PGInput::into_moves`], and the same as [`PGInput::new`].
+    pub fn from_moves(moves: Vec<(CarId, Direction)>) -> Self {
+        Self::new(moves)
+    }
+
+    /// Create a new, empty [`PGInput`] with space for at least `capacity` moves.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+
     /// The moves contained within this inputs.
     ///
     /// This is stored as a sequence of pairs of (1) car that is moved and (2) which direction.
-    pub fn moves(&self) -> &[(NonZeroUsize, Direction)] {
+    pub fn moves(&self) -> &[(CarId, Direction)] {
         &self.moves
     }
 
     /// A mutable reference to the sequence of moves in this input, for use in mutators.
-    pub fn moves_mut(&mut self) -> &mut Vec<(NonZeroUsize, Direction)> {
+    pub fn moves_mut(&mut self) -> &mut Vec<(CarId, Direction)> {
         &mut self.moves
     }
+
+    /// Consumes this input, returning its sequence of moves without copying them.
+    pub fn into_moves(self) -> Vec<(CarId, Direction)> {
+        self.moves
+    }
+
+    /// Appends a move of the provided car in the provided direction to the end of this input.
+    pub fn push(&mut self, car: CarId, dir: Direction) {
+        self.moves.push((car, dir));
+    }
+
+    /// Appends the provided moves to the end of this input, in order.
+    pub fn extend_from_slice(&mut self, moves: &[(CarId, Direction)]) {
+        self.moves.extend_from_slice(moves);
+    }
+
+    /// Keeps only the moves for which `f` returns true, in order.
+    pub fn retain<F: FnMut(&(CarId, Direction)) -> bool>(&mut self, f: F) {
+        self.moves.retain(f);
+    }
+
+    /// Removes repeats of a move made immediately after itself, such that `1R 1R 2L 1R` becomes
+    /// `1R 2L 1R`. Moves which cancel out are not removed; see
+    /// [`PGInput::deduplicate_consecutive`] for that.
+    pub fn dedup(&mut self) {
+        self.moves.dedup();
+    }
+
+    /// The number of moves in this input.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Whether this input contains no moves.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Applies the moves in this input to the provided state, in order. Returns the number of moves
+    /// applied, or the index of the first invalid move; moves before that index are not undone.
+    ///
+    /// Panics if the provided state is itself invalid.
+    pub fn apply<T: BoardValue>(&self, state: &mut State<T>) -> Result<usize, usize> {
+        let mut board = state.board_mut().expect("The state should be valid.");
+        apply_moves(&self.moves, &mut board, |_, _| ControlFlow::Continue(()))
+    }
+
+    /// The shortest prefix of this input which solves the puzzle from the provided state, i.e.,
+    /// after which no car is between the objective car and the wall, as with
+    /// [`crate::feedbacks::SolvedFeedback`]. This is empty if the puzzle is already solved, and
+    /// [`None`] if no prefix solves it, including if a move is invalid before the puzzle is solved.
+    ///
+    /// Panics if the provided state is itself invalid.
+    pub fn prefix_up_to_solution<T: BoardValue>(&self, initial: &State<T>) -> Option<PGInput> {
+        let mut state = initial.clone();
+        let mut board = state.board_mut().expect("The state should be valid.");
+        if blockers_in_path(&board) == 0 {
+            return Some(Self::default());
+        }
+        let applied = apply_moves(&self.moves, &mut board, |_, board| {
+            if blockers_in_path(board) == 0 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .ok()?;
+        (blockers_in_path(&board) == 0).then(|| self.truncate(applied))
+    }
+
+    /// Checks that every move in this input may be applied to the provided state, in order. Returns
+    /// the index of the first invalid move, if there is one. The provided state is not modified.
+    ///
+    /// Panics if the provided state is itself invalid.
+    pub fn validate<T: BoardValue>(&self, state: &State<T>) -> Result<(), usize> {
+        self.apply(&mut state.clone()).map(|_| ())
+    }
+
+    /// The Levenshtein distance between the moves of this input and the other, where each move is
+    /// inserted, deleted, or substituted as a whole.
+    pub fn edit_distance(&self, other: &PGInput) -> usize {
+        // only the previous row of the table is needed at any time
+        let mut row = (0..=other.moves.len()).collect::<Vec<_>>();
+        for (i, a) in self.moves.iter().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+            for (j, b) in other.moves.iter().enumerate() {
+                let substitution = diagonal + usize::from(a != b);
+                diagonal = row[j + 1];
+                row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+            }
+        }
+        row[other.moves.len()]
+    }
+
+    /// The longest sequence of moves shared at the start of both inputs. When an input is mutated
+    /// from another, this is the part of it which need not be executed again.
+    pub fn common_prefix(a: &PGInput, b: &PGInput) -> PGInput {
+        let len = a
+            .moves
+            .iter()
+            .zip(&b.moves)
+            .take_while(|(a, b)| a == b)
+            .count();
+        Self::new(a.moves[..len].to_vec())
+    }
+
+    /// Whether every move of this input appears in the other, in the same order, though perhaps
+    /// with other moves in between. The other input then makes the same moves as this one and more,
+    /// so may be redundant if this one is as good.
+    pub fn is_subsequence_of(&self, other: &PGInput) -> bool {
+        let mut others = other.moves.iter();
+        self.moves.iter().all(|mv| others.any(|other| other == mv))
+    }
+
+    /// The longest sequence of moves appearing in both inputs in the same order, though perhaps
+    /// with other moves in between. Where there are several, the one used is unspecified.
+    pub fn longest_common_subsequence(a: &PGInput, b: &PGInput) -> PGInput {
+        // lengths[i][j] is the length of the longest common subsequence of a[i..] and b[j..]
+        let mut lengths = vec![vec![0; b.moves.len() + 1]; a.moves.len() + 1];
+        for (i, x) in a.moves.iter().enumerate().rev() {
+            for (j, y) in b.moves.iter().enumerate().rev() {
+                lengths[i][j] = if x == y {
+                    lengths[i + 1][j + 1] + 1
+                } else {
+                    lengths[i + 1][j].max(lengths[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        let mut moves = Vec::with_capacity(lengths[0][0]);
+        while i < a.moves.len() && j < b.moves.len() {
+            if a.moves[i] == b.moves[j] {
+                moves.push(a.moves[i]);
+                i += 1;
+                j += 1;
+            } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self::new(moves)
+    }
+
+    /// A new input containing the first `n` moves of this input, or all of them if there are fewer.
+    pub fn truncate(&self, n: usize) -> PGInput {
+        self.split_at(n).0
+    }
+
+    /// A new input containing the moves of this input after the first `n`, or none if there are
+    /// fewer.
+    pub fn skip(&self, n: usize) -> PGInput {
+        self.split_at(n).1
+    }
+
+    /// Splits this input into two new inputs, as with [`PGInput::truncate`] and [`PGInput::skip`].
+    pub fn split_at(&self, n: usize) -> (PGInput, PGInput) {
+        let (prefix, suffix) = self.moves.split_at(n.min(self.moves.len()));
+        (Self::new(prefix.to_vec()), Self::new(suffix.to_vec()))
+    }
+
+    /// A new input containing the moves of `a` followed by the moves of `b`.
+    pub fn merge(a: &PGInput, b: &PGInput) -> PGInput {
+        Self::new([a.moves(), b.moves()].concat())
+    }
+
+    /// A new input alternating between the moves of `a` and `b`, starting with `a`. Once the
+    /// shorter input runs out, the rest of the longer input is appended.
+    pub fn interleave(a: &PGInput, b: &PGInput) -> PGInput {
+        let shared = a.moves.len().min(b.moves.len());
+        let mut moves = Vec::with_capacity(a.moves.len() + b.moves.len());
+        for (a, b) in a.moves.iter().zip(&b.moves) {
+            moves.extend([*a, *b]);
+        }
+        moves.extend_from_slice(&a.moves[shared..]);
+        moves.extend_from_slice(&b.moves[shared..]);
+        Self::new(moves)
+    }
+
+    /// A new input of `len` moves, each of a car chosen uniformly from `1..=car_count` in a
+    /// direction chosen uniformly from all four. The moves need not be valid.
+    ///
+    /// Panics if `car_count` is zero, since there are no cars to move.
+    pub fn random(car_count: usize, len: usize, rng: &mut impl Rand) -> PGInput {
+        let car_count = NonZeroUsize::new(car_count).expect("There should be a car to move.");
+        let directions = [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ];
+        let moves = (0..len)
+            .map(|_| {
+                let car = CarId::new(rng.below(car_count) + 1).unwrap();
+                (car, rng.choose(directions).unwrap())
+            })
+            .collect();
+        Self::new(moves)
+    }
+
+    /// A new input containing `len` moves of this input (or all of them if there are fewer), chosen
+    /// at random without replacement and kept in their original order.
+    pub fn random_subsequence(&self, len: usize, rng: &mut impl Rand) -> PGInput {
+        // reservoir sampling: keep the first `len` indices, then replace a random one with the
+        // `i`th index with probability `len / (i + 1)`; sort the kept indices to preserve order
+        let len = len.min(self.moves.len());
+        let mut reservoir = (0..len).collect::<Vec<_>>();
+        for i in len..self.moves.len() {
+            let j = rng.below(NonZeroUsize::new(i + 1).unwrap());
+            if j < len {
+                reservoir[j] = i;
+            }
+        }
+        reservoir.sort_unstable();
+        let moves = reservoir.into_iter().map(|i| self.moves[i]).collect();
+        Self::new(moves)
+    }
+
+    /// Applies the moves in this input to the provided state and returns the indices of the first
+    /// pair of moves after which the board is in the same state, if there is one. States are
+    /// compared by [`hash_state`]. If a move is invalid, only the states reached before it are
+    /// considered.
+    ///
+    /// Panics if the provided state is itself invalid.
+    pub fn has_cycle<T: BoardValue>(&self, initial: &State<T>) -> Option<(usize, usize)> {
+        let mut state = initial.clone();
+        let mut seen = HashMap::new();
+        for (i, (car, dir)) in self.moves.iter().enumerate() {
+            let mut board = state.board_mut().expect("The state should be valid.");
+            board.shift_car(car.as_nonzero(), *dir).ok()?;
+            drop(board);
+            if let Some(first) = seen.insert(hash_state(&state)?, i) {
+                return Some((first, i));
+            }
+        }
+        None
+    }
+
+    /// Applies the moves in this input to the provided state, sampling `k` evenly spaced states
+    /// along the way: the state after `i * len / k` moves for each `i` in `0..k`, so the provided
+    /// state is always the first sample. Moves after the last sample are not applied; if a move
+    /// before it is invalid, its index is returned instead.
+    ///
+    /// Panics if the provided state is itself invalid.
+    pub fn sample_states<T: BoardValue>(
+        &self,
+        initial: &State<T>,
+        k: usize,
+    ) -> Result<Vec<State<T>>, usize> {
+        let len = self.moves.len();
+        let mut state = initial.clone();
+        let mut samples = Vec::with_capacity(k);
+        let mut applied = 0;
+        for i in 0..k {
+            let next = i * len / k;
+            let mut board = state.board_mut().expect("The state should be valid.");
+            applied += apply_moves(&self.moves[applied..next], &mut board, |_, _| {
+                ControlFlow::Continue(())
+            })
+            .map_err(|invalid| applied + invalid)?;
+            drop(board);
+            samples.push(state.clone());
+        }
+        Ok(samples)
+    }
+
+    /// A new input without any moves which are immediately undone, i.e., a move of a car where
+    /// the next move of that car is in the opposite direction. Moves of other cars in between are
+    /// kept, and removing a pair may bring another pair together, so pairs are removed until none
+    /// remain.
+    ///
+    /// This does not simulate the moves, so if a move in between relied on a removed move, the
+    /// normalized input is no longer valid even if this input was.
+    pub fn normalize(&self) -> PGInput {
+        // as with matching brackets, a stack per car removes nested pairs in a single pass
+        let mut moves = Vec::with_capacity(self.moves.len());
+        let mut last: HashMap<CarId, Vec<usize>> = HashMap::new();
+        for &(car, dir) in &self.moves {
+            let indices = last.entry(car).or_default();
+            match indices.last() {
+                Some(&i) if moves[i] == Some((car, dir.opposite())) => {
+                    moves[i] = None;
+                    indices.pop();
+                }
+                _ => {
+                    indices.push(moves.len());
+                    moves.push(Some((car, dir)));
+                }
+            }
+        }
+        Self::new(moves.into_iter().flatten().collect())
+    }
+
+    /// Removes each move immediately followed by the same car moving back, until no such pairs
+    /// remain; removing a pair may bring another pair together, as in `1R 2L 2R 1L`. Unlike
+    /// [`PGInput::normalize`], moves separated by a move of another car are kept, so the result is
+    /// valid whenever this input was.
+    pub fn deduplicate_consecutive(&mut self) {
+        // treating the kept moves as a stack cancels newly adjacent pairs as we go, in one pass
+        let mut kept = 0;
+        for i in 0..self.moves.len() {
+            let (car, dir) = self.moves[i];
+            if kept > 0 && self.moves[kept - 1] == (car, dir.opposite()) {
+                kept -= 1;
+            } else {
+                self.moves[kept] = (car, dir);
+                kept += 1;
+            }
+        }
+        self.moves.truncate(kept);
+    }
+
+    /// Whether any move is immediately followed by the same car moving back, i.e., whether
+    /// [`PGInput::deduplicate_consecutive`] would change this input. This is cheap enough to filter
+    /// out wasteful inputs before they are executed.
+    pub fn has_consecutive_cancels(&self) -> bool {
+        self.moves
+            .windows(2)
+            .any(|pair| pair[1] == (pair[0].0, pair[0].1.opposite()))
+    }
+
+    /// Sorts the moves by car, keeping the moves of each car in the order they were made.
+    ///
+    /// As with [`PGInput::normalize`], the moves are not simulated, so the sorted input may not be
+    /// valid even if this input was: a car may now move before the car blocking it is moved away.
+    pub fn sort_by_car(&mut self) {
+        self.moves.sort_by_key(|(car, _)| *car);
+    }
+
+    /// A copy of this input with its moves sorted by car, as by [`PGInput::sort_by_car`]. Inputs
+    /// which only differ in how the moves of different cars are interleaved have the same canonical
+    /// form.
+    pub fn canonicalize(&self) -> PGInput {
+        let mut canonical = self.clone();
+        canonical.sort_by_car();
+        canonical
+    }
+
+    /// The number of moves of each car in this input. Cars which are never moved are absent.
+    pub fn car_histogram(&self) -> HashMap<CarId, usize> {
+        let mut histogram = HashMap::new();
+        for (car, _) in &self.moves {
+            *histogram.entry(*car).or_default() += 1;
+        }
+        histogram
+    }
+
+    /// The cars moved in this input, in ascending order and without duplicates.
+    pub fn unique_cars(&self) -> Vec<CarId> {
+        let mut cars = self.moves.iter().map(|(car, _)| *car).collect::<Vec<_>>();
+        cars.sort_unstable();
+        cars.dedup();
+        cars
+    }
+
+    /// The number of different cars moved in this input.
+    pub fn car_count(&self) -> usize {
+        self.unique_cars().len()
+    }
+
+    /// The index and direction of each move of the provided car in this input, in order.
+    pub fn moves_for_car(&self, car: CarId) -> impl Iterator<Item = (usize, Direction)> + '_ {
+        self.moves
+            .iter()
+            .enumerate()
+            .filter(move |(_, (moved, _))| *moved == car)
+            .map(|(i, (_, dir))| (i, *dir))
+    }
+
+    /// The directions the provided car is moved in this input, in order, without the moves of other
+    /// cars. This is empty if the car is never moved.
+    pub fn car_move_sequence(&self, car: CarId) -> Vec<Direction> {
+        self.moves_for_car(car).map(|(_, dir)| dir).collect()
+    }
+
+    /// The indices of the first and last moves of the provided car in this input, or [`None`] if
+    /// it is never moved. Moves of other cars may lie in between.
+    pub fn car_range(&self, car: CarId) -> Option<(usize, usize)> {
+        let first = self.moves.iter().position(|(moved, _)| *moved == car)?;
+        let last = self.moves.iter().rposition(|(moved, _)| *moved == car)?;
+        Some((first, last))
+    }
+
+    /// Each run of `size` consecutive moves in this input, in order. There are none if `size` is
+    /// zero or greater than the number of moves.
+    pub fn window_iter(&self, size: usize) -> impl Iterator<Item = &[(CarId, Direction)]> + '_ {
+        // unlike `slice::windows`, a size of zero is no mistake, just a window which fits nowhere
+        NonZeroUsize::new(size)
+            .into_iter()
+            .flat_map(|size| self.moves.windows(size.get()))
+    }
+}
+
+/// Applies the provided moves to the board, in order, as in [`PGInput::apply`]. This operates on
+/// slices of moves so that the executor may skip moves that have already been applied.
+///
+/// `after_move` is called with the index of each move once it has been applied, and may stop any
+/// further moves from being applied; the number of moves applied so far is then returned.
+pub(crate) fn apply_moves<T, S>(
+    moves: &[(CarId, Direction)],
+    board: &mut Board<S, T>,
+    mut after_move: impl FnMut(usize, &Board<S, T>) -> ControlFlow<()>,
+) -> Result<usize, usize>
+where
+    T: BoardValue,
+    S: DerefMut<Target = State<T>>,
+{
+    for (i, (car, dir)) in moves.iter().enumerate() {
+        board.shift_car(car.as_nonzero(), *dir).map_err(|_| i)?;
+        if after_move(i, board).is_break() {
+            return Ok(i + 1);
+        }
+    }
+    Ok(moves.len())
 }
 
 // Make it compatible with LibAFL!
 impl Input for PGInput {}
+
+impl IntoIterator for PGInput {
+    type Item = (CarId, Direction);
+    type IntoIter = std::vec::IntoIter<(CarId, Direction)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a PGInput {
+    type Item = &'a (CarId, Direction);
+    type IntoIter = std::slice::Iter<'a, (CarId, Direction)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.iter()
+    }
+}
+
+impl Extend<(CarId, Direction)> for PGInput {
+    fn extend<I: IntoIterator<Item = (CarId, Direction)>>(&mut self, iter: I) {
+        self.moves.extend(iter);
+    }
+}
+
+impl Index<usize> for PGInput {
+    type Output = (CarId, Direction);
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.moves[index]
+    }
+}
+
+/// An error encountered while parsing a [`PGInput`] from a string, or decoding one from bytes. The
+/// position is the index of the offending move, counting from zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PGInputParseError {
+    /// The token was not a move.
+    InvalidToken {
+        /// The token, as written.
+        token: String,
+        /// The position of the token.
+        position: usize,
+    },
+    /// The car id was not a number.
+    InvalidCarId {
+        /// The car id, as written.
+        token: String,
+        /// The position of the move.
+        position: usize,
+    },
+    /// The car id was zero; cars are indexed from one.
+    CarIdZero {
+        /// The position of the move.
+        position: usize,
+    },
+    /// The direction was not one of `U`, `D`, `L`, or `R`.
+    InvalidDirection {
+        /// The direction, as written.
+        ch: char,
+        /// The position of the move.
+        position: usize,
+    },
+    /// The input ended partway through a move.
+    UnexpectedEnd,
+}
+
+impl Display for PGInputParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PGInputParseError::InvalidToken { token, position } => {
+                write!(f, "invalid move `{token}` at token position {position}")
+            }
+            PGInputParseError::InvalidCarId { token, position } => {
+                write!(f, "invalid car id `{token}` at token position {position}")
+            }
+            PGInputParseError::CarIdZero { position } => {
+                write!(
+                    f,
+                    "car id 0 at token position {position}; car ids start from 1"
+                )
+            }
+            PGInputParseError::InvalidDirection { ch, position } => {
+                write!(f, "invalid direction {ch:?} at token position {position}")
+            }
+            PGInputParseError::UnexpectedEnd => {
+                f.write_str("unexpected end of input partway through a move")
+            }
+        }
+    }
+}
+
+impl Error for PGInputParseError {}
+
+fn parse_car(id: &str, position: usize) -> Result<CarId, PGInputParseError> {
+    let id = id
+        .parse::<usize>()
+        .map_err(|_| PGInputParseError::InvalidCarId {
+            token: id.to_string(),
+            position,
+        })?;
+    CarId::new(id).ok_or(PGInputParseError::CarIdZero { position })
+}
+
+/// The display format for an input without any moves.
+const EMPTY: &str = "<empty>";
+
+/// The letter standing for the direction in the compact move format.
+pub(crate) fn direction_letter(dir: Direction) -> char {
+    match dir {
+        Direction::Up => 'U',
+        Direction::Down => 'D',
+        Direction::Left => 'L',
+        Direction::Right => 'R',
+    }
+}
+
+/// Displays moves in the compact format `1R 2L 3D 1U`, which may be parsed again with
+/// [`PGInput::from_str`].
+impl Display for PGInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.moves.is_empty() {
+            return f.write_str(EMPTY);
+        }
+        for (i, (car, dir)) in self.moves.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{car}{}", direction_letter(*dir))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses moves in the compact format `1R 2L 3D 1U`, where each move is the car id followed by the
+/// first letter of the direction.
+impl FromStr for PGInput {
+    type Err = PGInputParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim() == EMPTY {
+            return Ok(Self::default());
+        }
+        s.split_whitespace()
+            .enumerate()
+            .map(|(position, token)| {
+                let Some((split, dir)) = token.char_indices().last().filter(|&(i, _)| i > 0) else {
+                    return Err(PGInputParseError::InvalidToken {
+                        token: token.to_string(),
+                        position,
+                    });
+                };
+                let car = parse_car(&token[..split], position)?;
+                let dir = match dir {
+                    'U' => Direction::Up,
+                    'D' => Direction::Down,
+                    'L' => Direction::Left,
+                    'R' => Direction::Right,
+                    ch => return Err(PGInputParseError::InvalidDirection { ch, position }),
+                };
+                Ok((car, dir))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+}
+
+impl PGInput {
+    /// Formats the moves in the verbose format `Car1→Right, Car2→Left`, which may be parsed again
+    /// with [`PGInput::from_algebraic`].
+    pub fn to_verbose_string(&self) -> String {
+        self.moves
+            .iter()
+            .map(|(car, dir)| format!("Car{car}→{dir:?}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Parses moves in the verbose format `Car1→Right, Car2→Left`.
+    pub fn from_algebraic(s: &str) -> Result<Self, PGInputParseError> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .enumerate()
+            .map(|(position, token)| {
+                let invalid = || PGInputParseError::InvalidToken {
+                    token: token.to_string(),
+                    position,
+                };
+                let (car, dir) = token
+                    .strip_prefix("Car")
+                    .and_then(|token| token.split_once('→'))
+                    .ok_or_else(invalid)?;
+                let car = parse_car(car, position)?;
+                let dir = match dir {
+                    "Up" => Direction::Up,
+                    "Down" => Direction::Down,
+                    "Left" => Direction::Left,
+                    "Right" => Direction::Right,
+                    _ => return Err(invalid()),
+                };
+                Ok((car, dir))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+
+    /// Encodes the moves compactly as two bytes each: the car id, and the direction (`0` for up,
+    /// `1` for down, `2` for left, and `3` for right). Car ids above 255 do not fit, and are
+    /// written as 255.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.moves
+            .iter()
+            .flat_map(|&(car, dir)| [car.get().min(u8::MAX.into()) as u8, dir.to_byte()])
+            .collect()
+    }
+
+    /// Decodes moves encoded by [`PGInput::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PGInputParseError> {
+        let moves = bytes.chunks_exact(2);
+        if !moves.remainder().is_empty() {
+            return Err(PGInputParseError::UnexpectedEnd);
+        }
+        moves
+            .enumerate()
+            .map(|(position, chunk)| {
+                let car =
+                    CarId::new(chunk[0].into()).ok_or(PGInputParseError::CarIdZero { position })?;
+                let dir =
+                    Direction::from_byte(chunk[1]).ok_or(PGInputParseError::InvalidDirection {
+                        ch: chunk[1].into(),
+                        position,
+                    })?;
+                Ok((car, dir))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::{PGInput, PGInputParseError};
+    use crate::observers::hash_state;
+    use crate::types::CarId;
+    use libafl_bolts::rands::StdRand;
+    use parking_game::{Board, BoardValue, Direction, State};
+    use std::collections::{HashMap, HashSet};
+    use std::error::Error;
+    use std::hash::{BuildHasher, RandomState};
+
+    #[test]
+    fn parse_moves() -> Result<(), Box<dyn Error>> {
+        let input = "1R 2L".parse::<PGInput>()?;
+        assert_eq!(
+            [
+                (CarId::new(1).unwrap(), Direction::Right),
+                (CarId::new(2).unwrap(), Direction::Left)
+            ],
+            input.moves()
+        );
+
+        let input = "".parse::<PGInput>()?;
+        assert!(input.moves().is_empty());
+
+        let input = PGInput::from_algebraic("Car1→Right, Car12→Up")?;
+        assert_eq!(
+            [
+                (CarId::new(1).unwrap(), Direction::Right),
+                (CarId::new(12).unwrap(), Direction::Up)
+            ],
+            input.moves()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn display_moves() -> Result<(), Box<dyn Error>> {
+        let input = PGInput::new(vec![
+            (CarId::new(1).unwrap(), Direction::Right),
+            (CarId::new(2).unwrap(), Direction::Left),
+            (CarId::new(3).unwrap(), Direction::Down),
+        ]);
+        assert_eq!("1R 2L 3D", input.to_string());
+        assert_eq!(
+            "Car1→Right, Car2→Left, Car3→Down",
+            input.to_verbose_string()
+        );
+        assert_eq!("<empty>", PGInput::default().to_string());
+
+        let directions = [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ];
+        for len in [0, 1, 100] {
+            let input = PGInput::new(
+                (0..len)
+                    .map(|i| (CarId::new(i % 7 + 1).unwrap(), directions[i % 4]))
+                    .collect(),
+            );
+            let parsed = input.to_string().parse::<PGInput>()?;
+            assert_eq!(input.moves(), parsed.moves());
+            let parsed = PGInput::from_algebraic(&input.to_verbose_string())?;
+            assert_eq!(input.moves(), parsed.moves());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn bytes() -> Result<(), Box<dyn Error>> {
+        assert!(PGInput::default().to_bytes().is_empty());
+        assert_eq!(PGInput::default(), PGInput::from_bytes(&[])?);
+
+        let input = "1R 2U 12D".parse::<PGInput>()?;
+        let bytes = input.to_bytes();
+        assert_eq!([1, 3, 2, 0, 12, 1], *bytes);
+        assert_eq!(input, PGInput::from_bytes(&bytes)?);
+
+        // car ids which do not fit in a byte are clamped
+        let wide = PGInput::new(vec![(CarId::new(300).unwrap(), Direction::Left)]);
+        assert_eq!([255, 2], *wide.to_bytes());
+
+        assert_eq!(
+            PGInputParseError::CarIdZero { position: 1 },
+            PGInput::from_bytes(&[1, 3, 0, 3]).unwrap_err()
+        );
+        assert_eq!(
+            PGInputParseError::InvalidDirection {
+                ch: '\u{4}',
+                position: 0
+            },
+            PGInput::from_bytes(&[1, 4]).unwrap_err()
+        );
+        assert_eq!(
+            PGInputParseError::UnexpectedEnd,
+            PGInput::from_bytes(&[1, 3, 1]).unwrap_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_input() {
+        #[derive(Default)]
+        struct Wrapper {
+            input: PGInput,
+        }
+
+        assert_eq!(PGInput::new(vec![]).moves(), PGInput::default().moves());
+        assert!(Wrapper::default().input.moves().is_empty());
+
+        let mut input = PGInput::with_capacity(100);
+        let capacity = input.moves_mut().capacity();
+        let ptr = input.moves().as_ptr();
+        assert!(capacity >= 100);
+        for i in 0..100 {
+            input.push(CarId::new(i % 3 + 1).unwrap(), Direction::Up);
+        }
+        assert_eq!(100, input.moves().len());
+        assert_eq!(capacity, input.moves_mut().capacity());
+        assert_eq!(ptr, input.moves().as_ptr());
+        assert_eq!((CarId::new(1).unwrap(), Direction::Up), input.moves()[99]);
+    }
+
+    #[test]
+    fn input_equality() -> Result<(), Box<dyn Error>> {
+        let hasher = RandomState::new();
+        let a = "1R 2L 1R".parse::<PGInput>()?;
+        let b = "1R 2L 1R".parse::<PGInput>()?;
+        assert_eq!(a, b);
+        assert_eq!(hasher.hash_one(&a), hasher.hash_one(&b));
+
+        assert_ne!(a, "1R 1R 2L".parse::<PGInput>()?);
+        assert_ne!(a, "1R 2L 1L".parse::<PGInput>()?);
+        assert_ne!(a, "1R 2L".parse::<PGInput>()?);
+        assert_eq!(PGInput::default(), PGInput::new(vec![]));
+
+        let inputs = ["", "1R", "1R 2L", "2L 1R", "1R 2L", "1U 1U 1U", "1U 1U 1U"]
+            .map(|s| s.parse::<PGInput>())
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        for a in &inputs {
+            for b in &inputs {
+                if a == b {
+                    assert_eq!(hasher.hash_one(a), hasher.hash_one(b));
+                }
+            }
+        }
+        assert_eq!(5, inputs.iter().collect::<HashSet<_>>().len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn iterate_moves() -> Result<(), Box<dyn Error>> {
+        let input = "1R 2L 3D 1U".parse::<PGInput>()?;
+
+        let borrowed = (&input).into_iter().collect::<Vec<_>>();
+        assert_eq!(input.moves().iter().collect::<Vec<_>>(), borrowed);
+        let mut count = 0;
+        for (car, _) in &input {
+            assert!(car.get() <= 3);
+            count += 1;
+        }
+        assert_eq!(4, count);
+
+        let expected = input.moves().to_vec();
+        assert_eq!(expected, input.clone().into_iter().collect::<Vec<_>>());
+
+        let mut extended = "1R".parse::<PGInput>()?;
+        extended.extend(input.clone().into_iter().skip(1));
+        assert_eq!(input, extended);
+        extended.extend([]);
+        assert_eq!(input, extended);
+
+        Ok(())
+    }
+
+    #[test]
+    fn car_counts() -> Result<(), Box<dyn Error>> {
+        let car = |id| CarId::new(id).unwrap();
+
+        let input = "1R 1R 2L".parse::<PGInput>()?;
+        assert_eq!(
+            HashMap::from([(car(1), 2), (car(2), 1)]),
+            input.car_histogram()
+        );
+        assert_eq!([car(1), car(2)], *input.unique_cars());
+
+        assert!(PGInput::default().car_histogram().is_empty());
+        assert!(PGInput::default().unique_cars().is_empty());
+
+        for input in ["3U 1R 12D 3D 2L 1L", "7L 7L 7L", "5U 4U 3U 2U 1U"] {
+            let input = input.parse::<PGInput>()?;
+            assert_eq!(input.len(), input.car_histogram().values().sum::<usize>());
+            let cars = input.unique_cars();
+            assert!(cars.is_sorted());
+            assert!(cars.windows(2).all(|pair| pair[0] != pair[1]));
+            assert_eq!(input.car_histogram().len(), cars.len());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn vec_conveniences() -> Result<(), Box<dyn Error>> {
+        let car = |id| CarId::new(id).unwrap();
+
+        let mut input = "1R".parse::<PGInput>()?;
+        input.extend_from_slice(&[
+            (car(2), Direction::Left),
+            (car(1), Direction::Right),
+            (car(3), Direction::Up),
+        ]);
+        assert_eq!("1R 2L 1R 3U".parse::<PGInput>()?, input);
+
+        input.retain(|&(car, _)| car.get() == 1);
+        assert_eq!("1R 1R".parse::<PGInput>()?, input);
+
+        input.dedup();
+        assert_eq!("1R".parse::<PGInput>()?, input);
+        let mut input = "1R 2L 1R 1L 1L 1L".parse::<PGInput>()?;
+        input.dedup();
+        assert_eq!("1R 2L 1R 1L".parse::<PGInput>()?, input);
+
+        Ok(())
+    }
+
+    #[test]
+    fn shorthands() -> Result<(), Box<dyn Error>> {
+        assert!(PGInput::default().is_empty());
+        assert_eq!(0, PGInput::default().car_count());
+
+        let moves = vec![
+            (CarId::new(2).unwrap(), Direction::Left),
+            (CarId::new(1).unwrap(), Direction::Right),
+            (CarId::new(2).unwrap(), Direction::Right),
+        ];
+        let input = PGInput::new(moves.clone());
+        assert!(!input.is_empty());
+        assert_eq!(moves.len(), input.len());
+        assert_eq!(moves[0], input[0]);
+        assert_eq!(2, input.car_count());
+        assert_eq!(1, "3U 3U 3D".parse::<PGInput>()?.car_count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn move_ownership() -> Result<(), Box<dyn Error>> {
+        let input = "1R 2L 1R 3U".parse::<PGInput>()?;
+        let mut moves = input.clone().into_moves();
+        moves.reserve(100);
+        let capacity = moves.capacity();
+        let mut roundtrip = PGInput::from_moves(moves);
+        assert_eq!(input, roundtrip);
+        assert_eq!(capacity, roundtrip.moves_mut().capacity());
+        assert_eq!(capacity, roundtrip.into_moves().capacity());
+
+        assert_eq!(PGInput::default(), PGInput::from_moves(Vec::new()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn car_moves() -> Result<(), Box<dyn Error>> {
+        let car = |id| CarId::new(id).unwrap();
+
+        let input = "1R 2D 1L 2U 3U 3D 1R 2D".parse::<PGInput>()?;
+        assert_eq!(Some((1, 7)), input.car_range(car(2)));
+        assert_eq!(
+            vec![
+                (1, Direction::Down),
+                (3, Direction::Up),
+                (7, Direction::Down)
+            ],
+            input.moves_for_car(car(2)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some((4, 5)), input.car_range(car(3)));
+        assert_eq!(None, input.car_range(car(4)));
+        assert_eq!(0, input.moves_for_car(car(4)).count());
+
+        let single = "5L".parse::<PGInput>()?;
+        assert_eq!(Some((0, 0)), single.car_range(car(5)));
+
+        let input = "1R 2L 1L 1R".parse::<PGInput>()?;
+        assert_eq!(
+            vec![Direction::Right, Direction::Left, Direction::Right],
+            input.car_move_sequence(car(1))
+        );
+        assert!(input.car_move_sequence(car(3)).is_empty());
+        let histogram = input.car_histogram();
+        for id in input.unique_cars() {
+            assert_eq!(histogram[&id], input.car_move_sequence(id).len());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn windows() -> Result<(), Box<dyn Error>> {
+        let input = "1R 2D 1L 3U".parse::<PGInput>()?;
+        let windows = input.window_iter(2).collect::<Vec<_>>();
+        assert_eq!(3, windows.len());
+        for (i, window) in windows.into_iter().enumerate() {
+            assert_eq!(&input.moves()[i..i + 2], window);
+        }
+        assert_eq!(
+            vec![input.moves()],
+            input.window_iter(input.len()).collect::<Vec<_>>()
+        );
+        assert_eq!(0, input.window_iter(input.len() + 1).count());
+        assert_eq!(0, input.window_iter(0).count());
+        assert_eq!(0, PGInput::default().window_iter(1).count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_moves() -> Result<(), Box<dyn Error>> {
+        let normalize = |moves: &str| -> Result<PGInput, PGInputParseError> {
+            Ok(moves.parse::<PGInput>()?.normalize())
+        };
+
+        assert_eq!(PGInput::default(), normalize("1R 1L")?);
+        assert_eq!("2L".parse::<PGInput>()?, normalize("1R 2L 1L")?);
+        assert_eq!(PGInput::default(), normalize("1R 2L 2R 1L")?);
+        assert_eq!(
+            "2R 1U".parse::<PGInput>()?,
+            normalize("1U 3L 3R 2D 2U 2R 1D 1U")?
+        );
+
+        assert_eq!("2U 1R".parse::<PGInput>()?, normalize("1R 2U 1L 3D 1R 3U")?);
+
+        for clean in ["", "1R", "1R 1R 2L", "1R 2L 1R", "1U 2D 1U 2D"] {
+            let input = clean.parse::<PGInput>()?;
+            assert_eq!(input, input.normalize());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn consecutive_cancels() -> Result<(), Box<dyn Error>> {
+        let deduplicate = |moves: &str| -> Result<PGInput, PGInputParseError> {
+            let mut input = moves.parse::<PGInput>()?;
+            let cancels = input.has_consecutive_cancels();
+            input.deduplicate_consecutive();
+            assert_eq!(cancels, input.len() != moves.split_whitespace().count());
+            assert!(!input.has_consecutive_cancels());
+            Ok(input)
+        };
+
+        assert_eq!(PGInput::default(), deduplicate("1R 1L")?);
+        assert_eq!("1R 2L 1L".parse::<PGInput>()?, deduplicate("1R 2L 1L")?);
+        assert_eq!("1R".parse::<PGInput>()?, deduplicate("2L 2R 1R")?);
+        // pairs brought together by removing another are removed too
+        assert_eq!(PGInput::default(), deduplicate("1R 2L 2R 1L")?);
+        assert_eq!(
+            "3U".parse::<PGInput>()?,
+            deduplicate("1U 2D 2U 1D 3U 1R 1L")?
+        );
+
+        for clean in ["", "1R", "1R 1R 2L", "1R 2L 1L", "1U 2D 1U 2D"] {
+            let input = clean.parse::<PGInput>()?;
+            assert!(!input.has_consecutive_cancels());
+            assert_eq!(input, deduplicate(clean)?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_moves() -> Result<(), Box<dyn Error>> {
+        let mut input = "2L 1R 2R 1L".parse::<PGInput>()?;
+        input.sort_by_car();
+        assert_eq!("1R 1L 2L 2R".parse::<PGInput>()?, input);
+
+        // the moves of each car stay in the order they were made
+        let input = "3U 1R 2D 1L 3D 1L 2U 1R".parse::<PGInput>()?;
+        let canonical = input.canonicalize();
+        assert_eq!("1R 1L 1L 1R 2D 2U 3U 3D".parse::<PGInput>()?, canonical);
+        assert_eq!("3U 1R 2D 1L 3D 1L 2U 1R".parse::<PGInput>()?, input);
+        for car in input.unique_cars() {
+            assert!(
+                input
+                    .moves_for_car(car)
+                    .map(|(_, dir)| dir)
+                    .eq(canonical.moves_for_car(car).map(|(_, dir)| dir))
+            );
+        }
+        assert_eq!(canonical, canonical.canonicalize());
+
+        // moving cars which do not block each other in a different interleaving gives the same
+        // final state, and the same canonical form
+        let initial = crate::parse_map::<u8>("oo...\n.....\n..12.\n..12.");
+        let a = "2U 3U 2U 3U".parse::<PGInput>()?;
+        let b = "3U 3U 2U 2U".parse::<PGInput>()?;
+        let (mut after_a, mut after_b) = (initial.clone(), initial.clone());
+        assert_eq!(Ok(4), a.apply(&mut after_a));
+        assert_eq!(Ok(4), b.apply(&mut after_b));
+        assert_eq!(hash_state(&after_a), hash_state(&after_b));
+        assert_eq!(a.canonicalize(), b.canonicalize());
+        assert_ne!(a.canonicalize(), "2U 3U".parse::<PGInput>()?.canonicalize());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_moves() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>(
+            r#"
+        oo...
+        1....
+        1....
+        .....
+        "#,
+        );
+        let before = format!("{initial:?}");
+
+        assert_eq!(Ok(()), PGInput::default().validate(&initial));
+        assert_eq!(
+            Ok(()),
+            "1R 1R 2U 2D 2D".parse::<PGInput>()?.validate(&initial)
+        );
+        assert_eq!(Err(2), "1R 2D 1D 1R".parse::<PGInput>()?.validate(&initial));
+        assert_eq!(before, format!("{initial:?}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn apply_in_place() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>(
+            r#"
+        oo...
+        1....
+        1....
+        .....
+        "#,
+        );
+
+        let mut state = initial.clone();
+        assert_eq!(
+            Ok(5),
+            "1R 1R 2U 2D 2D".parse::<PGInput>()?.apply(&mut state)
+        );
+        assert_eq!(2, *state.cars()[0].0.column());
+        assert_eq!(2, *state.cars()[1].0.row());
+
+        // the moves before the crash are kept
+        let mut state = initial.clone();
+        assert_eq!(Err(2), "1R 2U 2U 1R".parse::<PGInput>()?.apply(&mut state));
+        assert_eq!(1, *state.cars()[0].0.column());
+        assert_eq!(0, *state.cars()[1].0.row());
+
+        Ok(())
+    }
+
+    #[test]
+    fn edit_distance() -> Result<(), Box<dyn Error>> {
+        let empty = PGInput::default();
+        let a = "1R".parse::<PGInput>()?;
+        let ab = "1R 2U".parse::<PGInput>()?;
+        let ba = "2U 1R".parse::<PGInput>()?;
+
+        assert_eq!(0, empty.edit_distance(&empty));
+        assert_eq!(1, a.edit_distance(&empty));
+        assert_eq!(1, empty.edit_distance(&a));
+        assert_eq!(0, a.edit_distance(&a));
+        assert_eq!(2, ab.edit_distance(&ba));
+
+        let x = "1R 2U 3L 1L".parse::<PGInput>()?;
+        let y = "2U 3L 3R".parse::<PGInput>()?;
+        let z = "1R 1R 3L 1L 2D".parse::<PGInput>()?;
+        assert_eq!(2, x.edit_distance(&y));
+        assert!(x.edit_distance(&z) <= x.edit_distance(&y) + y.edit_distance(&z));
+        assert!(y.edit_distance(&z) <= y.edit_distance(&x) + x.edit_distance(&z));
+        assert!(x.edit_distance(&y) <= x.edit_distance(&z) + z.edit_distance(&y));
+
+        Ok(())
+    }
+
+    #[test]
+    fn common_prefix() -> Result<(), Box<dyn Error>> {
+        let abc = "1R 2U 3L".parse::<PGInput>()?;
+        let abd = "1R 2U 3R".parse::<PGInput>()?;
+        let a = "1R".parse::<PGInput>()?;
+        let empty = PGInput::default();
+
+        let prefix = PGInput::common_prefix(&abc, &abd);
+        assert_eq!("1R 2U".parse::<PGInput>()?.moves(), prefix.moves());
+        assert_eq!(prefix.moves(), PGInput::common_prefix(&abd, &abc).moves());
+        assert!(PGInput::common_prefix(&empty, &abc).moves().is_empty());
+        assert!(PGInput::common_prefix(&abc, &empty).moves().is_empty());
+        assert_eq!(a.moves(), PGInput::common_prefix(&a, &a).moves());
+
+        Ok(())
+    }
+
+    #[test]
+    fn subsequences() -> Result<(), Box<dyn Error>> {
+        let empty = PGInput::default();
+        let a = "1R".parse::<PGInput>()?;
+        let ab = "1R 2L".parse::<PGInput>()?;
+        let b = "2L".parse::<PGInput>()?;
+
+        assert!(empty.is_subsequence_of(&empty));
+        assert!(empty.is_subsequence_of(&ab));
+        assert!(a.is_subsequence_of(&ab));
+        assert!(!a.is_subsequence_of(&b));
+        assert!(!ab.is_subsequence_of(&a));
+        assert!(!"2L 1R".parse::<PGInput>()?.is_subsequence_of(&ab));
+        assert!(ab.is_subsequence_of(&"3D 1R 3U 2L".parse()?));
+
+        let x = "1R 2L 3D".parse::<PGInput>()?;
+        let y = "1R 3D 2L".parse::<PGInput>()?;
+        let lcs = PGInput::longest_common_subsequence(&x, &y);
+        assert!(["1R 3D".parse::<PGInput>()?, ab.clone()].contains(&lcs));
+        assert!(lcs.is_subsequence_of(&x) && lcs.is_subsequence_of(&y));
+        assert_eq!(x, PGInput::longest_common_subsequence(&x, &x));
+        assert_eq!(empty, PGInput::longest_common_subsequence(&x, &empty));
+        assert_eq!(empty, PGInput::longest_common_subsequence(&a, &b));
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_moves() -> Result<(), Box<dyn Error>> {
+        let input = "1R 2U 3L 1L 2D".parse::<PGInput>()?;
+
+        assert_eq!(
+            "1R 2U 3L".parse::<PGInput>()?.moves(),
+            input.truncate(3).moves()
+        );
+        assert_eq!("1L 2D".parse::<PGInput>()?.moves(), input.skip(3).moves());
+        let (prefix, suffix) = input.split_at(3);
+        assert_eq!(input.truncate(3).moves(), prefix.moves());
+        assert_eq!(input.skip(3).moves(), suffix.moves());
+
+        // out of range
+        assert_eq!(input.moves(), input.truncate(10).moves());
+        assert!(input.skip(10).moves().is_empty());
+        let (prefix, suffix) = input.split_at(10);
+        assert_eq!(input.moves(), prefix.moves());
+        assert!(suffix.moves().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn combine_moves() -> Result<(), Box<dyn Error>> {
+        let a = "1R 2U".parse::<PGInput>()?;
+        let b = "3L 1L 2D".parse::<PGInput>()?;
+
+        let merged = PGInput::merge(&a, &b);
+        assert_eq!(a.moves().len() + b.moves().len(), merged.moves().len());
+        assert_eq!("1R 2U 3L 1L 2D".parse::<PGInput>()?.moves(), merged.moves());
+
+        assert_eq!(
+            "1R 3L 2U 1L 2D".parse::<PGInput>()?.moves(),
+            PGInput::interleave(&a, &b).moves()
+        );
+        assert_eq!(
+            "3L 1R 1L 2U 2D".parse::<PGInput>()?.moves(),
+            PGInput::interleave(&b, &a).moves()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn random_input() {
+        let mut rng = StdRand::with_seed(1337);
+        let input = PGInput::random(3, 10, &mut rng);
+        assert_eq!(10, input.len());
+        for (car, _) in input.moves() {
+            assert!((1..=3).contains(&car.get()));
+        }
+        assert_ne!(input, PGInput::random(3, 10, &mut rng));
+        assert!(PGInput::random(3, 0, &mut rng).is_empty());
+
+        // every car and direction is eventually chosen
+        let input = PGInput::random(3, 1000, &mut rng);
+        assert_eq!(3, input.unique_cars().len());
+        let directions = input.moves().iter().map(|(_, dir)| *dir);
+        assert_eq!(4, directions.collect::<HashSet<_>>().len());
+    }
+
+    #[test]
+    #[should_panic(expected = "There should be a car to move.")]
+    fn random_input_without_cars() {
+        PGInput::random(0, 5, &mut StdRand::with_seed(1337));
+    }
+
+    #[test]
+    fn random_subsequence() -> Result<(), Box<dyn Error>> {
+        let mut rng = StdRand::with_seed(1337);
+        let input = "1R 2U 3L 1L 2D 3R 4U".parse::<PGInput>()?;
+
+        for len in 0..10 {
+            let subsequence = input.random_subsequence(len, &mut rng);
+            assert_eq!(len.min(input.moves().len()), subsequence.moves().len());
+            // every move is found after the last one that was matched
+            let mut remaining = input.moves().iter();
+            for chosen in subsequence.moves() {
+                assert!(remaining.any(|other| other == chosen));
+            }
+        }
+
+        // 35 ways to pick 3 of 7 moves, so a few seeds should not all agree
+        let picks = (0..8)
+            .map(|seed| input.random_subsequence(3, &mut StdRand::with_seed(seed)))
+            .collect::<HashSet<_>>();
+        assert!(picks.len() > 1);
+        assert!(picks.iter().all(|pick| pick.is_subsequence_of(&input)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn has_cycle() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>(
+            r#"
+        .oo.
+        1...
+        1...
+        "#,
+        );
+
+        assert_eq!(
+            Some((0, 2)),
+            "1R 1L 1R 1L".parse::<PGInput>()?.has_cycle(&initial)
+        );
+        assert_eq!(
+            Some((0, 4)),
+            "1R 2U 1L 2D 1R".parse::<PGInput>()?.has_cycle(&initial)
+        );
+        assert_eq!(None, "1R 2U 1L".parse::<PGInput>()?.has_cycle(&initial));
+        // the repeated state is never reached because 2D is invalid
+        assert_eq!(None, "1R 1L 2D 1R".parse::<PGInput>()?.has_cycle(&initial));
+
+        Ok(())
+    }
+
+    #[test]
+    fn prefix_up_to_solution() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n....");
+
+        let input = "1R 1L 2D 2U 2D".parse::<PGInput>()?;
+        assert_eq!(
+            Some("1R 1L 2D".parse()?),
+            input.prefix_up_to_solution(&initial)
+        );
+        let solved = "2D".parse::<PGInput>()?;
+        assert_eq!(Some(solved.clone()), solved.prefix_up_to_solution(&initial));
+        assert_eq!(
+            None,
+            "1R 1L".parse::<PGInput>()?.prefix_up_to_solution(&initial)
+        );
+        // 2U is invalid, so the puzzle is never solved
+        assert_eq!(
+            None,
+            "2U 2D".parse::<PGInput>()?.prefix_up_to_solution(&initial)
+        );
+        assert_eq!(None, PGInput::default().prefix_up_to_solution(&initial));
+
+        let already_solved = crate::parse_map::<u8>("oo..\n....");
+        for input in ["", "1R", "1R 1L"] {
+            assert_eq!(
+                Some(PGInput::default()),
+                input
+                    .parse::<PGInput>()?
+                    .prefix_up_to_solution(&already_solved)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        let errors = [
+            (
+                PGInputParseError::InvalidDirection {
+                    ch: 'X',
+                    position: 3,
+                },
+                "1R 2L 1U 3X".parse::<PGInput>().unwrap_err(),
+                "invalid direction 'X' at token position 3",
+            ),
+            (
+                PGInputParseError::CarIdZero { position: 0 },
+                "0R".parse::<PGInput>().unwrap_err(),
+                "car id 0 at token position 0; car ids start from 1",
+            ),
+            (
+                PGInputParseError::InvalidCarId {
+                    token: "a".to_string(),
+                    position: 1,
+                },
+                "1R aR".parse::<PGInput>().unwrap_err(),
+                "invalid car id `a` at token position 1",
+            ),
+            (
+                PGInputParseError::InvalidToken {
+                    token: "R".to_string(),
+                    position: 1,
+                },
+                "1R R".parse::<PGInput>().unwrap_err(),
+                "invalid move `R` at token position 1",
+            ),
+            (
+                PGInputParseError::InvalidToken {
+                    token: "1→Right".to_string(),
+                    position: 0,
+                },
+                PGInput::from_algebraic("1→Right").unwrap_err(),
+                "invalid move `1→Right` at token position 0",
+            ),
+            (
+                PGInputParseError::CarIdZero { position: 2 },
+                PGInput::from_algebraic("Car1→Up, Car2→Left, Car0→Up").unwrap_err(),
+                "car id 0 at token position 2; car ids start from 1",
+            ),
+            (
+                PGInputParseError::UnexpectedEnd,
+                PGInput::from_bytes(&[1, 3, 2]).unwrap_err(),
+                "unexpected end of input partway through a move",
+            ),
+        ];
+        for (expected, actual, message) in errors {
+            assert_eq!(expected, actual);
+            assert_eq!(message, actual.to_string());
+        }
+
+        // usable wherever other errors are
+        let boxed: Box<dyn Error> = Box::new(PGInputParseError::UnexpectedEnd);
+        assert!(boxed.source().is_none());
+    }
+
+    #[test]
+    fn sample_states() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo....\n....1.\n....1.\n......");
+        let input = "1R 1R 2D 1R 1R 1L 1L 2U 1R 1L 1L 1L".parse::<PGInput>()?;
+        let hash = |state: &State<u8>| hash_state(state).unwrap();
+
+        let samples = input.sample_states(&initial, 3).unwrap();
+        assert_eq!(3, samples.len());
+        for (sample, moves) in samples.iter().zip([0, 4, 8]) {
+            // the sample is a valid board in the same state as after that many moves
+            sample.board()?;
+            let mut expected = initial.clone();
+            input.truncate(moves).apply(&mut expected).unwrap();
+            assert_eq!(hash(&expected), hash(sample));
+        }
+        assert_eq!(
+            hash(&initial),
+            hash(&input.sample_states(&initial, 1).unwrap()[0])
+        );
+        assert!(input.sample_states(&initial, 0).unwrap().is_empty());
+
+        // the sixth move runs into the objective car
+        let crashing = "1R 1R 1R 1R 1L 2U 1L 1L 1L 1L 1L 1L".parse::<PGInput>()?;
+        assert_eq!(Err(5), crashing.validate(&initial));
+        assert_eq!(Err(5), crashing.sample_states(&initial, 3).map(|_| ()));
+        assert_eq!(Err(5), crashing.sample_states(&initial, 2).map(|_| ()));
+        // the invalid move is never reached if sampling stops short of it
+        assert_eq!(1, crashing.sample_states(&initial, 1).unwrap().len());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,863 @@
+//! Fuzzer for [`parking_game`] puzzles. This is meant as an exercise for learning how to use
+//! LibAFL, and potentially not great for "real" applications, if they exist.
+
+pub mod analysis;
+pub mod compact;
+pub mod corpus;
+pub mod coverage;
+pub mod error;
+pub mod executor;
+pub mod feedbacks;
+pub mod generator;
+pub mod hash;
+pub mod input;
+pub mod map_format;
+pub mod mutators;
+pub mod observers;
+pub mod plugin;
+pub mod pool;
+pub mod schedulers;
+pub mod serialization;
+pub mod session;
+pub mod solver;
+pub mod stages;
+pub mod stats;
+pub mod types;
+
+use parking_game::{BoardValue, Car, Direction, Orientation, Position, State};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{fs, io};
+
+/// An error encountered while parsing a map with [`try_parse_map`].
+#[derive(Debug)]
+pub enum MapParseError {
+    /// The map file could not be read.
+    Io(io::Error),
+    /// The map contained no rows.
+    Empty,
+    /// The dimensions of the map are too large for the board's value type.
+    TooLarge,
+    /// The row with the provided index was not the same width as the first row.
+    RaggedRow(usize),
+    /// There was no objective car (`o`) in the map.
+    MissingObjective,
+    /// The car with the provided name was not laid out in a single straight line.
+    MisshapenCar(char),
+    /// The cars could not be placed on the board.
+    InvalidState(String),
+}
+
+impl Display for MapParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapParseError::Io(e) => write!(f, "could not read the map: {e}"),
+            MapParseError::Empty => write!(f, "the map is empty"),
+            MapParseError::TooLarge => write!(f, "the map is too large"),
+            MapParseError::RaggedRow(row) => {
+                write!(f, "row {row} is not the same width as the first row")
+            }
+            MapParseError::MissingObjective => write!(f, "no objective car (`o`) was found"),
+            MapParseError::MisshapenCar(car) => write!(f, "car `{car}` is not in a straight line"),
+            MapParseError::InvalidState(e) => write!(f, "the map is not a valid state: {e}"),
+        }
+    }
+}
+
+impl Error for MapParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MapParseError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MapParseError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Parses a map with the following rules:
+/// 1. Empty spaces are denoted with `.`.
+/// 2. The car which must be moved to the objective is referenced with `o`. This will be index 1.
+/// 3. All other cars are uniquely named. They will be indexed in lexicographical order.
+/// 4. All cars are at least length 2.
+/// 5. Wall cells, which no car may enter, are denoted with `#`. They are empty in the parsed state;
+///    use [`parse_map_with_options`] to find where they are.
+///
+/// Any map not following this pattern is not guaranteed to be parsed correctly. Panics if the map
+/// could not be parsed; see [`try_parse_map`] for a non-panicking variant.
+pub fn parse_map<T>(map: &str) -> State<T>
+where
+    T: BoardValue,
+{
+    try_parse_map(map).unwrap()
+}
+
+/// Parses a map following the same rules as [`parse_map`], returning an error if the map is
+/// malformed rather than panicking.
+pub fn try_parse_map<T>(map: &str) -> Result<State<T>, MapParseError>
+where
+    T: BoardValue,
+{
+    let map = map.trim_ascii();
+    let first = map.lines().next().ok_or(MapParseError::Empty)?.trim_ascii();
+    let width = first.len();
+    let rows = map
+        .lines()
+        .count()
+        .try_into()
+        .map_err(|_| MapParseError::TooLarge)?;
+    let cols: T = width.try_into().map_err(|_| MapParseError::TooLarge)?;
+
+    let mut cars: HashMap<char, (Position<T>, Orientation, T)> = HashMap::new();
+
+    let mut prev = None;
+    for (ridx, row) in map.lines().enumerate() {
+        let row = row.trim_ascii();
+        if row.len() != width {
+            return Err(MapParseError::RaggedRow(ridx));
+        }
+        let ridx = ridx.try_into().map_err(|_| MapParseError::TooLarge)?;
+        for (cidx, col) in row.chars().enumerate() {
+            let cidx: T = cidx.try_into().map_err(|_| MapParseError::TooLarge)?;
+            match (prev, col) {
+                (Some(car), next) => {
+                    match cars.entry(car) {
+                        Entry::Occupied(mut e) => {
+                            let entry = e.get_mut();
+                            if !(entry.0.row() == &ridx || entry.0.column() == &(cidx - T::one())) {
+                                return Err(MapParseError::MisshapenCar(car));
+                            }
+                            entry.2 += T::one();
+                        }
+                        Entry::Vacant(e) => {
+                            if car == next {
+                                // same car: we are in the same row, init with left-right
+                                e.insert((
+                                    (ridx, cidx - T::one()).into(),
+                                    Orientation::LeftRight,
+                                    T::one(),
+                                ));
+                            } else {
+                                // different car: different row, init with up-down
+                                e.insert((
+                                    (ridx, cidx - T::one()).into(),
+                                    Orientation::UpDown,
+                                    T::one(),
+                                ));
+                            }
+                        }
+                    }
+                    prev = if next == '.' || next == '#' {
+                        None
+                    } else {
+                        Some(next)
+                    };
+                }
+                (None, '.' | '#') => {
+                    // do nothing
+                }
+                (None, next) => {
+                    prev = Some(next);
+                }
+            }
+        }
+
+        if let Some(car) = prev.take() {
+            match cars.entry(car) {
+                Entry::Occupied(mut e) => {
+                    let entry = e.get_mut();
+                    if !(entry.0.row() == &ridx || entry.0.column() == &(cols - T::one())) {
+                        return Err(MapParseError::MisshapenCar(car));
+                    }
+                    entry.2 += T::one();
+                }
+                Entry::Vacant(e) => {
+                    // this has to be up-down orientation: we haven't seen it earlier
+                    e.insert((
+                        (ridx, cols - T::one()).into(),
+                        Orientation::UpDown,
+                        T::one(),
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut state = State::empty((rows, cols)).map_err(|_| MapParseError::TooLarge)?;
+    let mut inserted = Vec::new();
+    inserted.push((
+        'o',
+        cars.remove(&'o').ok_or(MapParseError::MissingObjective)?,
+    ));
+    inserted.extend(cars);
+    inserted[1..].sort_by_key(|(name, _)| *name); // lexographical sort
+
+    let mut board = state
+        .board_mut()
+        .map_err(|e| MapParseError::InvalidState(e.to_string()))?;
+    for (name, (position, orientation, len)) in inserted {
+        let car = Car::new(len, orientation).ok_or(MapParseError::MisshapenCar(name))?;
+        board
+            .add_car(position, car)
+            .map_err(|e| MapParseError::InvalidState(e.to_string()))?;
+    }
+    drop(board);
+
+    Ok(state)
+}
+
+/// Options for parsing a map with [`parse_map_with_options`].
+#[derive(Debug, Clone, Copy)]
+pub struct MapParseOptions<T> {
+    /// The cell through which the objective car leaves the board, which must be at either end of
+    /// its lane. By default, this is the right (or, for a car oriented [`Orientation::UpDown`],
+    /// bottom) edge of the board.
+    pub exit_position: Option<Position<T>>,
+}
+
+impl<T> Default for MapParseOptions<T> {
+    fn default() -> Self {
+        Self {
+            exit_position: None,
+        }
+    }
+}
+
+/// What a map says about a puzzle beyond the initial state: where the objective car leaves the
+/// board, and which cells are walls. Provide it to [`feedbacks::SolvedFeedback::with_map`] to
+/// decide when a puzzle is solved, and its walls to [`executor::PGExecutorBuilder::with_walls`] and
+/// [`observers::ViewObserver::with_walls`] so that no car may enter them.
+#[derive(Debug, Clone)]
+pub struct MapMetadata<T> {
+    exit_position: Position<T>,
+    exit_direction: Direction,
+    walls: Vec<Position<T>>,
+}
+
+impl<T> MapMetadata<T>
+where
+    T: BoardValue,
+{
+    /// The metadata of a map with the default exit, at the right (or bottom) edge of the board in
+    /// the objective car's lane.
+    ///
+    /// Panics if the state has no objective car.
+    pub fn new(state: &State<T>) -> Self {
+        let (position, car) = state
+            .cars()
+            .first()
+            .expect("The map should have an objective car.");
+        let dimensions = state.dimensions();
+        match car.orientation() {
+            Orientation::LeftRight => Self {
+                exit_position: (*position.row(), *dimensions.columns() - T::one()).into(),
+                exit_direction: Direction::Right,
+                walls: Vec::new(),
+            },
+            Orientation::UpDown => Self {
+                exit_position: (*dimensions.rows() - T::one(), *position.column()).into(),
+                exit_direction: Direction::Down,
+                walls: Vec::new(),
+            },
+        }
+    }
+
+    /// The metadata of a map with the provided exit, or [`None`] if the exit is not at either end
+    /// of the objective car's lane.
+    pub fn with_exit(state: &State<T>, exit_position: Position<T>) -> Option<Self> {
+        let (position, car) = state.cars().first()?;
+        let last_row = *state.dimensions().rows() - T::one();
+        let last_column = *state.dimensions().columns() - T::one();
+        let (row, column) = (*exit_position.row(), *exit_position.column());
+        let exit_direction = match car.orientation() {
+            Orientation::LeftRight if row == *position.row() && column.is_zero() => Direction::Left,
+            Orientation::LeftRight if row == *position.row() && column == last_column => {
+                Direction::Right
+            }
+            Orientation::UpDown if column == *position.column() && row.is_zero() => Direction::Up,
+            Orientation::UpDown if column == *position.column() && row == last_row => {
+                Direction::Down
+            }
+            _ => return None,
+        };
+        Some(Self {
+            exit_position,
+            exit_direction,
+            walls: Vec::new(),
+        })
+    }
+
+    /// Whether the wall at the provided position lies in the objective car's lane, between it and
+    /// the exit.
+    fn blocks_exit(&self, state: &State<T>, wall: &Position<T>) -> bool {
+        let (position, _) = &state.cars()[0];
+        match self.exit_direction {
+            Direction::Left => wall.row() == position.row() && wall.column() < position.column(),
+            Direction::Right => wall.row() == position.row() && wall.column() > position.column(),
+            Direction::Up => wall.column() == position.column() && wall.row() < position.row(),
+            Direction::Down => wall.column() == position.column() && wall.row() > position.row(),
+        }
+    }
+}
+
+impl<T> MapMetadata<T> {
+    /// The cell through which the objective car leaves the board.
+    pub fn exit_position(&self) -> &Position<T> {
+        &self.exit_position
+    }
+
+    /// The direction in which the objective car must move to reach the exit.
+    pub fn exit_direction(&self) -> Direction {
+        self.exit_direction
+    }
+
+    /// The cells which no car may enter, denoted with `#` in the map.
+    pub fn walls(&self) -> &[Position<T>] {
+        &self.walls
+    }
+}
+
+/// Parses a map as with [`parse_map`], alongside its [`MapMetadata`] as configured by the provided
+/// options. Panics if the map could not be parsed, if the exit is not at either end of the
+/// objective car's lane, or if a wall lies between the objective car and the exit.
+pub fn parse_map_with_options<T>(
+    map: &str,
+    options: MapParseOptions<T>,
+) -> (State<T>, MapMetadata<T>)
+where
+    T: BoardValue,
+{
+    let state = parse_map(map);
+    let mut metadata = match options.exit_position {
+        Some(exit) => MapMetadata::with_exit(&state, exit)
+            .expect("The exit should be at either end of the objective car's lane."),
+        None => MapMetadata::new(&state),
+    };
+    // the map was parsed, so every position fits in T
+    for (ridx, row) in map.trim_ascii().lines().enumerate() {
+        for (cidx, _) in row.trim_ascii().match_indices('#') {
+            let wall: Position<T> =
+                (ridx.try_into().ok().unwrap(), cidx.try_into().ok().unwrap()).into();
+            assert!(
+                !metadata.blocks_exit(&state, &wall),
+                "No wall should lie between the objective car and the exit."
+            );
+            metadata.walls.push(wall);
+        }
+    }
+    (state, metadata)
+}
+
+/// Maps loaded by [`load_maps_from_dir`], alongside the paths they were loaded from.
+pub type LoadedMaps<T> = Vec<(PathBuf, State<T>)>;
+
+/// Loads all the maps (files with the `.map` extension) in the provided directory, sorted by their
+/// file name. Every map is attempted; if any of them fail to load, only the failures are returned.
+pub fn load_maps_from_dir<T>(path: &Path) -> Result<LoadedMaps<T>, Vec<(PathBuf, MapParseError)>>
+where
+    T: BoardValue,
+{
+    let mut paths = fs::read_dir(path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| vec![(path.to_path_buf(), e.into())])?;
+    paths.retain(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "map"));
+    paths.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+
+    let mut maps = Vec::new();
+    let mut errors = Vec::new();
+    for path in paths {
+        match fs::read_to_string(&path)
+            .map_err(MapParseError::from)
+            .and_then(|map| try_parse_map(&map))
+        {
+            Ok(state) => maps.push((path, state)),
+            Err(e) => errors.push((path, e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(maps)
+    } else {
+        Err(errors)
+    }
+}
+
+/// An error encountered while reading the command line with [`FuzzerConfig::from_args`], or while
+/// checking the configuration with [`FuzzerConfig::validate`].
+#[derive(Debug)]
+pub enum ConfigError {
+    /// No map was provided.
+    MissingMap,
+    /// The provided map does not exist.
+    MapNotFound(PathBuf),
+    /// The flag with the provided name was not followed by a value.
+    MissingValue(&'static str),
+    /// The value provided for the flag with the provided name could not be parsed.
+    InvalidValue(&'static str, String),
+    /// The provided argument was not recognised.
+    UnknownArgument(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::MissingMap => write!(f, "provide the path to the desired map"),
+            ConfigError::MapNotFound(path) => {
+                write!(f, "the map {} does not exist", path.display())
+            }
+            ConfigError::MissingValue(flag) => write!(f, "`{flag}` requires a value"),
+            ConfigError::InvalidValue(flag, value) => {
+                write!(f, "`{value}` is not a valid value for `{flag}`")
+            }
+            ConfigError::UnknownArgument(arg) => write!(f, "unknown argument `{arg}`"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Configuration for the fuzzer, as provided on the command line.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FuzzerConfig {
+    /// The map to solve, provided with `--map` or as the first positional argument.
+    pub map_path: PathBuf,
+    /// The directory to save solutions in, provided with `--output`.
+    pub output_dir: Option<PathBuf>,
+    /// The maximum number of entries to keep in the corpus, provided with `--max-corpus`. Once the
+    /// corpus is full, the least recently used entry is evicted to make room for a new one; see
+    /// [`corpus::BoundedCorpus`]. Zero is rejected, as the corpus could then hold nothing.
+    pub max_corpus: Option<usize>,
+    /// The seed for the random number generator, provided with `--seed`. Fuzzing with the same
+    /// seed replays the same run.
+    pub seed: Option<u64>,
+    /// The maximum number of seconds spent executing a single input, provided with `--timeout`.
+    pub timeout_secs: Option<u64>,
+    /// Whether to print more about what the fuzzer is doing, enabled with `--verbose`.
+    pub verbose: bool,
+    /// A solution to print step by step instead of fuzzing, provided with `--visualize`.
+    pub visualize: Option<PathBuf>,
+}
+
+impl FuzzerConfig {
+    /// Parses the configuration from the provided arguments, e.g. [`std::env::args_os`]. As usual,
+    /// the first argument is the name of the program and is ignored.
+    pub fn from_args<I>(args: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OsString>,
+    {
+        fn value(
+            args: &mut impl Iterator<Item = OsString>,
+            flag: &'static str,
+        ) -> Result<OsString, ConfigError> {
+            args.next().ok_or(ConfigError::MissingValue(flag))
+        }
+
+        fn parsed<V: FromStr>(
+            args: &mut impl Iterator<Item = OsString>,
+            flag: &'static str,
+        ) -> Result<V, ConfigError> {
+            let value = value(args, flag)?;
+            value
+                .to_str()
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| ConfigError::InvalidValue(flag, value.to_string_lossy().into()))
+        }
+
+        let mut args = args.into_iter().map(Into::into).skip(1);
+        let mut map_path = None;
+        let mut config = Self::default();
+        while let Some(arg) = args.next() {
+            match arg.to_str() {
+                Some("--map") => map_path = Some(value(&mut args, "--map")?.into()),
+                Some("--output") => config.output_dir = Some(value(&mut args, "--output")?.into()),
+                Some("--max-corpus") => {
+                    let max_corpus: NonZeroUsize = parsed(&mut args, "--max-corpus")?;
+                    config.max_corpus = Some(max_corpus.get());
+                }
+                Some("--seed") => config.seed = Some(parsed(&mut args, "--seed")?),
+                Some("--timeout") => config.timeout_secs = Some(parsed(&mut args, "--timeout")?),
+                Some("--verbose") => config.verbose = true,
+                Some("--visualize") => {
+                    config.visualize = Some(value(&mut args, "--visualize")?.into())
+                }
+                _ if map_path.is_none() && !arg.to_string_lossy().starts_with("--") => {
+                    map_path = Some(arg.into());
+                }
+                _ => return Err(ConfigError::UnknownArgument(arg.to_string_lossy().into())),
+            }
+        }
+        config.map_path = map_path.ok_or(ConfigError::MissingMap)?;
+        Ok(config)
+    }
+
+    /// Checks that the configuration is usable, i.e. that the map exists.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.map_path.exists() {
+            Ok(())
+        } else {
+            Err(ConfigError::MapNotFound(self.map_path.clone()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ConfigError, FuzzerConfig, MapMetadata, MapParseError, MapParseOptions, load_maps_from_dir,
+        parse_map, parse_map_with_options,
+    };
+    use parking_game::{Direction, Orientation};
+    use std::error::Error;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn map_options() {
+        let (state, map) = parse_map_with_options::<u8>("....\n.oo.\n....", Default::default());
+        assert_eq!(
+            (1, 3),
+            (*map.exit_position().row(), *map.exit_position().column())
+        );
+        assert_eq!(Direction::Right, map.exit_direction());
+
+        let (_, map) = parse_map_with_options::<u8>(
+            "....\n.oo.\n....",
+            MapParseOptions {
+                exit_position: Some((1, 0).into()),
+            },
+        );
+        assert_eq!(
+            (1, 0),
+            (*map.exit_position().row(), *map.exit_position().column())
+        );
+        assert_eq!(Direction::Left, map.exit_direction());
+
+        // the exit must be at an end of the objective car's lane
+        for exit in [(0, 0), (1, 2), (2, 3)] {
+            assert!(MapMetadata::with_exit(&state, exit.into()).is_none());
+        }
+
+        let (state, map) = parse_map_with_options::<u8>(".o.\n.o.\n...", Default::default());
+        assert_eq!(
+            (2, 1),
+            (*map.exit_position().row(), *map.exit_position().column())
+        );
+        assert_eq!(Direction::Down, map.exit_direction());
+        let up = MapMetadata::with_exit(&state, (0, 1).into()).unwrap();
+        assert_eq!(Direction::Up, up.exit_direction());
+    }
+
+    #[test]
+    fn walls() {
+        let (state, map) = parse_map_with_options::<u8>("#oo.\n.1#.\n.1..", Default::default());
+        assert_eq!(2, state.cars().len());
+        assert_eq!(
+            vec![(0, 0), (1, 2)],
+            map.walls()
+                .iter()
+                .map(|wall| (*wall.row(), *wall.column()))
+                .collect::<Vec<_>>()
+        );
+        // walls are empty cells of the parsed state
+        let board = state.board().unwrap();
+        assert!(board.get((0, 0)).unwrap().is_none());
+        assert!(board.get((1, 2)).unwrap().is_none());
+        drop(board);
+
+        // a wall may not lie between the objective car and the exit
+        let blocked = std::panic::catch_unwind(|| {
+            parse_map_with_options::<u8>("oo.#", Default::default());
+        });
+        assert!(blocked.is_err());
+        let (_, map) = parse_map_with_options::<u8>(
+            "oo.#",
+            MapParseOptions {
+                exit_position: Some((0, 0).into()),
+            },
+        );
+        assert_eq!(1, map.walls().len());
+    }
+
+    #[test]
+    fn cars_at_row_ends() {
+        let state = parse_map::<u8>("a.oo\na...\na..b\na..b");
+        let cars = state
+            .cars()
+            .iter()
+            .map(|(position, car)| {
+                (
+                    (*position.row(), *position.column()),
+                    *car.length(),
+                    matches!(car.orientation(), Orientation::UpDown),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                // ends at the last column, discovered within the row
+                ((0, 2), 2, false),
+                // spans the whole first column
+                ((0, 0), 4, true),
+                // only ever seen at the last column, discovered at the end of each row
+                ((2, 3), 2, true),
+            ],
+            cars
+        );
+
+        // a map of a single column is all first and last column
+        let state = parse_map::<u8>("o\no\n.");
+        let (position, car) = &state.cars()[0];
+        assert_eq!((0, 0), (*position.row(), *position.column()));
+        assert_eq!(2, *car.length());
+        assert!(matches!(car.orientation(), Orientation::UpDown));
+    }
+
+    #[test]
+    fn load_dir_with_invalid() -> Result<(), Box<dyn Error>> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("a.map"), "oo.")?;
+        fs::write(dir.path().join("b.map"), "oo.\n.1.\n.1.")?;
+        fs::write(dir.path().join("c.map"), "1oo\n1..")?;
+        fs::write(dir.path().join("d.map"), "11.")?;
+        fs::write(dir.path().join("notes.txt"), "not a map")?;
+
+        let errors = load_maps_from_dir::<u8>(dir.path()).unwrap_err();
+        assert_eq!(1, errors.len());
+        assert_eq!(dir.path().join("d.map"), errors[0].0);
+        assert!(matches!(errors[0].1, MapParseError::MissingObjective));
+
+        fs::remove_file(dir.path().join("d.map"))?;
+        let maps = load_maps_from_dir::<u8>(dir.path()).unwrap();
+        assert_eq!(
+            vec!["a.map", "b.map", "c.map"],
+            maps.iter()
+                .map(|(path, _)| path.file_name().unwrap())
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_config() -> Result<(), Box<dyn Error>> {
+        let config =
+            FuzzerConfig::from_args(["myfuzzer", "--map", "a.map", "--seed", "42", "--verbose"])?;
+        assert_eq!(
+            FuzzerConfig {
+                map_path: PathBuf::from("a.map"),
+                output_dir: None,
+                max_corpus: None,
+                seed: Some(42),
+                timeout_secs: None,
+                verbose: true,
+                visualize: None,
+            },
+            config
+        );
+
+        let config = FuzzerConfig::from_args([
+            "myfuzzer",
+            "b.map",
+            "--output",
+            "out",
+            "--max-corpus",
+            "100",
+            "--timeout",
+            "5",
+        ])?;
+        assert_eq!(PathBuf::from("b.map"), config.map_path);
+        assert_eq!(Some(PathBuf::from("out")), config.output_dir);
+        assert_eq!(Some(100), config.max_corpus);
+        assert_eq!(Some(5), config.timeout_secs);
+        assert!(!config.verbose);
+        assert_eq!(None, config.visualize);
+
+        let config = FuzzerConfig::from_args(["myfuzzer", "c.map", "--visualize", "c.sol"])?;
+        assert_eq!(Some(PathBuf::from("c.sol")), config.visualize);
+
+        assert!(matches!(
+            FuzzerConfig::from_args(["myfuzzer"]),
+            Err(ConfigError::MissingMap)
+        ));
+        assert!(matches!(
+            FuzzerConfig::from_args(["myfuzzer", "a.map", "--seed"]),
+            Err(ConfigError::MissingValue("--seed"))
+        ));
+        assert!(matches!(
+            FuzzerConfig::from_args(["myfuzzer", "a.map", "--seed", "lots"]),
+            Err(ConfigError::InvalidValue("--seed", _))
+        ));
+        assert!(matches!(
+            FuzzerConfig::from_args(["myfuzzer", "a.map", "--max-corpus", "0"]),
+            Err(ConfigError::InvalidValue("--max-corpus", _))
+        ));
+        assert!(matches!(
+            FuzzerConfig::from_args(["myfuzzer", "a.map", "--fast"]),
+            Err(ConfigError::UnknownArgument(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_config() -> Result<(), Box<dyn Error>> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("a.map");
+        let config = FuzzerConfig::from_args([
+            "myfuzzer".into(),
+            "--map".into(),
+            path.clone().into_os_string(),
+        ])?;
+        assert!(matches!(config.validate(), Err(ConfigError::MapNotFound(p)) if p == path));
+
+        fs::write(&path, "oo.")?;
+        config.validate()?;
+
+        Ok(())
+    }
+
+    mod properties {
+        use crate::MapParseError;
+        use crate::serialization::serialize_state;
+        use parking_game::Orientation;
+        use proptest::prelude::*;
+
+        /// The names given to generated cars, in the order they are placed.
+        const NAMES: [char; 8] = ['o', 'a', 'b', 'c', 'd', 'e', 'f', 'g'];
+
+        /// A car in a generated map: its name, top-left cell, whether it is horizontal, and length.
+        type Placement = (char, usize, usize, bool, usize);
+
+        /// Valid maps of 3-10 rows and columns, with 2-8 non-overlapping cars (the first being the
+        /// objective car), alongside where each car was placed.
+        fn arb_map() -> impl Strategy<Value = (String, Vec<Placement>)> {
+            (3..=10usize, 3..=10usize)
+                .prop_flat_map(|(rows, cols)| {
+                    let car = (0..rows, 0..cols, any::<bool>(), 2..=3usize);
+                    (Just(rows), Just(cols), prop::collection::vec(car, 2..=8))
+                })
+                .prop_map(|(rows, cols, cars)| {
+                    let mut grid = vec![vec!['.'; cols]; rows];
+                    let mut placed = Vec::new();
+                    for (row, col, horizontal, len) in cars {
+                        let cells = (0..len)
+                            .map(|i| if horizontal { (row, col + i) } else { (row + i, col) })
+                            .collect::<Vec<_>>();
+                        if cells
+                            .iter()
+                            .all(|&(r, c)| r < rows && c < cols && grid[r][c] == '.')
+                        {
+                            let name = NAMES[placed.len()];
+                            for (r, c) in cells {
+                                grid[r][c] = name;
+                            }
+                            placed.push((name, row, col, horizontal, len));
+                        }
+                    }
+                    let map = grid
+                        .into_iter()
+                        .map(String::from_iter)
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    (map, placed)
+                })
+                .prop_filter("at least two cars must fit", |(_, placed)| placed.len() >= 2)
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(1000))]
+
+            #[test]
+            fn parse_valid((map, placed) in arb_map()) {
+                let state = crate::try_parse_map::<u8>(&map).unwrap();
+                prop_assert_eq!(placed.len(), state.cars().len());
+
+                let (rows, cols) = (*state.dimensions().rows(), *state.dimensions().columns());
+                for (&(_, row, col, horizontal, len), (position, car)) in
+                    placed.iter().zip(state.cars())
+                {
+                    prop_assert_eq!(
+                        (row as u8, col as u8),
+                        (*position.row(), *position.column())
+                    );
+                    prop_assert_eq!(len as u8, *car.length());
+                    prop_assert_eq!(
+                        horizontal,
+                        matches!(car.orientation(), Orientation::LeftRight)
+                    );
+                    let (end_row, end_col) = if horizontal {
+                        (*position.row(), *position.column() + *car.length() - 1)
+                    } else {
+                        (*position.row() + *car.length() - 1, *position.column())
+                    };
+                    prop_assert!(end_row < rows && end_col < cols);
+                }
+
+                let serialized = serialize_state(&state);
+                let reparsed = crate::try_parse_map::<u8>(&serialized).unwrap();
+                prop_assert_eq!(serialized, serialize_state(&reparsed));
+            }
+
+            #[test]
+            fn parse_missing_objective((map, _) in arb_map()) {
+                let map = map.replace('o', ".");
+                prop_assert!(matches!(
+                    crate::try_parse_map::<u8>(&map),
+                    Err(MapParseError::MissingObjective)
+                ));
+            }
+
+            #[test]
+            fn parse_overlapping(
+                (map, placed) in arb_map(),
+                car in any::<prop::sample::Index>(),
+                cell in any::<prop::sample::Index>()
+            ) {
+                // another cell named after an existing car, but in line with neither its row nor
+                // its column, so that it cannot be part of that car
+                let (name, row, col, _, _) = placed[car.index(placed.len())];
+                let mut grid = map
+                    .lines()
+                    .map(|line| line.chars().collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                let free = grid
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(r, line)| {
+                        line.iter().enumerate().map(move |(c, &name)| (r, c, name))
+                    })
+                    .filter(|&(r, c, name)| name == '.' && r != row && c != col)
+                    .map(|(r, c, _)| (r, c))
+                    .collect::<Vec<_>>();
+                prop_assume!(!free.is_empty());
+                let (r, c) = free[cell.index(free.len())];
+                grid[r][c] = name;
+                let map = grid
+                    .into_iter()
+                    .map(String::from_iter)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                prop_assert!(matches!(
+                    crate::try_parse_map::<u8>(&map),
+                    Err(MapParseError::MisshapenCar(misshapen)) if misshapen == name
+                ));
+            }
+        }
+    }
+}
@@ -1,145 +1,59 @@
-//! Fuzzer for [`parking_game`] puzzles. This is meant as an exercise for learning how to use
-//! LibAFL, and potentially not great for "real" applications, if they exist.
-
-pub mod executor;
-pub mod feedbacks;
-pub mod input;
-pub mod mutators;
-pub mod observers;
-pub mod stages;
-
-use crate::input::PGInput;
-use libafl::{feedback_and, feedback_not};
+//! Command line entry point for the fuzzer; see [`parking_game_fuzzer::FuzzerConfig`] for usage.
+
+use libafl::{HasMetadata, feedback_and, feedback_not};
 use libafl::corpus::{Corpus, InMemoryCorpus};
-use libafl::state::{HasSolutions, StdState};
-use libafl::fuzzer::StdFuzzer;
+use libafl::state::{HasCorpus, HasSolutions, StdState};
+use libafl::events::SimpleEventManager;
+use libafl::fuzzer::{Evaluator, StdFuzzer};
 use libafl::schedulers::queue::QueueScheduler;
+use libafl::stages::StdMutationalStage;
 use libafl_bolts::rands::StdRand;
+use libafl_bolts::tuples::tuple_list;
 use libafl::feedbacks::{CrashFeedback, new_hash_feedback::NewHashFeedback};
-use parking_game::{BoardValue, Car, Orientation, Position, State};
-use std::collections::HashMap;
-use std::collections::hash_map::Entry;
-use std::error::Error;
-use std::fmt::Debug;
+use parking_game_fuzzer::error::FuzzerError;
+use parking_game_fuzzer::input::PGInput;
+use parking_game_fuzzer::{
+    ConfigError, FuzzerConfig, analysis, corpus, executor, feedbacks, mutators, observers,
+    session, try_parse_map,
+};
+use std::time::{Duration, Instant};
 use std::{env, fs};
-use libafl_bolts::tuples::Handled;
-
-/// Parses a map with the following rules:
-/// 1. Empty spaces are denoted with `.`.
-/// 2. The car which must be moved to the objective is referenced with `o`. This will be index 1.
-/// 3. All other cars are uniquely named. They will be indexed in lexicographical order.
-/// 4. All cars are at least length 2.
-///
-/// Any map not following this pattern is not guaranteed to be parsed correctly.
-fn parse_map<T>(map: &str) -> State<T>
-where
-    T: BoardValue + TryFrom<usize>,
-    T::Error: Debug,
-{
-    let map = map.trim_ascii();
-    let rows = map.lines().count().try_into().unwrap();
-    let cols = map.lines().next().unwrap().len().try_into().unwrap();
-
-    let mut cars: HashMap<char, (Position<T>, Orientation, T)> = HashMap::new();
-
-    let mut prev = None;
-    for (ridx, row) in map.lines().enumerate() {
-        let ridx = ridx.try_into().unwrap();
-        let row = row.trim_ascii();
-        for (cidx, col) in row.chars().enumerate() {
-            let cidx: T = cidx.try_into().unwrap();
-            match (prev, col) {
-                (Some(car), next) => {
-                    match cars.entry(car) {
-                        Entry::Occupied(mut e) => {
-                            let entry = e.get_mut();
-                            assert!(
-                                entry.0.row() == &ridx || entry.0.column() == &(cidx - T::one())
-                            );
-                            entry.2 += T::one();
-                        }
-                        Entry::Vacant(e) => {
-                            if car == next {
-                                // same car: we are in the same row, init with left-right
-                                e.insert((
-                                    (ridx, cidx - T::one()).into(),
-                                    Orientation::LeftRight,
-                                    T::one(),
-                                ));
-                            } else {
-                                // different car: different row, init with up-down
-                                e.insert((
-                                    (ridx, cidx - T::one()).into(),
-                                    Orientation::UpDown,
-                                    T::one(),
-                                ));
-                            }
-                        }
-                    }
-                    prev = if next == '.' { None } else { Some(next) };
-                }
-                (None, '.') => {
-                    // do nothing
-                }
-                (None, next) => {
-                    prev = Some(next);
-                }
-            }
-        }
-
-        if let Some(car) = prev.take() {
-            match cars.entry(car) {
-                Entry::Occupied(mut e) => {
-                    let entry = e.get_mut();
-                    assert!(entry.0.row() == &ridx || entry.0.column() == &(cols - T::one()));
-                    entry.2 += T::one();
-                }
-                Entry::Vacant(e) => {
-                    // this has to be up-down orientation: we haven't seen it earlier
-                    e.insert((
-                        (ridx, cols - T::one()).into(),
-                        Orientation::UpDown,
-                        T::one(),
-                    ));
-                }
-            }
-        }
-    }
 
-    let mut state = State::empty((rows, cols)).unwrap();
-    let mut inserted = Vec::new();
-    inserted.push(('o', cars.remove(&'o').unwrap()));
-    inserted.extend(cars);
-    inserted[1..].sort_by_key(|(name, _)| *name); // lexographical sort
-
-    let mut board = state.board_mut().unwrap();
-    for (_name, (position, orientation, len)) in inserted {
-        board
-            .add_car(position, Car::new(len, orientation).unwrap())
-            .unwrap();
-    }
-    drop(board);
+/// How often a row is appended to `stats.csv` in the output directory.
+const STATS_INTERVAL: Duration = Duration::from_secs(5);
 
-    state
-}
+/// The number of fuzzing iterations between checks of whether a row is due.
+const STATS_CHECK_ITERATIONS: u64 = 100;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let path = env::args_os()
-        .nth(1)
-        .expect("Provide the path to the desired map.");
+fn main() -> Result<(), FuzzerError> {
+    let config = FuzzerConfig::from_args(env::args_os())?;
+    config.validate()?;
     // adjust u8 to u16 as necessary
     // for the maps in `maps/`, you only need u8; for larger maps, you may need to increase this
     // maps with side lengths >255 are not supported (also: where did you get them? :D)
-    let init = parse_map::<u8>(&fs::read_to_string(path).unwrap());
+    let init = try_parse_map::<u8>(&fs::read_to_string(&config.map_path)?)?;
+    if let Some(path) = &config.visualize {
+        let solution = fs::read_to_string(path)?
+            .parse::<PGInput>()
+            .map_err(|e| ConfigError::InvalidValue("--visualize", e.to_string()))?;
+        println!("{}", analysis::visualize_input(&solution, &init).join("\n---\n"));
+        return Ok(());
+    }
+    let mut output = config
+        .output_dir
+        .as_ref()
+        .map(|dir| session::SessionOutput::create(dir, &init))
+        .transpose()?;
+    let start = Instant::now();
     println!("Attempting to solve:");
     println!("{}", init.board().unwrap());
 
     // TODO(pt.1): create a ViewObserver with ViewObserver::<u8>::default()
     // this creates a view observer for a map which is indexed by u8s
-    let pgViewObserver = observers::ViewObserver::<u8>::default();
+    let (pgObservers, pgViewHandle, _pgFinalHandle) =
+        observers::ObserverBundle::<u8>::new().into_parts();
 
     // TODO(pt.1): create a FinalStateObserver with its default method for a map indexed by u8s
-    let pgFinalObserver = observers::FinalStateObserver::<u8>::default();
     // TODO(pt.1): create a feedback which will add an entry to the corpus if we see a new state
     //  - this feedback should first check that the target has **not** "crashed"
     //  - iff so, we should check if this is a newly observed state by checking its hash
@@ -148,8 +62,11 @@ fn main() -> Result<(), Box<dyn Error>> {
     //    - hint: check https://docs.rs/libafl/latest/libafl/index.html#macros for combining feedbacks
     //    - hint: check https://github.com/AFLplusplus/LibAFL/tree/main/fuzzers for examples
    
-    let handle = pgViewObserver.handle();
-    let mut pgFeedback = NewHashFeedback::new(&feedback_and!(feedback_not!(CrashFeedback::new()),handle)); 
+    let mut pgFeedback = feedback_and!(
+        feedback_not!(CrashFeedback::new()),
+        feedbacks::MovementBudgetFeedback::default(),
+        NewHashFeedback::new(&pgObservers.1.0)
+    );
     // TODO(pt.1): after implementing CrashRateFeedback, add it here at an appropriate place
     //  - you should see a failure rate of >80% for tokyo1.map, >95% for tokyo36.map
     //  - hint: consider the order of the feedback evaluation; where would be best to put this?
@@ -165,14 +82,17 @@ fn main() -> Result<(), Box<dyn Error>> {
     //  - then, we should check if the puzzle is solved
     //    - hint: this is mostly the same as setting up the feedback
    
-    let handle1 = pgViewObserver.handle();
-    let mut pgObjective = feedback_and!(feedback_not!(CrashFeedback::new()),feedbacks::SolvedFeedback::new(&handle1));
+    let mut pgObjective = feedback_and!(
+        feedback_not!(CrashFeedback::new()),
+        feedbacks::SolvedFeedback::with_handle(pgViewHandle)
+    );
 
 
-    // sets up the state and storage for preserved inputs and the solutions
+    // sets up the state and storage for preserved inputs and the solutions; the preserved inputs
+    // are bounded by `--max-corpus`, if provided
     let mut state = StdState::new(
-        StdRand::new(),
-        InMemoryCorpus::<PGInput>::new(),
+        config.seed.map_or_else(StdRand::new, StdRand::with_seed),
+        corpus::BoundedCorpus::<PGInput>::new(config.max_corpus.unwrap_or(usize::MAX)),
         InMemoryCorpus::new(),
         &mut pgFeedback,
         &mut pgObjective,
@@ -189,7 +109,11 @@ fn main() -> Result<(), Box<dyn Error>> {
     //    - extra: why do we format lists of data of different types like this?
     //
 
-    let mut pgExecutor = executor::PGExecutor::new(init, pgViewObserver);
+    let mut pgExecutor = executor::PGExecutorBuilder::with_initial(init);
+    if let Some(secs) = config.timeout_secs {
+        pgExecutor = pgExecutor.with_timeout(Duration::from_secs(secs));
+    }
+    let mut pgExecutor = pgExecutor.build(pgObservers);
 
     // TODO(pt.1): create a fuzzer which uses a queue scheduler and the provided feedback/objective
     //  - see: https://docs.rs/libafl/latest/libafl/fuzzer/struct.StdFuzzer.html
@@ -202,9 +126,10 @@ fn main() -> Result<(), Box<dyn Error>> {
     //  - hint: look at https://docs.rs/libafl/latest/libafl/stages/index.html
     //    - is there a (concrete) type which does this? which is suitable for our use case?
     //  - hint: the stages are of differing types; how do we construct this for LibAFL?
+    let mut pgStages = tuple_list!(StdMutationalStage::new(pgMutator));
 
     // TODO(pt.1): simple printing manager; you can use alternatives if you want to try them out!
-    // let mut mgr = SimpleEventManager::printing();
+    let mut mgr: session::PGEventManager = SimpleEventManager::printing();
 
     // TODO(pt.1): evaluate an input with no moves
     //  - for the mutator to work correctly, we need an existing input!
@@ -212,13 +137,49 @@ fn main() -> Result<(), Box<dyn Error>> {
     //  - see: https://docs.rs/libafl/latest/libafl/fuzzer/trait.Evaluator.html
     //    - what variable from earlier implements this?
     //  - hint: how do we make an input with no moves?
+    pgFuzzer.add_input(&mut state, &mut pgExecutor, &mut mgr, PGInput::default())?;
+
+    // loop and fuzz until we have a solution, appending to the stats every so often if saving
+    let mut last_stats = Instant::now();
+    let mut histogram_recorded = 0;
+    while !session::run_until_solved_with_limit(
+        &mut pgFuzzer,
+        &mut pgStages,
+        &mut pgExecutor,
+        &mut state,
+        &mut mgr,
+        Some(STATS_CHECK_ITERATIONS),
+    )? {
+        if last_stats.elapsed() >= STATS_INTERVAL {
+            analysis::InputSizeHistogram::record_corpus(&mut state, histogram_recorded)?;
+            histogram_recorded = state.corpus().count();
+            if let Some(output) = &output {
+                output.append_report(&analysis::FuzzingReport::from_state(&state, start))?;
+            }
+            if config.verbose {
+                state
+                    .metadata::<analysis::InputSizeHistogram>()?
+                    .print_histogram();
+            }
+            last_stats = Instant::now();
+        }
+    }
 
-    // TODO(pt.1): loop and fuzz until we have a solution
-    //  - we don't need to fuzz forever; just until we find an input that gets the puzzle solved
-    //  - hint: how do we access the solutions in the state?
-    //  - hint: how do we know if there are any solutions?
-    //  - hint: what fuzz method would be most appropriate?
-    //    - see: https://docs.rs/libafl/latest/libafl/fuzzer/trait.Fuzzer.html
+    if config.verbose {
+        println!(
+            "Corpus: {}",
+            analysis::analyze_corpus(state.corpus(), pgExecutor.initial())
+        );
+    }
+    let solutions =
+        analysis::SolutionDatabase::from_corpus(pgExecutor.initial().clone(), state.solutions())?;
+    if let Some(shortest) = solutions.shortest() {
+        println!(
+            "Found {} solutions; the shortest has {} moves",
+            solutions.count(),
+            shortest.len()
+        );
+    }
 
     // get the last input and print out the moves!
     let idx = state
@@ -229,5 +190,14 @@ fn main() -> Result<(), Box<dyn Error>> {
     let moves = tc.input().as_ref().unwrap().moves();
     println!("{} moves: {:?}", moves.len(), moves);
 
+    if let Some(output) = &mut output {
+        for nth in 0..state.solutions().count() {
+            let solution = state.solutions().get(state.solutions().nth(nth))?.borrow();
+            output.save_solution(solution.input().as_ref().unwrap())?;
+        }
+        output.save_report(&analysis::FuzzingReport::from_state(&state, start))?;
+        println!("Saved the solutions to {}", output.dir().display());
+    }
+
     Ok(())
 }
@@ -0,0 +1,344 @@
+//! Alternative map formats for [`parking_game`] puzzles, for when ASCII art isn't convenient (e.g.,
+//! when maps are produced by other tools).
+
+use crate::types::CarId;
+use parking_game::{BoardValue, Car, Orientation, State};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Names given to the non-objective cars when writing out maps, in index order. These are ordered
+/// lexicographically so that the parsers will assign the same indices when reading them back in.
+const CAR_NAMES: &str = "123456789abcdefghijklmnpqrstuvwxyz";
+
+/// The name of the provided car when writing out maps, or [`None`] if we've run out of names.
+pub(crate) fn car_name(car: CarId) -> Option<char> {
+    match car.get() {
+        1 => Some('o'),
+        n => CAR_NAMES.chars().nth(n - 2),
+    }
+}
+
+/// An error encountered while parsing a JSON map with [`parse_map_json`].
+#[derive(Debug)]
+pub enum JsonMapParseError {
+    /// The input was not valid JSON, or did not have the expected structure.
+    InvalidJson(serde_json::Error),
+    /// A required field was missing.
+    MissingField(&'static str),
+    /// The dimensions of the board were not usable.
+    InvalidDimensions,
+    /// The car id was not a single character other than `.`.
+    UnknownCarId(String),
+    /// The car id was used by more than one car.
+    DuplicateCarId(String),
+    /// There was no objective car (`o`).
+    MissingObjective,
+    /// The car with the provided id does not fit within the bounds of the board.
+    CarOutOfBounds(String),
+    /// The car with the provided id overlaps with another car.
+    CarOverlap(String),
+}
+
+impl Display for JsonMapParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonMapParseError::InvalidJson(e) => write!(f, "invalid JSON map: {e}"),
+            JsonMapParseError::MissingField(field) => write!(f, "missing field `{field}`"),
+            JsonMapParseError::InvalidDimensions => write!(f, "the board dimensions are invalid"),
+            JsonMapParseError::UnknownCarId(id) => write!(f, "unknown car id `{id}`"),
+            JsonMapParseError::DuplicateCarId(id) => write!(f, "duplicate car id `{id}`"),
+            JsonMapParseError::MissingObjective => write!(f, "no objective car (`o`) was found"),
+            JsonMapParseError::CarOutOfBounds(id) => write!(f, "car `{id}` is out of bounds"),
+            JsonMapParseError::CarOverlap(id) => write!(f, "car `{id}` overlaps another car"),
+        }
+    }
+}
+
+impl Error for JsonMapParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            JsonMapParseError::InvalidJson(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonMapParseError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::InvalidJson(e)
+    }
+}
+
+// every field is optional here so that we can report exactly which one is missing
+#[derive(Deserialize)]
+struct JsonMap {
+    rows: Option<usize>,
+    cols: Option<usize>,
+    cars: Option<Vec<JsonCar>>,
+}
+
+#[derive(Deserialize)]
+struct JsonCar {
+    id: Option<String>,
+    row: Option<usize>,
+    col: Option<usize>,
+    orientation: Option<JsonOrientation>,
+    length: Option<usize>,
+}
+
+#[derive(Copy, Clone, Deserialize)]
+enum JsonOrientation {
+    #[serde(rename = "lr")]
+    LeftRight,
+    #[serde(rename = "ud")]
+    UpDown,
+}
+
+/// Parses a map in the following JSON format:
+///
+/// ```json
+/// {
+///   "rows": 1,
+///   "cols": 3,
+///   "cars": [{ "id": "o", "row": 0, "col": 0, "orientation": "lr", "length": 2 }]
+/// }
+/// ```
+///
+/// Car ids follow the same rules as the ASCII format: the objective car is `o`, and all other cars
+/// are single characters, which are indexed in lexicographical order.
+pub fn parse_map_json<T>(json: &str) -> Result<State<T>, JsonMapParseError>
+where
+    T: BoardValue,
+{
+    let map: JsonMap = serde_json::from_str(json)?;
+    let rows = map.rows.ok_or(JsonMapParseError::MissingField("rows"))?;
+    let cols = map.cols.ok_or(JsonMapParseError::MissingField("cols"))?;
+    let cars = map.cars.ok_or(JsonMapParseError::MissingField("cars"))?;
+
+    let mut seen = HashSet::new();
+    let mut inserted = Vec::with_capacity(cars.len());
+    for car in cars {
+        let id = car.id.ok_or(JsonMapParseError::MissingField("id"))?;
+        let row = car.row.ok_or(JsonMapParseError::MissingField("row"))?;
+        let col = car.col.ok_or(JsonMapParseError::MissingField("col"))?;
+        let orientation = car
+            .orientation
+            .ok_or(JsonMapParseError::MissingField("orientation"))?;
+        let length = car
+            .length
+            .ok_or(JsonMapParseError::MissingField("length"))?;
+
+        let mut chars = id.chars();
+        let name = match (chars.next(), chars.next()) {
+            (Some(name), None) if name != '.' => name,
+            _ => return Err(JsonMapParseError::UnknownCarId(id)),
+        };
+        if !seen.insert(name) {
+            return Err(JsonMapParseError::DuplicateCarId(id));
+        }
+
+        let last = match orientation {
+            JsonOrientation::LeftRight => col
+                .checked_add(length.saturating_sub(1))
+                .map(|last_col| (row, last_col)),
+            JsonOrientation::UpDown => row
+                .checked_add(length.saturating_sub(1))
+                .map(|last_row| (last_row, col)),
+        };
+        let Some((last_row, last_col)) = last else {
+            return Err(JsonMapParseError::CarOutOfBounds(id));
+        };
+        if length == 0 || last_row >= rows || last_col >= cols {
+            return Err(JsonMapParseError::CarOutOfBounds(id));
+        }
+
+        inserted.push((name, (row, col), orientation, length));
+    }
+
+    // same ordering as the ASCII parser: objective first, then lexicographical
+    inserted.sort_by_key(|&(name, ..)| (name != 'o', name));
+    if inserted.first().is_none_or(|&(name, ..)| name != 'o') {
+        return Err(JsonMapParseError::MissingObjective);
+    }
+
+    let convert = |v: usize| T::try_from(v).map_err(|_| JsonMapParseError::InvalidDimensions);
+    let mut state = State::empty((convert(rows)?, convert(cols)?))
+        .map_err(|_| JsonMapParseError::InvalidDimensions)?;
+
+    let mut board = state
+        .board_mut()
+        .map_err(|_| JsonMapParseError::InvalidDimensions)?;
+    for (name, (row, col), orientation, length) in inserted {
+        let orientation = match orientation {
+            JsonOrientation::LeftRight => Orientation::LeftRight,
+            JsonOrientation::UpDown => Orientation::UpDown,
+        };
+        // all of these fit within the dimensions, which we already converted successfully
+        let car = Car::new(convert(length)?, orientation).unwrap();
+        board
+            .add_car((convert(row)?, convert(col)?), car)
+            .map_err(|_| JsonMapParseError::CarOverlap(name.to_string()))?;
+    }
+    drop(board);
+
+    Ok(state)
+}
+
+/// Serializes the provided state into the JSON format understood by [`parse_map_json`].
+///
+/// The objective car is written as `o`, and all other cars are given names in index order, so that
+/// parsing the result produces the same state.
+pub fn serialize_state_json<T>(state: &State<T>) -> String
+where
+    T: BoardValue,
+{
+    let cars = state
+        .cars()
+        .iter()
+        .enumerate()
+        .map(|(idx, (position, car))| {
+            let name = car_name(CarId::new(idx + 1).unwrap())
+                .expect("Too many cars to be named in a map!");
+            let (row, col): (usize, usize) =
+                ((*position.row()).into(), (*position.column()).into());
+            let length: usize = (*car.length()).into();
+            json!({
+                "id": name.to_string(),
+                "row": row,
+                "col": col,
+                "orientation": match car.orientation() {
+                    Orientation::LeftRight => "lr",
+                    Orientation::UpDown => "ud",
+                },
+                "length": length,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let (rows, cols): (usize, usize) = (
+        (*state.dimensions().rows()).into(),
+        (*state.dimensions().columns()).into(),
+    );
+    json!({
+        "rows": rows,
+        "cols": cols,
+        "cars": cars,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::map_format::{JsonMapParseError, parse_map_json, serialize_state_json};
+    use std::error::Error;
+
+    #[test]
+    fn matches_ascii() -> Result<(), Box<dyn Error>> {
+        let ascii = crate::parse_map::<u8>(
+            r#"
+        .2.
+        .2.
+        oo.
+        33.
+        "#,
+        );
+        let json = parse_map_json::<u8>(
+            r#"{
+            "rows": 4,
+            "cols": 3,
+            "cars": [
+                { "id": "3", "row": 3, "col": 0, "orientation": "lr", "length": 2 },
+                { "id": "o", "row": 2, "col": 0, "orientation": "lr", "length": 2 },
+                { "id": "2", "row": 0, "col": 1, "orientation": "ud", "length": 2 }
+            ]
+        }"#,
+        )?;
+
+        assert_eq!(format!("{ascii:?}"), format!("{json:?}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_id() {
+        let res = parse_map_json::<u8>(
+            r#"{ "rows": 1, "cols": 3, "cars": [
+                { "row": 0, "col": 0, "orientation": "lr", "length": 2 }
+            ] }"#,
+        );
+        assert!(matches!(res, Err(JsonMapParseError::MissingField("id"))));
+    }
+
+    #[test]
+    fn invalid_maps() {
+        assert!(matches!(
+            parse_map_json::<u8>("{ not json"),
+            Err(JsonMapParseError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            parse_map_json::<u8>(
+                r#"{ "rows": 1, "cols": 3, "cars": [
+                    { "id": "oo", "row": 0, "col": 0, "orientation": "lr", "length": 2 }
+                ] }"#,
+            ),
+            Err(JsonMapParseError::UnknownCarId(_))
+        ));
+        assert!(matches!(
+            parse_map_json::<u8>(
+                r#"{ "rows": 2, "cols": 3, "cars": [
+                    { "id": "o", "row": 0, "col": 0, "orientation": "lr", "length": 2 },
+                    { "id": "o", "row": 1, "col": 0, "orientation": "lr", "length": 2 }
+                ] }"#,
+            ),
+            Err(JsonMapParseError::DuplicateCarId(_))
+        ));
+        assert!(matches!(
+            parse_map_json::<u8>(
+                r#"{ "rows": 1, "cols": 3, "cars": [
+                    { "id": "o", "row": 0, "col": 2, "orientation": "lr", "length": 2 }
+                ] }"#,
+            ),
+            Err(JsonMapParseError::CarOutOfBounds(_))
+        ));
+        assert!(matches!(
+            parse_map_json::<u8>(
+                r#"{ "rows": 1, "cols": 3, "cars": [
+                    { "id": "o", "row": 0, "col": 18446744073709551615, "orientation": "lr",
+                      "length": 2 }
+                ] }"#,
+            ),
+            Err(JsonMapParseError::CarOutOfBounds(_))
+        ));
+        assert!(matches!(
+            parse_map_json::<u8>(
+                r#"{ "rows": 3, "cols": 1, "cars": [
+                    { "id": "o", "row": 18446744073709551615, "col": 0, "orientation": "ud",
+                      "length": 2 }
+                ] }"#,
+            ),
+            Err(JsonMapParseError::CarOutOfBounds(_))
+        ));
+    }
+
+    #[test]
+    fn round_trip() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>(
+            r#"
+        ......
+        ......
+        ...2oo
+        .332.4
+        .5.2.4
+        .5.664
+        "#,
+        );
+        let parsed = parse_map_json::<u8>(&serialize_state_json(&initial))?;
+
+        assert_eq!(format!("{initial:?}"), format!("{parsed:?}"));
+
+        Ok(())
+    }
+}
@@ -1,24 +1,34 @@
 //! Mutators for [`PGInput`]s -- so you can fuzz [`parking_game`] puzzles!
 
+use crate::analysis::{
+    BoardSymmetry, CorrelationAnalysis, detect_symmetry, forced_moves, mirrored_cars,
+};
+use crate::feedbacks::FinalStateMetadata;
 use crate::input::PGInput;
-use libafl::Error;
-use libafl::corpus::CorpusId;
+use crate::observers::{Axis, OrientationExt, hash_state};
+use crate::types::CarId;
+use libafl::corpus::{Corpus, CorpusId};
 use libafl::mutators::{MutationResult, Mutator};
-use libafl::state::{HasCurrentTestcase, HasRand};
+use libafl::state::{HasCorpus, HasCurrentTestcase, HasRand};
+use libafl::{Error, HasMetadata};
 use libafl_bolts::Named;
 use libafl_bolts::rands::Rand;
-use parking_game::{BoardValue, State};
+use parking_game::{BoardValue, Direction, State};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex, PoisonError};
 
 /// Randomly mutate the moves -- at any point with anything.
 ///
 /// TODO(pt.1): explain PGRandMutator's weaknesses in a comment.
 pub struct PGRandMutator<T> {
     count: usize,
+    insert_prob: f64,
+    delete_prob: f64,
     phantom: PhantomData<T>,
 }
 
@@ -27,9 +37,45 @@ impl<T> PGRandMutator<T> {
     pub fn new(state: &State<T>) -> Self {
         Self {
             count: state.cars().len(),
+            insert_prob: 1.0,
+            delete_prob: 0.0,
             phantom: PhantomData,
         }
     }
+
+    /// Construct a [`PGRandMutator`] for the given state which inserts a random move with
+    /// probability `insert_prob`, deletes a random move with probability `delete_prob`, and
+    /// otherwise skips the mutation. Inputs which only ever grow quickly become unwieldy, so a
+    /// little deletion keeps the corpus in check.
+    ///
+    /// Fails if either probability is negative, or if they sum to more than 1.
+    pub fn with_insert_and_delete_probabilities(
+        state: &State<T>,
+        insert_prob: f64,
+        delete_prob: f64,
+    ) -> Result<Self, Error> {
+        if !(insert_prob >= 0.0 && delete_prob >= 0.0 && insert_prob + delete_prob <= 1.0) {
+            return Err(Error::illegal_argument(format!(
+                "Mutation probabilities should be non-negative and sum to at most 1, but were \
+                 {insert_prob} (insert) and {delete_prob} (delete)."
+            )));
+        }
+        Ok(Self {
+            insert_prob,
+            delete_prob,
+            ..Self::new(state)
+        })
+    }
+
+    /// The probability of inserting a random move.
+    pub fn insert_prob(&self) -> f64 {
+        self.insert_prob
+    }
+
+    /// The probability of deleting a random move.
+    pub fn delete_prob(&self) -> f64 {
+        self.delete_prob
+    }
 }
 
 impl<T> Named for PGRandMutator<T> {
@@ -45,10 +91,26 @@ where
     T: BoardValue + DeserializeOwned + Serialize + 'static,
 {
     fn mutate(&mut self, state: &mut S, input: &mut PGInput) -> Result<MutationResult, Error> {
+        // always inserting needs no draw, which keeps the random sequence of existing runs intact
+        if self.insert_prob < 1.0 {
+            let r = state.rand_mut().next_float();
+            if r >= self.insert_prob + self.delete_prob {
+                return Ok(MutationResult::Skipped);
+            }
+            if r >= self.insert_prob {
+                let Some(len) = NonZeroUsize::new(input.len()) else {
+                    return Ok(MutationResult::Skipped);
+                };
+                let ind = state.rand_mut().below(len);
+                input.moves_mut().remove(ind);
+                return Ok(MutationResult::Mutated);
+            }
+        }
+
         // select a random car
         // because of the formatting of the car numbering, this is a little clunky
         // I've done this for you because this is my fault :)
-        let car = NonZeroUsize::new(
+        let car = CarId::new(
             state
                 .rand_mut()
                 .below(NonZeroUsize::new(self.count).unwrap())
@@ -75,7 +137,13 @@ where
 
 /// Mutator which adds a _valid_ move to the end of the sequence. Only valid when used as the only
 /// mutator and when [`crate::feedbacks::ViewMetadata`] is available on the mutated testcase.
+///
+/// A car which can move `n` spaces is meant to be moved at most [`PGTailMutator::max_extension`] of
+/// them in one mutation: with the default of 1, the input grows one move at a time, while a high
+/// limit lets a car cross all the free space in one go. Appending moves is still left to pt.2, so
+/// until [`Mutator::mutate`] is implemented the limit only affects [`PGTailMutator::extension`].
 pub struct PGTailMutator<T> {
+    max_extension: usize,
     phantom: PhantomData<T>,
 }
 
@@ -83,9 +151,37 @@ impl<T> PGTailMutator<T> {
     /// Create a new mutator for the provided state.
     pub fn new(_state: &State<T>) -> Self {
         Self {
+            max_extension: 1,
             phantom: PhantomData,
         }
     }
+
+    /// Append at most `max_extension` moves per mutation, rather than 1.
+    ///
+    /// Panics if `max_extension` is zero.
+    pub fn with_max_extension(mut self, max_extension: usize) -> Self {
+        self.set_max_extension(max_extension);
+        self
+    }
+
+    /// Set the most moves appended per mutation.
+    ///
+    /// Panics if `max_extension` is zero.
+    pub fn set_max_extension(&mut self, max_extension: usize) {
+        assert_ne!(max_extension, 0, "The maximum extension must be non-zero.");
+        self.max_extension = max_extension;
+    }
+
+    /// The most moves appended per mutation.
+    pub fn max_extension(&self) -> usize {
+        self.max_extension
+    }
+
+    /// The number of moves to append for a car which can move `distance` spaces in the chosen
+    /// direction: the whole distance, up to [`PGTailMutator::max_extension`].
+    pub fn extension(&self, distance: usize) -> usize {
+        distance.min(self.max_extension)
+    }
 }
 
 impl<T> Named for PGTailMutator<T> {
@@ -112,6 +208,7 @@ where
         //    - `drop(...)` the testcase after use so that you can mutably use the state again
         //  - finally, select from this list randomly with `state.rand_mut().choose(...)` and apply
         //    the mutation with `.push()` (potentially multiple times for `T > 1`)
+        //    - push exactly `self.extension(distance)` moves, so that `max_extension` is honoured
 
         todo!("Indicate that the input was mutated")
     }
@@ -121,3 +218,544 @@ where
         Ok(())
     }
 }
+
+/// Mutator which prepends the moves forced upon the cars blocking the objective car in the initial
+/// state (see [`forced_moves`]), as a solution must eventually make them anyway. Forced moves which
+/// are no longer valid once the earlier ones are made are left out.
+pub struct ConstraintMutator {
+    forced: Vec<(CarId, Direction)>,
+}
+
+impl ConstraintMutator {
+    /// Create a new mutator for the provided initial state.
+    pub fn new<T: BoardValue>(initial: &State<T>) -> Self {
+        let mut state = initial.clone();
+        let mut board = state.board_mut().expect("The initial state should be valid.");
+        let forced = forced_moves(initial)
+            .into_iter()
+            .filter(|&(car, dir)| board.shift_car(car.as_nonzero(), dir).is_ok())
+            .collect();
+        Self { forced }
+    }
+}
+
+impl Named for ConstraintMutator {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_constraint");
+        &NAME
+    }
+}
+
+impl<S> Mutator<PGInput, S> for ConstraintMutator {
+    fn mutate(&mut self, _state: &mut S, input: &mut PGInput) -> Result<MutationResult, Error> {
+        if self.forced.is_empty() || input.moves().starts_with(&self.forced) {
+            return Ok(MutationResult::Skipped);
+        }
+        input.moves_mut().splice(0..0, self.forced.iter().copied());
+        Ok(MutationResult::Mutated)
+    }
+
+    fn post_exec(&mut self, _state: &mut S, _new_corpus_id: Option<CorpusId>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Mutator which wraps another to record, in a shared [`CorrelationAnalysis`], whether each
+/// mutation it makes leads to an input which is added to the corpus.
+pub struct CorrelatedMutator<M> {
+    inner: M,
+    analysis: Arc<Mutex<CorrelationAnalysis>>,
+}
+
+impl<M> CorrelatedMutator<M> {
+    /// Wrap the provided mutator, recording its mutations in the provided analysis.
+    pub fn new(inner: M, analysis: Arc<Mutex<CorrelationAnalysis>>) -> Self {
+        Self { inner, analysis }
+    }
+
+    /// The wrapped mutator.
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+}
+
+impl<M> Named for CorrelatedMutator<M>
+where
+    M: Named,
+{
+    fn name(&self) -> &Cow<'static, str> {
+        self.inner.name()
+    }
+}
+
+impl<M, S> Mutator<PGInput, S> for CorrelatedMutator<M>
+where
+    M: Mutator<PGInput, S>,
+{
+    fn mutate(&mut self, state: &mut S, input: &mut PGInput) -> Result<MutationResult, Error> {
+        self.inner.mutate(state, input)
+    }
+
+    fn post_exec(&mut self, state: &mut S, new_corpus_id: Option<CorpusId>) -> Result<(), Error> {
+        self.analysis
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .record(self.inner.name().clone(), new_corpus_id.is_some());
+        self.inner.post_exec(state, new_corpus_id)
+    }
+}
+
+/// Mutator which wraps another to skip its mutations which produce the mirror image of an input it
+/// already added to the corpus, for puzzles which are unchanged by mirroring (see
+/// [`detect_symmetry`]): such an input explores nothing its mirror image has not.
+pub struct SymmetryReducingMutator<M> {
+    inner: M,
+    symmetry: BoardSymmetry,
+    cars: Option<Vec<CarId>>,
+    known: HashSet<PGInput>,
+}
+
+impl<M> SymmetryReducingMutator<M> {
+    /// Wrap the provided mutator, skipping the mirror images of inputs for the provided state.
+    pub fn new<T: BoardValue>(inner: M, state: &State<T>) -> Self {
+        let symmetry = detect_symmetry(state);
+        let cars = match symmetry {
+            BoardSymmetry::None => None,
+            symmetry => mirrored_cars(state, symmetry),
+        };
+        Self {
+            inner,
+            symmetry,
+            cars,
+            known: HashSet::new(),
+        }
+    }
+
+    /// The wrapped mutator.
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// The symmetry of the puzzle whose mirror images are skipped.
+    pub fn symmetry(&self) -> BoardSymmetry {
+        self.symmetry
+    }
+
+    /// The mirror image of the provided input, or [`None`] if the puzzle has no symmetry.
+    pub fn mirror(&self, input: &PGInput) -> Option<PGInput> {
+        let cars = self.cars.as_ref()?;
+        Some(PGInput::new(
+            input
+                .moves()
+                .iter()
+                .map(|&(car, direction)| {
+                    (
+                        cars.get(car.get() - 1).copied().unwrap_or(car),
+                        self.symmetry.mirror_direction(direction),
+                    )
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl<M> Named for SymmetryReducingMutator<M>
+where
+    M: Named,
+{
+    fn name(&self) -> &Cow<'static, str> {
+        self.inner.name()
+    }
+}
+
+impl<M, S> Mutator<PGInput, S> for SymmetryReducingMutator<M>
+where
+    M: Mutator<PGInput, S>,
+    S: HasCorpus<PGInput>,
+{
+    fn mutate(&mut self, state: &mut S, input: &mut PGInput) -> Result<MutationResult, Error> {
+        let result = self.inner.mutate(state, input)?;
+        if result == MutationResult::Mutated
+            && let Some(mirror) = self.mirror(input)
+            && mirror != *input
+            && self.known.contains(&mirror)
+        {
+            return Ok(MutationResult::Skipped);
+        }
+        Ok(result)
+    }
+
+    fn post_exec(&mut self, state: &mut S, new_corpus_id: Option<CorpusId>) -> Result<(), Error> {
+        if let Some(id) = new_corpus_id
+            && self.cars.is_some()
+        {
+            self.known.insert(state.corpus().cloned_input_for_id(id)?);
+        }
+        self.inner.post_exec(state, new_corpus_id)
+    }
+}
+
+/// Mutator which learns which move to append to an input from the state its execution finished in,
+/// by Q-learning: each move from each state is valued by whether appending it led to a new corpus
+/// entry, plus the discounted value of the best move from the state that entry finished in. With
+/// probability `epsilon`, a random move is appended to explore; otherwise, the most valuable so
+/// far. A random move is always appended from states which have not been learned about yet. Only
+/// valid when [`FinalStateMetadata`] is available on the mutated testcase, e.g., via
+/// [`crate::feedbacks::FinalStateMetadataFeedback`]; other testcases are skipped.
+pub struct ReinforcementMutator<T> {
+    q_table: HashMap<u64, HashMap<(CarId, Direction), f64>>,
+    actions: Vec<(CarId, Direction)>,
+    epsilon: f64,
+    learning_rate: f64,
+    discount: f64,
+    last: Option<(u64, (CarId, Direction))>,
+    phantom: PhantomData<T>,
+}
+
+impl<T> ReinforcementMutator<T> {
+    /// The probability of exploring used by [`ReinforcementMutator::new`].
+    pub const DEFAULT_EPSILON: f64 = 0.1;
+    /// The learning rate used by [`ReinforcementMutator::new`].
+    pub const DEFAULT_LEARNING_RATE: f64 = 0.5;
+    /// The discount of future values used by [`ReinforcementMutator::new`].
+    pub const DEFAULT_DISCOUNT: f64 = 0.9;
+
+    /// Construct a [`ReinforcementMutator`] for the given state, with the default parameters.
+    pub fn new(state: &State<T>) -> Self {
+        // each car only moves along its own axis, so the other two directions would always crash
+        let actions = state
+            .cars()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (_, car))| {
+                let car_id = CarId::new(i + 1).unwrap();
+                match car.orientation().axis() {
+                    Axis::Horizontal => [Direction::Left, Direction::Right],
+                    Axis::Vertical => [Direction::Up, Direction::Down],
+                }
+                .map(|dir| (car_id, dir))
+            })
+            .collect();
+        Self {
+            q_table: HashMap::new(),
+            actions,
+            epsilon: Self::DEFAULT_EPSILON,
+            learning_rate: Self::DEFAULT_LEARNING_RATE,
+            discount: Self::DEFAULT_DISCOUNT,
+            last: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Construct a [`ReinforcementMutator`] for the given state which explores with probability
+    /// `epsilon`, and updates values by `learning_rate` of the difference to what was just learned,
+    /// with the values of the moves after it discounted by `discount`.
+    ///
+    /// Fails if any of the parameters are not between 0 and 1.
+    pub fn with_parameters(
+        state: &State<T>,
+        epsilon: f64,
+        learning_rate: f64,
+        discount: f64,
+    ) -> Result<Self, Error> {
+        if ![epsilon, learning_rate, discount]
+            .iter()
+            .all(|p| (0.0..=1.0).contains(p))
+        {
+            return Err(Error::illegal_argument(format!(
+                "Reinforcement parameters should be between 0 and 1, but were {epsilon} \
+                 (epsilon), {learning_rate} (learning rate) and {discount} (discount)."
+            )));
+        }
+        Ok(Self {
+            epsilon,
+            learning_rate,
+            discount,
+            ..Self::new(state)
+        })
+    }
+
+    /// The probability of appending a random move rather than the most valuable.
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// The value learned for each move from each state, keyed by the [`hash_state`] of the state.
+    pub fn q_table(&self) -> &HashMap<u64, HashMap<(CarId, Direction), f64>> {
+        &self.q_table
+    }
+
+    /// The value learned for making the provided move from the state with the provided hash, or 0
+    /// if nothing has been learned about it yet.
+    pub fn q_value(&self, hash: u64, action: (CarId, Direction)) -> f64 {
+        self.q_table
+            .get(&hash)
+            .and_then(|values| values.get(&action))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// The most valuable move from the state with the provided hash, or [`None`] if nothing has
+    /// been learned about the state yet. Ties are broken by the first such move.
+    pub fn best_action(&self, hash: u64) -> Option<(CarId, Direction)> {
+        let values = self.q_table.get(&hash)?;
+        self.actions.iter().copied().reduce(|best, action| {
+            let value = values.get(&action).copied().unwrap_or(0.0);
+            if value > values.get(&best).copied().unwrap_or(0.0) {
+                action
+            } else {
+                best
+            }
+        })
+    }
+
+    /// Update the value of making the provided move from the state with the provided hash, having
+    /// received `reward` for it and reached the state with the hash `next`, if known.
+    pub fn update(
+        &mut self,
+        hash: u64,
+        action: (CarId, Direction),
+        reward: f64,
+        next: Option<u64>,
+    ) {
+        let max_future = next
+            .and_then(|next| self.q_table.get(&next))
+            .and_then(|values| values.values().copied().reduce(f64::max))
+            .unwrap_or(0.0);
+        let value = self
+            .q_table
+            .entry(hash)
+            .or_default()
+            .entry(action)
+            .or_insert(0.0);
+        *value += self.learning_rate * (reward + self.discount * max_future - *value);
+    }
+}
+
+impl<T> Named for ReinforcementMutator<T> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_reinforcement");
+        &NAME
+    }
+}
+
+impl<S, T> Mutator<PGInput, S> for ReinforcementMutator<T>
+where
+    S: HasRand + HasCurrentTestcase<PGInput> + HasCorpus<PGInput>,
+    T: BoardValue + DeserializeOwned + Serialize + 'static,
+{
+    fn mutate(&mut self, state: &mut S, input: &mut PGInput) -> Result<MutationResult, Error> {
+        self.last = None;
+        // without a current testcase (or its final state), there is nothing to learn from
+        let Some(hash) = state.current_testcase().ok().and_then(|testcase| {
+            testcase
+                .metadata::<FinalStateMetadata<T>>()
+                .ok()
+                .and_then(|meta| hash_state(meta.state()))
+        }) else {
+            return Ok(MutationResult::Skipped);
+        };
+        let action = match self.best_action(hash) {
+            Some(best) if state.rand_mut().next_float() >= self.epsilon => best,
+            _ => match state.rand_mut().choose(&self.actions) {
+                Some(&action) => action,
+                None => return Ok(MutationResult::Skipped),
+            },
+        };
+        input.push(action.0, action.1);
+        self.last = Some((hash, action));
+        Ok(MutationResult::Mutated)
+    }
+
+    fn post_exec(&mut self, state: &mut S, new_corpus_id: Option<CorpusId>) -> Result<(), Error> {
+        let Some((hash, action)) = self.last.take() else {
+            return Ok(());
+        };
+        let Some(id) = new_corpus_id else {
+            self.update(hash, action, 0.0, None);
+            return Ok(());
+        };
+        let next = state
+            .corpus()
+            .get(id)?
+            .borrow()
+            .metadata::<FinalStateMetadata<T>>()
+            .ok()
+            .and_then(|meta| hash_state(meta.state()));
+        self.update(hash, action, 1.0, next);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::feedbacks::FinalStateMetadata;
+    use crate::input::PGInput;
+    use crate::mutators::{PGRandMutator, PGTailMutator, ReinforcementMutator};
+    use crate::observers::hash_state;
+    use crate::types::CarId;
+    use libafl::HasMetadata;
+    use libafl::corpus::{Corpus, InMemoryCorpus, Testcase};
+    use libafl::mutators::{MutationResult, Mutator};
+    use libafl::state::{HasCorpus, HasCurrentCorpusId, StdState};
+    use libafl_bolts::rands::StdRand;
+    use parking_game::Direction;
+
+    #[test]
+    fn tail_extension() {
+        // `mutate` is left to pt.2, so only the number of moves it should append is checked here
+        let initial = crate::parse_map::<u8>("oo....\n......");
+        let conservative = PGTailMutator::new(&initial);
+        assert_eq!(1, conservative.max_extension());
+        for distance in 1..6 {
+            assert_eq!(1, conservative.extension(distance));
+        }
+
+        let mut bounded = PGTailMutator::new(&initial).with_max_extension(3);
+        assert_eq!(3, bounded.extension(5));
+        assert_eq!(2, bounded.extension(2));
+        bounded.set_max_extension(10);
+        assert_eq!(2, bounded.extension(2));
+        assert_eq!(5, bounded.extension(5));
+    }
+
+    #[test]
+    fn insert_and_delete() {
+        let initial = crate::parse_map::<u8>("oo..\n..1.\n..1.");
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )
+        .unwrap();
+        let mut mutate = |mutator: &mut PGRandMutator<u8>, input: &mut PGInput| {
+            mutator.mutate(&mut state, input).unwrap()
+        };
+
+        assert!(PGRandMutator::with_insert_and_delete_probabilities(&initial, 0.7, 0.4).is_err());
+        assert!(PGRandMutator::with_insert_and_delete_probabilities(&initial, -0.1, 0.4).is_err());
+        assert!(
+            PGRandMutator::with_insert_and_delete_probabilities(&initial, f64::NAN, 0.0).is_err()
+        );
+
+        // only the outcome of insertions is checked, as what they insert is up to `mutate`
+        let mut inserting =
+            PGRandMutator::with_insert_and_delete_probabilities(&initial, 1.0, 0.0).unwrap();
+        let mut input = PGInput::default();
+        for _ in 0..100 {
+            assert_eq!(MutationResult::Mutated, mutate(&mut inserting, &mut input));
+        }
+
+        let mut deleting =
+            PGRandMutator::with_insert_and_delete_probabilities(&initial, 0.0, 1.0).unwrap();
+        let mut input = vec!["1R 2D 1L 2U"; 25]
+            .join(" ")
+            .parse::<PGInput>()
+            .unwrap();
+        let original = input.clone();
+        for len in (0..100).rev() {
+            assert_eq!(MutationResult::Mutated, mutate(&mut deleting, &mut input));
+            assert_eq!(len, input.len());
+            // what remains is still in the original order
+            let mut remaining = original.moves().iter();
+            assert!(
+                input
+                    .moves()
+                    .iter()
+                    .all(|kept| remaining.any(|m| m == kept))
+            );
+        }
+        assert_eq!(MutationResult::Skipped, mutate(&mut deleting, &mut input));
+
+        let mut half =
+            PGRandMutator::with_insert_and_delete_probabilities(&initial, 0.5, 0.0).unwrap();
+        let inserted = (0..10_000)
+            .filter(|_| mutate(&mut half, &mut input) == MutationResult::Mutated)
+            .count();
+        assert!((4_500..5_500).contains(&inserted));
+    }
+
+    #[test]
+    fn reinforcement() {
+        let initial = crate::parse_map::<u8>("oo..\n..1.\n..1.");
+        let car = |id| CarId::new(id).unwrap();
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )
+        .unwrap();
+
+        // nothing has been scheduled yet, so there is no state to act from
+        let mut mutator = ReinforcementMutator::new(&initial);
+        assert_eq!(
+            MutationResult::Skipped,
+            mutator.mutate(&mut state, &mut PGInput::default()).unwrap()
+        );
+
+        let mut testcase = Testcase::new(PGInput::default());
+        testcase.add_metadata(FinalStateMetadata::new(initial.clone()));
+        let id = state.corpus_mut().add(testcase).unwrap();
+        state.set_corpus_id(id).unwrap();
+        let start = hash_state(&initial).unwrap();
+
+        assert!(ReinforcementMutator::with_parameters(&initial, 1.5, 0.5, 0.9).is_err());
+        assert!(ReinforcementMutator::with_parameters(&initial, 0.1, -0.5, 0.9).is_err());
+
+        // only moving the objective car right leads to a new corpus entry
+        let rewarded = (car(1), Direction::Right);
+        let mut learning = ReinforcementMutator::with_parameters(&initial, 0.5, 0.5, 0.9).unwrap();
+        for _ in 0..1000 {
+            let mut input = PGInput::default();
+            assert_eq!(
+                MutationResult::Mutated,
+                learning.mutate(&mut state, &mut input).unwrap()
+            );
+            let new_id = if input[0] == rewarded {
+                let mut after = initial.clone();
+                input.apply(&mut after).unwrap();
+                let mut testcase = Testcase::new(input);
+                testcase.add_metadata(FinalStateMetadata::new(after));
+                Some(state.corpus_mut().add(testcase).unwrap())
+            } else {
+                None
+            };
+            learning.post_exec(&mut state, new_id).unwrap();
+        }
+        assert!(learning.q_table().contains_key(&start));
+        assert_eq!(Some(rewarded), learning.best_action(start));
+        for action in [
+            (car(1), Direction::Left),
+            (car(2), Direction::Up),
+            (car(2), Direction::Down),
+        ] {
+            assert!(learning.q_value(start, action) < learning.q_value(start, rewarded));
+        }
+
+        // the best move is appended unless exploring, which picks any of the four moves
+        let best = (car(2), Direction::Up);
+        for (epsilon, expected) in [
+            (0.0, 10_000..=10_000),
+            (1.0, 2_200..=2_800),
+            (0.5, 5_900..=6_600),
+        ] {
+            let mut mutator =
+                ReinforcementMutator::with_parameters(&initial, epsilon, 0.5, 0.9).unwrap();
+            mutator.update(start, best, 1.0, None);
+            let mut chosen = 0;
+            for _ in 0..10_000 {
+                let mut input = PGInput::default();
+                mutator.mutate(&mut state, &mut input).unwrap();
+                chosen += usize::from(input[0] == best);
+            }
+            assert!(
+                expected.contains(&chosen),
+                "{chosen} with epsilon {epsilon}"
+            );
+        }
+    }
+}
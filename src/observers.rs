@@ -1,14 +1,30 @@
 //! Observers which collect data from [`crate::executor::PGExecutor`] executions.
 
+use crate::analysis::adjacent;
+use crate::compact::CompactBoard;
+use crate::coverage::CoverageMap;
+#[cfg(feature = "incremental-hash")]
+use crate::hash::IncrementalHasher;
 use crate::input::PGInput;
+use crate::types::CarId;
+use libafl::HasMetadata;
+use libafl::executors::ExitKind;
 use libafl::observers::{Observer, ObserverWithHashField};
-use libafl_bolts::{Error, Named};
-use parking_game::{Board, BoardValue, Direction, Orientation, Position, State};
+use libafl::state::HasExecutions;
+use libafl_bolts::tuples::{Handle, Handled, tuple_list, tuple_list_type};
+use libafl_bolts::{Error, Named, impl_serdeany};
+use parking_game::{Board, BoardValue, Car, Direction, Orientation, Position, State};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::num::NonZeroUsize;
 use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::atomic::{self, AtomicUsize};
+use std::time::{Duration, Instant};
 
 /// An [`Observer`] compatible with [`crate::executor::PGExecutor`].
 pub trait PGObserver<T> {
@@ -19,6 +35,41 @@ pub trait PGObserver<T> {
     fn final_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
         // do nothing
     }
+
+    /// Passes the board to the observer just before each move is applied, with the car about to be
+    /// moved and the direction it will move in. The move may turn out to be invalid, in which case
+    /// [`PGObserver::after_move`] is not called for it. As with [`PGObserver::after_move`],
+    /// executors are not required to call this at all. Does nothing by default.
+    #[allow(unused_variables)]
+    fn pre_move(
+        &mut self,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+        car: CarId,
+        direction: Direction,
+    ) {
+        // do nothing
+    }
+
+    /// Passes the board to the observer after each move is applied, with the car moved and the
+    /// direction it moved in. This is called before [`PGObserver::final_board`], though executors
+    /// are not required to call it at all (e.g., if the moves were not applied one at a time), so
+    /// observers must not rely on it. Does nothing by default.
+    #[allow(unused_variables)]
+    fn after_move(
+        &mut self,
+        car: CarId,
+        direction: Direction,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        // do nothing
+    }
+
+    /// Checks that what the observer collected is consistent, returning a description of the
+    /// problem if it is not. Executors call this after each execution when built with debug
+    /// assertions. Always succeeds by default.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 /// Utility trait for marking [`libafl_bolts::tuples::tuple_list`]s as "all PG observers".
@@ -32,12 +83,53 @@ pub trait PGObserver<T> {
 pub trait PGObserverTuple<T> {
     /// Iterate all boards contained here and pass the provided board.
     fn final_board_all(&mut self, board: &Board<impl Deref<Target = State<T>>, T>);
+
+    /// Iterate all observers contained here and pass the board and the move about to be applied.
+    fn pre_move_all(
+        &mut self,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+        car: CarId,
+        direction: Direction,
+    );
+
+    /// Iterate all observers contained here and pass the move just applied and the board after it.
+    fn after_move_all(
+        &mut self,
+        car: CarId,
+        direction: Direction,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    );
+
+    /// Validate all observers contained here, stopping at the first which is inconsistent.
+    fn validate_all(&self) -> Result<(), String>;
 }
 
 impl<T> PGObserverTuple<T> for () {
     fn final_board_all(&mut self, _board: &Board<impl Deref<Target = State<T>>, T>) {
         // this is the end of the list, so we're done
     }
+
+    fn pre_move_all(
+        &mut self,
+        _board: &Board<impl Deref<Target = State<T>>, T>,
+        _car: CarId,
+        _direction: Direction,
+    ) {
+        // likewise
+    }
+
+    fn after_move_all(
+        &mut self,
+        _car: CarId,
+        _direction: Direction,
+        _board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        // likewise
+    }
+
+    fn validate_all(&self) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 // Remember: the list looks like `(a, (b, (c, ())))`.
@@ -51,24 +143,90 @@ where
         self.0.final_board(board);
         self.1.final_board_all(board);
     }
+
+    fn pre_move_all(
+        &mut self,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+        car: CarId,
+        direction: Direction,
+    ) {
+        self.0.pre_move(board, car, direction);
+        self.1.pre_move_all(board, car, direction);
+    }
+
+    fn after_move_all(
+        &mut self,
+        car: CarId,
+        direction: Direction,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        self.0.after_move(car, direction, board);
+        self.1.after_move_all(car, direction, board);
+    }
+
+    fn validate_all(&self) -> Result<(), String> {
+        self.0.validate()?;
+        self.1.validate_all()
+    }
 }
 
 /// Observer which stashes the final state of the board after an execution.
-#[derive(Debug, Deserialize, Serialize)]
+///
+/// With the `incremental-hash` feature, the hash of the final state is kept up to date by an
+/// [`IncrementalHasher`] as each move is applied, rather than computed from every cell of the final
+/// board with [`hash_state`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FinalStateObserver<T> {
     final_state: Option<State<T>>,
+    expected_car_count: Option<usize>,
+    #[cfg(feature = "incremental-hash")]
+    hasher: Option<IncrementalHasher<T>>,
+    #[cfg(feature = "incremental-hash")]
+    final_hash: Option<u64>,
 }
 
 impl<T> FinalStateObserver<T> {
+    /// Create an observer for boards reached from the provided state, which checks that the final
+    /// state has as many cars.
+    pub fn for_state(state: &State<T>) -> Self {
+        Self {
+            expected_car_count: Some(state.cars().len()),
+            ..Self::default()
+        }
+    }
+
     /// The final state observed -- if it exists (which, it will not if there is an error!).
     pub fn final_state(&self) -> Option<&State<T>> {
         self.final_state.as_ref()
     }
+
+    /// The number of cars the final state should have, if this observer checks it.
+    pub fn expected_car_count(&self) -> Option<usize> {
+        self.expected_car_count
+    }
+
+    /// Checks that the final state observed, if any, has the expected number of cars.
+    fn check_car_count(&self) -> Result<(), String> {
+        match (&self.final_state, self.expected_car_count) {
+            (Some(state), Some(expected)) if state.cars().len() != expected => Err(format!(
+                "the final state has {} cars, but there should be {expected}",
+                state.cars().len()
+            )),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl<T> Default for FinalStateObserver<T> {
     fn default() -> Self {
-        Self { final_state: None }
+        Self {
+            final_state: None,
+            expected_car_count: None,
+            #[cfg(feature = "incremental-hash")]
+            hasher: None,
+            #[cfg(feature = "incremental-hash")]
+            final_hash: None,
+        }
     }
 }
 
@@ -81,22 +239,81 @@ impl<T> Named for FinalStateObserver<T> {
 
 impl<S, T> Observer<PGInput, S> for FinalStateObserver<T> {
     fn flush(&mut self) -> Result<(), Error> {
+        self.check_car_count().map_err(Error::illegal_state)?;
         self.final_state = None;
+        #[cfg(feature = "incremental-hash")]
+        {
+            self.hasher = None;
+            self.final_hash = None;
+        }
         Ok(())
     }
 
     fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
         self.final_state = None;
+        #[cfg(feature = "incremental-hash")]
+        {
+            self.hasher = None;
+            self.final_hash = None;
+        }
         Ok(())
     }
+
+    fn post_exec(
+        &mut self,
+        _state: &mut S,
+        _input: &PGInput,
+        _exit_kind: &ExitKind,
+    ) -> Result<(), Error> {
+        self.check_car_count().map_err(Error::illegal_state)
+    }
 }
 
 impl<T> PGObserver<T> for FinalStateObserver<T>
 where
-    T: Clone,
+    T: BoardValue,
 {
     fn final_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
         self.final_state = Some(board.state().clone());
+        // the hasher is not kept if no moves were applied one at a time
+        #[cfg(feature = "incremental-hash")]
+        {
+            self.final_hash = Some(match self.hasher.take() {
+                Some(hasher) => hasher.hash(),
+                None => IncrementalHasher::new(board.state()).hash(),
+            });
+        }
+    }
+
+    #[cfg(feature = "incremental-hash")]
+    fn after_move(
+        &mut self,
+        car: CarId,
+        direction: Direction,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        let new_pos = board.state().cars()[car.get() - 1].0;
+        match &mut self.hasher {
+            Some(hasher) => {
+                let old_pos = new_pos
+                    .shift(direction.opposite(), T::one())
+                    .expect("The car should have moved from inside the board.");
+                hasher.update(car, old_pos, new_pos);
+            }
+            // the executor may have skipped moves already applied, so start from wherever we are
+            None => self.hasher = Some(IncrementalHasher::new(board.state())),
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.check_car_count()?;
+        // every board has at least the objective car, so an empty one was not observed correctly
+        match &self.final_state {
+            Some(state) if state.cars().is_empty() => {
+                Err("the final state has no cars".to_string())
+            }
+            _ => Ok(()),
+        }
     }
 }
 
@@ -104,40 +321,164 @@ impl<T> ObserverWithHashField for FinalStateObserver<T>
 where
     T: BoardValue,
 {
+    #[cfg(not(feature = "incremental-hash"))]
     fn hash(self: &FinalStateObserver<T>) -> Option<u64> {
-        if let Some(final_state) = &self.final_state {
-            let mut hasher = DefaultHasher::new();
-            let board = final_state.board().ok()?;
-            dbg!(&board);
-            // TODO(pt.0): build a hash which uniquely identifies the state
-            for c in board.concrete() {
-                if let Some(val) = c {
-                    hasher.write_usize((*val).into());
-                } else {
-                    hasher.write_usize(0);
-                }
+        hash_state(self.final_state.as_ref()?)
+    }
 
-            }
-            //  - remember, not all parts of the state need to be hashed to identify it uniquely
-            //  - only hash the parts which are necessary to distinguish the states
-            Some(hasher.finish())
-        } else {
-            None
+    #[cfg(feature = "incremental-hash")]
+    fn hash(self: &FinalStateObserver<T>) -> Option<u64> {
+        self.final_hash
+    }
+}
+
+/// Builds a hash which uniquely identifies the provided state, or [`None`] if the state is invalid.
+///
+/// Only the occupant of each cell is hashed, as the cars themselves never change between states.
+/// The cells are hashed by their [`CompactBoard`] encoding.
+pub fn hash_state<T: BoardValue>(state: &State<T>) -> Option<u64> {
+    Some(CompactBoard::from_board(&state.board().ok()?).hash())
+}
+
+/// The axis along which a car moves.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum Axis {
+    /// Along a row, i.e., [`Direction::Left`] and [`Direction::Right`].
+    Horizontal,
+    /// Along a column, i.e., [`Direction::Up`] and [`Direction::Down`].
+    Vertical,
+}
+
+/// Utility methods for [`Direction`].
+pub trait DirectionExt {
+    /// The direction pointing the other way along the same axis.
+    fn opposite(self) -> Direction;
+
+    /// The axis this direction points along.
+    fn axis(self) -> Axis;
+
+    /// The byte standing for this direction in compact binary formats, such as
+    /// [`PGInput::to_bytes`]: 0 for up, 1 for down, 2 for left and 3 for right.
+    fn to_byte(self) -> u8;
+
+    /// The direction the provided byte stands for, as encoded by [`DirectionExt::to_byte`], or
+    /// [`None`] if it stands for no direction.
+    fn from_byte(byte: u8) -> Option<Direction>;
+
+    /// The direction as a signed step along its axis: -1 for left, 1 for right, -2 for up and 2 for
+    /// down, so that the opposite direction is its negation.
+    fn to_signed(self) -> i8;
+
+    /// The direction the provided signed step stands for, as encoded by
+    /// [`DirectionExt::to_signed`], or [`None`] if it stands for no direction.
+    fn from_signed(step: i8) -> Option<Direction>;
+}
+
+impl DirectionExt for Direction {
+    fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    fn axis(self) -> Axis {
+        match self {
+            Direction::Left | Direction::Right => Axis::Horizontal,
+            Direction::Up | Direction::Down => Axis::Vertical,
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
         }
     }
+
+    fn from_byte(byte: u8) -> Option<Direction> {
+        match byte {
+            0 => Some(Direction::Up),
+            1 => Some(Direction::Down),
+            2 => Some(Direction::Left),
+            3 => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    fn to_signed(self) -> i8 {
+        match self {
+            Direction::Up => -2,
+            Direction::Down => 2,
+            Direction::Left => -1,
+            Direction::Right => 1,
+        }
+    }
+
+    fn from_signed(step: i8) -> Option<Direction> {
+        match step {
+            -2 => Some(Direction::Up),
+            2 => Some(Direction::Down),
+            -1 => Some(Direction::Left),
+            1 => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Utility methods for [`Orientation`].
+pub trait OrientationExt {
+    /// The axis along which a car with this orientation moves.
+    fn axis(&self) -> Axis;
+}
+
+impl OrientationExt for Orientation {
+    fn axis(&self) -> Axis {
+        match self {
+            Orientation::LeftRight => Axis::Horizontal,
+            Orientation::UpDown => Axis::Vertical,
+        }
+    }
+}
+
+/// Stepping a [`BoardValue`] by one without overflowing, for offsets and distances near the edges
+/// of the board.
+///
+/// Nothing in the observers calls this yet: its intended use is inside `step_until_seen`, which is
+/// left for pt.0. Until that part is done, only the values themselves are tested here.
+pub trait SaturatingBoardValue {
+    /// One more than this value, or this value if it is already the greatest.
+    fn saturating_add_one(self) -> Self;
+
+    /// One less than this value, or zero if it is already zero.
+    fn saturating_sub_one(self) -> Self;
+}
+
+impl<T: BoardValue> SaturatingBoardValue for T {
+    fn saturating_add_one(self) -> Self {
+        self.checked_add(&T::one()).unwrap_or(self)
+    }
+
+    fn saturating_sub_one(self) -> Self {
+        self.checked_sub(&T::one()).unwrap_or(self)
+    }
 }
 
 /// View from a car in a potential direction of travel. Useful for knowing where a car can move.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct View<T> {
     direction: Direction,
-    observed: Option<NonZeroUsize>,
+    observed: Option<CarId>,
     distance: T,
 }
 
 impl<T> View<T> {
     /// Create a new [`View`] in the given direction.
-    pub fn new(direction: Direction, observed: Option<NonZeroUsize>, distance: T) -> Self {
+    pub fn new(direction: Direction, observed: Option<CarId>, distance: T) -> Self {
         Self {
             direction,
             observed,
@@ -152,7 +493,7 @@ impl<T> View<T> {
 
     /// The car observed in this view, or [`None`] if we don't see a car (i.e. the closest thing is
     /// a wall).
-    pub fn observed(&self) -> Option<NonZeroUsize> {
+    pub fn observed(&self) -> Option<CarId> {
         self.observed
     }
 
@@ -169,13 +510,22 @@ impl<T> View<T> {
 }
 
 /// The view from a car, forward and backward.
-#[derive(Debug, Copy, Clone, Deserialize, Serialize)]
+///
+/// Views are ordered by how far the car may move forward, then backward, then by the cars observed
+/// forward and backward. The directions are only compared last, so that the order is consistent
+/// with equality.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct ViewFrom<T> {
     backward: View<T>,
     forward: View<T>,
 }
 
 impl<T> ViewFrom<T> {
+    /// Create a view from the views behind and ahead of a car.
+    pub fn new(backward: View<T>, forward: View<T>) -> Self {
+        Self { backward, forward }
+    }
+
     /// The view behind the car (if the car is oriented [`Orientation::LeftRight`], the view will
     /// have [`Direction::Left`]; otherwise, it will be [`Direction::Up`].
     pub fn backward(&self) -> &View<T> {
@@ -187,21 +537,83 @@ impl<T> ViewFrom<T> {
     pub fn forward(&self) -> &View<T> {
         &self.forward
     }
+
+    fn key(&self) -> (&T, &T, Option<CarId>, Option<CarId>, Direction, Direction) {
+        (
+            &self.forward.distance,
+            &self.backward.distance,
+            self.forward.observed,
+            self.backward.observed,
+            self.forward.direction,
+            self.backward.direction,
+        )
+    }
+}
+
+impl<T> ViewFrom<T>
+where
+    T: BoardValue,
+{
+    /// The number of spaces the car may move in either direction.
+    pub fn total_free_distance(&self) -> T {
+        self.forward.distance + self.backward.distance
+    }
+
+    /// Whether the car may not move at all, i.e., it is blocked both forward and backward.
+    pub fn is_completely_blocked(&self) -> bool {
+        self.total_free_distance().is_zero()
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for ViewFrom<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key().partial_cmp(&other.key())
+    }
+}
+
+impl<T: Ord> Ord for ViewFrom<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
 }
 
 /// An observer which collects [`View`] information for each car.
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct ViewObserver<T> {
     views: Vec<ViewFrom<T>>,
+    cars: Option<usize>,
+    walls: Vec<Position<T>>,
 }
 
 impl<T> ViewObserver<T> {
+    /// Create an observer for boards reached from the provided state, which checks that it
+    /// collects a view for each of its cars.
+    pub fn for_state(state: &State<T>) -> Self {
+        Self {
+            views: Vec::new(),
+            cars: Some(state.cars().len()),
+            walls: Vec::new(),
+        }
+    }
+
+    /// Treat the provided cells as walls, so that a car sees them as it would an edge of the board.
+    /// See [`crate::MapMetadata::walls`].
+    pub fn with_walls(mut self, walls: impl IntoIterator<Item = Position<T>>) -> Self {
+        self.walls = walls.into_iter().collect();
+        self
+    }
+
     /// An iterator over the views. The objective car will be the first.
-    pub fn views(&self) -> impl Iterator<Item = (NonZeroUsize, &ViewFrom<T>)> {
+    pub fn views(&self) -> impl Iterator<Item = (CarId, &ViewFrom<T>)> {
         self.views
             .iter()
             .enumerate()
-            .map(|(i, e)| (NonZeroUsize::new(i + 1).unwrap(), e))
+            .map(|(i, e)| (CarId::new(i + 1).unwrap(), e))
+    }
+
+    /// The view from the provided car, or [`None`] if no view was collected for it.
+    pub fn views_for_car(&self, car: CarId) -> Option<&ViewFrom<T>> {
+        self.views.get(car.get() - 1)
     }
 }
 
@@ -242,191 +654,2532 @@ fn step_until_seen<T: BoardValue>(
     //  - hint: you can use `position.shift(...)` to get a position at a given offset
     //    - check return values for both `position.shift(...)` and `board.get(...)` for gotchas
     //  - hint: you can increment offset with `offset += T::one()`, likewise with distance
+    //    - or with `offset = offset.saturating_add_one()` (see `SaturatingBoardValue`), which
+    //      can't overflow on the largest boards
     //  - hint: an obstacle directly adjacent should be considered as zero units away
+    //  - hint: the board holds `NonZeroUsize`s; turn one into a `CarId` with `.into()`
     //  - this method is _extensively_ tested in simple_observation
     todo!("Implement as above!")
 }
 
+/// The views from the car in this position in every direction, as with [`step_until_seen`], in the
+/// order [`Direction::Up`], [`Direction::Down`], [`Direction::Left`] and [`Direction::Right`]. A
+/// view towards an edge of the board the car is against sees no car, zero units away.
+pub fn step_until_seen_all_directions<T: BoardValue>(
+    board: &Board<impl Deref<Target = State<T>>, T>,
+    position: Position<T>,
+) -> [View<T>; 4] {
+    in_all_directions(|direction| step_until_seen(board, position, direction))
+}
+
+/// Looks in every direction with the provided function, in the order of
+/// [`step_until_seen_all_directions`].
+fn in_all_directions<V>(look: impl FnMut(Direction) -> V) -> [V; 4] {
+    [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ]
+    .map(look)
+}
+
+/// Shortens the view from the car in this position to stop at the nearest wall in its direction, if
+/// that is closer than what it sees. Walls are empty cells of the board, so [`step_until_seen`]
+/// sees straight through them; a wall is seen as an edge of the board, observing no car.
+fn stop_at_walls<T: BoardValue>(
+    view: View<T>,
+    position: Position<T>,
+    car: &Car<T>,
+    walls: &[Position<T>],
+) -> View<T> {
+    let (row, column) = (*position.row(), *position.column());
+    let end = match car.orientation() {
+        Orientation::LeftRight => column + *car.length(),
+        Orientation::UpDown => row + *car.length(),
+    };
+    let nearest = walls
+        .iter()
+        .filter_map(|wall| match view.direction {
+            Direction::Left if *wall.row() == row && *wall.column() < column => {
+                Some(column - *wall.column() - T::one())
+            }
+            Direction::Right if *wall.row() == row && *wall.column() >= end => {
+                Some(*wall.column() - end)
+            }
+            Direction::Up if *wall.column() == column && *wall.row() < row => {
+                Some(row - *wall.row() - T::one())
+            }
+            Direction::Down if *wall.column() == column && *wall.row() >= end => {
+                Some(*wall.row() - end)
+            }
+            _ => None,
+        })
+        .min();
+    match nearest {
+        Some(distance) if distance <= view.distance => View::new(view.direction, None, distance),
+        _ => view,
+    }
+}
+
+/// The views behind and ahead of the car in this position.
+fn view_from<T: BoardValue>(
+    board: &Board<impl Deref<Target = State<T>>, T>,
+    position: Position<T>,
+    car: &Car<T>,
+) -> ViewFrom<T> {
+    let backward = match car.orientation().axis() {
+        Axis::Vertical => Direction::Up,
+        Axis::Horizontal => Direction::Left,
+    };
+
+    let forward = step_until_seen(board, position, backward.opposite());
+    let backward = step_until_seen(board, position, backward);
+
+    ViewFrom { backward, forward }
+}
+
+/// Every move which may be made on the provided board, i.e., each car paired with each direction
+/// along its axis in which it is not directly blocked. Moves are listed car by car, with the move
+/// up or left before the move down or right.
+pub fn valid_moves_for_board<T: BoardValue>(
+    board: &Board<impl Deref<Target = State<T>>, T>,
+) -> Vec<(CarId, Direction)> {
+    let mut moves = Vec::new();
+    for (i, (position, car)) in board.state().cars().iter().enumerate() {
+        let car_id = CarId::new(i + 1).unwrap();
+        let backward = match car.orientation().axis() {
+            Axis::Vertical => Direction::Up,
+            Axis::Horizontal => Direction::Left,
+        };
+        for direction in [backward, backward.opposite()] {
+            if adjacent(board, *position, car, direction) == Some(None) {
+                moves.push((car_id, direction));
+            }
+        }
+    }
+    moves
+}
+
 impl<T> PGObserver<T> for ViewObserver<T>
 where
     T: BoardValue,
 {
     fn final_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
-        for (position, car) in board.state().cars().iter().copied() {
-            let backward = match car.orientation() {
-                Orientation::UpDown => Direction::Up,
-                Orientation::LeftRight => Direction::Left,
-            };
-
-            let forward = step_until_seen(board, position, -backward);
-            let backward = step_until_seen(board, position, backward);
+        for (position, car) in board.state().cars() {
+            let view = view_from(board, *position, car);
+            self.views.push(ViewFrom {
+                backward: stop_at_walls(view.backward, *position, car, &self.walls),
+                forward: stop_at_walls(view.forward, *position, car, &self.walls),
+            });
+        }
+    }
 
-            self.views.push(ViewFrom { backward, forward });
+    fn validate(&self) -> Result<(), String> {
+        // there are no views at all if the execution did not complete
+        match self.cars {
+            Some(cars) if !self.views.is_empty() && self.views.len() != cars => Err(format!(
+                "observed {} views, but there are {cars} cars",
+                self.views.len()
+            )),
+            _ => Ok(()),
         }
     }
 }
 
-#[cfg(test)]
-mod test {
-    use crate::input::PGInput;
-    use crate::observers::{FinalStateObserver, PGObserverTuple, View, ViewObserver};
-    use libafl::executors::ExitKind;
-    use libafl::observers::{ObserverWithHashField, ObserversTuple};
-    use libafl::state::NopState;
-    use libafl_bolts::tuples::{Handled, tuple_list};
-    use parking_game::Direction;
-    use std::error::Error;
-    use std::num::NonZeroUsize;
+/// An observer which collects the same [`View`] information as [`ViewObserver`], but keeps it up
+/// to date after each move rather than computing it from the final board. After a car moves, only
+/// the views which may have changed are recomputed: those of the car itself, those of the cars
+/// which could see it before the move, and those of the cars in the rows or columns it now covers.
+///
+/// This is useful for observers and feedbacks which need the views of the intermediate states; if
+/// only the final views are needed, [`ViewObserver`] does less work.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct IncrementalViewObserver<T> {
+    views: Vec<ViewFrom<T>>,
+}
 
-    #[test]
-    fn simple_observation() -> Result<(), Box<dyn Error>> {
-        let initial = crate::parse_map::<u8>("33oo22.");
-        let obs = ViewObserver::<u8>::default();
-        let handle = obs.handle();
+impl<T> IncrementalViewObserver<T> {
+    /// An iterator over the views. The objective car will be the first.
+    pub fn views(&self) -> impl Iterator<Item = (CarId, &ViewFrom<T>)> {
+        self.views
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (CarId::new(i + 1).unwrap(), e))
+    }
 
-        let mut observers = tuple_list!(obs);
+    /// The cars directly behind and ahead of the provided car, if they are not walls, as seen from
+    /// the last board observed.
+    pub fn blocker_of(&self, car: CarId) -> (Option<CarId>, Option<CarId>) {
+        self.views.get(car.get() - 1).map_or((None, None), |view| {
+            (view.backward.observed, view.forward.observed)
+        })
+    }
+}
 
-        let mut state = NopState::<PGInput>::new();
+impl<T> Named for IncrementalViewObserver<T> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_incremental_view");
+        &NAME
+    }
+}
 
-        let nop_input = PGInput::new(vec![]);
-        observers.pre_exec_all(&mut state, &nop_input)?;
-        observers.final_board_all(&initial.board()?);
-        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+impl<S, T> Observer<PGInput, S> for IncrementalViewObserver<T> {
+    fn flush(&mut self) -> Result<(), Error> {
+        self.views.clear();
+        Ok(())
+    }
 
-        assert_eq!(
-            observers.0.views().next().unwrap().1.backward,
-            View {
-                direction: Direction::Left,
-                observed: NonZeroUsize::new(3),
-                distance: 0
-            }
-        );
-        assert_eq!(
-            observers.0.views().next().unwrap().1.forward,
-            View {
-                direction: Direction::Right,
-                observed: NonZeroUsize::new(2),
-                distance: 0
-            }
-        );
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.views.clear();
+        Ok(())
+    }
+}
 
-        let initial = crate::parse_map::<u8>("oo.");
+impl<T> IncrementalViewObserver<T>
+where
+    T: BoardValue,
+{
+    fn update_all(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
+        self.views.clear();
+        for (position, car) in board.state().cars() {
+            self.views.push(view_from(board, *position, car));
+        }
+    }
 
-        observers.pre_exec_all(&mut state, &nop_input)?;
-        observers.final_board_all(&initial.board()?);
-        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+    /// The indices of the cars whose views may have changed after the provided car moved: the car
+    /// itself, the cars which saw it before it moved, and the cars in the rows or columns it now
+    /// covers which could see it along their axis.
+    fn affected_by(
+        &self,
+        car: CarId,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    ) -> Vec<usize> {
+        // the rows and columns covered by the moved car, as half-open ranges
+        let cars = board.state().cars();
+        let (position, moved) = &cars[car.get() - 1];
+        let (row, column) = (*position.row(), *position.column());
+        let (rows, columns) = match moved.orientation().axis() {
+            Axis::Horizontal => (row..row + T::one(), column..column + *moved.length()),
+            Axis::Vertical => (row..row + *moved.length(), column..column + T::one()),
+        };
 
-        assert_eq!(
-            observers.0.views().next().unwrap().1.backward,
-            View {
-                direction: Direction::Left,
-                observed: None,
-                distance: 0
-            }
-        );
-        assert_eq!(
-            observers.0.views().next().unwrap().1.forward,
-            View {
-                direction: Direction::Right,
-                observed: None,
-                distance: 1
-            }
-        );
+        cars.iter()
+            .enumerate()
+            .filter(|(i, (position, other))| {
+                let view = &self.views[*i];
+                *i == car.get() - 1
+                    || view.backward.observed == Some(car)
+                    || view.forward.observed == Some(car)
+                    || match other.orientation().axis() {
+                        Axis::Horizontal => rows.contains(position.row()),
+                        Axis::Vertical => columns.contains(position.column()),
+                    }
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
 
-        let initial = crate::parse_map::<u8>(
-            r#"
-            3
-            3
-            o
-            o
-            2
-            2
-            .
-            "#,
-        );
+impl<T> PGObserver<T> for IncrementalViewObserver<T>
+where
+    T: BoardValue,
+{
+    fn final_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
+        // no moves were observed, so there is nothing to start from
+        if self.views.is_empty() {
+            self.update_all(board);
+        }
+    }
+
+    fn after_move(
+        &mut self,
+        car: CarId,
+        _direction: Direction,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        if self.views.is_empty() {
+            self.update_all(board);
+            return;
+        }
+
+        let cars = board.state().cars();
+        for i in self.affected_by(car, board) {
+            let (position, other) = &cars[i];
+            self.views[i] = view_from(board, *position, other);
+        }
+    }
+}
+
+/// An observer which counts how many times each cell was occupied by a car, across the states
+/// reached after each move of an execution. The initial state is not counted.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct HeatmapObserver {
+    heatmap: Vec<Vec<u32>>,
+}
+
+impl HeatmapObserver {
+    /// The number of times each cell was occupied, indexed by row and then by column. This is empty
+    /// if no moves were made.
+    pub fn heatmap(&self) -> &Vec<Vec<u32>> {
+        &self.heatmap
+    }
+
+    /// The position (row, column) of the cell occupied most often, or [`None`] if no moves were
+    /// made. Ties are broken by the first such cell, row by row.
+    pub fn hot_cell(&self) -> Option<(usize, usize)> {
+        let mut hottest = None;
+        let mut most = 0;
+        for (r, row) in self.heatmap.iter().enumerate() {
+            for (c, &count) in row.iter().enumerate() {
+                if count > most {
+                    hottest = Some((r, c));
+                    most = count;
+                }
+            }
+        }
+        hottest
+    }
+}
+
+impl Named for HeatmapObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_heatmap");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for HeatmapObserver {
+    fn flush(&mut self) -> Result<(), Error> {
+        self.heatmap.clear();
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.heatmap.clear();
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for HeatmapObserver
+where
+    T: BoardValue,
+{
+    fn after_move(
+        &mut self,
+        _car: CarId,
+        _direction: Direction,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        let dimensions = board.state().dimensions();
+        let columns = (*dimensions.columns()).into();
+        if self.heatmap.is_empty() {
+            self.heatmap = vec![vec![0; columns]; (*dimensions.rows()).into()];
+        }
+        for (i, cell) in board.concrete().iter().enumerate() {
+            if cell.is_some() {
+                self.heatmap[i / columns][i % columns] += 1;
+            }
+        }
+    }
+}
+
+/// An observer which counts the empty cells of the final board.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct FreeSpaceObserver {
+    free_cells: usize,
+}
+
+impl FreeSpaceObserver {
+    /// The number of empty cells in the final board, or zero if the execution did not complete.
+    pub fn free_cells(&self) -> usize {
+        self.free_cells
+    }
+}
+
+impl Named for FreeSpaceObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_free_space");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for FreeSpaceObserver {
+    fn flush(&mut self) -> Result<(), Error> {
+        self.free_cells = 0;
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.free_cells = 0;
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for FreeSpaceObserver {
+    fn final_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
+        self.free_cells = board
+            .concrete()
+            .iter()
+            .filter(|cell| cell.is_none())
+            .count();
+    }
+}
+
+/// The number of executions over which [`ExecutionTimeObserver::average_duration`] is computed.
+const DURATION_WINDOW: usize = 100;
+
+/// An observer which measures the wall-clock duration of each execution.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ExecutionTimeObserver {
+    #[serde(skip)]
+    start: Option<Instant>,
+    durations: VecDeque<Duration>,
+}
+
+impl ExecutionTimeObserver {
+    /// The duration of the last execution, or [`None`] if there has not been one.
+    pub fn last_duration(&self) -> Option<Duration> {
+        self.durations.back().copied()
+    }
+
+    /// The mean duration of the last (up to) 100 executions, or [`None`] if there have not been
+    /// any.
+    pub fn average_duration(&self) -> Option<Duration> {
+        let count = u32::try_from(self.durations.len())
+            .ok()
+            .filter(|&n| n > 0)?;
+        Some(self.durations.iter().sum::<Duration>() / count)
+    }
+
+    fn record(&mut self, duration: Duration) {
+        if self.durations.len() == DURATION_WINDOW {
+            self.durations.pop_front();
+        }
+        self.durations.push_back(duration);
+    }
+}
+
+impl Named for ExecutionTimeObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_execution_time");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for ExecutionTimeObserver {
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.start = Some(Instant::now());
+        Ok(())
+    }
+
+    fn post_exec(
+        &mut self,
+        _state: &mut S,
+        _input: &PGInput,
+        _exit_kind: &ExitKind,
+    ) -> Result<(), Error> {
+        if let Some(start) = self.start.take() {
+            self.record(start.elapsed());
+        }
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for ExecutionTimeObserver {}
+
+/// The length of the shortest solution found so far, shared between a [`SolutionLengthObserver`]
+/// and the feedback which decides what is a solution (see
+/// [`crate::feedbacks::SolvedFeedback::with_length_tracker`]).
+#[derive(Debug, Clone)]
+pub struct SolutionLengthTracker {
+    min_length: Arc<AtomicUsize>,
+}
+
+impl SolutionLengthTracker {
+    /// Records a solution of the provided length.
+    pub fn record(&self, length: usize) {
+        self.min_length.fetch_min(length, atomic::Ordering::Relaxed);
+    }
+
+    /// The length of the shortest solution recorded, or [`None`] if none have been.
+    pub fn min_length(&self) -> Option<usize> {
+        Some(self.min_length.load(atomic::Ordering::Relaxed)).filter(|&len| len != usize::MAX)
+    }
+}
+
+impl Default for SolutionLengthTracker {
+    fn default() -> Self {
+        Self {
+            min_length: Arc::new(AtomicUsize::new(usize::MAX)),
+        }
+    }
+}
+
+/// An observer which records the length of each input executed, along with the length of the
+/// shortest solution found so far.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct SolutionLengthObserver {
+    last_length: usize,
+    #[serde(skip)]
+    tracker: SolutionLengthTracker,
+}
+
+impl SolutionLengthObserver {
+    /// The number of moves in the input last executed.
+    pub fn last_length(&self) -> usize {
+        self.last_length
+    }
+
+    /// The length of the shortest solution found so far, or [`None`] if none have been found. This
+    /// is only updated by feedbacks given this observer's [`SolutionLengthObserver::tracker`].
+    pub fn min_length_ever(&self) -> Option<usize> {
+        self.tracker.min_length()
+    }
+
+    /// A handle to the shortest solution length, to be updated when solutions are found.
+    pub fn tracker(&self) -> SolutionLengthTracker {
+        self.tracker.clone()
+    }
+}
+
+impl Named for SolutionLengthObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_solution_length");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for SolutionLengthObserver {
+    fn pre_exec(&mut self, _state: &mut S, input: &PGInput) -> Result<(), Error> {
+        self.last_length = input.len();
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for SolutionLengthObserver {}
+
+/// An observer which checks whether any car may move on the final board. A deadlocked board is a
+/// dead end: no further moves may be made from it, so it can never lead to a solution.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct DeadlockObserver {
+    deadlocked: bool,
+}
+
+impl DeadlockObserver {
+    /// Whether no car could move on the final board. This is false if the execution did not
+    /// complete.
+    pub fn is_deadlocked(&self) -> bool {
+        self.deadlocked
+    }
+}
+
+impl Named for DeadlockObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_deadlock");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for DeadlockObserver {
+    fn flush(&mut self) -> Result<(), Error> {
+        self.deadlocked = false;
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.deadlocked = false;
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for DeadlockObserver
+where
+    T: BoardValue,
+{
+    fn final_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
+        self.deadlocked = valid_moves_for_board(board).is_empty();
+    }
+}
+
+/// An observer which counts the moves available in each state reached during an execution, i.e.,
+/// the branching factor of the states explored. The initial state is not counted.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct BranchingFactorObserver {
+    states: usize,
+    moves: usize,
+}
+
+impl BranchingFactorObserver {
+    /// The mean number of moves available after each move of the execution, or zero if no moves
+    /// were made.
+    pub fn avg_branching_factor(&self) -> f64 {
+        if self.states == 0 {
+            0.0
+        } else {
+            self.moves as f64 / self.states as f64
+        }
+    }
+}
+
+impl Named for BranchingFactorObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_branching_factor");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for BranchingFactorObserver {
+    fn flush(&mut self) -> Result<(), Error> {
+        *self = Self::default();
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        *self = Self::default();
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for BranchingFactorObserver
+where
+    T: BoardValue,
+{
+    fn after_move(
+        &mut self,
+        _car: CarId,
+        _direction: Direction,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        self.states += 1;
+        self.moves += valid_moves_for_board(board).len();
+    }
+}
+
+/// An observer which finds the longest run of consecutive moves of the same car in each input.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct MaxChainLengthObserver {
+    max_chain: usize,
+}
+
+impl MaxChainLengthObserver {
+    /// The length of the longest run of consecutive moves of the same car in the input last
+    /// executed, or zero if it had no moves.
+    pub fn max_chain(&self) -> usize {
+        self.max_chain
+    }
+}
+
+impl Named for MaxChainLengthObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_max_chain_length");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for MaxChainLengthObserver {
+    fn pre_exec(&mut self, _state: &mut S, input: &PGInput) -> Result<(), Error> {
+        self.max_chain = input
+            .moves()
+            .chunk_by(|(a, _), (b, _)| a == b)
+            .map(<[_]>::len)
+            .max()
+            .unwrap_or(0);
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for MaxChainLengthObserver {}
+
+/// An observer which measures how deeply the objective car is blocked in the final board. The
+/// objective car is blocked by the car ahead of it; every other car is blocked by the cars it sees
+/// ahead of and behind it. The depth is the number of cars in the longest chain of blocking cars
+/// starting from the one ahead of the objective car, so a board where the objective car is blocked
+/// by a single car which sees only walls has a depth of one.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct DependencyDepthObserver {
+    max_depth: usize,
+}
+
+impl DependencyDepthObserver {
+    /// The number of cars in the longest chain of cars blocking the objective car, or zero if the
+    /// objective car is not blocked or the execution did not complete.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+/// The number of cars in the longest chain of blocking cars which starts at the provided car and
+/// does not revisit any car already on the path.
+fn longest_chain<T>(views: &[ViewFrom<T>], car: CarId, on_path: &mut [bool]) -> usize {
+    let i = car.get() - 1;
+    on_path[i] = true;
+    let mut longest = 0;
+    for next in [views[i].backward.observed, views[i].forward.observed]
+        .into_iter()
+        .flatten()
+    {
+        if !on_path[next.get() - 1] {
+            longest = longest.max(longest_chain(views, next, on_path));
+        }
+    }
+    on_path[i] = false;
+    longest + 1
+}
+
+/// The number of cars in the longest chain of blocking cars which starts at the car ahead of the
+/// objective car, given the views of every car, or zero if the objective car sees no car ahead.
+fn blocking_depth<T>(views: &[ViewFrom<T>]) -> usize {
+    let Some(blocker) = views.first().and_then(|view| view.forward.observed) else {
+        return 0;
+    };
+    let mut on_path = vec![false; views.len()];
+    // the objective car is on the path, so is never counted as a blocker
+    on_path[0] = true;
+    longest_chain(views, blocker, &mut on_path)
+}
+
+impl Named for DependencyDepthObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_dependency_depth");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for DependencyDepthObserver {
+    fn flush(&mut self) -> Result<(), Error> {
+        self.max_depth = 0;
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.max_depth = 0;
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for DependencyDepthObserver
+where
+    T: BoardValue,
+{
+    fn final_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
+        let views = board
+            .state()
+            .cars()
+            .iter()
+            .map(|(position, car)| view_from(board, *position, car))
+            .collect::<Vec<_>>();
+        self.max_depth = blocking_depth(&views);
+    }
+}
+
+/// An observer which measures how far the objective car moved toward the exit over an execution,
+/// relative to where it starts in the initial state.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ObjectiveProgressObserver<T> {
+    initial: Position<T>,
+    orientation: Orientation,
+    final_position: Option<Position<T>>,
+}
+
+impl<T> ObjectiveProgressObserver<T>
+where
+    T: Copy,
+{
+    /// Create an observer measuring progress from the objective car's position in the provided
+    /// initial state, or [`None`] if the state has no cars.
+    pub fn new(initial: &State<T>) -> Option<Self> {
+        let (position, car) = initial.cars().first()?;
+        Some(Self {
+            initial: *position,
+            orientation: car.orientation(),
+            final_position: None,
+        })
+    }
+}
+
+impl<T> ObjectiveProgressObserver<T>
+where
+    T: BoardValue,
+{
+    /// How many cells the objective car moved forward (toward the exit) over the last execution,
+    /// negative if it ended up further from the exit. Zero if the execution did not complete.
+    pub fn net_progress(&self) -> i64 {
+        let Some(final_position) = self.final_position else {
+            return 0;
+        };
+        let along = |position: Position<T>| -> i64 {
+            let coordinate = match self.orientation.axis() {
+                Axis::Horizontal => *position.column(),
+                Axis::Vertical => *position.row(),
+            };
+            coordinate.into() as i64
+        };
+        along(final_position) - along(self.initial)
+    }
+}
+
+impl<T> Named for ObjectiveProgressObserver<T> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_objective_progress");
+        &NAME
+    }
+}
+
+impl<S, T> Observer<PGInput, S> for ObjectiveProgressObserver<T> {
+    fn flush(&mut self) -> Result<(), Error> {
+        self.final_position = None;
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.final_position = None;
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for ObjectiveProgressObserver<T>
+where
+    T: Copy,
+{
+    fn final_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
+        self.final_position = board.state().cars().first().map(|(position, _)| *position);
+    }
+}
+
+/// Metadata which holds every transition between states seen by [`TransitionCountObserver`]s over
+/// the lifetime of the fuzzer, as pairs of [`hash_state`]-style hashes of the states, along with
+/// what is known of the states themselves.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct TransitionMetadata {
+    transitions: HashMap<(u64, u64), (CarId, Direction)>,
+    first_seen: HashMap<u64, u64>,
+    solved: HashSet<u64>,
+}
+
+impl TransitionMetadata {
+    /// The transitions seen so far, from the hash of the earlier state to that of the later one,
+    /// each with the move which first made it.
+    pub fn transitions(&self) -> &HashMap<(u64, u64), (CarId, Direction)> {
+        &self.transitions
+    }
+
+    /// The number of executions the fuzzer had run when the state with the provided hash was first
+    /// reached by a move, or [`None`] if it has not been.
+    pub fn first_seen(&self, hash: u64) -> Option<u64> {
+        self.first_seen.get(&hash).copied()
+    }
+
+    /// Whether the state with the provided hash has nothing between the objective car and the wall
+    /// ahead of it.
+    pub fn is_solved(&self, hash: u64) -> bool {
+        self.solved.contains(&hash)
+    }
+}
+
+impl_serdeany!(TransitionMetadata);
+
+/// An observer which collects the transitions between states made by each execution, and records
+/// them in the [`TransitionMetadata`] of the fuzzer's state. Only transitions between states
+/// reached by moves are recorded, so not the first move of an execution, out of the initial state.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct TransitionCountObserver {
+    previous: Option<u64>,
+    transitions: Vec<((u64, u64), (CarId, Direction))>,
+    states: Vec<(u64, bool)>,
+    transition_count: usize,
+    new_transitions: usize,
+}
+
+impl TransitionCountObserver {
+    /// The number of distinct transitions seen over the lifetime of the fuzzer, as of the end of
+    /// the last execution.
+    pub fn transition_count(&self) -> usize {
+        self.transition_count
+    }
+
+    /// The number of transitions seen for the first time in the last execution.
+    pub fn new_transitions(&self) -> usize {
+        self.new_transitions
+    }
+}
+
+impl Named for TransitionCountObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_transition_count");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for TransitionCountObserver
+where
+    S: HasMetadata + HasExecutions,
+{
+    fn flush(&mut self) -> Result<(), Error> {
+        self.previous = None;
+        self.transitions.clear();
+        self.states.clear();
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.previous = None;
+        self.transitions.clear();
+        self.states.clear();
+        self.new_transitions = 0;
+        Ok(())
+    }
+
+    fn post_exec(
+        &mut self,
+        state: &mut S,
+        _input: &PGInput,
+        _exit_kind: &ExitKind,
+    ) -> Result<(), Error> {
+        let executions = *state.executions();
+        let metadata = state.metadata_or_insert_with(TransitionMetadata::default);
+        for (hash, solved) in self.states.drain(..) {
+            metadata.first_seen.entry(hash).or_insert(executions);
+            if solved {
+                metadata.solved.insert(hash);
+            }
+        }
+        self.new_transitions = 0;
+        for (transition, made_by) in self.transitions.drain(..) {
+            if let Entry::Vacant(entry) = metadata.transitions.entry(transition) {
+                entry.insert(made_by);
+                self.new_transitions += 1;
+            }
+        }
+        self.transition_count = metadata.transitions.len();
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for TransitionCountObserver
+where
+    T: BoardValue,
+{
+    fn after_move(
+        &mut self,
+        car: CarId,
+        direction: Direction,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        let current = CompactBoard::from_board(board).hash();
+        self.states.push((current, blockers_in_path(board) == 0));
+        if let Some(previous) = self.previous.replace(current) {
+            self.transitions
+                .push(((previous, current), (car, direction)));
+        }
+    }
+}
+
+/// An observer which collects the cells occupied by any car after each move of an execution, and
+/// merges them into the [`CoverageMap`] of the fuzzer's state. Cells only occupied in the initial
+/// state are not collected, as no move was made to reach them.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CoverageMapObserver<T> {
+    coverage: Option<CoverageMap<T>>,
+    new_cells: usize,
+}
+
+impl<T> CoverageMapObserver<T> {
+    /// The cells visited by the last execution, or [`None`] if it made no moves.
+    pub fn coverage(&self) -> Option<&CoverageMap<T>> {
+        self.coverage.as_ref()
+    }
+
+    /// The number of cells visited for the first time over the lifetime of the fuzzer in the last
+    /// execution.
+    pub fn new_cells(&self) -> usize {
+        self.new_cells
+    }
+}
+
+impl<T> Default for CoverageMapObserver<T> {
+    fn default() -> Self {
+        Self {
+            coverage: None,
+            new_cells: 0,
+        }
+    }
+}
+
+impl<T> Named for CoverageMapObserver<T> {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_coverage_map");
+        &NAME
+    }
+}
+
+impl<S, T> Observer<PGInput, S> for CoverageMapObserver<T>
+where
+    S: HasMetadata,
+    T: BoardValue + DeserializeOwned + Serialize + 'static,
+{
+    fn flush(&mut self) -> Result<(), Error> {
+        self.coverage = None;
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.coverage = None;
+        self.new_cells = 0;
+        Ok(())
+    }
+
+    fn post_exec(
+        &mut self,
+        state: &mut S,
+        _input: &PGInput,
+        _exit_kind: &ExitKind,
+    ) -> Result<(), Error> {
+        let Some(coverage) = &self.coverage else {
+            return Ok(());
+        };
+        self.new_cells = coverage.merge_into_metadata(state);
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for CoverageMapObserver<T>
+where
+    T: BoardValue,
+{
+    fn after_move(
+        &mut self,
+        _car: CarId,
+        _direction: Direction,
+        board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        self.coverage
+            .get_or_insert_with(|| CoverageMap::new(board.state()))
+            .mark_board(board);
+    }
+}
+
+/// Metadata which counts the executions seen by [`CrashRateObserver`]s over the lifetime of the
+/// fuzzer, and how many of them crashed.
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize)]
+pub struct ExecutionCountMetadata {
+    crashes: u64,
+    total: u64,
+}
+
+impl ExecutionCountMetadata {
+    /// The number of executions which crashed.
+    pub fn crashes(&self) -> u64 {
+        self.crashes
+    }
+
+    /// The number of executions overall.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+impl_serdeany!(ExecutionCountMetadata);
+
+/// An observer which counts crashing executions in the [`ExecutionCountMetadata`] of the fuzzer's
+/// state, so that the proportion of inputs which make invalid moves may be measured.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct CrashRateObserver {
+    counts: ExecutionCountMetadata,
+}
+
+impl CrashRateObserver {
+    /// The proportion of executions which crashed, as of the end of the last execution, or zero if
+    /// there have been no executions.
+    pub fn crash_rate(&self) -> f64 {
+        if self.counts.total == 0 {
+            return 0.0;
+        }
+        self.counts.crashes as f64 / self.counts.total as f64
+    }
+
+    /// The number of executions over the lifetime of the fuzzer, as of the end of the last
+    /// execution.
+    pub fn total_executions(&self) -> u64 {
+        self.counts.total
+    }
+}
+
+impl Named for CrashRateObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_crash_rate");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for CrashRateObserver
+where
+    S: HasMetadata,
+{
+    fn post_exec(
+        &mut self,
+        state: &mut S,
+        _input: &PGInput,
+        exit_kind: &ExitKind,
+    ) -> Result<(), Error> {
+        let counts = state.metadata_or_insert_with(ExecutionCountMetadata::default);
+        if *exit_kind == ExitKind::Crash {
+            counts.crashes += 1;
+        }
+        counts.total += 1;
+        self.counts = *counts;
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for CrashRateObserver {}
+
+/// An observer which counts how many times each move (a car and a direction) appears in each input.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct MoveRepetitionObserver {
+    counts: HashMap<(CarId, Direction), usize>,
+    total: usize,
+}
+
+impl MoveRepetitionObserver {
+    /// How many times each move appears in the input last executed.
+    pub fn counts(&self) -> &HashMap<(CarId, Direction), usize> {
+        &self.counts
+    }
+
+    /// The number of distinct moves in the input last executed.
+    pub fn unique_moves(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// The number of moves in the input last executed.
+    pub fn total_moves(&self) -> usize {
+        self.total
+    }
+}
+
+impl Named for MoveRepetitionObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_move_repetition");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for MoveRepetitionObserver {
+    fn pre_exec(&mut self, _state: &mut S, input: &PGInput) -> Result<(), Error> {
+        self.counts.clear();
+        for &(car, direction) in input.moves() {
+            *self.counts.entry((car, direction)).or_default() += 1;
+        }
+        self.total = input.len();
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for MoveRepetitionObserver {}
+
+/// An observer which records which cars were moved by each execution, as a bitmask where car `n`
+/// is bit `n - 1`. Only the first 64 cars are recorded.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct CarMovementObserver {
+    moved: u64,
+}
+
+impl CarMovementObserver {
+    /// The cars moved in the last execution; bit `n - 1` is set if car `n` was moved.
+    pub fn moved_bitmask(&self) -> u64 {
+        self.moved
+    }
+
+    /// Whether the provided car was moved in the last execution. Always false beyond the 64th car.
+    pub fn moved(&self, car: CarId) -> bool {
+        car.get() <= 64 && self.moved & (1 << (car.get() - 1)) != 0
+    }
+}
+
+impl Named for CarMovementObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_car_movement");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for CarMovementObserver {
+    fn flush(&mut self) -> Result<(), Error> {
+        self.moved = 0;
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.moved = 0;
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for CarMovementObserver {
+    fn after_move(
+        &mut self,
+        car: CarId,
+        _direction: Direction,
+        _board: &Board<impl Deref<Target = State<T>>, T>,
+    ) {
+        if car.get() <= 64 {
+            self.moved |= 1 << (car.get() - 1);
+        }
+    }
+}
+
+/// The distinct cars with a cell in the provided row (if `horizontal`) or column of the board, in
+/// ascending order. There are none if the lane is not on the board.
+pub fn cars_in_lane<T: BoardValue>(
+    board: &Board<impl Deref<Target = State<T>>, T>,
+    lane_idx: T,
+    horizontal: bool,
+) -> Vec<CarId> {
+    let columns: usize = (*board.state().dimensions().columns()).into();
+    let lane: usize = lane_idx.into();
+    let cells = board.concrete();
+    let mut cars = if horizontal {
+        cells
+            .chunks(columns)
+            .nth(lane)
+            .unwrap_or_default()
+            .iter()
+            .flatten()
+            .map(|&car| car.into())
+            .collect::<Vec<_>>()
+    } else if lane < columns {
+        cells
+            .iter()
+            .skip(lane)
+            .step_by(columns)
+            .flatten()
+            .map(|&car| car.into())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    cars.sort_unstable();
+    cars.dedup();
+    cars
+}
+
+/// The distinct cars between the objective car and the wall ahead of it, from nearest to furthest.
+pub fn blocking_path<T: BoardValue>(board: &Board<impl Deref<Target = State<T>>, T>) -> Vec<CarId> {
+    let Some((position, car)) = board.state().cars().first() else {
+        return Vec::new();
+    };
+    let forward = match car.orientation().axis() {
+        Axis::Vertical => Direction::Down,
+        Axis::Horizontal => Direction::Right,
+    };
+    let mut offset = *car.length();
+    let mut blockers = Vec::new();
+    // stop once we step off the board
+    while let Some(cell) = position
+        .shift(forward, offset)
+        .and_then(|ahead| board.get(ahead))
+    {
+        if let Some(blocker) = cell.map(CarId::from)
+            && !blockers.contains(&blocker)
+        {
+            blockers.push(blocker);
+        }
+        offset += T::one();
+    }
+    blockers
+}
+
+/// The number of distinct cars between the objective car and the wall ahead of it.
+pub fn blockers_in_path<T: BoardValue>(board: &Board<impl Deref<Target = State<T>>, T>) -> usize {
+    blocking_path(board).len()
+}
+
+/// An observer which counts the cars between the objective car and the wall in the final board; the
+/// fewer there are, the closer the board is to being solved.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct NearSolutionObserver {
+    blockers: Option<usize>,
+}
+
+impl NearSolutionObserver {
+    /// The number of distinct cars in the path of the objective car after the last execution, or
+    /// [`None`] if the execution did not complete.
+    pub fn blockers_in_path(&self) -> Option<usize> {
+        self.blockers
+    }
+}
+
+impl Named for NearSolutionObserver {
+    fn name(&self) -> &Cow<'static, str> {
+        static NAME: Cow<'static, str> = Cow::Borrowed("pg_near_solution");
+        &NAME
+    }
+}
+
+impl<S> Observer<PGInput, S> for NearSolutionObserver {
+    fn flush(&mut self) -> Result<(), Error> {
+        self.blockers = None;
+        Ok(())
+    }
+
+    fn pre_exec(&mut self, _state: &mut S, _input: &PGInput) -> Result<(), Error> {
+        self.blockers = None;
+        Ok(())
+    }
+}
+
+impl<T> PGObserver<T> for NearSolutionObserver
+where
+    T: BoardValue,
+{
+    fn final_board(&mut self, board: &Board<impl Deref<Target = State<T>>, T>) {
+        self.blockers = Some(blockers_in_path(board));
+    }
+}
+
+/// The observers used by the fuzzer: a [`ViewObserver`] followed by a [`FinalStateObserver`].
+pub type PGObservers<T> = tuple_list_type!(ViewObserver<T>, FinalStateObserver<T>);
+
+/// The standard [`PGObservers`], along with handles to each observer for constructing feedbacks
+/// before the observers are moved into the executor.
+#[derive(Debug)]
+pub struct ObserverBundle<T> {
+    observers: PGObservers<T>,
+    view: Handle<ViewObserver<T>>,
+    final_state: Handle<FinalStateObserver<T>>,
+}
+
+impl<T> ObserverBundle<T> {
+    /// Creates the standard observers and extracts their handles.
+    pub fn new() -> Self {
+        let view = ViewObserver {
+            views: Vec::new(),
+            cars: None,
+            walls: Vec::new(),
+        };
+        let final_state = FinalStateObserver::default();
+        Self {
+            view: view.handle(),
+            final_state: final_state.handle(),
+            observers: tuple_list!(view, final_state),
+        }
+    }
+
+    /// The observers, for use in feedbacks which are constructed from a reference to the observer.
+    pub fn observers(&self) -> &PGObservers<T> {
+        &self.observers
+    }
+
+    /// The handle to the [`ViewObserver`].
+    pub fn view_handle(&self) -> Handle<ViewObserver<T>> {
+        self.view.clone()
+    }
+
+    /// The handle to the [`FinalStateObserver`].
+    pub fn final_handle(&self) -> Handle<FinalStateObserver<T>> {
+        self.final_state.clone()
+    }
+
+    /// Splits the bundle into the observers, to be passed to the executor, and their handles.
+    pub fn into_parts(
+        self,
+    ) -> (
+        PGObservers<T>,
+        Handle<ViewObserver<T>>,
+        Handle<FinalStateObserver<T>>,
+    ) {
+        (self.observers, self.view, self.final_state)
+    }
+}
+
+impl<T> Default for ObserverBundle<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::coverage::CoverageMap;
+    use crate::executor::PGExecutorBuilder;
+    use crate::input::PGInput;
+    use crate::observers::{
+        Axis, BranchingFactorObserver, CarMovementObserver, CoverageMapObserver, CrashRateObserver,
+        DeadlockObserver, DirectionExt, ExecutionTimeObserver, FinalStateObserver,
+        FreeSpaceObserver, HeatmapObserver, IncrementalViewObserver, MaxChainLengthObserver,
+        MoveRepetitionObserver, NearSolutionObserver, ObjectiveProgressObserver, ObserverBundle,
+        OrientationExt, PGObserver, PGObserverTuple, SaturatingBoardValue, SolutionLengthObserver,
+        TransitionCountObserver, TransitionMetadata, View, ViewFrom, ViewObserver,
+        blockers_in_path, blocking_depth, cars_in_lane, hash_state, in_all_directions,
+        stop_at_walls, valid_moves_for_board,
+    };
+    use crate::types::CarId;
+    use libafl::HasMetadata;
+    use libafl::NopFuzzer;
+    use libafl::events::SimpleEventManager;
+    use libafl::executors::{Executor, ExitKind, HasObservers};
+    use libafl::feedbacks::{Feedback, NewHashFeedback, StateInitializer};
+    use libafl::observers::{Observer, ObserverWithHashField, ObserversTuple};
+    use libafl::state::{HasExecutions, NopState};
+    use libafl_bolts::tuples::{Handled, MatchNameRef, tuple_list};
+    use parking_game::{BoardValue, Car, Direction, Orientation, State};
+    use std::cmp::Ordering;
+    use std::error::Error;
+    use std::ptr;
+    use std::time::Duration;
+
+    #[test]
+    fn simple_observation() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("33oo22.");
+        let obs = ViewObserver::<u8>::default();
+        let handle = obs.handle();
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+
+        let nop_input = PGInput::new(vec![]);
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.final_board_all(&initial.board()?);
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+
+        assert_eq!(
+            observers.0.views().next().unwrap().1.backward,
+            View {
+                direction: Direction::Left,
+                observed: CarId::new(3),
+                distance: 0
+            }
+        );
+        assert_eq!(
+            observers.0.views().next().unwrap().1.forward,
+            View {
+                direction: Direction::Right,
+                observed: CarId::new(2),
+                distance: 0
+            }
+        );
+
+        let initial = crate::parse_map::<u8>("oo.");
+
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.final_board_all(&initial.board()?);
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+
+        assert_eq!(
+            observers.0.views().next().unwrap().1.backward,
+            View {
+                direction: Direction::Left,
+                observed: None,
+                distance: 0
+            }
+        );
+        assert_eq!(
+            observers.0.views().next().unwrap().1.forward,
+            View {
+                direction: Direction::Right,
+                observed: None,
+                distance: 1
+            }
+        );
+
+        let initial = crate::parse_map::<u8>(
+            r#"
+            3
+            3
+            o
+            o
+            2
+            2
+            .
+            "#,
+        );
+
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.final_board_all(&initial.board()?);
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+
+        assert_eq!(
+            observers.0.views().next().unwrap().1.backward,
+            View {
+                direction: Direction::Up,
+                observed: CarId::new(3),
+                distance: 0
+            }
+        );
+        assert_eq!(
+            observers.0.views().next().unwrap().1.forward,
+            View {
+                direction: Direction::Down,
+                observed: CarId::new(2),
+                distance: 0
+            }
+        );
+
+        let initial = crate::parse_map::<u8>(
+            r#"
+            o
+            o
+            .
+            "#,
+        );
+
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.final_board_all(&initial.board()?);
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+
+        assert_eq!(
+            observers.0.views().next().unwrap().1.backward,
+            View {
+                direction: Direction::Up,
+                observed: None,
+                distance: 0
+            }
+        );
+        assert_eq!(
+            observers.0.views().next().unwrap().1.forward,
+            View {
+                direction: Direction::Down,
+                observed: None,
+                distance: 1
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn all_directions() {
+        let directions = [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ];
+
+        // `step_until_seen` is left to pt.0, so each direction is looked in with a stand-in
+        let mut looked = Vec::new();
+        let views = in_all_directions(|direction| {
+            looked.push(direction);
+            View::<u8>::new(direction, None, looked.len() as u8)
+        });
+        assert_eq!(directions.to_vec(), looked);
+        assert_eq!(directions, views.map(|view| view.direction()));
+        assert_eq!([1, 2, 3, 4], views.map(|view| *view.distance()));
+    }
+
+    #[test]
+    fn wall_observation() {
+        let (initial, map) =
+            crate::parse_map_with_options::<u8>("oo....\n11.#22", Default::default());
+        let walls = map.walls();
+        let car = |id| CarId::new(id).unwrap();
+        // the views of each car as seen through the wall, which is an empty cell of the board
+        let stop = |id: usize, view: View<u8>| {
+            let (position, parked) = &initial.cars()[id - 1];
+            stop_at_walls(view, *position, parked, walls)
+        };
+
+        // the objective car's lane has no walls
+        assert_eq!(
+            View::new(Direction::Right, None, 4),
+            stop(1, View::new(Direction::Right, None, 4))
+        );
+        // the wall between cars 2 and 3 hides them from each other, as an edge of the board would
+        assert_eq!(
+            View::new(Direction::Right, None, 1),
+            stop(2, View::new(Direction::Right, Some(car(3)), 2))
+        );
+        assert_eq!(
+            View::new(Direction::Left, None, 0),
+            stop(3, View::new(Direction::Left, Some(car(2)), 2))
+        );
+        // a wall on the other side of a car is not seen
+        assert_eq!(
+            View::new(Direction::Right, None, 0),
+            stop(3, View::new(Direction::Right, None, 0))
+        );
+        assert_eq!(
+            View::new(Direction::Left, None, 0),
+            stop(2, View::new(Direction::Left, None, 0))
+        );
+    }
+
+    #[test]
+    fn views_for_car() {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n22..");
+        let mut obs = ViewObserver::for_state(&initial);
+        assert!(obs.views_for_car(CarId::new(1).unwrap()).is_none());
+        // the views of the initial board, as the observer would have seen them
+        obs.views = vec![
+            ViewFrom::new(
+                View::new(Direction::Left, None, 0),
+                View::new(Direction::Right, Some(CarId::new(2).unwrap()), 1),
+            ),
+            ViewFrom::new(
+                View::new(Direction::Up, None, 0),
+                View::new(Direction::Down, None, 1),
+            ),
+            ViewFrom::new(
+                View::new(Direction::Left, None, 0),
+                View::new(Direction::Right, None, 2),
+            ),
+        ];
+        assert_eq!(Ok(()), obs.validate());
+
+        let objective = obs.views_for_car(CarId::new(1).unwrap()).unwrap();
+        assert_eq!(
+            objective.forward,
+            View::new(Direction::Right, Some(CarId::new(2).unwrap()), 1)
+        );
+        assert!(obs.views_for_car(CarId::new(100).unwrap()).is_none());
+        for (car, view) in obs.views() {
+            assert!(ptr::eq(view, obs.views_for_car(car).unwrap()));
+        }
+    }
+
+    #[test]
+    fn distinguish_states() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("33oo22.");
+        let obs = FinalStateObserver::<u8>::default();
+        let handle = obs.handle();
+
+        let mut observers = tuple_list!(obs);
+
+        let mut state = NopState::<PGInput>::new();
+
+        let nop_input = PGInput::new(vec![]);
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.final_board_all(&initial.board()?);
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+
+        let first_hash = observers.0.hash().unwrap();
+
+        // same as above, but 2 is shifted right
+        let initial = crate::parse_map::<u8>("33oo.22");
+
+        let nop_input = PGInput::new(vec![]);
+        observers.pre_exec_all(&mut state, &nop_input)?;
+        observers.final_board_all(&initial.board()?);
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+
+        let second_hash = observers.0.hash().unwrap();
+
+        assert_ne!(first_hash, second_hash);
+
+        Ok(())
+    }
+
+    #[test]
+    fn distinguish_symmetric_states() -> Result<(), Box<dyn Error>> {
+        let mut observers = tuple_list!(FinalStateObserver::<u8>::default());
+        let mut state = NopState::<PGInput>::new();
+        let mut hash = |map: &str| -> Result<u64, Box<dyn Error>> {
+            let initial = crate::parse_map::<u8>(map);
+            let nop_input = PGInput::new(vec![]);
+            observers.pre_exec_all(&mut state, &nop_input)?;
+            observers.final_board_all(&initial.board()?);
+            observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+            Ok(observers.0.hash().unwrap())
+        };
+
+        // the same cars occupy the same number of cells, only mirrored, so only the positions of
+        // the cells tell these apart
+        assert_ne!(hash("oo....\n1.....")?, hash("....oo\n.....1")?);
+        assert_ne!(hash("oo...1\n......")?, hash("1...oo\n......")?);
+        assert_ne!(hash("1.oo.2\n1....2")?, hash("2.oo.1\n2....1")?);
+        assert_eq!(hash("oo....\n1.....")?, hash("oo....\n1.....")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn hash_matches_observer() -> Result<(), Box<dyn Error>> {
+        let maps = [
+            "oo.",
+            ".oo",
+            "33oo22.",
+            "33oo.22",
+            "o\no\n.",
+            ".\no\no",
+            "oo.\n.22",
+            "oo.\n22.",
+            ".1.\n.1.\noo.\n22.",
+            "...\n.1.\n.1o\n22o",
+        ];
+        let mut observer = FinalStateObserver::<u8>::default();
+        for map in maps {
+            let initial = crate::parse_map::<u8>(map);
+            observer.final_board(&initial.board()?);
+            #[cfg(not(feature = "incremental-hash"))]
+            assert_eq!(observer.hash(), hash_state(&initial));
+            #[cfg(feature = "incremental-hash")]
+            assert_eq!(
+                observer.hash(),
+                Some(crate::hash::IncrementalHasher::new(&initial).hash())
+            );
+        }
+
+        // the same state, built by hand
+        let mut built = State::<u8>::empty((1, 3))?;
+        built
+            .board_mut()?
+            .add_car((0, 1), Car::new(2, Orientation::LeftRight).unwrap())?;
+        assert_eq!(
+            hash_state(&crate::parse_map::<u8>(".oo")),
+            hash_state(&built)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn final_state_car_count() -> Result<(), Box<dyn Error>> {
+        let three = crate::parse_map::<u8>("oo.1\n...1\n22..");
+        let two = crate::parse_map::<u8>("oo.1\n...1\n....");
+        let mut state = NopState::<PGInput>::new();
+        let input = PGInput::default();
+        let flush = |observer: &mut FinalStateObserver<u8>| {
+            Observer::<PGInput, NopState<PGInput>>::flush(observer)
+        };
+
+        let mut observer = FinalStateObserver::for_state(&three);
+        assert_eq!(Some(3), observer.expected_car_count());
+        observer.final_board(&three.board()?);
+        observer.post_exec(&mut state, &input, &ExitKind::Ok)?;
+        assert_eq!(Ok(()), observer.validate());
 
-        observers.pre_exec_all(&mut state, &nop_input)?;
-        observers.final_board_all(&initial.board()?);
-        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+        // as though the executor had lost a car
+        observer.final_state = Some(two.clone());
+        assert!(observer.validate().is_err());
+        assert!(
+            observer
+                .post_exec(&mut state, &input, &ExitKind::Ok)
+                .is_err()
+        );
+        assert!(flush(&mut observer).is_err());
+        observer.final_state = None;
+        assert!(flush(&mut observer).is_ok());
 
-        assert_eq!(
-            observers.0.views().next().unwrap().1.backward,
-            View {
-                direction: Direction::Up,
-                observed: NonZeroUsize::new(3),
-                distance: 0
+        // nothing is checked without an expected count
+        let mut unchecked = FinalStateObserver {
+            final_state: Some(two),
+            ..FinalStateObserver::default()
+        };
+        assert_eq!(None, unchecked.expected_car_count());
+        assert!(flush(&mut unchecked).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn observer_bundle() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1");
+        let bundle = ObserverBundle::<u8>::new();
+        let mut new_state = NewHashFeedback::new(&bundle.observers().1.0);
+        let (mut observers, view, final_state) = bundle.into_parts();
+        assert!(observers.get(&view).is_some());
+        assert!(observers.get(&final_state).is_some());
+
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+        new_state.init_state(&mut state)?;
+
+        let nop_input = PGInput::new(vec![]);
+        for expected in [true, false] {
+            observers.pre_exec_all(&mut state, &nop_input)?;
+            observers.1.0.final_board(&initial.board()?);
+            observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+            assert_eq!(
+                expected,
+                new_state.is_interesting(
+                    &mut state,
+                    &mut mgr,
+                    &nop_input,
+                    &observers,
+                    &ExitKind::Ok
+                )?
+            );
+        }
+
+        let executor = PGExecutorBuilder::with_initial(initial).build(observers);
+        assert!(executor.observers().get(&view).is_some());
+        assert!(executor.observers().get(&final_state).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn view_ordering() {
+        let view_from = |forward: u8, backward: u8, seen_forward, seen_backward| ViewFrom {
+            backward: View::new(Direction::Left, CarId::new(seen_backward), backward),
+            forward: View::new(Direction::Right, CarId::new(seen_forward), forward),
+        };
+
+        let view = view_from(1, 0, 0, 0);
+        assert_eq!(view, view);
+        assert_eq!(Ordering::Equal, view.cmp(&view));
+        assert_ne!(view, view_from(1, 0, 2, 0));
+
+        // each field takes priority over all of those after it
+        let ordered = [
+            view_from(0, 3, 3, 3),
+            view_from(1, 0, 3, 3),
+            view_from(1, 1, 0, 3),
+            view_from(1, 1, 1, 0),
+            view_from(1, 1, 1, 1),
+            view_from(2, 0, 0, 0),
+        ];
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(i.cmp(&j), a.cmp(b));
+                assert_eq!(Some(i.cmp(&j)), a.partial_cmp(b));
             }
+        }
+
+        let vertical = ViewFrom {
+            backward: View::new(Direction::Up, None, 1),
+            forward: View::new(Direction::Down, None, 1),
+        };
+        assert_ne!(view_from(1, 1, 0, 0), vertical);
+        assert_ne!(Ordering::Equal, view_from(1, 1, 0, 0).cmp(&vertical));
+
+        let mut views = vec![ordered[3], ordered[0], ordered[5], ordered[1]];
+        views.sort();
+        assert_eq!([ordered[0], ordered[1], ordered[3], ordered[5]], *views);
+    }
+
+    #[test]
+    fn validate_observers() -> Result<(), Box<dyn Error>> {
+        let mut state = State::<u8>::empty((3, 3))?;
+        let mut final_state = FinalStateObserver::<u8>::default();
+        assert_eq!(Ok(()), final_state.validate());
+        final_state.final_state = Some(state.clone());
+        assert!(final_state.validate().is_err());
+
+        let mut board = state.board_mut()?;
+        board.add_car((0, 0), Car::new(2, Orientation::LeftRight).unwrap())?;
+        board.add_car((1, 2), Car::new(2, Orientation::UpDown).unwrap())?;
+        drop(board);
+        final_state.final_board(&state.board()?);
+        assert_eq!(Ok(()), final_state.validate());
+
+        let view_from = ViewFrom {
+            backward: View::new(Direction::Left, None, 0),
+            forward: View::new(Direction::Right, None, 1),
+        };
+        let mut view = ViewObserver::for_state(&state);
+        assert_eq!(Ok(()), view.validate());
+        view.views = vec![view_from; 2];
+        assert_eq!(Ok(()), view.validate());
+        view.views.pop();
+        assert!(view.validate().is_err());
+        assert!((view, (final_state, ())).validate_all().is_err());
+
+        // without the state, there is nothing to compare to
+        let mut view = ViewObserver::<u8>::default();
+        view.views = vec![view_from];
+        assert_eq!(Ok(()), view.validate());
+
+        Ok(())
+    }
+
+    #[test]
+    fn free_distance() {
+        let free = ViewFrom {
+            backward: View::new(Direction::Left, None, 1u8),
+            forward: View::new(Direction::Right, CarId::new(2), 2u8),
+        };
+        assert_eq!(3, free.total_free_distance());
+        assert!(!free.is_completely_blocked());
+
+        let blocked = ViewFrom {
+            backward: View::new(Direction::Up, CarId::new(2), 0u16),
+            forward: View::new(Direction::Down, None, 0u16),
+        };
+        assert_eq!(0, blocked.total_free_distance());
+        assert!(blocked.is_completely_blocked());
+
+        let one_way = ViewFrom {
+            backward: View::new(Direction::Up, None, 0u16),
+            forward: View::new(Direction::Down, None, 300u16),
+        };
+        assert_eq!(300, one_way.total_free_distance());
+        assert!(!one_way.is_completely_blocked());
+    }
+
+    #[test]
+    fn direction_utilities() {
+        let pairs = [
+            (Direction::Up, Direction::Down),
+            (Direction::Down, Direction::Up),
+            (Direction::Left, Direction::Right),
+            (Direction::Right, Direction::Left),
+        ];
+        for (dir, opposite) in pairs {
+            assert_eq!(opposite, dir.opposite());
+            assert_eq!(-dir, dir.opposite());
+            assert_eq!(dir, dir.opposite().opposite());
+            assert_eq!(dir.axis(), opposite.axis());
+        }
+
+        assert_eq!(Axis::Vertical, Direction::Up.axis());
+        assert_eq!(Axis::Vertical, Direction::Down.axis());
+        assert_eq!(Axis::Horizontal, Direction::Left.axis());
+        assert_eq!(Axis::Horizontal, Direction::Right.axis());
+        assert_eq!(Axis::Horizontal, Orientation::LeftRight.axis());
+        assert_eq!(Axis::Vertical, Orientation::UpDown.axis());
+    }
+
+    #[test]
+    fn direction_encodings() {
+        // the encodings are written to disk, so they must never change
+        let encoded = [
+            (Direction::Up, 0, -2),
+            (Direction::Down, 1, 2),
+            (Direction::Left, 2, -1),
+            (Direction::Right, 3, 1),
+        ];
+        for (dir, byte, signed) in encoded {
+            assert_eq!(byte, dir.to_byte());
+            assert_eq!(Some(dir), Direction::from_byte(byte));
+            assert_eq!(signed, dir.to_signed());
+            assert_eq!(Some(dir), Direction::from_signed(signed));
+            assert_eq!(-signed, dir.opposite().to_signed());
+        }
+
+        assert!((4..=u8::MAX).all(|byte| Direction::from_byte(byte).is_none()));
+        for step in [i8::MIN, -3, 0, 3, i8::MAX] {
+            assert_eq!(None, Direction::from_signed(step));
+        }
+    }
+
+    #[test]
+    fn valid_moves() -> Result<(), Box<dyn Error>> {
+        let car = |id| CarId::new(id).unwrap();
+
+        let initial = crate::parse_map::<u8>("......\n..oo..\n......");
+        assert_eq!(
+            vec![(car(1), Direction::Left), (car(1), Direction::Right)],
+            valid_moves_for_board(&initial.board()?)
+        );
+
+        let initial = crate::parse_map::<u8>("11oo22\n334455");
+        assert!(valid_moves_for_board(&initial.board()?).is_empty());
+
+        let initial = crate::parse_map::<u8>(
+            r#"
+        1.....
+        1.oo.2
+        ..3..2
+        ..3...
+        "#,
         );
         assert_eq!(
-            observers.0.views().next().unwrap().1.forward,
-            View {
-                direction: Direction::Down,
-                observed: NonZeroUsize::new(2),
-                distance: 0
-            }
+            vec![
+                (car(1), Direction::Left),
+                (car(1), Direction::Right),
+                (car(2), Direction::Down),
+                (car(3), Direction::Up),
+                (car(3), Direction::Down),
+            ],
+            valid_moves_for_board(&initial.board()?)
         );
 
+        Ok(())
+    }
+
+    #[test]
+    fn incremental_views() -> Result<(), Box<dyn Error>> {
+        let car = |id| CarId::new(id).unwrap();
         let initial = crate::parse_map::<u8>(
             r#"
-            o
-            o
-            .
-            "#,
+        1.....
+        1.oo.2
+        ..3..2
+        ..3...
+        "#,
         );
 
-        observers.pre_exec_all(&mut state, &nop_input)?;
+        // the views of the initial board, as the observer would have seen them before the move
+        let seeded = || IncrementalViewObserver {
+            views: vec![
+                ViewFrom::new(
+                    View::new(Direction::Left, Some(car(2)), 1u8),
+                    View::new(Direction::Right, Some(car(3)), 1),
+                ),
+                ViewFrom::new(
+                    View::new(Direction::Up, None, 0),
+                    View::new(Direction::Down, None, 2),
+                ),
+                ViewFrom::new(
+                    View::new(Direction::Up, None, 1),
+                    View::new(Direction::Down, None, 1),
+                ),
+                ViewFrom::new(
+                    View::new(Direction::Up, Some(car(1)), 0),
+                    View::new(Direction::Down, None, 0),
+                ),
+            ],
+        };
+
+        let observer = seeded();
+        assert_eq!((Some(car(2)), Some(car(3))), observer.blocker_of(car(1)));
+        assert_eq!((Some(car(1)), None), observer.blocker_of(car(4)));
+        assert_eq!((None, None), observer.blocker_of(car(5)));
+
+        for (moved, direction, expected) in [
+            // car 4 saw the objective car, which now covers no other car's lane
+            (car(1), Direction::Right, vec![0, 3]),
+            // the objective car saw car 2, which now also covers its row
+            (car(2), Direction::Down, vec![0, 1]),
+            // the objective car saw car 3, even though it has now moved out of the objective's row
+            (car(3), Direction::Up, vec![0, 2]),
+        ] {
+            let mut state = initial.clone();
+            let mut board = state.board_mut()?;
+            board.shift_car(moved.as_nonzero(), direction).unwrap();
+            assert_eq!(
+                expected,
+                observer.affected_by(moved, &board),
+                "{moved:?} {direction:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn heatmap() -> Result<(), Box<dyn Error>> {
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let mut heatmap_of = |map: &str, input: &str| -> Result<_, Box<dyn Error>> {
+            let input = input.parse::<PGInput>()?;
+            let mut executor = PGExecutorBuilder::with_initial(crate::parse_map::<u8>(map))
+                .build(tuple_list!(HeatmapObserver::default()));
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            assert_eq!(ExitKind::Ok, kind);
+            let observer = &executor.observers().0;
+            Ok((observer.heatmap().clone(), observer.hot_cell()))
+        };
+
+        let (heatmap, hot) = heatmap_of("oo....\n..1...\n..1...", "1R 1R 1R 1L 1R 1R")?;
+        assert_eq!(vec![0, 1, 3, 4, 3, 1], heatmap[0]);
+        // the other car never moves, so its cells are counted once for each move
+        assert_eq!(vec![0, 0, 6, 0, 0, 0], heatmap[1]);
+        assert_eq!(heatmap[1], heatmap[2]);
+        // ties are broken by the first cell
+        assert_eq!(Some((1, 2)), hot);
+
+        // with only one car moving along the top row, nothing else is ever occupied
+        let (heatmap, hot) = heatmap_of("oo....\n......", "1R 1R 1L 1R 1R 1R 1L 1L 1L")?;
+        assert_eq!(vec![0, 3, 6, 5, 3, 1], heatmap[0]);
+        assert!(heatmap[1].iter().all(|&count| count == 0));
+        assert_eq!(Some((0, 2)), hot);
+
+        let (heatmap, hot) = heatmap_of("oo....\n......", "")?;
+        assert!(heatmap.is_empty());
+        assert_eq!(None, hot);
+
+        Ok(())
+    }
+
+    #[test]
+    fn free_space() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("......\n......\n....oo");
+        let mut executor = PGExecutorBuilder::with_initial(initial)
+            .build(tuple_list!(FreeSpaceObserver::default()));
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        for (input, expected) in [("", 3 * 6 - 2), ("1L 1L", 3 * 6 - 2), ("1R", 0)] {
+            let input = input.parse::<PGInput>()?;
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            assert_eq!(expected, executor.observers().0.free_cells());
+        }
+
+        let initial = crate::parse_map::<u8>("1oo2\n1332\n4455");
+        let mut observers = tuple_list!(FreeSpaceObserver::default());
+        observers.pre_exec_all(&mut state, &PGInput::default())?;
         observers.final_board_all(&initial.board()?);
-        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+        assert_eq!(0, observers.0.free_cells());
 
-        assert_eq!(
-            observers.0.views().next().unwrap().1.backward,
-            View {
-                direction: Direction::Up,
-                observed: None,
-                distance: 0
-            }
-        );
-        assert_eq!(
-            observers.0.views().next().unwrap().1.forward,
-            View {
-                direction: Direction::Down,
-                observed: None,
-                distance: 1
+        Ok(())
+    }
+
+    #[test]
+    fn execution_time() -> Result<(), Box<dyn Error>> {
+        let mut executor = PGExecutorBuilder::with_initial(crate::parse_map::<u8>("oo."))
+            .with_move_delay(Duration::from_millis(1))
+            .build(tuple_list!(ExecutionTimeObserver::default()));
+        assert_eq!(None, executor.observers().0.last_duration());
+        assert_eq!(None, executor.observers().0.average_duration());
+
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let input = ["1R 1L"; 50].join(" ").parse::<PGInput>()?;
+        executor.observers_mut().pre_exec_all(&mut state, &input)?;
+        let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+        executor
+            .observers_mut()
+            .post_exec_all(&mut state, &input, &kind)?;
+        let last = executor.observers().0.last_duration().unwrap();
+        assert!(last >= Duration::from_millis(100));
+        assert_eq!(Some(last), executor.observers().0.average_duration());
+
+        // only the most recent executions are averaged
+        let mut observer = ExecutionTimeObserver::default();
+        for millis in 0..50 {
+            observer.record(Duration::from_millis(1000 + millis));
+        }
+        for _ in 0..100 {
+            observer.record(Duration::from_millis(10));
+        }
+        assert_eq!(Some(Duration::from_millis(10)), observer.average_duration());
+        for millis in 0..100 {
+            observer.record(Duration::from_millis(millis * 2));
+        }
+        assert_eq!(Some(Duration::from_millis(99)), observer.average_duration());
+        assert_eq!(Some(Duration::from_millis(198)), observer.last_duration());
+
+        Ok(())
+    }
+
+    #[test]
+    fn solution_length() -> Result<(), Box<dyn Error>> {
+        let mut observer = SolutionLengthObserver::default();
+        let mut state = NopState::<PGInput>::new();
+        assert_eq!(0, observer.last_length());
+        assert_eq!(None, observer.min_length_ever());
+
+        observer.pre_exec(&mut state, &"1R 2L 1R".parse()?)?;
+        assert_eq!(3, observer.last_length());
+        observer.pre_exec(&mut state, &PGInput::default())?;
+        assert_eq!(0, observer.last_length());
+
+        let tracker = observer.tracker();
+        tracker.record(7);
+        assert_eq!(Some(7), observer.min_length_ever());
+        tracker.record(9);
+        observer.tracker().record(4);
+        assert_eq!(Some(4), observer.min_length_ever());
+        assert_eq!(Some(4), tracker.min_length());
+
+        Ok(())
+    }
+
+    #[test]
+    fn deadlock() -> Result<(), Box<dyn Error>> {
+        let mut observers = tuple_list!(DeadlockObserver::default());
+        let mut state = NopState::<PGInput>::new();
+        let nop_input = PGInput::default();
+
+        for (map, expected) in [
+            ("11oo22\n334455", true),
+            ("1oo2\n1332\n4455", true),
+            ("11oo..\n334455", false),
+            ("oo.", false),
+        ] {
+            let initial = crate::parse_map::<u8>(map);
+            let board = initial.board()?;
+            assert_eq!(expected, valid_moves_for_board(&board).is_empty(), "{map}");
+
+            observers.pre_exec_all(&mut state, &nop_input)?;
+            assert!(!observers.0.is_deadlocked());
+            observers.final_board_all(&board);
+            observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+            assert_eq!(expected, observers.0.is_deadlocked(), "{map}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn branching_factor() -> Result<(), Box<dyn Error>> {
+        let mut state = NopState::<PGInput>::new();
+
+        // moves each car by hand, passing every board reached to the observer
+        let mut branching_factor = |map: &str, input: &str| -> Result<f64, Box<dyn Error>> {
+            let input = input.parse::<PGInput>()?;
+            let mut observer = BranchingFactorObserver::default();
+            Observer::<PGInput, _>::pre_exec(&mut observer, &mut state, &input)?;
+
+            let mut walk = crate::parse_map::<u8>(map);
+            let mut board = walk.board_mut()?;
+            for (car, dir) in input.moves() {
+                board.shift_car(car.as_nonzero(), *dir)?;
+                observer.after_move(*car, *dir, &board);
             }
-        );
+            Ok(observer.avg_branching_factor())
+        };
+
+        let open = "......\n..oo..\n......";
+        assert_eq!(2.0, branching_factor(open, "1L 1R 1R")?);
+        // after 1L 1L, the car is against the wall and may only move right
+        assert_eq!(1.5, branching_factor(open, "1L 1L")?);
+
+        let crowded = "......\n..oo..\n.1..2.\n.1..2.";
+        // after 1L, the objective car blocks car 2 from moving up
+        assert_eq!(3.5, branching_factor(crowded, "1L 1R")?);
+        assert!(branching_factor(crowded, "1L 1R")? > branching_factor(open, "1L 1R")?);
+
+        assert_eq!(0.0, branching_factor("11oo22\n334455", "")?);
 
         Ok(())
     }
 
     #[test]
-    fn distinguish_states() -> Result<(), Box<dyn Error>> {
-        let initial = crate::parse_map::<u8>("33oo22.");
-        let obs = FinalStateObserver::<u8>::default();
-        let handle = obs.handle();
+    fn max_chain_length() -> Result<(), Box<dyn Error>> {
+        let mut observer = MaxChainLengthObserver::default();
+        let mut state = NopState::<PGInput>::new();
 
-        let mut observers = tuple_list!(obs);
+        for (input, expected) in [
+            ("1R 1R 1R 2L 1R", 3),
+            ("1R 2L 1R 2L", 1),
+            ("1R", 1),
+            ("2U 1R 1L 3D 3D 3U 3D", 4),
+            ("", 0),
+        ] {
+            observer.pre_exec(&mut state, &input.parse()?)?;
+            assert_eq!(expected, observer.max_chain(), "{input}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn dependency_depth() {
+        // only the cars seen behind and ahead of each car matter to the depth
+        let view = |backward: Option<usize>, forward: Option<usize>| {
+            ViewFrom::new(
+                View::new(Direction::Left, backward.and_then(CarId::new), 0u8),
+                View::new(Direction::Right, forward.and_then(CarId::new), 0),
+            )
+        };
 
+        for (map, views, expected) in [
+            // the objective is blocked by 1, which is blocked by 2, which is blocked by 3
+            (
+                "..1...\noo1...\n..223.\n....3.",
+                vec![
+                    view(None, Some(2)),
+                    view(None, Some(3)),
+                    view(None, Some(4)),
+                    view(None, None),
+                ],
+                3,
+            ),
+            // 1 may only be blocked by the walls
+            ("oo1.\n..1.", vec![view(None, Some(2)), view(None, None)], 1),
+            ("oo..\n..1.", vec![view(None, None), view(None, None)], 0),
+            // 1 and 2 block each other, but each is only counted once
+            (
+                "oo1..\n..1..\n..23.\n..23.",
+                vec![
+                    view(None, Some(2)),
+                    view(None, Some(3)),
+                    view(Some(2), None),
+                    view(None, None),
+                ],
+                2,
+            ),
+        ] {
+            assert_eq!(expected, blocking_depth(&views), "{map}");
+        }
+    }
+
+    #[test]
+    fn objective_progress() -> Result<(), Box<dyn Error>> {
+        let mut fuzzer = NopFuzzer::new();
         let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
 
-        let nop_input = PGInput::new(vec![]);
-        observers.pre_exec_all(&mut state, &nop_input)?;
-        observers.final_board_all(&initial.board()?);
-        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+        let mut progress = |map: &str, input: &str| -> Result<i64, Box<dyn Error>> {
+            let input = input.parse::<PGInput>()?;
+            let initial = crate::parse_map::<u8>(map);
+            let observer = ObjectiveProgressObserver::new(&initial).unwrap();
+            let mut executor =
+                PGExecutorBuilder::with_initial(initial).build(tuple_list!(observer));
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            Ok(executor.observers().0.net_progress())
+        };
 
-        let first_hash = observers.0.hash().unwrap();
+        let map = "......\n.oo...\n......";
+        assert_eq!(1, progress(map, "1R 1R 1L")?);
+        assert_eq!(-1, progress(map, "1L")?);
+        assert_eq!(0, progress(map, "")?);
+        // the objective car can't move up, so the execution crashes
+        assert_eq!(0, progress(map, "1R 1U")?);
 
-        // same as above, but 2 is shifted right
-        let initial = crate::parse_map::<u8>("33oo.22");
+        let vertical = ".o.\n.o.\n...\n...";
+        assert_eq!(2, progress(vertical, "1D 1D")?);
+        assert_eq!(1, progress(vertical, "1D 1D 1U")?);
 
-        let nop_input = PGInput::new(vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn transition_count() -> Result<(), Box<dyn Error>> {
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let mut executor = PGExecutorBuilder::with_initial(crate::parse_map::<u8>(
+            "......\n.oo...\n......\n.1....",
+        ))
+        .build(tuple_list!(TransitionCountObserver::default()));
+        let mut execute = |input: &str| -> Result<(usize, usize), Box<dyn Error>> {
+            let input = input.parse::<PGInput>()?;
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            executor
+                .observers_mut()
+                .post_exec_all(&mut state, &input, &kind)?;
+            let observer = &executor.observers().0;
+            Ok((observer.transition_count(), observer.new_transitions()))
+        };
+
+        assert_eq!((0, 0), execute("1R")?);
+        assert_eq!((1, 1), execute("1R 2U")?);
+        assert_eq!((1, 0), execute("1R 2U")?);
+        // the same final state, but through a different intermediate state
+        assert_eq!((2, 1), execute("2U 1R")?);
+        // 1L returns to the state after 2U, so only the transition back to it is new
+        assert_eq!((3, 1), execute("2U 1R 1L 1R")?);
+
+        // each transition keeps the move which first made it; the objective car is never blocked
+        let executions = *state.executions();
+        let metadata = state.metadata::<TransitionMetadata>()?;
+        let moves = metadata.transitions().values().copied().collect::<Vec<_>>();
+        assert!(moves.contains(&(CarId::new(2).unwrap(), Direction::Up)));
+        assert!(moves.contains(&(CarId::new(1).unwrap(), Direction::Left)));
+        for &(from, to) in metadata.transitions().keys() {
+            assert!(metadata.is_solved(from) && metadata.is_solved(to));
+            assert!(
+                metadata
+                    .first_seen(to)
+                    .is_some_and(|seen| seen <= executions)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn coverage_map() -> Result<(), Box<dyn Error>> {
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let mut executor = PGExecutorBuilder::with_initial(crate::parse_map::<u8>(
+            "oo....\n......\n.1....\n.1....",
+        ))
+        .build(tuple_list!(CoverageMapObserver::<u8>::default()));
+        let mut execute = |input: &str| -> Result<usize, Box<dyn Error>> {
+            let input = input.parse::<PGInput>()?;
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            executor
+                .observers_mut()
+                .post_exec_all(&mut state, &input, &kind)?;
+            Ok(executor.observers().0.new_cells())
+        };
+
+        // only cells occupied after a move count, so the car must drive back to its first cell
+        assert_eq!(8, execute("1R 1R 1R 1R 1L 1L 1L 1L")?);
+        assert_eq!(0, execute("1R 1R")?);
+
+        let coverage = state.metadata::<CoverageMap<u8>>()?;
+        assert!((0..6).all(|col| coverage.is_visited(0, col) == Some(true)));
+        assert!((0..6).all(|col| coverage.is_visited(1, col) == Some(false)));
+        assert_eq!(8.0 / 24.0, coverage.coverage_fraction());
+
+        Ok(())
+    }
+
+    #[test]
+    fn coverage_map_independent() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("......\n.oo...\n......\n.1....");
+        let input = "1R 2U 1L 2U 1R 1R".parse::<PGInput>()?;
+        let mut coverage = Vec::new();
+        for _ in 0..2 {
+            let mut fuzzer = NopFuzzer::new();
+            let mut state = NopState::<PGInput>::new();
+            let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+            let mut executor = PGExecutorBuilder::with_initial(initial.clone())
+                .build(tuple_list!(CoverageMapObserver::<u8>::default()));
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            let kind = executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            executor
+                .observers_mut()
+                .post_exec_all(&mut state, &input, &kind)?;
+
+            let observed = executor.observers().0.coverage().unwrap().clone();
+            assert_eq!(&observed, state.metadata::<CoverageMap<u8>>()?);
+            coverage.push(observed);
+        }
+        assert_eq!(coverage[0], coverage[1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn crash_rate() -> Result<(), Box<dyn Error>> {
+        let mut observers = tuple_list!(CrashRateObserver::default());
+        let mut state = NopState::<PGInput>::new();
+        let nop_input = PGInput::default();
+
+        assert_eq!(0.0, observers.0.crash_rate());
+        assert_eq!(0, observers.0.total_executions());
+
+        for (i, exit_kind) in [ExitKind::Crash, ExitKind::Ok].into_iter().enumerate() {
+            observers.pre_exec_all(&mut state, &nop_input)?;
+            observers.post_exec_all(&mut state, &nop_input, &exit_kind)?;
+            assert_eq!(1.0 / (i + 1) as f64, observers.0.crash_rate());
+        }
+        for _ in 0..9 {
+            for exit_kind in [ExitKind::Crash, ExitKind::Ok] {
+                observers.pre_exec_all(&mut state, &nop_input)?;
+                observers.post_exec_all(&mut state, &nop_input, &exit_kind)?;
+            }
+        }
+        assert_eq!(0.5, observers.0.crash_rate());
+        assert_eq!(20, observers.0.total_executions());
+
+        // the counts live in the state, so a new observer picks up where the last left off
+        let mut observers = tuple_list!(CrashRateObserver::default());
         observers.pre_exec_all(&mut state, &nop_input)?;
-        observers.final_board_all(&initial.board()?);
-        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+        observers.post_exec_all(&mut state, &nop_input, &ExitKind::Crash)?;
+        assert_eq!(11.0 / 21.0, observers.0.crash_rate());
+        assert_eq!(21, observers.0.total_executions());
 
-        let second_hash = observers.0.hash().unwrap();
+        Ok(())
+    }
 
-        assert_ne!(first_hash, second_hash);
+    #[test]
+    fn move_repetition() -> Result<(), Box<dyn Error>> {
+        let mut observer = MoveRepetitionObserver::default();
+        let mut state = NopState::<PGInput>::new();
+
+        observer.pre_exec(&mut state, &"1R 2L 1R 1L 1R".parse()?)?;
+        assert_eq!(3, observer.unique_moves());
+        assert_eq!(5, observer.total_moves());
+        assert_eq!(
+            Some(&3),
+            observer
+                .counts()
+                .get(&(CarId::new(1).unwrap(), Direction::Right))
+        );
+
+        observer.pre_exec(&mut state, &PGInput::default())?;
+        assert_eq!(0, observer.unique_moves());
+        assert_eq!(0, observer.total_moves());
+
+        Ok(())
+    }
+
+    #[test]
+    fn car_movement() -> Result<(), Box<dyn Error>> {
+        let mut fuzzer = NopFuzzer::new();
+        let mut state = NopState::<PGInput>::new();
+        let mut mgr = SimpleEventManager::<PGInput, _, NopState<PGInput>>::printing();
+
+        let mut executor = PGExecutorBuilder::with_initial(crate::parse_map::<u8>(
+            "......\n.oo...\n.....1\n.....1",
+        ))
+        .build(tuple_list!(CarMovementObserver::default()));
+        for (input, expected) in [
+            ("1R 1L", 0b01),
+            ("2U 1L 2D", 0b11),
+            ("", 0b00),
+            ("2L", 0b00),
+        ] {
+            let input = input.parse::<PGInput>()?;
+            executor.observers_mut().pre_exec_all(&mut state, &input)?;
+            executor.run_target(&mut fuzzer, &mut state, &mut mgr, &input)?;
+            assert_eq!(expected, executor.observers().0.moved_bitmask(), "{input}");
+        }
+        assert!(!executor.observers().0.moved(CarId::new(100).unwrap()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn near_solution() -> Result<(), Box<dyn Error>> {
+        let mut observers = tuple_list!(NearSolutionObserver::default());
+        let mut state = NopState::<PGInput>::new();
+        let nop_input = PGInput::default();
+
+        assert_eq!(None, observers.0.blockers_in_path());
+        for (map, expected) in [
+            ("oo.1.2.3\n...1.2.3", 3),
+            // cars are only counted once, however many cells they block
+            ("oo111..2", 2),
+            // cars behind the objective car do not block it
+            ("11oo....", 0),
+            ("1.\no.\no.\n2.\n2.\n..", 1),
+        ] {
+            let initial = crate::parse_map::<u8>(map);
+            assert_eq!(expected, blockers_in_path(&initial.board()?), "{map}");
+            observers.pre_exec_all(&mut state, &nop_input)?;
+            observers.final_board_all(&initial.board()?);
+            observers.post_exec_all(&mut state, &nop_input, &ExitKind::Ok)?;
+            assert_eq!(Some(expected), observers.0.blockers_in_path(), "{map}");
+        }
 
         Ok(())
     }
+
+    #[test]
+    fn lane_cars() -> Result<(), Box<dyn Error>> {
+        fn check<T: BoardValue>(map: &str) -> Result<(), Box<dyn Error>> {
+            let initial = crate::parse_map::<T>(map);
+            let board = initial.board()?;
+            let lane = |idx: usize, horizontal| {
+                cars_in_lane(&board, T::try_from(idx).ok().unwrap(), horizontal)
+                    .into_iter()
+                    .map(CarId::get)
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(vec![1, 2], lane(0, true));
+            assert!(lane(1, true).is_empty());
+            // car 3 covers three cells of the row, but is only listed once
+            assert_eq!(vec![3], lane(2, true));
+            assert_eq!(vec![1, 3], lane(0, false));
+            assert_eq!(vec![2], lane(4, false));
+            // lanes off the board have no cars
+            assert!(lane(3, true).is_empty());
+            assert!(lane(5, false).is_empty());
+            Ok(())
+        }
+
+        let map = "oo.11\n.....\n222..";
+        check::<u8>(map)?;
+        check::<u16>(map)
+    }
+
+    #[test]
+    fn saturating_values() {
+        fn check<T: BoardValue>(max: T) {
+            assert_eq!(max, max.saturating_add_one());
+            assert_eq!(T::zero(), T::zero().saturating_sub_one());
+            assert_eq!(T::one(), T::zero().saturating_add_one());
+            assert_eq!(T::zero(), T::one().saturating_sub_one());
+            assert_eq!(max - T::one(), max.saturating_sub_one());
+        }
+
+        check(u8::MAX);
+        check(u16::MAX);
+        check(usize::MAX);
+    }
 }
@@ -0,0 +1,413 @@
+//! Plugins which are told about the progress of a [`crate::session::FuzzingSession`] as it fuzzes,
+//! e.g., to report it live.
+
+use crate::analysis::{CorrelationAnalysis, CrashInputDatabase};
+use crate::input::PGInput;
+use crate::mutators::CorrelatedMutator;
+use indicatif::{ProgressBar, ProgressStyle};
+use parking_game::{BoardValue, State};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Callbacks made by a [`crate::session::FuzzingSession`] while it fuzzes. Each does nothing by
+/// default, so plugins need only implement the ones they care about.
+pub trait FuzzerPlugin<T>: Send + Sync {
+    /// Called after each fuzzing iteration with the total number of executions so far, and the
+    /// current sizes of the corpus and of the solutions.
+    fn on_execution(&self, _total: u64, _corpus_size: usize, _solutions: usize) {}
+
+    /// Called for each input added to the corpus while fuzzing.
+    fn on_corpus_add(&self, _input: &PGInput) {}
+
+    /// Called for each solution found while fuzzing.
+    fn on_solution_found(&self, _input: &PGInput) {}
+
+    /// Called for each input which crashed while fuzzing.
+    fn on_crash(&self, _input: &PGInput) {}
+}
+
+// This allows a plugin to be shared with the session, so that it may be inspected while fuzzing.
+impl<T, P> FuzzerPlugin<T> for Arc<P>
+where
+    P: FuzzerPlugin<T> + ?Sized,
+{
+    fn on_execution(&self, total: u64, corpus_size: usize, solutions: usize) {
+        (**self).on_execution(total, corpus_size, solutions);
+    }
+
+    fn on_corpus_add(&self, input: &PGInput) {
+        (**self).on_corpus_add(input);
+    }
+
+    fn on_solution_found(&self, input: &PGInput) {
+        (**self).on_solution_found(input);
+    }
+
+    fn on_crash(&self, input: &PGInput) {
+        (**self).on_crash(input);
+    }
+}
+
+/// A plugin which displays a live progress bar with the rate of executions, the sizes of the corpus
+/// and of the solutions, and the length of the shortest solution found so far. Nothing is drawn
+/// when the output is not a terminal.
+pub struct ProgressBarPlugin {
+    bar: ProgressBar,
+    rate: Mutex<ExecutionRate>,
+    best: AtomicUsize,
+}
+
+/// The rate of executions, measured over the time since it was last updated.
+struct ExecutionRate {
+    executions: u64,
+    at: Instant,
+    per_sec: f64,
+}
+
+impl ProgressBarPlugin {
+    /// How long to count executions for before updating the rate, so that it does not flicker.
+    const RATE_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Create a new progress bar, drawn to the terminal until the plugin is dropped. As the number
+    /// of executions needed is not known, only their count is shown.
+    pub fn new() -> Self {
+        let bar = ProgressBar::new_spinner().with_style(
+            ProgressStyle::with_template("{spinner} [{elapsed_precise}] {pos} execs | {msg}")
+                .expect("The template should be valid."),
+        );
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Self::with_bar(bar)
+    }
+
+    /// Create a new progress bar, drawn to the terminal until the plugin is dropped, which is full
+    /// once the provided number of executions have been run.
+    pub fn with_length(executions: u64) -> Self {
+        let bar = ProgressBar::new(executions).with_style(
+            ProgressStyle::with_template(
+                "[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} | {msg}",
+            )
+            .expect("The template should be valid."),
+        );
+        Self::with_bar(bar)
+    }
+
+    fn with_bar(bar: ProgressBar) -> Self {
+        Self {
+            bar,
+            rate: Mutex::new(ExecutionRate {
+                executions: 0,
+                at: Instant::now(),
+                per_sec: 0.0,
+            }),
+            best: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    /// The rate of executions per second as of the provided total, updated at most once every
+    /// [`ProgressBarPlugin::RATE_INTERVAL`].
+    fn rate(&self, total: u64) -> f64 {
+        let mut rate = self.rate.lock().unwrap_or_else(PoisonError::into_inner);
+        let elapsed = rate.at.elapsed();
+        if elapsed >= Self::RATE_INTERVAL {
+            rate.per_sec = total.saturating_sub(rate.executions) as f64 / elapsed.as_secs_f64();
+            rate.executions = total;
+            rate.at = Instant::now();
+        }
+        rate.per_sec
+    }
+}
+
+impl Default for ProgressBarPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ProgressBarPlugin {
+    fn drop(&mut self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+impl<T> FuzzerPlugin<T> for ProgressBarPlugin {
+    fn on_execution(&self, total: u64, corpus_size: usize, solutions: usize) {
+        let rate = self.rate(total);
+        let best = match self.best.load(Ordering::Relaxed) {
+            usize::MAX => "-".to_string(),
+            best => best.to_string(),
+        };
+        self.bar.set_position(total);
+        self.bar.set_message(format!(
+            "{rate:.0}/s | Corpus: {corpus_size} | Solutions: {solutions} | Best: {best}"
+        ));
+    }
+
+    fn on_solution_found(&self, input: &PGInput) {
+        self.best.fetch_min(input.len(), Ordering::Relaxed);
+    }
+}
+
+/// A plugin which collects the inputs which crash into a [`CrashInputDatabase`], keeping one for
+/// each board state in which they crashed.
+pub struct CrashLoggingPlugin<T> {
+    crashes: Mutex<CrashInputDatabase<T>>,
+}
+
+impl<T> CrashLoggingPlugin<T>
+where
+    T: BoardValue,
+{
+    /// Create a plugin collecting crashes from the provided initial state.
+    pub fn new(initial: State<T>) -> Self {
+        Self {
+            crashes: Mutex::new(CrashInputDatabase::new(initial)),
+        }
+    }
+
+    /// The crashes collected so far.
+    pub fn crashes(&self) -> MutexGuard<'_, CrashInputDatabase<T>> {
+        self.crashes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> FuzzerPlugin<T> for CrashLoggingPlugin<T>
+where
+    T: BoardValue + Send,
+{
+    fn on_crash(&self, input: &PGInput) {
+        self.crashes().insert(input);
+    }
+}
+
+/// A plugin which keeps a [`CorrelationAnalysis`] of how effective each mutator is. Mutators are
+/// tracked by wrapping them with [`CorrelationPlugin::track`]: they know precisely which of their
+/// mutations were added to the corpus, which the callbacks, made once per iteration, do not.
+#[derive(Default)]
+pub struct CorrelationPlugin {
+    analysis: Arc<Mutex<CorrelationAnalysis>>,
+}
+
+impl CorrelationPlugin {
+    /// Create a plugin which is not yet tracking any mutators.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap the provided mutator so that its mutations are recorded by this plugin.
+    pub fn track<M>(&self, mutator: M) -> CorrelatedMutator<M> {
+        CorrelatedMutator::new(mutator, self.analysis.clone())
+    }
+
+    /// The effectiveness of the tracked mutators so far.
+    pub fn analysis(&self) -> MutexGuard<'_, CorrelationAnalysis> {
+        self.analysis.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> FuzzerPlugin<T> for CorrelationPlugin {}
+
+#[cfg(test)]
+mod test {
+    use crate::feedbacks::{FinalStateMetadataFeedback, SolvedFeedback};
+    use crate::input::PGInput;
+    use crate::mutators::ReinforcementMutator;
+    use crate::observers::{FinalStateObserver, NearSolutionObserver};
+    use crate::plugin::{CorrelationPlugin, CrashLoggingPlugin, FuzzerPlugin, ProgressBarPlugin};
+    use crate::session::FuzzingSession;
+    use indicatif::ProgressBar;
+    use libafl::corpus::{Corpus, CorpusId};
+    use libafl::feedbacks::CrashFeedback;
+    use libafl::feedbacks::new_hash_feedback::NewHashFeedback;
+    use libafl::mutators::{MutationResult, Mutator};
+    use libafl::state::{HasCorpus, HasSolutions};
+    use libafl::{feedback_and, feedback_and_fast, feedback_not, feedback_or};
+    use libafl_bolts::Named;
+    use libafl_bolts::tuples::tuple_list;
+    use std::borrow::Cow;
+    use std::error::Error;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct Calls {
+        executions: AtomicUsize,
+        corpus_adds: AtomicUsize,
+        solutions: AtomicUsize,
+        crashes: AtomicUsize,
+    }
+
+    struct MockPlugin(Arc<Calls>);
+
+    impl FuzzerPlugin<u8> for MockPlugin {
+        fn on_execution(&self, _total: u64, _corpus_size: usize, _solutions: usize) {
+            self.0.executions.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_corpus_add(&self, _input: &PGInput) {
+            self.0.corpus_adds.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_solution_found(&self, _input: &PGInput) {
+            self.0.solutions.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_crash(&self, _input: &PGInput) {
+            self.0.crashes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn callbacks() -> Result<(), Box<dyn Error>> {
+        // car 1 fills its column in the first map, so it is never solved and every iteration is run
+        for (map, iterations) in [("oo1\n..1", 100), ("oo.1\n...1\n....", 10_000)] {
+            let initial = crate::parse_map::<u8>(map);
+            let near = NearSolutionObserver::default();
+            let final_state = FinalStateObserver::<u8>::default();
+            let calls = Arc::new(Calls::default());
+            let crashes = Arc::new(CrashLoggingPlugin::new(initial.clone()));
+
+            // the views and the insertions of the random mutator are left to the homework, so
+            // this solves with the blockers counted ahead of the objective car and learned moves
+            let mut session = FuzzingSession::builder(initial.clone())
+                .with_feedback(feedback_or!(
+                    feedback_and!(
+                        feedback_not!(CrashFeedback::new()),
+                        NewHashFeedback::new(&final_state)
+                    ),
+                    FinalStateMetadataFeedback::new(&final_state)
+                ))
+                .with_objective(feedback_and_fast!(
+                    feedback_not!(CrashFeedback::new()),
+                    SolvedFeedback::<u8>::from_near_solution(&near)
+                ))
+                .with_mutator(ReinforcementMutator::new(&initial))
+                .with_observers(tuple_list!(near, final_state))
+                .with_plugin(MockPlugin(calls.clone()))
+                .with_plugin(crashes.clone())
+                .build()?;
+            let solution = session.run_for_iterations(iterations)?;
+
+            let executions = calls.executions.load(Ordering::Relaxed);
+            if solution.is_some() {
+                assert!(executions <= iterations as usize);
+            } else {
+                assert_eq!(iterations as usize, executions);
+            }
+            // the empty input is added when the session is built, before fuzzing starts
+            assert_eq!(
+                session.state().corpus().count() - 1,
+                calls.corpus_adds.load(Ordering::Relaxed)
+            );
+            assert_eq!(
+                session.state().solutions().count(),
+                calls.solutions.load(Ordering::Relaxed)
+            );
+            assert_eq!(
+                solution.is_some(),
+                calls.solutions.load(Ordering::Relaxed) > 0
+            );
+            assert!(
+                crashes.crashes().unique_crash_count() <= calls.crashes.load(Ordering::Relaxed)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn crash_logging() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n....");
+        let plugin = CrashLoggingPlugin::new(initial);
+        // the first two crash with car 2 moved down once, the third before any move
+        for input in ["2D 1L", "2D 2D 2D", "1L", "2D"] {
+            plugin.on_crash(&input.parse()?);
+        }
+        assert_eq!(2, plugin.crashes().unique_crash_count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn progress_bar() -> Result<(), Box<dyn Error>> {
+        let plugin = ProgressBarPlugin::with_bar(ProgressBar::hidden());
+        FuzzerPlugin::<u8>::on_execution(&plugin, 0, 0, 0);
+        assert_eq!(
+            "0/s | Corpus: 0 | Solutions: 0 | Best: -",
+            plugin.bar.message()
+        );
+
+        FuzzerPlugin::<u8>::on_solution_found(&plugin, &"1R 2D 1R".parse()?);
+        FuzzerPlugin::<u8>::on_solution_found(&plugin, &"1R 1R 1R 1R".parse()?);
+        FuzzerPlugin::<u8>::on_execution(&plugin, 10, 3, 2);
+        let message = plugin.bar.message();
+        assert!(message.ends_with("Corpus: 3 | Solutions: 2 | Best: 3"));
+        assert_eq!(10, plugin.bar.position());
+        // the message is plain text: any styling is left to the template
+        assert!(!message.contains('\x1b'));
+
+        // a bar is still created when the output is not a terminal, it is just not drawn
+        drop(ProgressBarPlugin::new());
+        drop(ProgressBarPlugin::with_length(1_000));
+
+        Ok(())
+    }
+
+    struct Mock {
+        name: Cow<'static, str>,
+        interesting_every: usize,
+    }
+
+    impl Named for Mock {
+        fn name(&self) -> &Cow<'static, str> {
+            &self.name
+        }
+    }
+
+    impl Mutator<PGInput, ()> for Mock {
+        fn mutate(
+            &mut self,
+            _state: &mut (),
+            _input: &mut PGInput,
+        ) -> Result<MutationResult, libafl::Error> {
+            Ok(MutationResult::Mutated)
+        }
+
+        fn post_exec(
+            &mut self,
+            _state: &mut (),
+            _new_corpus_id: Option<CorpusId>,
+        ) -> Result<(), libafl::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn correlation() -> Result<(), Box<dyn Error>> {
+        let plugin = CorrelationPlugin::new();
+        let mut mutators = [("often", 2), ("rarely", 10)].map(|(name, interesting_every)| {
+            plugin.track(Mock {
+                name: Cow::Borrowed(name),
+                interesting_every,
+            })
+        });
+
+        let mut input = PGInput::default();
+        for i in 0..100 {
+            for mutator in &mut mutators {
+                mutator.mutate(&mut (), &mut input)?;
+                // stand in for the feedback deciding whether the mutated input is interesting
+                let interesting = i % mutator.inner().interesting_every == 0;
+                mutator.post_exec(&mut (), interesting.then(|| CorpusId::from(i)))?;
+            }
+        }
+
+        let analysis = plugin.analysis();
+        assert_eq!(Some((50, 100)), analysis.counts("often"));
+        assert!((analysis.effectiveness("often").unwrap() - 0.5).abs() < 0.05);
+        assert!((analysis.effectiveness("rarely").unwrap() - 0.1).abs() < 0.05);
+        assert_eq!(None, analysis.effectiveness("never"));
+
+        Ok(())
+    }
+}
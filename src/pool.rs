@@ -0,0 +1,135 @@
+//! A bounded working set of corpus entries, ordered by score, for schedulers which only need to
+//! keep the most promising entries at hand.
+
+use libafl::corpus::CorpusId;
+use ordered_float::OrderedFloat;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::marker::PhantomData;
+
+/// A pool of at most `max_size` corpus entries, from which the highest-scoring entry is taken
+/// first. When an entry is added to a full pool, the lowest-scoring entry is evicted to make room.
+/// Entries with the same score are taken in the order they were added.
+#[derive(Debug, Clone)]
+pub struct TestcasePool<T> {
+    heap: BinaryHeap<(OrderedFloat<f64>, Reverse<CorpusId>)>,
+    max_size: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T> TestcasePool<T> {
+    /// Create an empty pool which holds at most `max_size` entries.
+    ///
+    /// Panics if `max_size` is zero.
+    pub fn new(max_size: usize) -> Self {
+        assert_ne!(0, max_size, "The pool should be able to hold an entry.");
+        Self {
+            heap: BinaryHeap::new(),
+            max_size,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The most entries this pool holds at once.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Add the entry with the provided score, evicting the lowest-scoring entry if the pool is then
+    /// over its size. This may be the entry just added.
+    pub fn add(&mut self, id: CorpusId, score: f64) {
+        self.heap.push((OrderedFloat(score), Reverse(id)));
+        if self.heap.len() > self.max_size {
+            // the heap only gives up its best entry cheaply, so find the worst by hand
+            let mut entries = std::mem::take(&mut self.heap).into_vec();
+            let (worst, _) = entries
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, entry)| *entry)
+                .unwrap();
+            entries.swap_remove(worst);
+            self.heap = entries.into();
+        }
+    }
+
+    /// Remove and return the highest-scoring entry, or [`None`] if the pool is empty.
+    pub fn pop_best(&mut self) -> Option<CorpusId> {
+        self.heap.pop().map(|(_, Reverse(id))| id)
+    }
+
+    /// The number of entries in the pool.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether the pool has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Whether the provided entry is in the pool.
+    pub fn contains(&self, id: CorpusId) -> bool {
+        self.heap.iter().any(|&(_, Reverse(entry))| entry == id)
+    }
+
+    /// Remove every entry from the pool, in no particular order.
+    pub fn drain(&mut self) -> impl Iterator<Item = CorpusId> + '_ {
+        self.heap.drain().map(|(_, Reverse(id))| id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::PGInput;
+    use crate::pool::TestcasePool;
+    use libafl::corpus::CorpusId;
+    use libafl_bolts::rands::{Rand, StdRand};
+
+    #[test]
+    fn best_entries() {
+        let mut rng = StdRand::with_seed(7);
+        for _ in 0..20 {
+            let scores = (0..100).map(|_| rng.next_float()).collect::<Vec<_>>();
+            let mut pool = TestcasePool::<PGInput>::new(10);
+            for (i, &score) in scores.iter().enumerate() {
+                pool.add(CorpusId(i), score);
+                assert!(pool.len() <= 10);
+            }
+
+            let mut expected = (0..scores.len()).collect::<Vec<_>>();
+            expected.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+            expected.truncate(10);
+            let taken = std::iter::from_fn(|| pool.pop_best())
+                .map(|id| id.0)
+                .collect::<Vec<_>>();
+            assert_eq!(expected, taken);
+        }
+    }
+
+    #[test]
+    fn eviction() {
+        let mut pool = TestcasePool::<PGInput>::new(3);
+        assert_eq!(None, pool.pop_best());
+
+        pool.add(CorpusId(0), 0.5);
+        pool.add(CorpusId(1), 0.1);
+        pool.add(CorpusId(2), 0.9);
+        assert_eq!(3, pool.len());
+        // the lowest score is evicted for a better entry...
+        pool.add(CorpusId(3), 0.7);
+        assert!(!pool.contains(CorpusId(1)));
+        // ...and a worse entry is evicted as soon as it is added
+        pool.add(CorpusId(4), 0.2);
+        assert!(!pool.contains(CorpusId(4)));
+        assert_eq!(3, pool.len());
+
+        // ties are taken in the order they were added
+        pool.add(CorpusId(5), 0.9);
+        assert!(!pool.contains(CorpusId(0)));
+        assert_eq!(Some(CorpusId(2)), pool.pop_best());
+        assert_eq!(Some(CorpusId(5)), pool.pop_best());
+        assert_eq!(Some(CorpusId(3)), pool.pop_best());
+        assert_eq!(None, pool.pop_best());
+        assert!(pool.is_empty());
+    }
+}
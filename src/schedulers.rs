@@ -0,0 +1,809 @@
+//! Schedulers which decide which [`PGInput`] in the corpus should be fuzzed next, using what the
+//! feedbacks in [`crate::feedbacks`] have learned about each entry.
+
+use crate::feedbacks::{HeuristicScoreMetadata, NearSolutionMetadata};
+use crate::input::PGInput;
+use crate::pool::TestcasePool;
+use libafl::HasMetadata;
+use libafl::corpus::{Corpus, CorpusId, Testcase};
+use libafl::schedulers::Scheduler;
+use libafl::state::{HasCorpus, HasRand};
+use libafl_bolts::rands::Rand;
+use libafl_bolts::tuples::MatchName;
+use libafl_bolts::{Error, impl_serdeany};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Scheduler which prefers the corpus entries closest to a solution: those with the highest
+/// [`HeuristicScoreMetadata`], as attached by the [`crate::feedbacks::ScoringFeedback`]. Entries
+/// which were not scored are ranked by the fewest cars between the objective car and the wall (per
+/// [`NearSolutionMetadata`]), then the shortest.
+///
+/// Each entry is scheduled once, best first; when all have been scheduled, the whole corpus is
+/// queued up again. Entries with the same score are scheduled in the order they were added. The
+/// queue is a [`TestcasePool`], so it may be limited with [`PGPriorityScheduler::with_pool_size`]
+/// to schedule only the best entries of each round.
+pub struct PGPriorityScheduler<T> {
+    pool: TestcasePool<T>,
+    iteration: u64,
+}
+
+impl<T> PGPriorityScheduler<T> {
+    /// Create a new [`PGPriorityScheduler`] with nothing queued.
+    pub fn new() -> Self {
+        Self::with_pool_size(usize::MAX)
+    }
+
+    /// Create a new [`PGPriorityScheduler`] which queues at most `size` entries at once, so that
+    /// only the best `size` entries of the corpus are scheduled in each round.
+    ///
+    /// Panics if `size` is zero.
+    pub fn with_pool_size(size: usize) -> Self {
+        Self {
+            pool: TestcasePool::new(size),
+            iteration: 0,
+        }
+    }
+
+    /// The priority of the provided testcase; higher is scheduled sooner. Testcases without
+    /// [`HeuristicScoreMetadata`] or [`NearSolutionMetadata`] are scheduled last.
+    pub fn score(testcase: &Testcase<PGInput>) -> f64 {
+        if let Ok(meta) = testcase.metadata::<HeuristicScoreMetadata>() {
+            return meta.score;
+        }
+        let blockers = testcase
+            .metadata::<NearSolutionMetadata>()
+            .map_or(f64::INFINITY, |meta| meta.blockers() as f64);
+        let len = testcase.input().as_ref().map_or(0, |input| input.len());
+        1.0 / (blockers + 1.0) / ((len + 1) as f64).sqrt()
+    }
+
+    fn push<S: HasCorpus<PGInput>>(&mut self, state: &S, id: CorpusId) -> Result<(), Error> {
+        let score = Self::score(&state.corpus().get(id)?.borrow());
+        self.pool.add(id, score);
+        Ok(())
+    }
+}
+
+impl<T> Default for PGPriorityScheduler<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, T> Scheduler<PGInput, S> for PGPriorityScheduler<T>
+where
+    S: HasCorpus<PGInput>,
+{
+    fn on_add(&mut self, state: &mut S, id: CorpusId) -> Result<(), Error> {
+        SchedulerMetadata::attach(state, id)?;
+        self.push(state, id)
+    }
+
+    fn on_evaluation<OT>(
+        &mut self,
+        state: &mut S,
+        _input: &PGInput,
+        _observers: &OT,
+    ) -> Result<(), Error>
+    where
+        OT: MatchName,
+    {
+        // the feedbacks may have updated the metadata of queued entries, so score them again
+        let queued = self.pool.drain().collect::<Vec<_>>();
+        for id in queued {
+            if state.corpus().get(id).is_ok() {
+                self.push(state, id)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn next(&mut self, state: &mut S) -> Result<CorpusId, Error> {
+        if state.corpus().count() == 0 {
+            return Err(Error::empty("No entries in corpus."));
+        }
+        if self.pool.is_empty() {
+            let mut next = state.corpus().first();
+            while let Some(id) = next {
+                self.push(state, id)?;
+                next = state.corpus().next(id);
+            }
+        }
+        let id = self.pool.pop_best().unwrap();
+        self.iteration += 1;
+        SchedulerMetadata::record_selection(state, id, self.iteration)?;
+        self.set_current_scheduled(state, Some(id))?;
+        Ok(id)
+    }
+
+    fn set_current_scheduled(
+        &mut self,
+        state: &mut S,
+        next_id: Option<CorpusId>,
+    ) -> Result<(), Error> {
+        *state.corpus_mut().current_mut() = next_id;
+        Ok(())
+    }
+}
+
+/// Metadata which records how often a testcase has been scheduled, and how rewarding fuzzing it has
+/// been, for schedulers which balance trying new entries against revisiting fruitful ones. The
+/// schedulers in this module attach it to each entry when it is added, and update it whenever the
+/// entry is selected; feedbacks may add rewards with [`SchedulerMetadata::reward`].
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+pub struct SchedulerMetadata {
+    /// The number of times the entry has been selected.
+    pub selection_count: usize,
+    /// The iteration of the scheduler in which the entry was last selected, counting calls to
+    /// [`Scheduler::next`] from one, or zero if it has never been selected.
+    pub last_selected_iter: u64,
+    /// The sum of the rewards given to the entry.
+    pub total_reward: f64,
+}
+
+impl SchedulerMetadata {
+    /// Add the provided reward to the testcase, attaching the metadata first if need be.
+    pub fn reward(testcase: &mut Testcase<PGInput>, reward: f64) {
+        testcase.metadata_or_insert_with(Self::default).total_reward += reward;
+    }
+
+    /// The mean reward per selection, counting a reward of one from a selection which has not
+    /// happened yet, so that entries which were never selected are tried.
+    pub fn mean_reward(&self) -> f64 {
+        (self.total_reward + 1.0) / (self.selection_count + 1) as f64
+    }
+
+    fn attach<S: HasCorpus<PGInput>>(state: &S, id: CorpusId) -> Result<(), Error> {
+        state
+            .corpus()
+            .get(id)?
+            .borrow_mut()
+            .metadata_or_insert_with(Self::default);
+        Ok(())
+    }
+
+    fn record_selection<S: HasCorpus<PGInput>>(
+        state: &S,
+        id: CorpusId,
+        iteration: u64,
+    ) -> Result<(), Error> {
+        let mut testcase = state.corpus().get(id)?.borrow_mut();
+        let meta = testcase.metadata_or_insert_with(Self::default);
+        meta.selection_count += 1;
+        meta.last_selected_iter = iteration;
+        Ok(())
+    }
+}
+
+impl_serdeany!(SchedulerMetadata);
+
+/// Metadata which records how many mutations separate a testcase from the initial seeds.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct GenerationMetadata {
+    depth: usize,
+}
+
+impl GenerationMetadata {
+    /// The generation of the testcase: zero for the initial seeds, or one more than the generation
+    /// of the testcase it was derived from.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+impl_serdeany!(GenerationMetadata);
+
+/// Scheduler which works like [`libafl::schedulers::QueueScheduler`], but in strict breadth-first
+/// order: every entry of one generation (see [`GenerationMetadata`]) is scheduled before any entry
+/// of the next. When no entries of the next generation exist, we start over from the seeds.
+#[derive(Debug, Default)]
+pub struct BFSInspiredScheduler {
+    generation: usize,
+    pending: VecDeque<CorpusId>,
+    iteration: u64,
+}
+
+impl BFSInspiredScheduler {
+    /// Create a new [`BFSInspiredScheduler`], starting with the initial seeds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The generation which is currently being scheduled.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    fn generation_of<S: HasCorpus<PGInput>>(
+        &self,
+        state: &S,
+        generation: usize,
+    ) -> Result<VecDeque<CorpusId>, Error> {
+        let mut ids = VecDeque::new();
+        let mut next = state.corpus().first();
+        while let Some(id) = next {
+            let depth = state
+                .corpus()
+                .get(id)?
+                .borrow()
+                .metadata::<GenerationMetadata>()
+                .map_or(0, GenerationMetadata::depth);
+            if depth == generation {
+                ids.push_back(id);
+            }
+            next = state.corpus().next(id);
+        }
+        Ok(ids)
+    }
+}
+
+impl<S> Scheduler<PGInput, S> for BFSInspiredScheduler
+where
+    S: HasCorpus<PGInput>,
+{
+    fn on_add(&mut self, state: &mut S, id: CorpusId) -> Result<(), Error> {
+        // whatever is currently being fuzzed is what this entry was derived from
+        let parent = *state.corpus().current();
+        let depth = match parent {
+            Some(parent) => {
+                state
+                    .corpus()
+                    .get(parent)?
+                    .borrow()
+                    .metadata::<GenerationMetadata>()
+                    .map_or(0, GenerationMetadata::depth)
+                    + 1
+            }
+            None => 0,
+        };
+        let mut testcase = state.corpus().get(id)?.borrow_mut();
+        testcase.set_parent_id_optional(parent);
+        testcase.add_metadata(GenerationMetadata { depth });
+        testcase.metadata_or_insert_with(SchedulerMetadata::default);
+        Ok(())
+    }
+
+    fn next(&mut self, state: &mut S) -> Result<CorpusId, Error> {
+        if state.corpus().count() == 0 {
+            return Err(Error::empty("No entries in corpus."));
+        }
+        if self.pending.is_empty() {
+            self.pending = self.generation_of(state, self.generation)?;
+            if self.pending.is_empty() {
+                self.generation = 0;
+                self.pending = self.generation_of(state, self.generation)?;
+            }
+        }
+        let Some(id) = self.pending.pop_front() else {
+            return Err(Error::empty("No seeds in corpus."));
+        };
+        if self.pending.is_empty() {
+            self.generation += 1;
+        }
+        self.iteration += 1;
+        SchedulerMetadata::record_selection(state, id, self.iteration)?;
+        self.set_current_scheduled(state, Some(id))?;
+        Ok(id)
+    }
+
+    fn set_current_scheduled(
+        &mut self,
+        state: &mut S,
+        next_id: Option<CorpusId>,
+    ) -> Result<(), Error> {
+        *state.corpus_mut().current_mut() = next_id;
+        Ok(())
+    }
+}
+
+/// Metadata which records how promising a feedback found a testcase; higher is better.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct ScoreMetadata {
+    score: f64,
+}
+
+impl ScoreMetadata {
+    /// Create metadata recording the provided score.
+    pub fn new(score: f64) -> Self {
+        Self { score }
+    }
+
+    /// The score of the testcase.
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+impl_serdeany!(ScoreMetadata);
+
+/// Scheduler which mostly exploits the best corpus entry found so far (per [`ScoreMetadata`]), but
+/// explores another entry instead on every `period`th call to [`Scheduler::next`].
+///
+/// Exploration is a form of Thompson sampling: the less recently an entry was selected (per
+/// [`SchedulerMetadata::last_selected_iter`]), the less we know about how it would fare now, so
+/// each entry draws a value uniformly between zero and the number of iterations since it was last
+/// selected, and the highest draw is explored. Entries which have been neglected the longest are
+/// the most likely to be explored, but any entry which was not just selected may be.
+#[derive(Debug)]
+pub struct ExplorationExploitationScheduler {
+    period: usize,
+    t: usize,
+}
+
+impl ExplorationExploitationScheduler {
+    /// Create a new [`ExplorationExploitationScheduler`] which explores once every `period` calls.
+    ///
+    /// Panics if `period` is zero.
+    pub fn new(period: usize) -> Self {
+        assert_ne!(period, 0, "The exploration period must be non-zero.");
+        Self { period, t: 0 }
+    }
+
+    /// The fraction of calls which pick a random entry rather than the best one.
+    pub fn exploration_fraction(&self) -> f64 {
+        1.0 / self.period as f64
+    }
+
+    fn best<S: HasCorpus<PGInput>>(state: &S) -> Result<Option<CorpusId>, Error> {
+        let mut best = None;
+        let mut best_score = f64::NEG_INFINITY;
+        let mut next = state.corpus().first();
+        while let Some(id) = next {
+            let score = state
+                .corpus()
+                .get(id)?
+                .borrow()
+                .metadata::<ScoreMetadata>()
+                .map_or(f64::NEG_INFINITY, ScoreMetadata::score);
+            // the earliest entry wins ties, including when nothing has been scored
+            if best.is_none() || score > best_score {
+                best = Some(id);
+                best_score = score;
+            }
+            next = state.corpus().next(id);
+        }
+        Ok(best)
+    }
+
+    fn sample<S: HasCorpus<PGInput> + HasRand>(&self, state: &mut S) -> Result<CorpusId, Error> {
+        let mut sampled = None;
+        let mut best_draw = f64::NEG_INFINITY;
+        let mut next = state.corpus().first();
+        while let Some(id) = next {
+            let last_selected = state
+                .corpus()
+                .get(id)?
+                .borrow()
+                .metadata::<SchedulerMetadata>()
+                .map_or(0, |meta| meta.last_selected_iter);
+            let staleness = (self.t as u64).saturating_sub(last_selected) as f64;
+            let draw = state.rand_mut().next_float() * staleness;
+            if sampled.is_none() || draw > best_draw {
+                sampled = Some(id);
+                best_draw = draw;
+            }
+            next = state.corpus().next(id);
+        }
+        sampled.ok_or_else(|| Error::empty("No entries in corpus."))
+    }
+}
+
+impl<S> Scheduler<PGInput, S> for ExplorationExploitationScheduler
+where
+    S: HasCorpus<PGInput> + HasRand,
+{
+    fn on_add(&mut self, state: &mut S, id: CorpusId) -> Result<(), Error> {
+        SchedulerMetadata::attach(state, id)
+    }
+
+    fn next(&mut self, state: &mut S) -> Result<CorpusId, Error> {
+        if state.corpus().count() == 0 {
+            return Err(Error::empty("No entries in corpus."));
+        }
+        self.t += 1;
+        let id = if self.t.is_multiple_of(self.period) {
+            self.sample(state)?
+        } else {
+            Self::best(state)?.unwrap()
+        };
+        SchedulerMetadata::record_selection(state, id, self.t as u64)?;
+        self.set_current_scheduled(state, Some(id))?;
+        Ok(id)
+    }
+
+    fn set_current_scheduled(
+        &mut self,
+        state: &mut S,
+        next_id: Option<CorpusId>,
+    ) -> Result<(), Error> {
+        *state.corpus_mut().current_mut() = next_id;
+        Ok(())
+    }
+}
+
+/// Scheduler which picks a random corpus entry, weighted by how rewarding fuzzing it has been so
+/// far: each entry is picked with probability proportional to its
+/// [`SchedulerMetadata::mean_reward`].
+#[derive(Debug, Default)]
+pub struct WeightedRandomScheduler {
+    iteration: u64,
+}
+
+impl WeightedRandomScheduler {
+    /// Create a new [`WeightedRandomScheduler`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Scheduler<PGInput, S> for WeightedRandomScheduler
+where
+    S: HasCorpus<PGInput> + HasRand,
+{
+    fn on_add(&mut self, state: &mut S, id: CorpusId) -> Result<(), Error> {
+        SchedulerMetadata::attach(state, id)
+    }
+
+    fn next(&mut self, state: &mut S) -> Result<CorpusId, Error> {
+        let mut weights = Vec::with_capacity(state.corpus().count());
+        let mut next = state.corpus().first();
+        while let Some(id) = next {
+            let weight = state
+                .corpus()
+                .get(id)?
+                .borrow()
+                .metadata::<SchedulerMetadata>()
+                .map_or(1.0, SchedulerMetadata::mean_reward)
+                .max(0.0);
+            weights.push((id, weight));
+            next = state.corpus().next(id);
+        }
+        let Some(&(last, _)) = weights.last() else {
+            return Err(Error::empty("No entries in corpus."));
+        };
+        let total = weights.iter().map(|(_, weight)| weight).sum::<f64>();
+        let mut target = state.rand_mut().next_float() * total;
+        // rounding may leave a sliver past the last weight, which belongs to the last entry
+        let id = weights
+            .into_iter()
+            .find(|(_, weight)| {
+                target -= weight;
+                target < 0.0
+            })
+            .map_or(last, |(id, _)| id);
+        self.iteration += 1;
+        SchedulerMetadata::record_selection(state, id, self.iteration)?;
+        self.set_current_scheduled(state, Some(id))?;
+        Ok(id)
+    }
+
+    fn set_current_scheduled(
+        &mut self,
+        state: &mut S,
+        next_id: Option<CorpusId>,
+    ) -> Result<(), Error> {
+        *state.corpus_mut().current_mut() = next_id;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::feedbacks::{HeuristicScoreMetadata, NearSolutionMetadata};
+    use crate::input::PGInput;
+    use crate::schedulers::{
+        BFSInspiredScheduler, ExplorationExploitationScheduler, GenerationMetadata,
+        PGPriorityScheduler, SchedulerMetadata, ScoreMetadata, WeightedRandomScheduler,
+    };
+    use libafl::HasMetadata;
+    use libafl::corpus::{Corpus, CorpusId, InMemoryCorpus, Testcase};
+    use libafl::schedulers::Scheduler;
+    use libafl::state::{HasCorpus, StdState};
+    use libafl_bolts::Error;
+    use libafl_bolts::rands::StdRand;
+    use std::collections::HashSet;
+
+    fn add<S, SC>(state: &mut S, scheduler: &mut SC, moves: &str, blockers: usize) -> CorpusId
+    where
+        S: HasCorpus<PGInput>,
+        SC: Scheduler<PGInput, S>,
+    {
+        let mut testcase = Testcase::new(moves.parse::<PGInput>().unwrap());
+        testcase.add_metadata(NearSolutionMetadata::new(blockers));
+        let id = state.corpus_mut().add(testcase).unwrap();
+        scheduler.on_add(state, id).unwrap();
+        id
+    }
+
+    #[test]
+    fn priority() {
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )
+        .unwrap();
+        let mut scheduler = PGPriorityScheduler::<u8>::new();
+
+        assert!(matches!(scheduler.next(&mut state), Err(Error::Empty(..))));
+
+        let long = add(&mut state, &mut scheduler, "1R 1L 1R 1L 1R 1L", 3);
+        let short = add(&mut state, &mut scheduler, "1R", 1);
+        assert_eq!(scheduler.next(&mut state).unwrap(), short);
+        assert_eq!(*state.corpus().current(), Some(short));
+        assert_eq!(scheduler.next(&mut state).unwrap(), long);
+
+        // once everything has been scheduled, we start over
+        assert_eq!(scheduler.next(&mut state).unwrap(), short);
+
+        // with room for one entry, only the best is scheduled in each round
+        let mut scheduler = PGPriorityScheduler::<u8>::with_pool_size(1);
+        scheduler.on_add(&mut state, long).unwrap();
+        scheduler.on_add(&mut state, short).unwrap();
+        assert_eq!(scheduler.next(&mut state).unwrap(), short);
+        assert_eq!(scheduler.next(&mut state).unwrap(), short);
+    }
+
+    #[test]
+    fn heuristic_priority() {
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )
+        .unwrap();
+        let mut scheduler = PGPriorityScheduler::<u8>::new();
+
+        let unscored = add(&mut state, &mut scheduler, "1R", 0);
+        let mut testcase = Testcase::new("1R 1L 1R 1L".parse::<PGInput>().unwrap());
+        testcase.add_metadata(NearSolutionMetadata::new(3));
+        testcase.add_metadata(HeuristicScoreMetadata { score: 2.0 });
+        let scored = state.corpus_mut().add(testcase).unwrap();
+        scheduler.on_add(&mut state, scored).unwrap();
+
+        // the score takes precedence over the blockers and length
+        assert_eq!(scheduler.next(&mut state).unwrap(), scored);
+        assert_eq!(scheduler.next(&mut state).unwrap(), unscored);
+    }
+
+    #[test]
+    fn ties() {
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )
+        .unwrap();
+        let mut scheduler = PGPriorityScheduler::<u8>::new();
+
+        let first = add(&mut state, &mut scheduler, "2U 1R", 2);
+        let second = add(&mut state, &mut scheduler, "1R 2U", 2);
+        let third = add(&mut state, &mut scheduler, "1L 1R", 2);
+        assert_eq!(scheduler.next(&mut state).unwrap(), first);
+        assert_eq!(scheduler.next(&mut state).unwrap(), second);
+        assert_eq!(scheduler.next(&mut state).unwrap(), third);
+    }
+
+    #[test]
+    fn rescore() {
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )
+        .unwrap();
+        let mut scheduler = PGPriorityScheduler::<u8>::new();
+
+        let first = add(&mut state, &mut scheduler, "1R", 2);
+        let second = add(&mut state, &mut scheduler, "1R", 2);
+        state
+            .corpus()
+            .get(second)
+            .unwrap()
+            .borrow_mut()
+            .add_metadata(NearSolutionMetadata::new(0));
+        scheduler
+            .on_evaluation(&mut state, &PGInput::default(), &())
+            .unwrap();
+        assert_eq!(scheduler.next(&mut state).unwrap(), second);
+        assert_eq!(scheduler.next(&mut state).unwrap(), first);
+    }
+
+    #[test]
+    fn breadth_first() {
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )
+        .unwrap();
+        let mut scheduler = BFSInspiredScheduler::new();
+        fn depth<S: HasCorpus<PGInput>>(state: &S, id: CorpusId) -> usize {
+            state
+                .corpus()
+                .get(id)
+                .unwrap()
+                .borrow()
+                .metadata::<GenerationMetadata>()
+                .unwrap()
+                .depth()
+        }
+
+        let seeds = [
+            add(&mut state, &mut scheduler, "1R", 0),
+            add(&mut state, &mut scheduler, "1L", 0),
+        ];
+        assert_eq!(scheduler.generation(), 0);
+
+        // children are derived from whichever entry is currently scheduled
+        assert_eq!(scheduler.next(&mut state).unwrap(), seeds[0]);
+        let mut children = vec![
+            add(&mut state, &mut scheduler, "1R 1L", 0),
+            add(&mut state, &mut scheduler, "1R 1R", 0),
+        ];
+        assert_eq!(scheduler.next(&mut state).unwrap(), seeds[1]);
+        children.push(add(&mut state, &mut scheduler, "1L 1R", 0));
+        assert_eq!(scheduler.generation(), 1);
+        for &child in &children {
+            assert_eq!(depth(&state, child), 1);
+        }
+
+        assert_eq!(scheduler.next(&mut state).unwrap(), children[0]);
+        let grandchild = add(&mut state, &mut scheduler, "1R 1L 1R", 0);
+        assert_eq!(depth(&state, grandchild), 2);
+        assert_eq!(scheduler.next(&mut state).unwrap(), children[1]);
+        assert_eq!(scheduler.next(&mut state).unwrap(), children[2]);
+        assert_eq!(scheduler.generation(), 2);
+        assert_eq!(scheduler.next(&mut state).unwrap(), grandchild);
+
+        // nothing deeper, so back to the seeds
+        assert_eq!(scheduler.next(&mut state).unwrap(), seeds[0]);
+        assert_eq!(scheduler.generation(), 0);
+    }
+
+    #[test]
+    fn exploration_exploitation() {
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )
+        .unwrap();
+        let mut scheduler = ExplorationExploitationScheduler::new(4);
+        assert_eq!(scheduler.exploration_fraction(), 0.25);
+
+        assert!(matches!(scheduler.next(&mut state), Err(Error::Empty(..))));
+
+        // with only one entry, exploring and exploiting agree
+        let only = add(&mut state, &mut scheduler, "1R", 0);
+        for _ in 0..8 {
+            assert_eq!(scheduler.next(&mut state).unwrap(), only);
+        }
+
+        let mut best = only;
+        for score in 1..10 {
+            let id = add(&mut state, &mut scheduler, "1R", 0);
+            let score = if score == 5 { 100.0 } else { f64::from(score) };
+            state
+                .corpus()
+                .get(id)
+                .unwrap()
+                .borrow_mut()
+                .add_metadata(ScoreMetadata::new(score));
+            if score == 100.0 {
+                best = id;
+            }
+        }
+
+        let mut explored = HashSet::new();
+        for _ in 0..25 {
+            for _ in 0..3 {
+                assert_eq!(scheduler.next(&mut state).unwrap(), best);
+            }
+            explored.insert(scheduler.next(&mut state).unwrap());
+        }
+        assert!(explored.len() > 1);
+    }
+
+    #[test]
+    fn selection_metadata() {
+        fn selections<S, SC>(mut state: S, mut scheduler: SC)
+        where
+            S: HasCorpus<PGInput>,
+            SC: Scheduler<PGInput, S>,
+        {
+            let id = add(&mut state, &mut scheduler, "1R", 0);
+            let meta = |state: &S| {
+                *state
+                    .corpus()
+                    .get(id)
+                    .unwrap()
+                    .borrow()
+                    .metadata::<SchedulerMetadata>()
+                    .unwrap()
+            };
+            assert_eq!(0, meta(&state).selection_count);
+            assert_eq!(0, meta(&state).last_selected_iter);
+
+            let mut last = 0;
+            for _ in 0..10 {
+                assert_eq!(id, scheduler.next(&mut state).unwrap());
+                assert!(meta(&state).last_selected_iter > last);
+                last = meta(&state).last_selected_iter;
+            }
+            assert_eq!(10, meta(&state).selection_count);
+        }
+        let state = || {
+            StdState::new(
+                StdRand::with_seed(0),
+                InMemoryCorpus::<PGInput>::new(),
+                InMemoryCorpus::new(),
+                &mut (),
+                &mut (),
+            )
+            .unwrap()
+        };
+
+        selections(state(), PGPriorityScheduler::<u8>::new());
+        selections(state(), BFSInspiredScheduler::new());
+        selections(state(), ExplorationExploitationScheduler::new(3));
+        selections(state(), WeightedRandomScheduler::new());
+
+        let mut testcase = Testcase::new(PGInput::default());
+        SchedulerMetadata::reward(&mut testcase, 1.5);
+        SchedulerMetadata::reward(&mut testcase, 2.0);
+        SchedulerMetadata::reward(&mut testcase, -0.5);
+        let meta = testcase.metadata::<SchedulerMetadata>().unwrap();
+        assert_eq!(3.0, meta.total_reward);
+        assert_eq!(0, meta.selection_count);
+    }
+
+    #[test]
+    fn weighted_random() {
+        let mut state = StdState::new(
+            StdRand::with_seed(0),
+            InMemoryCorpus::<PGInput>::new(),
+            InMemoryCorpus::new(),
+            &mut (),
+            &mut (),
+        )
+        .unwrap();
+        let mut scheduler = WeightedRandomScheduler::new();
+        assert!(matches!(scheduler.next(&mut state), Err(Error::Empty(..))));
+
+        let dull = add(&mut state, &mut scheduler, "1R", 0);
+        let rewarding = add(&mut state, &mut scheduler, "1L", 0);
+        SchedulerMetadata::reward(
+            &mut state.corpus().get(rewarding).unwrap().borrow_mut(),
+            1000.0,
+        );
+
+        let picked = (0..100)
+            .filter(|_| scheduler.next(&mut state).unwrap() == rewarding)
+            .count();
+        assert!(picked > 90);
+        // every selection is recorded, whichever entry was picked
+        let dull_selections = state
+            .corpus()
+            .get(dull)
+            .unwrap()
+            .borrow()
+            .metadata::<SchedulerMetadata>()
+            .unwrap()
+            .selection_count;
+        assert_eq!(100 - picked, dull_selections);
+    }
+}
@@ -0,0 +1,175 @@
+//! Serialization of [`parking_game`] states, so that they may be saved and loaded again later.
+
+use crate::map_format::car_name;
+use parking_game::{BoardValue, State};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Serializes the provided state into the ASCII map format understood by `parse_map`.
+///
+/// Empty cells are written as `.`, the objective car as `o`, and all other cars are given names in
+/// index order, so that parsing the result produces the same state.
+pub fn serialize_state<T: BoardValue>(state: &State<T>) -> String {
+    let board = state.board().expect("Only valid states may be serialized.");
+    let columns = (*state.dimensions().columns()).into();
+
+    board
+        .concrete()
+        .chunks(columns)
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    None => '.',
+                    Some(car) => {
+                        car_name((*car).into()).expect("Too many cars to be named in a map!")
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Encodes the provided state with [`bincode`], for storing states far more compactly than
+/// [`serialize_state`] does.
+pub fn state_to_bytes<T: BoardValue + Serialize>(state: &State<T>) -> Vec<u8> {
+    bincode::serialize(state).expect("States should always be serializable.")
+}
+
+/// Decodes a state encoded by [`state_to_bytes`]. Fails if the bytes are not an encoded state, or
+/// if the state they encode is not valid (e.g., its cars overlap).
+pub fn state_from_bytes<T: BoardValue + DeserializeOwned>(
+    bytes: &[u8],
+) -> Result<State<T>, bincode::Error> {
+    let state: State<T> = bincode::deserialize(bytes)?;
+    if let Err(e) = state.board() {
+        return Err(bincode::ErrorKind::Custom(e.to_string()).into());
+    }
+    Ok(state)
+}
+
+/// Serializes states as the bytes produced by [`state_to_bytes`], for use with
+/// `#[serde(with = "crate::serialization::state_bytes")]`.
+pub mod state_bytes {
+    use crate::serialization::{state_from_bytes, state_to_bytes};
+    use parking_game::{BoardValue, State};
+    use serde::de::{DeserializeOwned, Error};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialize the state as bytes.
+    pub fn serialize<S, T>(state: &State<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: BoardValue + Serialize,
+    {
+        serializer.serialize_bytes(&state_to_bytes(state))
+    }
+
+    /// Deserialize the state from bytes.
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<State<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: BoardValue + DeserializeOwned,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        state_from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::feedbacks::FinalStateMetadata;
+    use crate::serialization::{serialize_state, state_from_bytes, state_to_bytes};
+    use parking_game::{Car, Orientation, State};
+    use std::error::Error;
+    use std::fs;
+
+    #[test]
+    fn simple_serialize() {
+        let initial = crate::parse_map::<u8>(
+            r#"
+        .1.
+        .1.
+        oo.
+        22.
+        "#,
+        );
+
+        assert_eq!(".1.\n.1.\noo.\n22.", serialize_state(&initial));
+    }
+
+    #[test]
+    fn round_trip_maps() -> Result<(), Box<dyn Error>> {
+        for entry in fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/maps"))? {
+            let map = fs::read_to_string(entry?.path())?;
+            if !map.contains('o') {
+                // not a puzzle (e.g., empty.map)
+                continue;
+            }
+            let initial = crate::parse_map::<u8>(&map);
+            let parsed = crate::parse_map::<u8>(&serialize_state(&initial));
+            assert_eq!(format!("{initial:?}"), format!("{parsed:?}"));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_generated() -> Result<(), Box<dyn Error>> {
+        let mut initial = State::<u8>::empty((7, 5))?;
+        let mut board = initial.board_mut()?;
+        board.add_car((3, 1), Car::new(2, Orientation::LeftRight).unwrap())?;
+        for column in 0..5 {
+            board.add_car((0, column), Car::new(3, Orientation::UpDown).unwrap())?;
+        }
+        board.add_car((4, 0), Car::new(3, Orientation::LeftRight).unwrap())?;
+        board.add_car((5, 4), Car::new(2, Orientation::UpDown).unwrap())?;
+        drop(board);
+
+        let parsed = crate::parse_map::<u8>(&serialize_state(&initial));
+        assert_eq!(format!("{initial:?}"), format!("{parsed:?}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bytes_round_trip() -> Result<(), Box<dyn Error>> {
+        let empty = State::<u8>::empty((6, 6))?;
+        let parsed = state_from_bytes::<u8>(&state_to_bytes(&empty))?;
+        assert_eq!(format!("{empty:?}"), format!("{parsed:?}"));
+
+        let initial = crate::parse_map::<u8>(
+            r#"
+        11.2..
+        ...2.3
+        oo.2.3
+        ...4..
+        ...4..
+        ......
+        "#,
+        );
+        assert_eq!(5, initial.cars().len());
+        let parsed = state_from_bytes::<u8>(&state_to_bytes(&initial))?;
+        assert_eq!(format!("{initial:?}"), format!("{parsed:?}"));
+
+        // metadata holding states is stored as the same bytes
+        let metadata = FinalStateMetadata::new(initial.clone());
+        let json = serde_json::to_string(&metadata)?;
+        let parsed = serde_json::from_str::<FinalStateMetadata<u8>>(&json)?;
+        assert_eq!(format!("{initial:?}"), format!("{:?}", parsed.state()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bytes_corrupted() {
+        let initial = crate::parse_map::<u8>("oo.\n.1.\n.1.");
+        let bytes = state_to_bytes(&initial);
+
+        assert!(state_from_bytes::<u8>(&[]).is_err());
+        assert!(state_from_bytes::<u8>(&bytes[..bytes.len() / 2]).is_err());
+        // every byte flipped may still decode, but not to a valid state of the same size
+        let flipped = bytes.iter().map(|b| !b).collect::<Vec<_>>();
+        assert!(state_from_bytes::<u8>(&flipped).is_err());
+    }
+}
@@ -0,0 +1,1242 @@
+//! A [`FuzzingSession`] bundles together all the components needed to fuzz a [`parking_game`]
+//! puzzle, so that they do not need to be wired up by hand.
+
+use crate::analysis::{FuzzingReport, InputSizeHistogram};
+use crate::error::FuzzerError;
+use crate::executor::PGExecutor;
+use crate::feedbacks::{FeedbackMetrics, SolvedFeedback};
+use crate::input::PGInput;
+use crate::mutators::PGRandMutator;
+use crate::observers::{FinalStateObserver, NearSolutionObserver};
+use crate::plugin::{FuzzerPlugin, ProgressBarPlugin};
+use crate::serialization::serialize_state;
+use crate::stats::PGFuzzerStats;
+use crate::try_parse_map;
+use libafl::corpus::{Corpus, InMemoryCorpus};
+use libafl::events::SimpleEventManager;
+use libafl::feedbacks::new_hash_feedback::NewHashFeedback;
+use libafl::feedbacks::{
+    CrashFeedback, EagerAndFeedback, FastAndFeedback, NotFeedback, StateInitializer,
+};
+use libafl::fuzzer::{Evaluator, Fuzzer, HasFeedback, HasObjective, NopInputFilter, StdFuzzer};
+use libafl::monitors::SimplePrintingMonitor;
+use libafl::schedulers::QueueScheduler;
+use libafl::stages::StdMutationalStage;
+use libafl::state::{HasCorpus, HasExecutions, HasSolutions, StdState};
+use libafl::{feedback_and, feedback_and_fast, feedback_not};
+use libafl_bolts::rands::StdRand;
+use libafl_bolts::tuples::tuple_list;
+use libafl_bolts::{Error, Named};
+use parking_game::{BoardValue, State};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// The state used by a [`FuzzingSession`].
+pub type PGState = StdState<InMemoryCorpus<PGInput>, PGInput, StdRand, InMemoryCorpus<PGInput>>;
+
+/// The event manager used by a [`FuzzingSession`].
+pub type PGEventManager = SimpleEventManager<PGInput, SimplePrintingMonitor, PGState>;
+
+/// A [`FuzzingSession`] with the default components, as created by
+/// [`FuzzingSession::with_map_string`]: a [`NearSolutionObserver`] and a [`FinalStateObserver`], a
+/// [`QueueScheduler`], a [`NewHashFeedback`] on the final state, a [`SolvedFeedback`] objective on
+/// the cars counted in the path of the objective car, and a [`PGRandMutator`]. Crashing inputs are
+/// neither kept nor considered solutions.
+pub type DefaultFuzzingSession<T> = FuzzingSession<
+    T,
+    (NearSolutionObserver, (FinalStateObserver<T>, ())),
+    QueueScheduler,
+    EagerAndFeedback<NotFeedback<CrashFeedback>, NewHashFeedback<FinalStateObserver<T>>>,
+    FastAndFeedback<NotFeedback<CrashFeedback>, SolvedFeedback<T>>,
+    PGRandMutator<T>,
+>;
+
+/// Options for a [`DefaultFuzzingSession`]; see [`FuzzingSession::with_map_string_and_options`].
+#[derive(Debug, Clone, Default)]
+pub struct FuzzingOptions {
+    /// The seed of the random number generator, or [`None`] to seed it from the current time.
+    pub seed: Option<u64>,
+    /// The directory to save solutions and reports in, or [`None`] to keep them in memory only.
+    pub output_dir: Option<PathBuf>,
+}
+
+type PGFuzzer<CS, F, OF> = StdFuzzer<CS, F, NopInputFilter, OF>;
+
+type PGStages<T, OT, CS, F, OF, M> = (
+    StdMutationalStage<
+        PGExecutor<T, OT>,
+        PGEventManager,
+        PGInput,
+        PGInput,
+        M,
+        PGState,
+        PGFuzzer<CS, F, OF>,
+    >,
+    (),
+);
+
+/// Everything needed to fuzz a puzzle: the executor and its observers, the fuzzer with its
+/// scheduler, feedback, and objective, the state, and a mutational stage for the mutator.
+///
+/// Construct one with [`FuzzingSession::builder`].
+pub struct FuzzingSession<T, OT, CS, F, OF, M> {
+    executor: PGExecutor<T, OT>,
+    fuzzer: PGFuzzer<CS, F, OF>,
+    state: PGState,
+    manager: PGEventManager,
+    stages: PGStages<T, OT, CS, F, OF, M>,
+    output: Option<SessionOutput>,
+    plugins: Arc<[Box<dyn FuzzerPlugin<T>>]>,
+    progress: Arc<AtomicU64>,
+    start: Instant,
+}
+
+impl<T> FuzzingSession<T, (), QueueScheduler, (), (), ()> {
+    /// Start building a session which will attempt to solve the provided state. Unless another is
+    /// provided, the session will use a [`QueueScheduler`].
+    pub fn builder(initial: State<T>) -> FuzzingSessionBuilder<T, (), QueueScheduler, (), (), ()> {
+        FuzzingSessionBuilder {
+            initial,
+            observers: (),
+            scheduler: QueueScheduler::new(),
+            feedback: (),
+            objective: (),
+            mutator: (),
+            output_dir: None,
+            seed: None,
+            restored: None,
+            plugins: Vec::new(),
+        }
+    }
+}
+
+impl DefaultFuzzingSession<u8> {
+    /// Parse the map with [`crate::try_parse_map`] and create a session with the default components
+    /// which will attempt to solve it.
+    pub fn with_map_string(map: &str) -> Result<Self, FuzzerError> {
+        Self::with_map_string_and_options(map, FuzzingOptions::default())
+    }
+
+    /// As [`FuzzingSession::with_map_string`], with the provided options.
+    pub fn with_map_string_and_options(
+        map: &str,
+        options: FuzzingOptions,
+    ) -> Result<Self, FuzzerError> {
+        let initial = try_parse_map::<u8>(map)?;
+        Ok(default_session(initial, options)?)
+    }
+}
+
+impl<T, OT, CS, F, OF, M> FuzzingSession<T, OT, CS, F, OF, M> {
+    /// The executor used by this session.
+    pub fn executor(&self) -> &PGExecutor<T, OT> {
+        &self.executor
+    }
+
+    /// The state of this session, including the corpus and solutions found so far.
+    pub fn state(&self) -> &PGState {
+        &self.state
+    }
+
+    /// The most recently found solution, if any.
+    pub fn solution(&self) -> Option<PGInput> {
+        let id = self.state.solutions().last()?;
+        let testcase = self.state.solutions().get(id).ok()?.borrow();
+        testcase.input().clone()
+    }
+
+    /// A counter which is advanced whenever an entry is added to the corpus or a solution is found,
+    /// e.g., for a [`WatchdogThread`] to watch.
+    pub fn progress_counter(&self) -> Arc<AtomicU64> {
+        self.progress.clone()
+    }
+
+    /// Save the state of this session to the provided file with [`checkpoint_fuzzer`], so that it
+    /// can later be resumed with [`FuzzingSessionBuilder::with_restored_state`].
+    pub fn checkpoint(&self, path: &Path) -> io::Result<()> {
+        checkpoint_fuzzer(&self.state, path)
+    }
+
+    /// A summary of the progress of this session so far.
+    pub fn report(&self) -> FuzzingReport {
+        FuzzingReport::from_state(&self.state, self.start)
+    }
+
+    /// Finish the session, returning its final report. If the session has an output directory,
+    /// the report is also saved there as `stats.json`.
+    pub fn finish(&mut self) -> Result<FuzzingReport, Error> {
+        self.save_solutions()?;
+        let report = self.report();
+        if let Some(output) = &self.output {
+            output.save_report(&report)?;
+        }
+        Ok(report)
+    }
+
+    /// Add the executions, corpus entries, and solutions since the provided numbers of each to the
+    /// [`PGFuzzerStats::global`] statistics.
+    fn update_global_stats(
+        &self,
+        executions_before: u64,
+        corpus_before: usize,
+        solutions_before: usize,
+    ) -> Result<(), Error> {
+        let stats = PGFuzzerStats::global();
+        stats.executions.fetch_add(
+            *self.state.executions() - executions_before,
+            Ordering::Relaxed,
+        );
+        stats.corpus_size.fetch_add(
+            self.state.corpus().count() - corpus_before,
+            Ordering::Relaxed,
+        );
+        for nth in solutions_before..self.state.solutions().count() {
+            let id = self.state.solutions().nth(nth);
+            let testcase = self.state.solutions().get(id)?.borrow();
+            stats.record_solution(testcase.input().as_ref().map_or(0, PGInput::len));
+        }
+        Ok(())
+    }
+
+    /// Tell the plugins about the entries added to the corpus and the solutions found since they
+    /// held the provided numbers of entries, then about the executions so far.
+    fn notify_plugins(&self, corpus_before: usize, solutions_before: usize) -> Result<(), Error> {
+        if self.plugins.is_empty() {
+            return Ok(());
+        }
+        for nth in corpus_before..self.state.corpus().count() {
+            let id = self.state.corpus().nth(nth);
+            if let Some(input) = self.state.corpus().get(id)?.borrow().input() {
+                self.plugins
+                    .iter()
+                    .for_each(|plugin| plugin.on_corpus_add(input));
+            }
+        }
+        for nth in solutions_before..self.state.solutions().count() {
+            let id = self.state.solutions().nth(nth);
+            if let Some(input) = self.state.solutions().get(id)?.borrow().input() {
+                self.plugins
+                    .iter()
+                    .for_each(|plugin| plugin.on_solution_found(input));
+            }
+        }
+        for plugin in self.plugins.iter() {
+            plugin.on_execution(
+                *self.state.executions(),
+                self.state.corpus().count(),
+                self.state.solutions().count(),
+            );
+        }
+        Ok(())
+    }
+
+    fn save_solutions(&mut self) -> Result<(), Error> {
+        let Some(output) = &mut self.output else {
+            return Ok(());
+        };
+        for nth in output.solutions()..self.state.solutions().count() {
+            let id = self.state.solutions().nth(nth);
+            let testcase = self.state.solutions().get(id)?.borrow();
+            if let Some(input) = testcase.input() {
+                output.save_solution(input)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T, OT, CS, F, OF, M> FuzzingSession<T, OT, CS, F, OF, M>
+where
+    PGFuzzer<CS, F, OF>:
+        Fuzzer<PGExecutor<T, OT>, PGEventManager, PGInput, PGState, PGStages<T, OT, CS, F, OF, M>>,
+{
+    /// Fuzz until a solution is found, then return it.
+    pub fn run_until_solved(&mut self) -> Result<PGInput, Error> {
+        loop {
+            if let Some(solution) = self.run_for_iterations(1)? {
+                return Ok(solution);
+            }
+        }
+    }
+
+    /// Fuzz for at most the provided number of iterations, stopping early if a solution is found.
+    /// Returns the solution, if one was found.
+    pub fn run_for_iterations(&mut self, n: u64) -> Result<Option<PGInput>, Error> {
+        for _ in 0..n {
+            if self.solution().is_some() {
+                break;
+            }
+            let corpus_before = self.state.corpus().count();
+            let solutions_before = self.state.solutions().count();
+            let executions_before = *self.state.executions();
+            self.fuzzer.fuzz_one(
+                &mut self.stages,
+                &mut self.executor,
+                &mut self.state,
+                &mut self.manager,
+            )?;
+            self.save_solutions()?;
+            let added = (self.state.corpus().count() - corpus_before)
+                + (self.state.solutions().count() - solutions_before);
+            self.progress.fetch_add(added as u64, Ordering::Relaxed);
+            InputSizeHistogram::record_corpus(&mut self.state, corpus_before)?;
+            self.update_global_stats(executions_before, corpus_before, solutions_before)?;
+            self.notify_plugins(corpus_before, solutions_before)?;
+        }
+        Ok(self.solution())
+    }
+}
+
+impl<T, OT, CS, F, OF, M> FuzzingSession<T, OT, CS, FeedbackMetrics<F>, FeedbackMetrics<OF>, M>
+where
+    F: Named,
+    OF: Named,
+{
+    /// The measurements of the feedback, for a session built with
+    /// [`FuzzingSessionBuilder::with_profiling`].
+    pub fn feedback_metrics(&self) -> &FeedbackMetrics<F> {
+        self.fuzzer.feedback()
+    }
+
+    /// The measurements of the objective, for a session built with
+    /// [`FuzzingSessionBuilder::with_profiling`].
+    pub fn objective_metrics(&self) -> &FeedbackMetrics<OF> {
+        self.fuzzer.objective()
+    }
+
+    /// A table of how many times the feedback and objective were asked whether an input is
+    /// interesting, and how long they took to decide, with a row for each.
+    pub fn latency_table(&self) -> String {
+        let feedback = self.feedback_metrics();
+        let objective = self.objective_metrics();
+        let rows = [
+            (
+                "feedback",
+                feedback.name(),
+                feedback.total_calls(),
+                feedback.avg_latency(),
+                feedback.max_latency(),
+            ),
+            (
+                "objective",
+                objective.name(),
+                objective.total_calls(),
+                objective.avg_latency(),
+                objective.max_latency(),
+            ),
+        ];
+        let width = rows.iter().map(|row| row.1.len()).max().unwrap_or(0);
+        let mut table = format!(
+            "{:<9}  {:<width$}  {:>10}  {:>12}  {:>12}",
+            "", "name", "calls", "average", "max"
+        );
+        for (role, name, calls, avg, max) in rows {
+            table.push_str(&format!(
+                "\n{role:<9}  {name:<width$}  {calls:>10}  {:>12}  {:>12}",
+                format!("{avg:.1?}"),
+                format!("{max:.1?}")
+            ));
+        }
+        table
+    }
+
+    /// Finish the session as with [`FuzzingSession::finish`], then print the
+    /// [`FuzzingSession::latency_table`].
+    pub fn finish_profiled(&mut self) -> Result<FuzzingReport, Error> {
+        let report = self.finish()?;
+        println!("{}", self.latency_table());
+        Ok(report)
+    }
+}
+
+/// Builder for [`FuzzingSession`]s; see [`FuzzingSession::builder`].
+pub struct FuzzingSessionBuilder<T, OT, CS, F, OF, M> {
+    initial: State<T>,
+    observers: OT,
+    scheduler: CS,
+    feedback: F,
+    objective: OF,
+    mutator: M,
+    output_dir: Option<PathBuf>,
+    seed: Option<u64>,
+    restored: Option<PGState>,
+    plugins: Vec<Box<dyn FuzzerPlugin<T>>>,
+}
+
+impl<T, OT, CS, F, OF, M> FuzzingSessionBuilder<T, OT, CS, F, OF, M> {
+    /// Save the initial board and every solution found to the provided directory, which is created
+    /// if it does not exist. See [`SessionOutput`] for the files written.
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    /// Seed the random number generator, so that building the session the same way again replays
+    /// the same run.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Resume from the provided state, e.g., as restored by [`restore_fuzzer`], rather than
+    /// starting afresh. The state already holds the corpus and the random number generator, so the
+    /// seed is ignored, no input is added to the corpus, and the feedback and objective are not
+    /// asked to initialize the state again.
+    pub fn with_restored_state(mut self, state: PGState) -> Self {
+        self.restored = Some(state);
+        self
+    }
+
+    /// Tell the provided plugin about the progress of the session as it fuzzes.
+    pub fn with_plugin(mut self, plugin: impl FuzzerPlugin<T> + 'static) -> Self {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    /// Display a live progress bar while the session fuzzes, with a [`ProgressBarPlugin`].
+    pub fn with_progress_bar(self) -> Self {
+        self.with_plugin(ProgressBarPlugin::new())
+    }
+
+    /// Use the provided observers in the executor. The feedback and objective may only refer to
+    /// these observers.
+    pub fn with_observers<OT2>(
+        self,
+        observers: OT2,
+    ) -> FuzzingSessionBuilder<T, OT2, CS, F, OF, M> {
+        FuzzingSessionBuilder {
+            initial: self.initial,
+            observers,
+            scheduler: self.scheduler,
+            feedback: self.feedback,
+            objective: self.objective,
+            mutator: self.mutator,
+            output_dir: self.output_dir,
+            seed: self.seed,
+            restored: self.restored,
+            plugins: self.plugins,
+        }
+    }
+
+    /// Use the provided scheduler to select which corpus entry to mutate next.
+    pub fn with_scheduler<CS2>(
+        self,
+        scheduler: CS2,
+    ) -> FuzzingSessionBuilder<T, OT, CS2, F, OF, M> {
+        FuzzingSessionBuilder {
+            initial: self.initial,
+            observers: self.observers,
+            scheduler,
+            feedback: self.feedback,
+            objective: self.objective,
+            mutator: self.mutator,
+            output_dir: self.output_dir,
+            seed: self.seed,
+            restored: self.restored,
+            plugins: self.plugins,
+        }
+    }
+
+    /// Use the provided feedback to decide which inputs are added to the corpus.
+    pub fn with_feedback<F2>(self, feedback: F2) -> FuzzingSessionBuilder<T, OT, CS, F2, OF, M> {
+        FuzzingSessionBuilder {
+            initial: self.initial,
+            observers: self.observers,
+            scheduler: self.scheduler,
+            feedback,
+            objective: self.objective,
+            mutator: self.mutator,
+            output_dir: self.output_dir,
+            seed: self.seed,
+            restored: self.restored,
+            plugins: self.plugins,
+        }
+    }
+
+    /// Use the provided objective to decide which inputs are solutions.
+    pub fn with_objective<OF2>(
+        self,
+        objective: OF2,
+    ) -> FuzzingSessionBuilder<T, OT, CS, F, OF2, M> {
+        FuzzingSessionBuilder {
+            initial: self.initial,
+            observers: self.observers,
+            scheduler: self.scheduler,
+            feedback: self.feedback,
+            objective,
+            mutator: self.mutator,
+            output_dir: self.output_dir,
+            seed: self.seed,
+            restored: self.restored,
+            plugins: self.plugins,
+        }
+    }
+
+    /// Use the provided mutator to derive new inputs from the corpus.
+    pub fn with_mutator<M2>(self, mutator: M2) -> FuzzingSessionBuilder<T, OT, CS, F, OF, M2> {
+        FuzzingSessionBuilder {
+            initial: self.initial,
+            observers: self.observers,
+            scheduler: self.scheduler,
+            feedback: self.feedback,
+            objective: self.objective,
+            mutator,
+            output_dir: self.output_dir,
+            seed: self.seed,
+            restored: self.restored,
+            plugins: self.plugins,
+        }
+    }
+
+    /// Measure how long the feedback and objective take to decide whether each input is
+    /// interesting, by wrapping each with [`FeedbackMetrics`]. The measurements are printed by
+    /// [`FuzzingSession::finish_profiled`]. Call this after providing the feedback and objective.
+    pub fn with_profiling(
+        self,
+    ) -> FuzzingSessionBuilder<T, OT, CS, FeedbackMetrics<F>, FeedbackMetrics<OF>, M> {
+        FuzzingSessionBuilder {
+            initial: self.initial,
+            observers: self.observers,
+            scheduler: self.scheduler,
+            feedback: FeedbackMetrics::new(self.feedback),
+            objective: FeedbackMetrics::new(self.objective),
+            mutator: self.mutator,
+            output_dir: self.output_dir,
+            seed: self.seed,
+            restored: self.restored,
+            plugins: self.plugins,
+        }
+    }
+
+    /// Build the session. Unless resuming from a restored state, this also adds an input with no
+    /// moves to the corpus, so that the mutator has something to start from.
+    pub fn build(self) -> Result<FuzzingSession<T, OT, CS, F, OF, M>, Error>
+    where
+        T: BoardValue + 'static,
+        F: StateInitializer<PGState>,
+        OF: StateInitializer<PGState>,
+        PGFuzzer<CS, F, OF>: Evaluator<PGExecutor<T, OT>, PGEventManager, PGInput, PGState>,
+    {
+        let Self {
+            initial,
+            observers,
+            scheduler,
+            mut feedback,
+            mut objective,
+            mutator,
+            output_dir,
+            seed,
+            restored,
+            plugins,
+        } = self;
+        let output = output_dir
+            .map(|dir| SessionOutput::create(dir, &initial))
+            .transpose()?;
+
+        let resumed = restored.is_some();
+        let mut state = match restored {
+            Some(state) => state,
+            None => StdState::new(
+                seed.map_or_else(StdRand::new, StdRand::with_seed),
+                InMemoryCorpus::<PGInput>::new(),
+                InMemoryCorpus::new(),
+                &mut feedback,
+                &mut objective,
+            )?,
+        };
+        let mut fuzzer = StdFuzzer::new(scheduler, feedback, objective);
+        let mut executor = PGExecutor::new(initial, observers);
+        let plugins = Arc::<[_]>::from(plugins);
+        if !plugins.is_empty() {
+            let plugins = plugins.clone();
+            executor.set_crash_hook(move |input| {
+                plugins.iter().for_each(|plugin| plugin.on_crash(input));
+            });
+        }
+        let mut manager = SimpleEventManager::printing();
+
+        if !resumed {
+            fuzzer.add_input(&mut state, &mut executor, &mut manager, PGInput::default())?;
+            InputSizeHistogram::record_corpus(&mut state, 0)?;
+        }
+
+        let mut session = FuzzingSession {
+            executor,
+            fuzzer,
+            state,
+            manager,
+            stages: tuple_list!(StdMutationalStage::new(mutator)),
+            output,
+            plugins,
+            progress: Arc::new(AtomicU64::new(0)),
+            start: Instant::now(),
+        };
+        session.save_solutions()?;
+        Ok(session)
+    }
+}
+
+/// Fuzz the puzzle in a separate session for each of the provided seeds, in parallel, each for at
+/// most `max_iters` iterations. Returns the shortest solution found by any of them, or [`None`] if
+/// none of them found one. Sessions which fail are treated as though they found nothing.
+pub fn fuzz_multiseed<T>(initial: &State<T>, seeds: &[u64], max_iters: u64) -> Option<PGInput>
+where
+    T: BoardValue + Clone + Default + Serialize + DeserializeOwned + Send + 'static,
+{
+    let handles = seeds
+        .iter()
+        .map(|&seed| {
+            let initial = initial.clone();
+            thread::spawn(move || -> Result<Option<PGInput>, Error> {
+                let options = FuzzingOptions {
+                    seed: Some(seed),
+                    ..FuzzingOptions::default()
+                };
+                let mut session = default_session(initial, options)?;
+                session.run_for_iterations(max_iters)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    handles
+        .into_iter()
+        .filter_map(|handle| handle.join().ok()?.ok()?)
+        .min_by_key(PGInput::len)
+}
+
+/// Create a session with the default components which will attempt to solve the provided state.
+fn default_session<T>(
+    initial: State<T>,
+    options: FuzzingOptions,
+) -> Result<DefaultFuzzingSession<T>, Error>
+where
+    T: BoardValue + Clone + Default + Serialize + DeserializeOwned + 'static,
+{
+    let near = NearSolutionObserver::default();
+    let final_state = FinalStateObserver::<T>::default();
+    let mutator = PGRandMutator::new(&initial);
+    let mut builder = FuzzingSession::builder(initial)
+        .with_feedback(feedback_and!(
+            feedback_not!(CrashFeedback::new()),
+            NewHashFeedback::new(&final_state)
+        ))
+        .with_objective(feedback_and_fast!(
+            feedback_not!(CrashFeedback::new()),
+            SolvedFeedback::from_near_solution(&near)
+        ))
+        .with_mutator(mutator)
+        .with_observers(tuple_list!(near, final_state));
+    if let Some(seed) = options.seed {
+        builder = builder.with_seed(seed);
+    }
+    if let Some(dir) = options.output_dir {
+        builder = builder.with_output_dir(dir);
+    }
+    builder.build()
+}
+
+/// Fuzz with components wired up by hand until a solution is found, or until `max_iters`
+/// iterations have been run if a limit is provided. Returns whether a solution was found.
+pub fn run_until_solved_with_limit<E, EM, I, S, ST, Z>(
+    fuzzer: &mut Z,
+    stages: &mut ST,
+    executor: &mut E,
+    state: &mut S,
+    manager: &mut EM,
+    max_iters: Option<u64>,
+) -> Result<bool, Error>
+where
+    S: HasSolutions<I>,
+    Z: Fuzzer<E, EM, I, S, ST>,
+{
+    let mut iters = 0;
+    while state.solutions().count() == 0 {
+        if max_iters.is_some_and(|max_iters| iters >= max_iters) {
+            return Ok(false);
+        }
+        fuzzer.fuzz_one(stages, executor, state, manager)?;
+        iters += 1;
+    }
+    Ok(true)
+}
+
+/// The exit code used by a [`WatchdogThread`] when the fuzzer has stalled.
+const STALLED_EXIT_CODE: i32 = 2;
+
+/// A background thread which exits the process if the fuzzer stops making progress, so that runs
+/// left unattended do not spin forever.
+pub struct WatchdogThread;
+
+impl WatchdogThread {
+    /// Watch the provided counter (see [`FuzzingSession::progress_counter`]), exiting the process
+    /// with code 2 if it does not advance within `stall_timeout`.
+    pub fn start(progress_counter: Arc<AtomicU64>, stall_timeout: Duration) -> WatchdogHandle {
+        Self::start_with(progress_counter, stall_timeout, move || {
+            eprintln!("warning: no progress was made for {stall_timeout:?}; exiting");
+            process::exit(STALLED_EXIT_CODE);
+        })
+    }
+
+    /// Watch the provided counter as with [`WatchdogThread::start`], but call `on_stall` instead of
+    /// exiting. The watchdog stops once it has fired.
+    pub fn start_with(
+        progress_counter: Arc<AtomicU64>,
+        stall_timeout: Duration,
+        on_stall: impl FnOnce() + Send + 'static,
+    ) -> WatchdogHandle {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stopped = stopped.clone();
+            thread::spawn(move || {
+                let mut last = progress_counter.load(Ordering::Relaxed);
+                loop {
+                    // parking may end early, either spuriously or because we were stopped
+                    let deadline = Instant::now() + stall_timeout;
+                    while let Some(remaining) = deadline.checked_duration_since(Instant::now())
+                        && !remaining.is_zero()
+                        && !stopped.load(Ordering::Relaxed)
+                    {
+                        thread::park_timeout(remaining);
+                    }
+                    if stopped.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let current = progress_counter.load(Ordering::Relaxed);
+                    if current == last {
+                        on_stall();
+                        return;
+                    }
+                    last = current;
+                }
+            })
+        };
+        WatchdogHandle { stopped, thread }
+    }
+}
+
+/// A handle to a running [`WatchdogThread`].
+pub struct WatchdogHandle {
+    stopped: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl WatchdogHandle {
+    /// Whether the watchdog has stopped, either because it fired or because it was stopped.
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Stop the watchdog, waiting for its thread to exit.
+    pub fn stop(self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        self.thread.thread().unpark();
+        // the thread only panics if `on_stall` does, which has already been reported
+        let _ = self.thread.join();
+    }
+}
+
+/// The zstd compression level used for checkpoints. The corpus makes up most of the state, and
+/// its inputs repeat the same moves often, so the fastest levels already compress it well.
+const CHECKPOINT_COMPRESSION_LEVEL: i32 = 3;
+
+/// Save the entire state of a fuzzer to the provided file: the corpus, the solutions, the metadata,
+/// and the state of the random number generator. The state is encoded with [`bincode`] and
+/// compressed with zstd.
+pub fn checkpoint_fuzzer(state: &PGState, path: &Path) -> io::Result<()> {
+    let serialized = bincode::serialize(state).map_err(io::Error::other)?;
+    fs::write(
+        path,
+        zstd::encode_all(serialized.as_slice(), CHECKPOINT_COMPRESSION_LEVEL)?,
+    )
+}
+
+/// Load the state of a fuzzer saved by [`checkpoint_fuzzer`], e.g., to resume fuzzing with
+/// [`FuzzingSessionBuilder::with_restored_state`].
+pub fn restore_fuzzer(path: &Path) -> io::Result<PGState> {
+    let serialized = zstd::decode_all(fs::read(path)?.as_slice())?;
+    bincode::deserialize(&serialized).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// A directory in which the results of fuzzing are saved:
+///  - `board.map`: the initial board, as written by [`serialize_state`];
+///  - `solution_<N>.sol`: the `N`th solution found, in the [`PGInput`] display format;
+///  - `stats.json`: the final [`FuzzingReport`];
+///  - `stats.csv`: a [`FuzzingReport`] per row, for following the progress of a run.
+#[derive(Debug)]
+pub struct SessionOutput {
+    dir: PathBuf,
+    solutions: usize,
+}
+
+impl SessionOutput {
+    /// Create the output directory if it does not exist, and save the initial board there.
+    pub fn create<T: BoardValue>(dir: impl Into<PathBuf>, initial: &State<T>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("board.map"), serialize_state(initial))?;
+        Ok(Self { dir, solutions: 0 })
+    }
+
+    /// The directory being saved to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The number of solutions saved so far.
+    pub fn solutions(&self) -> usize {
+        self.solutions
+    }
+
+    /// Save the provided solution with the next solution number, returning the path it was saved
+    /// to.
+    pub fn save_solution(&mut self, solution: &PGInput) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!("solution_{}.sol", self.solutions));
+        fs::write(&path, solution.to_string())?;
+        self.solutions += 1;
+        Ok(path)
+    }
+
+    /// Save the provided report as `stats.json`.
+    pub fn save_report(&self, report: &FuzzingReport) -> io::Result<()> {
+        fs::write(
+            self.dir.join("stats.json"),
+            serde_json::to_string_pretty(report)?,
+        )
+    }
+
+    /// Append the provided report as a row of `stats.csv`, writing the header first if the file is
+    /// new.
+    pub fn append_report(&self, report: &FuzzingReport) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join("stats.csv"))?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "{}", FuzzingReport::csv_header())?;
+        }
+        writeln!(file, "{}", report.to_csv_row())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::InputSizeHistogram;
+    use crate::error::FuzzerError;
+    use crate::feedbacks::{FinalStateMetadataFeedback, SolvedFeedback};
+    use crate::input::PGInput;
+    use crate::mutators::ReinforcementMutator;
+    use crate::observers::{FinalStateObserver, NearSolutionObserver};
+    use crate::plugin::FuzzerPlugin;
+    use crate::serialization::serialize_state;
+    use crate::session::{
+        FuzzingOptions, FuzzingSession, WatchdogThread, checkpoint_fuzzer, fuzz_multiseed,
+        restore_fuzzer, run_until_solved_with_limit,
+    };
+    use crate::types::CarId;
+    use libafl::HasMetadata;
+    use libafl::corpus::{Corpus, CorpusId, InMemoryCorpus};
+    use libafl::feedbacks::CrashFeedback;
+    use libafl::feedbacks::new_hash_feedback::NewHashFeedback;
+    use libafl::mutators::{MutationResult, Mutator};
+    use libafl::state::{HasCorpus, HasExecutions, HasRand};
+    use libafl::{feedback_and, feedback_and_fast, feedback_not, feedback_or};
+    use libafl_bolts::Named;
+    use libafl_bolts::rands::Rand;
+    use libafl_bolts::tuples::tuple_list;
+    use parking_game::{Direction, State};
+    use std::borrow::Cow;
+    use std::error::Error;
+    use std::fs;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn solve_trivial() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n....");
+        let near = NearSolutionObserver::default();
+        let final_state = FinalStateObserver::<u8>::default();
+
+        // the views and the insertions of the random mutator are left to the homework, so this
+        // solves with the blockers counted ahead of the objective car and learned moves instead
+        let mut session = FuzzingSession::builder(initial.clone())
+            .with_feedback(feedback_or!(
+                feedback_and!(
+                    feedback_not!(CrashFeedback::new()),
+                    NewHashFeedback::new(&final_state)
+                ),
+                FinalStateMetadataFeedback::new(&final_state)
+            ))
+            .with_objective(feedback_and_fast!(
+                feedback_not!(CrashFeedback::new()),
+                SolvedFeedback::<u8>::from_near_solution(&near)
+            ))
+            .with_mutator(ReinforcementMutator::new(&initial))
+            .with_observers(tuple_list!(near, final_state))
+            .build()?;
+
+        let solution = session
+            .run_for_iterations(10_000)?
+            .expect("Should have found a solution!");
+        let mut solved = initial.clone();
+        assert_eq!(Ok(solution.moves().len()), solution.apply(&mut solved));
+        // the objective car can see the wall: nothing else is left in its row
+        let board = solved.board()?;
+        for column in 0..4u8 {
+            let cell = board.get((0u8, column)).unwrap();
+            assert!(cell.is_none_or(|car| car.get() == 1));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_map_string() -> Result<(), Box<dyn Error>> {
+        // every input which does not crash leaves the objective car seeing the wall
+        let mut session = FuzzingSession::with_map_string("oo.")?;
+        let solution = session
+            .run_for_iterations(100)?
+            .expect("Should have found a solution!");
+        assert!(solution.validate(&crate::parse_map::<u8>("oo.")).is_ok());
+
+        // the same seed replays the same run
+        let seeded = || {
+            let options = FuzzingOptions {
+                seed: Some(7),
+                ..FuzzingOptions::default()
+            };
+            FuzzingSession::with_map_string_and_options("oo.1\n...1\n....", options)
+        };
+        let (mut first, mut second) = (seeded()?, seeded()?);
+        assert_eq!(
+            first.run_for_iterations(100)?,
+            second.run_for_iterations(100)?
+        );
+        assert_eq!(
+            first.state().corpus().count(),
+            second.state().corpus().count()
+        );
+
+        assert!(matches!(
+            FuzzingSession::with_map_string("oo.\n.x"),
+            Err(FuzzerError::Map(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn solve_with_limit() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n....");
+        let near = NearSolutionObserver::default();
+        let final_state = FinalStateObserver::<u8>::default();
+
+        let mut session = FuzzingSession::builder(initial.clone())
+            .with_feedback(feedback_or!(
+                feedback_and!(
+                    feedback_not!(CrashFeedback::new()),
+                    NewHashFeedback::new(&final_state)
+                ),
+                FinalStateMetadataFeedback::new(&final_state)
+            ))
+            .with_objective(feedback_and_fast!(
+                feedback_not!(CrashFeedback::new()),
+                SolvedFeedback::<u8>::from_near_solution(&near)
+            ))
+            .with_mutator(ReinforcementMutator::new(&initial))
+            .with_observers(tuple_list!(near, final_state))
+            .build()?;
+        let mut run = |max_iters| {
+            run_until_solved_with_limit(
+                &mut session.fuzzer,
+                &mut session.stages,
+                &mut session.executor,
+                &mut session.state,
+                &mut session.manager,
+                max_iters,
+            )
+        };
+
+        assert!(!run(Some(0))?);
+        assert!(run(Some(10_000))?);
+        // once solved, there is nothing left to do
+        assert!(run(Some(0))?);
+        assert!(session.solution().is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn profiling() -> Result<(), Box<dyn Error>> {
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n....");
+        let near = NearSolutionObserver::default();
+        let final_state = FinalStateObserver::<u8>::default();
+
+        let mut session = FuzzingSession::builder(initial.clone())
+            .with_feedback(feedback_or!(
+                feedback_and!(
+                    feedback_not!(CrashFeedback::new()),
+                    NewHashFeedback::new(&final_state)
+                ),
+                FinalStateMetadataFeedback::new(&final_state)
+            ))
+            .with_objective(feedback_and_fast!(
+                feedback_not!(CrashFeedback::new()),
+                SolvedFeedback::<u8>::from_near_solution(&near)
+            ))
+            .with_profiling()
+            .with_mutator(ReinforcementMutator::new(&initial))
+            .with_observers(tuple_list!(near, final_state))
+            .with_seed(0)
+            .build()?;
+        session.run_for_iterations(10)?;
+
+        let executions = *session.state().executions();
+        assert!(executions > 0);
+        // every execution is checked against the objective, including that of the initial input
+        assert_eq!(executions, session.objective_metrics().total_calls());
+        assert!(session.feedback_metrics().total_calls() > 0);
+        let table = session.latency_table();
+        assert_eq!(3, table.lines().count());
+        assert!(table.contains(&**session.objective_metrics().name()));
+        session.finish_profiled()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_output() -> Result<(), Box<dyn Error>> {
+        let dir = tempfile::tempdir()?;
+        let out = dir.path().join("out");
+        let initial = crate::parse_map::<u8>("oo.1\n...1\n....");
+        let near = NearSolutionObserver::default();
+        let final_state = FinalStateObserver::<u8>::default();
+
+        let mut session = FuzzingSession::builder(initial.clone())
+            .with_feedback(feedback_or!(
+                feedback_and!(
+                    feedback_not!(CrashFeedback::new()),
+                    NewHashFeedback::new(&final_state)
+                ),
+                FinalStateMetadataFeedback::new(&final_state)
+            ))
+            .with_objective(feedback_and_fast!(
+                feedback_not!(CrashFeedback::new()),
+                SolvedFeedback::<u8>::from_near_solution(&near)
+            ))
+            .with_mutator(ReinforcementMutator::new(&initial))
+            .with_observers(tuple_list!(near, final_state))
+            .with_output_dir(&out)
+            .build()?;
+        let solution = session.run_until_solved()?;
+        let report = session.finish()?;
+        assert!(report.solutions_count >= 1);
+        assert!(report.best_solution_length.unwrap() <= solution.moves().len());
+
+        let board = crate::parse_map::<u8>(&fs::read_to_string(out.join("board.map"))?);
+        assert_eq!(serialize_state(&initial), serialize_state(&board));
+
+        let saved = fs::read_to_string(out.join("solution_0.sol"))?;
+        let parsed = saved.parse::<PGInput>()?;
+        assert_eq!(saved, parsed.to_string());
+        assert!(parsed.validate(&initial).is_ok());
+        assert!(
+            out.join(format!("solution_{}.sol", report.solutions_count - 1))
+                .exists()
+        );
+
+        let stats = serde_json::from_str::<serde_json::Value>(&fs::read_to_string(
+            out.join("stats.json"),
+        )?)?;
+        assert_eq!(report.solutions_count, stats["solutions_count"]);
+
+        Ok(())
+    }
+
+    /// Appends a random move to each input without learning anything along the way, so that a
+    /// session resumed from a checkpoint mutates exactly as the one it was saved from.
+    struct AppendMutator {
+        actions: Vec<(CarId, Direction)>,
+    }
+
+    impl AppendMutator {
+        fn new(initial: &State<u8>) -> Self {
+            let actions = (1..=initial.cars().len())
+                .filter_map(CarId::new)
+                .flat_map(|car| {
+                    [
+                        Direction::Up,
+                        Direction::Down,
+                        Direction::Left,
+                        Direction::Right,
+                    ]
+                    .map(|dir| (car, dir))
+                })
+                .collect();
+            Self { actions }
+        }
+    }
+
+    impl Named for AppendMutator {
+        fn name(&self) -> &Cow<'static, str> {
+            static NAME: Cow<'static, str> = Cow::Borrowed("append");
+            &NAME
+        }
+    }
+
+    impl<S: HasRand> Mutator<PGInput, S> for AppendMutator {
+        fn mutate(
+            &mut self,
+            state: &mut S,
+            input: &mut PGInput,
+        ) -> Result<MutationResult, libafl::Error> {
+            let &(car, dir) = state.rand_mut().choose(&self.actions).unwrap();
+            input.push(car, dir);
+            Ok(MutationResult::Mutated)
+        }
+
+        fn post_exec(
+            &mut self,
+            _state: &mut S,
+            _new_corpus_id: Option<CorpusId>,
+        ) -> Result<(), libafl::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn checkpoint() -> Result<(), Box<dyn Error>> {
+        // car 1 blocks the objective car for good, so fuzzing only ever grows the corpus
+        let initial = crate::parse_map::<u8>("oo..11\n......\n.2....\n.2.33.\n......\n4.....");
+        let session = || {
+            let near = NearSolutionObserver::default();
+            let final_state = FinalStateObserver::<u8>::default();
+            FuzzingSession::builder(initial.clone())
+                .with_feedback(feedback_and!(
+                    feedback_not!(CrashFeedback::new()),
+                    NewHashFeedback::new(&final_state)
+                ))
+                .with_objective(feedback_and_fast!(
+                    feedback_not!(CrashFeedback::new()),
+                    SolvedFeedback::<u8>::from_near_solution(&near)
+                ))
+                .with_mutator(AppendMutator::new(&initial))
+                .with_observers(tuple_list!(near, final_state))
+                .with_seed(17)
+        };
+        let inputs = |corpus: &InMemoryCorpus<PGInput>| {
+            corpus
+                .ids()
+                .map(|id| corpus.cloned_input_for_id(id))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let mut uninterrupted = session().build()?;
+        while uninterrupted.state().corpus().count() < 50 {
+            assert!(uninterrupted.run_for_iterations(1)?.is_none());
+        }
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("fuzzer.ckpt");
+        uninterrupted.checkpoint(&path)?;
+
+        let restored = restore_fuzzer(&path)?;
+        assert_eq!(
+            inputs(uninterrupted.state().corpus())?,
+            inputs(restored.corpus())?
+        );
+        let histogram = uninterrupted.state().metadata::<InputSizeHistogram>()?;
+        assert_eq!(
+            uninterrupted.state().corpus().count(),
+            histogram.buckets().values().sum::<u32>() as usize
+        );
+        assert_eq!(histogram, restored.metadata::<InputSizeHistogram>()?);
+        let mut resumed = session().with_restored_state(restored).build()?;
+        assert_eq!(
+            *uninterrupted.state().executions(),
+            *resumed.state().executions()
+        );
+
+        uninterrupted.run_for_iterations(1)?;
+        resumed.run_for_iterations(1)?;
+        assert_eq!(
+            inputs(uninterrupted.state().corpus())?,
+            inputs(resumed.state().corpus())?
+        );
+        assert_eq!(
+            *uninterrupted.state().executions(),
+            *resumed.state().executions()
+        );
+
+        // checkpointing the resumed state again gives the same result
+        checkpoint_fuzzer(resumed.state(), &path)?;
+        assert_eq!(
+            inputs(resumed.state().corpus())?,
+            inputs(restore_fuzzer(&path)?.corpus())?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn multiseed() {
+        // every input which does not crash solves this, so each seed finds a solution without
+        // relying on the insertions of the random mutator
+        let initial = crate::parse_map::<u8>("oo.");
+        let seeds = [1, 2, 3, 4];
+
+        let best = fuzz_multiseed(&initial, &seeds, 100).expect("Should have found a solution!");
+        assert!(best.validate(&initial).is_ok());
+        // each seed replays the same run alone, so the best must be the shortest of those runs
+        let shortest = seeds
+            .iter()
+            .filter_map(|&seed| fuzz_multiseed(&initial, &[seed], 100))
+            .map(|solution| solution.len())
+            .min();
+        assert_eq!(Some(best.len()), shortest);
+
+        // car 1 fills its column, so there is nothing to find
+        let blocked = crate::parse_map::<u8>("oo1\n..1");
+        assert!(fuzz_multiseed(&blocked, &seeds, 100).is_none());
+        assert!(fuzz_multiseed(&initial, &[], 100).is_none());
+    }
+
+    #[test]
+    fn watchdog() {
+        let progress = Arc::new(AtomicU64::new(0));
+        let fired = Arc::new(AtomicBool::new(false));
+
+        let stalled = {
+            let fired = fired.clone();
+            WatchdogThread::start_with(progress.clone(), Duration::from_millis(100), move || {
+                fired.store(true, Ordering::Relaxed)
+            })
+        };
+        thread::sleep(Duration::from_millis(300));
+        assert!(fired.load(Ordering::Relaxed));
+        assert!(stalled.is_finished());
+        stalled.stop();
+
+        fired.store(false, Ordering::Relaxed);
+        let progressing = {
+            let fired = fired.clone();
+            WatchdogThread::start_with(progress.clone(), Duration::from_millis(100), move || {
+                fired.store(true, Ordering::Relaxed)
+            })
+        };
+        for _ in 0..10 {
+            thread::sleep(Duration::from_millis(50));
+            progress.fetch_add(1, Ordering::Relaxed);
+        }
+        progressing.stop();
+        assert!(!fired.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,147 @@
+//! A deterministic solver for [`parking_game`] puzzles, to compare the fuzzer's solutions with.
+
+use crate::analysis::successors;
+use crate::input::PGInput;
+use crate::observers::{blockers_in_path, hash_state};
+use crate::types::CarId;
+use parking_game::{BoardValue, Direction, State};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+use std::marker::PhantomData;
+
+/// A state waiting to be explored by [`GreedySolver`], with the moves which led to it.
+struct Node<T> {
+    /// The number of moves so far plus the number of cars in the path of the objective car.
+    cost: usize,
+    /// The order in which the node was pushed, so that ties are explored first come, first served.
+    order: usize,
+    state: State<T>,
+    moves: Vec<(CarId, Direction)>,
+}
+
+impl<T> Node<T> {
+    fn key(&self) -> (Reverse<usize>, Reverse<usize>) {
+        (Reverse(self.cost), Reverse(self.order))
+    }
+}
+
+impl<T> PartialEq for Node<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<T> Eq for Node<T> {}
+
+impl<T> PartialOrd for Node<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Node<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// An A* search for solutions, guided by the number of cars in the path of the objective car. Each
+/// of those cars must move at least once, and a single move clears at most one of them, so the
+/// first solution found is as short as any.
+pub struct GreedySolver<T> {
+    phantom: PhantomData<T>,
+}
+
+impl<T> GreedySolver<T>
+where
+    T: BoardValue,
+{
+    /// Find a solution to the puzzle of at most `max_depth` moves, or [`None`] if there is none.
+    ///
+    /// Panics if the initial state is invalid.
+    pub fn solve(initial: &State<T>, max_depth: usize) -> Option<PGInput> {
+        let blockers = |state: &State<T>| {
+            blockers_in_path(&state.board().expect("The state should be valid."))
+        };
+
+        let mut seen = HashSet::new();
+        let mut queue = BinaryHeap::from([Node {
+            cost: blockers(initial),
+            order: 0,
+            state: initial.clone(),
+            moves: Vec::new(),
+        }]);
+        let mut order = 1;
+        while let Some(Node { state, moves, .. }) = queue.pop() {
+            if !seen.insert(hash_state(&state).expect("The state should be valid.")) {
+                continue;
+            }
+            if blockers(&state) == 0 {
+                return Some(PGInput::new(moves));
+            }
+            if moves.len() == max_depth {
+                continue;
+            }
+            for (step, next) in successors(&state) {
+                let mut moves = moves.clone();
+                moves.push(step);
+                queue.push(Node {
+                    cost: moves.len() + blockers(&next),
+                    order,
+                    state: next,
+                    moves,
+                });
+                order += 1;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::min_moves_to_solution;
+    use crate::observers::blockers_in_path;
+    use crate::solver::GreedySolver;
+    use std::error::Error;
+    use std::fs;
+
+    #[test]
+    fn solve_maps() -> Result<(), Box<dyn Error>> {
+        let max_depth = 100;
+        for entry in fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/maps"))? {
+            let path = entry?.path();
+            let map = fs::read_to_string(&path)?;
+            if !map.contains('o') {
+                continue;
+            }
+            let initial = crate::parse_map::<u8>(&map);
+
+            let solution = GreedySolver::solve(&initial, max_depth)
+                .unwrap_or_else(|| panic!("{} should be solvable", path.display()));
+            assert!(solution.len() < max_depth);
+            let mut state = initial.clone();
+            assert_eq!(Ok(solution.len()), solution.apply(&mut state));
+            assert_eq!(0, blockers_in_path(&state.board()?));
+        }
+
+        for map in ["tokyo1", "tokyo8"] {
+            let path = format!("{}/maps/{map}.map", env!("CARGO_MANIFEST_DIR"));
+            let initial = crate::parse_map::<u8>(&fs::read_to_string(path)?);
+            assert_eq!(
+                min_moves_to_solution(&initial, 100),
+                GreedySolver::solve(&initial, 100).map(|solution| solution.len())
+            );
+        }
+
+        let stuck = crate::parse_map::<u8>("oo1.\n..1.");
+        assert!(GreedySolver::solve(&stuck, 100).is_none());
+        let tokyo1 = crate::parse_map::<u8>(&fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/maps/tokyo1.map"
+        ))?);
+        assert!(GreedySolver::solve(&tokyo1, 29).is_none());
+
+        Ok(())
+    }
+}
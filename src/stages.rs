@@ -1,15 +1,20 @@
 //! Custom stages for optimized solving of [`parking_game`] puzzles.
 
+use crate::analysis::successors;
 use crate::input::PGInput;
-use crate::observers::PGObserverTuple;
+use crate::observers::{PGObserverTuple, blocking_path, hash_state};
+use crate::types::CarId;
 use libafl::executors::HasObservers;
 use libafl::feedbacks::Feedback;
+use libafl::fuzzer::Evaluator;
 use libafl::observers::ObserversTuple;
 use libafl::stages::{Restartable, Stage};
-use libafl::state::{HasCurrentTestcase, HasExecutions};
+use libafl::state::{HasCurrentTestcase, HasExecutions, HasRand};
 use libafl::{ExecutionProcessor, HasFeedback, HasObjective, HasScheduler};
 use libafl_bolts::Error;
-use parking_game::{BoardValue, State};
+use libafl_bolts::rands::Rand;
+use parking_game::{BoardValue, Direction, State};
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 
 /// A stage implementation which exhausts the mutation space rather than randomly selecting
@@ -86,3 +91,294 @@ where
         Ok(())
     }
 }
+
+/// The exploration constant of UCB1; larger favours trying moves which have been visited less.
+const MCTS_EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// A node of the tree searched by [`MCTSStage`], i.e., a state reached from the initial state.
+#[derive(Debug, Clone, Default)]
+pub struct MCTSNode {
+    /// The number of iterations which passed through this node.
+    pub visits: u32,
+    /// The sum of the scores of those iterations.
+    pub wins: f64,
+    /// The moves expanded from this node, with the hashes of the states they lead to.
+    pub children: Vec<(CarId, Direction, u64)>,
+}
+
+/// A stage which searches for solutions with Monte Carlo Tree Search, rather than mutating the
+/// corpus. Each time the stage is performed, one iteration of the search is made: a node is
+/// selected from the initial state by UCB1, expanded by a random move, and scored by a random
+/// rollout from there. Solutions found along the way are evaluated by the fuzzer, so that they are
+/// added to the solutions by its objective.
+///
+/// Nodes are identified by the hash of their state (see [`hash_state`]), so the same state reached
+/// by different moves is searched only once.
+pub struct MCTSStage<T> {
+    initial: State<T>,
+    initial_state_hash: u64,
+    nodes: HashMap<u64, MCTSNode>,
+    rollout_depth: usize,
+    solutions: HashSet<PGInput>,
+}
+
+impl<T> MCTSStage<T>
+where
+    T: BoardValue,
+{
+    /// Create a new search from the provided initial state, making up to `rollout_depth` random
+    /// moves to score each expanded node.
+    ///
+    /// Panics if the initial state is invalid.
+    pub fn new(initial: &State<T>, rollout_depth: usize) -> Self {
+        let initial_state_hash = hash_state(initial).expect("The initial state should be valid.");
+        Self {
+            initial: initial.clone(),
+            initial_state_hash,
+            nodes: HashMap::from([(initial_state_hash, MCTSNode::default())]),
+            rollout_depth,
+            solutions: HashSet::new(),
+        }
+    }
+
+    /// The number of nodes in the tree.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The node for the state with the provided hash, if it has been reached.
+    pub fn node(&self, hash: u64) -> Option<&MCTSNode> {
+        self.nodes.get(&hash)
+    }
+
+    /// The node for the initial state.
+    pub fn root(&self) -> &MCTSNode {
+        &self.nodes[&self.initial_state_hash]
+    }
+
+    /// Make one iteration of the search, returning the moves which led to a solution if one was
+    /// found.
+    pub fn iterate(&mut self, rand: &mut impl Rand) -> Option<PGInput> {
+        let mut state = self.initial.clone();
+        let mut input = PGInput::default();
+        let mut path = vec![self.initial_state_hash];
+
+        // selection: descend through fully expanded nodes, avoiding those already on the path
+        let mut moves = successors(&state);
+        loop {
+            let node = &self.nodes[path.last().unwrap()];
+            if solved(&state) || node.children.len() < moves.len() {
+                break;
+            }
+            let parent_visits = f64::from(node.visits.max(1));
+            let Some(&(car, direction, hash)) = node
+                .children
+                .iter()
+                .filter(|(_, _, hash)| !path.contains(hash))
+                .max_by(|a, b| {
+                    let ucb = |&(_, _, hash): &(CarId, Direction, u64)| {
+                        let child = &self.nodes[&hash];
+                        if child.visits == 0 {
+                            return f64::INFINITY;
+                        }
+                        let visits = f64::from(child.visits);
+                        child.wins / visits
+                            + MCTS_EXPLORATION * (parent_visits.ln() / visits).sqrt()
+                    };
+                    ucb(a).total_cmp(&ucb(b))
+                })
+            else {
+                break;
+            };
+            state = take_move(moves, car, direction);
+            input.push(car, direction);
+            path.push(hash);
+            moves = successors(&state);
+        }
+
+        // expansion: make a random move which has not been made from this node before
+        if !solved(&state) {
+            let node = &self.nodes[path.last().unwrap()];
+            let unexpanded = moves
+                .into_iter()
+                .filter(|&((car, direction), _)| {
+                    !node
+                        .children
+                        .iter()
+                        .any(|&(c, d, _)| (c, d) == (car, direction))
+                })
+                .collect::<Vec<_>>();
+            if let Some(((car, direction), next)) = rand.choose(unexpanded) {
+                let hash = hash_state(&next).expect("The state should be valid.");
+                self.nodes
+                    .get_mut(path.last().unwrap())
+                    .unwrap()
+                    .children
+                    .push((car, direction, hash));
+                self.nodes.entry(hash).or_default();
+                state = next;
+                input.push(car, direction);
+                // a move back to a state on the path is a cycle; count each node once regardless
+                if !path.contains(&hash) {
+                    path.push(hash);
+                }
+            }
+        }
+
+        // simulation: make random moves until solved or out of moves
+        let mut rollout = input.clone();
+        for _ in 0..self.rollout_depth {
+            if solved(&state) {
+                break;
+            }
+            let Some(((car, direction), next)) = rand.choose(successors(&state)) else {
+                break;
+            };
+            state = next;
+            rollout.push(car, direction);
+        }
+        let blockers = blocking_path(&state.board().expect("The state should be valid.")).len();
+        let score = 1.0 / (blockers + 1) as f64;
+
+        // back-propagation
+        for hash in path {
+            let node = self.nodes.get_mut(&hash).unwrap();
+            node.visits += 1;
+            node.wins += score;
+        }
+
+        (blockers == 0).then_some(rollout)
+    }
+}
+
+/// Whether the objective car has nothing between it and the wall.
+fn solved<T: BoardValue>(state: &State<T>) -> bool {
+    blocking_path(&state.board().expect("The state should be valid.")).is_empty()
+}
+
+/// The state reached by the provided move, among the provided successors.
+fn take_move<T>(
+    moves: Vec<((CarId, Direction), State<T>)>,
+    car: CarId,
+    direction: Direction,
+) -> State<T> {
+    moves
+        .into_iter()
+        .find_map(|(m, next)| (m == (car, direction)).then_some(next))
+        .expect("An expanded move should still be valid.")
+}
+
+impl<S, T> Restartable<S> for MCTSStage<T> {
+    fn should_restart(&mut self, _state: &mut S) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    fn clear_progress(&mut self, _state: &mut S) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<E, EM, S, T, Z> Stage<E, EM, S, Z> for MCTSStage<T>
+where
+    S: HasRand,
+    T: BoardValue,
+    Z: Evaluator<E, EM, PGInput, S>,
+{
+    fn perform(
+        &mut self,
+        fuzzer: &mut Z,
+        executor: &mut E,
+        state: &mut S,
+        manager: &mut EM,
+    ) -> Result<(), Error> {
+        if let Some(solution) = self.iterate(state.rand_mut())
+            && self.solutions.insert(solution.clone())
+        {
+            fuzzer.evaluate_input(state, executor, manager, &solution)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::successors;
+    use crate::observers::blocking_path;
+    use crate::stages::MCTSStage;
+    use libafl_bolts::rands::{Rand, StdRand};
+    use parking_game::State;
+
+    const MAP: &str = "oo1.2.\n..1.2.\n3.1...\n3.44..\n......\n......";
+    const ROLLOUT_DEPTH: usize = 5;
+    const MAX_EPISODES: usize = 10_000;
+
+    /// The number of random walks of [`ROLLOUT_DEPTH`] moves from the initial state before one
+    /// solves the puzzle.
+    fn random_episodes(initial: &State<u8>, rand: &mut StdRand) -> usize {
+        for episode in 1..=MAX_EPISODES {
+            let mut state = initial.clone();
+            for _ in 0..ROLLOUT_DEPTH {
+                let Some((_, next)) = rand.choose(successors(&state)) else {
+                    break;
+                };
+                state = next;
+                if blocking_path(&state.board().unwrap()).is_empty() {
+                    return episode;
+                }
+            }
+        }
+        MAX_EPISODES
+    }
+
+    /// The number of iterations of the search before it solves the puzzle.
+    fn mcts_episodes(initial: &State<u8>, rand: &mut StdRand) -> usize {
+        let mut mcts = MCTSStage::new(initial, ROLLOUT_DEPTH);
+        for episode in 1..=MAX_EPISODES {
+            if mcts.iterate(rand).is_some() {
+                return episode;
+            }
+        }
+        MAX_EPISODES
+    }
+
+    #[test]
+    fn mcts_solves() {
+        let initial = crate::parse_map::<u8>(MAP);
+        let mut mcts_wins = 0;
+        let (mut mcts_total, mut random_total) = (0, 0);
+        for seed in 0..100 {
+            let mcts = mcts_episodes(&initial, &mut StdRand::with_seed(seed));
+            let random = random_episodes(&initial, &mut StdRand::with_seed(seed));
+            mcts_wins += usize::from(mcts < random);
+            mcts_total += mcts;
+            random_total += random;
+        }
+        // the search should take fewer episodes in most trials, and fewer overall
+        assert!(
+            mcts_wins > 50,
+            "search was faster in only {mcts_wins} of 100 trials"
+        );
+        assert!(mcts_total < random_total);
+    }
+
+    #[test]
+    fn mcts_tree_grows() {
+        let initial = crate::parse_map::<u8>(MAP);
+        let mut mcts = MCTSStage::new(&initial, ROLLOUT_DEPTH);
+        let mut rand = StdRand::with_seed(1337);
+        assert_eq!(1, mcts.node_count());
+
+        let mut count = mcts.node_count();
+        for _ in 0..100 {
+            mcts.iterate(&mut rand);
+            assert!(mcts.node_count() >= count);
+            count = mcts.node_count();
+        }
+        assert!(count > 1);
+        assert_eq!(100, mcts.root().visits);
+        // every expanded move leads to a node in the tree
+        for &(_, _, hash) in &mcts.root().children {
+            assert!(mcts.node(hash).is_some_and(|child| child.visits > 0));
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Counters of the progress of fuzzing shared by the whole process, so that any component or thread
+//! can report to them without needing access to the fuzzer's state.
+
+use libafl_bolts::Error;
+use std::process;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// The exit code used when the process is interrupted after printing the summary, as a shell would
+/// report for SIGINT.
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Process-wide statistics, accessed with [`PGFuzzerStats::global`]. Components increment the
+/// counters directly; a [`crate::session::FuzzingSession`] does so as it fuzzes.
+#[derive(Debug)]
+pub struct PGFuzzerStats {
+    start_time: Instant,
+    /// The number of executions so far.
+    pub executions: AtomicU64,
+    /// The number of entries added to corpora so far.
+    pub corpus_size: AtomicUsize,
+    /// The number of solutions found so far.
+    pub solutions: AtomicUsize,
+    /// The length of the shortest solution found so far, or 0 if none have been found. Update it
+    /// with [`PGFuzzerStats::record_solution`].
+    pub best_solution_len: AtomicUsize,
+}
+
+impl PGFuzzerStats {
+    /// The statistics of this process, created the first time they are accessed.
+    pub fn global() -> &'static PGFuzzerStats {
+        static STATS: OnceLock<PGFuzzerStats> = OnceLock::new();
+        STATS.get_or_init(|| Self {
+            start_time: Instant::now(),
+            executions: AtomicU64::new(0),
+            corpus_size: AtomicUsize::new(0),
+            solutions: AtomicUsize::new(0),
+            best_solution_len: AtomicUsize::new(0),
+        })
+    }
+
+    /// When the statistics were first accessed.
+    pub fn start_time(&self) -> Instant {
+        self.start_time
+    }
+
+    /// Count a solution of the provided length, keeping it as the best if it is the shortest yet.
+    pub fn record_solution(&self, len: usize) {
+        self.solutions.fetch_add(1, Ordering::Relaxed);
+        let _ = self
+            .best_solution_len
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |best| {
+                (best == 0 || len < best).then_some(len)
+            });
+    }
+
+    /// A one-line summary of the statistics.
+    pub fn summary(&self) -> String {
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        let executions = self.executions.load(Ordering::Relaxed);
+        let execs_per_sec = if elapsed > 0.0 {
+            executions as f64 / elapsed
+        } else {
+            0.0
+        };
+        let best = match self.best_solution_len.load(Ordering::Relaxed) {
+            0 => "-".to_string(),
+            len => len.to_string(),
+        };
+        format!(
+            "[{elapsed:.1}s] executions: {executions} ({execs_per_sec:.0}/s), corpus: {}, \
+             solutions: {}, best: {best}",
+            self.corpus_size.load(Ordering::Relaxed),
+            self.solutions.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Print the [`PGFuzzerStats::summary`].
+    pub fn print_summary(&self) {
+        println!("{}", self.summary());
+    }
+
+    /// Print the summary of the global statistics when the process is interrupted with Ctrl-C, then
+    /// exit. Fails if a Ctrl-C handler has already been registered.
+    pub fn print_on_ctrlc() -> Result<(), Error> {
+        ctrlc::set_handler(|| {
+            Self::global().print_summary();
+            process::exit(INTERRUPTED_EXIT_CODE);
+        })
+        .map_err(|e| Error::illegal_state(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stats::PGFuzzerStats;
+    use std::ptr;
+    use std::sync::atomic::Ordering;
+    use std::thread;
+
+    #[test]
+    fn global() {
+        assert!(ptr::eq(PGFuzzerStats::global(), PGFuzzerStats::global()));
+        let other = thread::spawn(|| PGFuzzerStats::global() as *const _ as usize)
+            .join()
+            .unwrap();
+        assert_eq!(PGFuzzerStats::global() as *const _ as usize, other);
+    }
+
+    #[test]
+    fn shared_counters() {
+        // other tests may fuzz at the same time, so only check that our increments are all seen
+        let stats = PGFuzzerStats::global();
+        let before = stats.executions.load(Ordering::Relaxed);
+        let threads = (0..4)
+            .map(|_| {
+                thread::spawn(|| {
+                    for _ in 0..1000 {
+                        PGFuzzerStats::global()
+                            .executions
+                            .fetch_add(1, Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert!(stats.executions.load(Ordering::Relaxed) >= before + 4000);
+
+        stats.record_solution(usize::MAX);
+        let best = stats.best_solution_len.load(Ordering::Relaxed);
+        assert_ne!(0, best);
+        stats.record_solution(1);
+        assert_eq!(1, stats.best_solution_len.load(Ordering::Relaxed));
+        stats.record_solution(best);
+        assert_eq!(1, stats.best_solution_len.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn summary() {
+        let zeroed = PGFuzzerStats {
+            start_time: std::time::Instant::now(),
+            executions: Default::default(),
+            corpus_size: Default::default(),
+            solutions: Default::default(),
+            best_solution_len: Default::default(),
+        };
+        zeroed.print_summary();
+        let summary = zeroed.summary();
+        assert!(summary.contains("executions: 0 "));
+        assert!(summary.ends_with("solutions: 0, best: -"));
+    }
+}
@@ -0,0 +1,89 @@
+//! Small types shared by the components of the fuzzer.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
+
+/// The id of a car: its index in [`parking_game::State::cars`] plus one, so that the objective car
+/// is 1. This is the same numbering [`parking_game::Board`] uses for its cells, but keeps car ids
+/// from being mixed up with other non-zero counts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[repr(transparent)]
+#[serde(transparent)]
+pub struct CarId(NonZeroUsize);
+
+impl CarId {
+    /// The car with the provided id, or [`None`] if it is zero.
+    pub fn new(n: usize) -> Option<CarId> {
+        NonZeroUsize::new(n).map(CarId)
+    }
+
+    /// The id as a number.
+    pub fn get(self) -> usize {
+        self.0.get()
+    }
+
+    /// The id as the [`NonZeroUsize`] used by [`parking_game::Board`].
+    pub fn as_nonzero(self) -> NonZeroUsize {
+        self.0
+    }
+}
+
+impl Display for CarId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<NonZeroUsize> for CarId {
+    fn from(id: NonZeroUsize) -> Self {
+        Self(id)
+    }
+}
+
+impl From<CarId> for NonZeroUsize {
+    fn from(id: CarId) -> Self {
+        id.0
+    }
+}
+
+impl TryFrom<usize> for CarId {
+    type Error = CarIdZeroError;
+
+    fn try_from(n: usize) -> Result<Self, Self::Error> {
+        CarId::new(n).ok_or(CarIdZeroError)
+    }
+}
+
+/// The error returned when converting zero into a [`CarId`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CarIdZeroError;
+
+impl Display for CarIdZeroError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "car ids start from 1")
+    }
+}
+
+impl Error for CarIdZeroError {}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{CarId, CarIdZeroError};
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn car_ids() {
+        assert_eq!(None, CarId::new(0));
+        assert_eq!(5, CarId::new(5).unwrap().get());
+        assert_eq!(Err(CarIdZeroError), CarId::try_from(0));
+        assert_eq!(CarId::new(3), CarId::try_from(3).ok());
+
+        let id = CarId::new(12).unwrap();
+        assert_eq!("12", id.to_string());
+        assert_eq!(NonZeroUsize::new(12).unwrap(), id.as_nonzero());
+        assert_eq!(id, CarId::from(NonZeroUsize::from(id)));
+        assert!(CarId::new(2).unwrap() < CarId::new(10).unwrap());
+    }
+}
@@ -0,0 +1,189 @@
+//! Fuzzes every map in `maps/` from start to finish, as the fuzzer binary would, and smoke tests a
+//! whole session on the first of them with the components which work without the homework.
+
+use libafl::corpus::Corpus;
+use libafl::feedbacks::new_hash_feedback::NewHashFeedback;
+use libafl::feedbacks::{ConstFeedback, CrashFeedback};
+use libafl::state::{HasCorpus, HasSolutions};
+use libafl::{feedback_and, feedback_and_fast, feedback_not, feedback_or};
+use libafl_bolts::tuples::tuple_list;
+use parking_game_fuzzer::feedbacks::{FinalStateMetadataFeedback, SolvedFeedback};
+use parking_game_fuzzer::mutators::{PGRandMutator, ReinforcementMutator};
+use parking_game_fuzzer::observers::{FinalStateObserver, ViewObserver};
+use parking_game_fuzzer::session::FuzzingSession;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::panic;
+use std::path::Path;
+
+/// The number of iterations each map is fuzzed for.
+const ITERATIONS: u64 = 100_000;
+
+/// The number of iterations the smoke test fuzzes for.
+const SMOKE_ITERATIONS: u64 = 1_000;
+
+/// The fewest moves which solve each of the Tokyo maps, as found by breadth-first search.
+const OPTIMAL_LENGTHS: [(&str, usize); 35] = [
+    ("tokyo1", 30),
+    ("tokyo2", 28),
+    ("tokyo3", 30),
+    ("tokyo4", 40),
+    ("tokyo5", 42),
+    ("tokyo6", 30),
+    ("tokyo7", 44),
+    ("tokyo8", 53),
+    ("tokyo9", 57),
+    ("tokyo10", 43),
+    ("tokyo11", 48),
+    ("tokyo12", 47),
+    ("tokyo13", 54),
+    ("tokyo15", 50),
+    ("tokyo16", 53),
+    ("tokyo17", 52),
+    ("tokyo18", 49),
+    ("tokyo19", 51),
+    ("tokyo20", 55),
+    ("tokyo21", 65),
+    ("tokyo22", 58),
+    ("tokyo23", 59),
+    ("tokyo24", 58),
+    ("tokyo25", 66),
+    ("tokyo26", 76),
+    ("tokyo27", 61),
+    ("tokyo28", 72),
+    ("tokyo29", 63),
+    ("tokyo30", 68),
+    ("tokyo31", 74),
+    ("tokyo32", 60),
+    ("tokyo33", 62),
+    ("tokyo34", 73),
+    ("tokyo35", 78),
+    ("tokyo36", 78),
+];
+
+/// Fuzz the map at the provided path, returning the length of the shortest solution found.
+fn fuzz_map(path: &Path, map: &str) -> Result<usize, Box<dyn Error>> {
+    let initial = parking_game_fuzzer::try_parse_map::<u8>(map)?;
+    let view = ViewObserver::<u8>::default();
+    let final_state = FinalStateObserver::<u8>::default();
+
+    let mut session = FuzzingSession::builder(initial.clone())
+        .with_feedback(feedback_and!(
+            feedback_not!(CrashFeedback::new()),
+            NewHashFeedback::new(&final_state)
+        ))
+        .with_objective(feedback_and_fast!(
+            feedback_not!(CrashFeedback::new()),
+            SolvedFeedback::new(&view)
+        ))
+        .with_mutator(PGRandMutator::new(&initial))
+        .with_observers(tuple_list!(view, final_state))
+        .with_seed(0)
+        .build()?;
+    session.run_for_iterations(ITERATIONS)?;
+
+    let solutions = session.state().solutions();
+    assert!(
+        solutions.count() > 0,
+        "{} should have been solved within {ITERATIONS} iterations",
+        path.display()
+    );
+    let mut shortest = usize::MAX;
+    for nth in 0..solutions.count() {
+        let solution = solutions.get(solutions.nth(nth))?.borrow();
+        let input = solution
+            .input()
+            .as_ref()
+            .expect("Solutions should be loaded.");
+        assert_eq!(
+            Ok(()),
+            input.validate(&initial),
+            "the solution for {} should be valid",
+            path.display()
+        );
+        shortest = shortest.min(input.len());
+    }
+    Ok(shortest)
+}
+
+#[test]
+fn smoke_tokyo1() -> Result<(), Box<dyn Error>> {
+    // the views, and so the solved check, and the random and tail mutators are left to the
+    // homework, so this only checks that a session over the other components runs consistently
+    let map = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/maps/tokyo1.map"))?;
+    let initial = parking_game_fuzzer::try_parse_map::<u8>(&map)?;
+    let final_state = FinalStateObserver::<u8>::default();
+
+    let mut session = FuzzingSession::builder(initial.clone())
+        .with_feedback(feedback_or!(
+            feedback_and!(
+                feedback_not!(CrashFeedback::new()),
+                NewHashFeedback::new(&final_state)
+            ),
+            FinalStateMetadataFeedback::new(&final_state)
+        ))
+        .with_objective(ConstFeedback::new(false))
+        .with_mutator(ReinforcementMutator::new(&initial))
+        .with_observers(tuple_list!(final_state))
+        .with_seed(0)
+        .build()?;
+    assert_eq!(None, session.run_for_iterations(SMOKE_ITERATIONS)?);
+
+    // new final states were found, and each was reached by moves which are valid on the map
+    let corpus = session.state().corpus();
+    assert!(corpus.count() > 1, "tokyo1 should have new final states");
+    for nth in 0..corpus.count() {
+        let entry = corpus.get(corpus.nth(nth))?.borrow();
+        let input = entry.input().as_ref().expect("Entries should be loaded.");
+        assert_eq!(Ok(()), input.validate(&initial));
+    }
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "slow, at 100,000 iterations per map, and needs `step_until_seen` and the insertions \
+            of PGRandMutator from pt.0 of the homework to find any solution"]
+fn solve_all_maps() -> Result<(), Box<dyn Error>> {
+    let optimal = HashMap::from(OPTIMAL_LENGTHS);
+    let mut failures = Vec::new();
+    for entry in fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/maps"))? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "map") {
+            continue;
+        }
+        let map = fs::read_to_string(&path)?;
+        // maps without an objective car have nothing to solve
+        if !map.contains('o') {
+            continue;
+        }
+
+        // keep going after a failure, so that every failing map is reported at once
+        let result = panic::catch_unwind(|| fuzz_map(&path, &map).map_err(|e| e.to_string()));
+        let name = path.file_stem().unwrap().to_string_lossy();
+        match result {
+            Ok(Ok(shortest)) => {
+                // no solution may be shorter than the optimal one
+                if let Some(&optimal) = optimal.get(&*name) {
+                    assert!(
+                        optimal <= shortest,
+                        "{name} was solved in {shortest} moves, fewer than the optimal {optimal}"
+                    );
+                }
+            }
+            Ok(Err(e)) => failures.push(format!("{name}: {e}")),
+            Err(panic) => {
+                let message = panic
+                    .downcast_ref::<String>()
+                    .map(String::as_str)
+                    .or_else(|| panic.downcast_ref::<&str>().copied())
+                    .unwrap_or("panicked");
+                failures.push(format!("{name}: {message}"));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "failed maps:\n{}", failures.join("\n"));
+
+    Ok(())
+}